@@ -0,0 +1,137 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, SyncSender};
+
+use tetrs_core::game::piece::PieceType;
+use tetrs_core::game::{GameEvent, GameState};
+
+/// Announcements waiting to be written; newer ones are dropped while it's
+/// full.
+const BACKLOG: usize = 16;
+
+/// Spoken-style descriptions of what happens in a game, for players using
+/// a screen reader: the piece that spawns, clears, incoming garbage and the
+/// stack height. The latest is shown on a line of its own; with
+/// `--announce` each is also written as a line to a file or named pipe.
+pub struct Announcer {
+    /// Latest announcement, for the line on screen.
+    pub line: Option<String>,
+    /// Piece last announced, and whether a new one is due to spawn.
+    piece: Option<PieceType>,
+    awaiting_spawn: bool,
+    /// Garbage queued when last announced.
+    pending: u32,
+    /// Stack height when last announced.
+    height: usize,
+    out: Option<SyncSender<String>>,
+}
+
+impl Announcer {
+    pub fn new() -> Self {
+        Self {
+            line: None,
+            piece: None,
+            awaiting_spawn: true,
+            pending: 0,
+            height: 0,
+            out: None,
+        }
+    }
+
+    /// Also write announcements to `path`, created if it doesn't exist.
+    /// Opening a named pipe waits for a reader, so it's done on a thread
+    /// of its own.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let (out, rx) = mpsc::sync_channel::<String>(BACKLOG);
+        let target: PathBuf = path.to_path_buf();
+        std::thread::Builder::new()
+            .name("tetrs-announce".to_string())
+            .spawn(move || {
+                let file = OpenOptions::new().append(true).create(true).open(&target);
+                let mut file = match file {
+                    Ok(file) => file,
+                    Err(e) => {
+                        tracing::warn!("can't announce to {}: {}", target.display(), e);
+                        return;
+                    }
+                };
+                for line in rx {
+                    if writeln!(file, "{}", line).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        tracing::info!("announcing to {}", path.display());
+        Ok(Self {
+            out: Some(out),
+            ..Self::new()
+        })
+    }
+
+    /// Whether announcements go to a file, which keeps them on regardless
+    /// of the setting.
+    pub fn has_output(&self) -> bool {
+        self.out.is_some()
+    }
+
+    /// Forget the last game, keeping the output.
+    pub fn reset(&mut self) {
+        *self = Self {
+            out: self.out.take(),
+            ..Self::new()
+        };
+    }
+
+    /// Describe a frame's events and any change in the game they left.
+    pub fn observe(&mut self, events: &[GameEvent], game: &GameState) {
+        let mut parts = Vec::new();
+        for event in events {
+            match event {
+                GameEvent::PieceLocked => self.awaiting_spawn = true,
+                GameEvent::Clear(clear_type) => {
+                    parts.push(clear_type.display_name().to_lowercase())
+                }
+                GameEvent::PerfectClear => parts.push("perfect clear".to_string()),
+                GameEvent::GarbageReceived(lines) => {
+                    parts.push(format!("{} garbage rising", lines))
+                }
+                GameEvent::GameOver => parts.push("game over".to_string()),
+                _ => {}
+            }
+        }
+
+        let pending = game.garbage.pending();
+        if pending > self.pending {
+            parts.push(format!("{} garbage incoming", pending));
+        }
+        self.pending = pending;
+
+        let height = game.board.max_height();
+        if height != self.height {
+            let danger = if game.is_danger() { ", danger" } else { "" };
+            parts.push(format!("height {}{}", height, danger));
+            self.height = height;
+        }
+
+        if let Some(ref piece) = game.current_piece {
+            // A hold swap changes the piece without a lock
+            if self.awaiting_spawn || self.piece != Some(piece.piece_type) {
+                parts.push(format!("{} piece", piece.piece_type.name()));
+                self.piece = Some(piece.piece_type);
+                self.awaiting_spawn = false;
+            }
+        }
+
+        if !parts.is_empty() {
+            self.announce(parts.join(", "));
+        }
+    }
+
+    fn announce(&mut self, text: String) {
+        if let Some(ref out) = self.out {
+            let _ = out.try_send(text.clone());
+        }
+        self.line = Some(text);
+    }
+}
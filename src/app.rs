@@ -1,34 +1,129 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
 
-use crate::ai::difficulty::AiDifficulty;
+use tetrs_core::ai::bitboard::BitBoard;
+use tetrs_core::ai::difficulty::AiDifficulty;
+use tetrs_core::ai::perfect_clear::{PcOutcome, PcPiece, PcRequest, PcWorker};
+use tetrs_core::ai::personality::AiPersonality;
+use tetrs_core::ai::rating;
+use tetrs_core::game::modifiers::{Modifier, Modifiers};
+use tetrs_core::game::piece::PieceType;
+use tetrs_core::game::ruleset::RulesetPreset;
+use tetrs_core::game::{DangerTier, GameAction, GameEvent, GameState};
+use tetrs_core::modes::attract::AttractMode;
+use tetrs_core::modes::cheat::Cheat;
+use tetrs_core::modes::endless::EndlessMode;
+use tetrs_core::modes::gauntlet::{self, Gauntlet, RoundOutcome};
+use tetrs_core::modes::ghost_run::Handling;
+use tetrs_core::modes::instant_replay::InstantReplay;
+use tetrs_core::modes::royale::{RoyaleMode, Target, ROYALE_OPPONENTS};
+use tetrs_core::modes::sprint::SprintMode;
+use tetrs_core::modes::trainer::{TrainerMode, OPENERS};
+use tetrs_core::modes::versus::{VersusGoal, VersusMode, MERCY_GRACE};
+use tetrs_core::modes::zen::ZenMode;
+use tetrs_core::modes::{format_number, GameMode, GameResult};
+
+use crate::announce::Announcer;
+use crate::cli::ModeArg;
+use crate::data::autosave::Autosave;
+use crate::data::config::{Config, PcHint};
+use crate::data::crash_report;
+use crate::data::ghosts::GhostStore;
 use crate::data::high_scores::HighScoreStore;
-use crate::game::{GameEvent, GameState};
+use crate::data::leaderboard::{Board, Leaderboard, Submission};
+use crate::data::match_history::MatchHistory;
+use crate::data::profile::{Profile, XpAward};
+use crate::data::summary;
+use crate::input::keybinds::{Action, KeybindPreset};
 use crate::input::{AppInput, InputHandler};
-use crate::modes::endless::EndlessMode;
-use crate::modes::sprint::SprintMode;
-use crate::modes::versus::VersusMode;
-use crate::modes::GameMode;
-use crate::ui::effects::Effects;
-use crate::ui::layout::{self, SingleLayout, VersusLayout};
-use crate::ui::screens::game::GameScreen;
-use crate::ui::screens::game_over::{GameOverScreen, GameResult};
-use crate::ui::screens::high_scores::HighScoresScreen;
+use crate::script::ModeScript;
+use crate::stream::StateStream;
+use crate::ui::effects::{EffectIntensity, Effects};
+use crate::ui::layout::{self, RoyaleLayout, SingleLayout, VersusLayout};
+use crate::ui::screens::diagnostics::InputDiagnosticsScreen;
+use crate::ui::screens::game::{GameScreen, PauseChoice};
+use crate::ui::screens::game_over::GameOverScreen;
+use crate::ui::screens::high_scores::{self, HighScoreDetailScreen, HighScoresScreen, TableView};
+use crate::ui::screens::match_history::MatchHistoryScreen;
 use crate::ui::screens::menu::{MenuChoice, MenuScreen};
+use crate::ui::screens::mode_setup::{ModeSetupScreen, SetupMode};
+use crate::ui::screens::replay::InstantReplayScreen;
+use crate::ui::screens::settings::{step_option, SettingItem, SettingsScreen};
 use crate::ui::theme;
+use crate::ui::widgets::ai_explain::AiExplainWidget;
+use crate::ui::widgets::battle_log::{BattleLog, BattleLogWidget, LogSide};
+use crate::ui::widgets::board::{blend_color, BoardWidget};
+use crate::ui::widgets::garbage_bar::GarbageBarWidget;
+use crate::ui::widgets::help_overlay::HelpOverlayWidget;
+use crate::ui::widgets::mini_board::{MiniBoardWidget, MINI_BOARD_H, MINI_BOARD_W};
+use crate::ui::widgets::perf_overlay::{PerfOverlayWidget, PerfStats};
+use crate::ui::widgets::text_input::{TextInput, TextInputWidget};
+use crate::ui::widgets::toast::ToastWidget;
+
+/// Config key for versus, which runs without a `GameMode`.
+const VERSUS_MODE_NAME: &str = "VERSUS";
+
+/// Longest player name kept with a high score.
+const NAME_MAX_LEN: usize = 10;
+
+/// Longest sandbox command that can be typed.
+const COMMAND_MAX_LEN: usize = 12;
+
+/// How often a game in a resumable mode is written out for crash recovery.
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a toast stays up.
+const TOAST_TIME: Duration = Duration::from_secs(6);
+
+/// Time to press quit again to leave a game in progress.
+const QUIT_CONFIRM_TIME: Duration = Duration::from_secs(1);
+
+/// How long the replay stays on the top out before the results.
+const REPLAY_HOLD: Duration = Duration::from_millis(1500);
+
+/// How far one press skips through the replay.
+const REPLAY_SEEK: Duration = Duration::from_secs(1);
+
+/// Idle time on the main menu before the AI demo starts.
+const ATTRACT_DELAY: Duration = Duration::from_secs(15);
+
+/// What a perfect clear search was run for: board, current piece, hold,
+/// whether hold is spent, and the visible queue.
+type PcKey = (BitBoard, PieceType, Option<PieceType>, bool, Vec<PieceType>);
 
 /// Top-level application state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AppState {
     Menu,
-    DifficultySelect,
+    /// Listing problems found in the config file at launch.
+    ConfigErrors,
+    /// Picking a key layout on the first launch.
+    Onboarding,
+    /// Offering to resume a game the last session left behind.
+    ResumePrompt,
+    /// Options for the mode picked in the menu, before it starts.
+    ModeSetup,
+    OpenerSelect,
+    /// Picking one of the scripts in the modes folder.
+    ScriptSelect,
     Playing,
+    /// Between gauntlet rounds.
+    GauntletBreak,
     Paused,
+    Settings,
+    /// Live input timing, opened from the settings.
+    InputDiagnostics,
+    /// Playing back the last seconds before a top out, ahead of the results.
+    Replay,
     GameOver,
     HighScores,
+    /// Every versus match played, opened from the menu.
+    MatchHistory,
     Quitting,
 }
 
@@ -41,72 +136,331 @@ pub struct App {
     pub input: InputHandler,
     pub effects: Effects,
     pub high_scores: HighScoreStore,
+    pub ghosts: GhostStore,
+    pub match_history: MatchHistory,
+    pub config: Config,
+    /// Seed every game is played with, from `--seed`; random when None.
+    pub seed: Option<u64>,
+    /// Entries of the config file that were ignored, shown at launch.
+    pub config_problems: Vec<String>,
+    /// Whether the config file itself had problems and was backed up, as
+    /// opposed to only the piece scripts it names.
+    config_backed_up: bool,
+    pub profile: Profile,
     pub game_result: Option<GameResult>,
+    /// XP the last finished game earned.
+    pub xp_award: Option<XpAward>,
+    /// How far the last versus match moved the player's rating.
+    pub rating_change: Option<f64>,
+    /// Whether the game over screen shows how the run was handled, or the
+    /// versus exchange timeline, rather than its stats.
+    pub show_details: bool,
+    /// Rows of the exchange timeline scrolled past.
+    pub timeline_scroll: usize,
+    /// Name being typed for a new high score, with its table.
+    pub name_entry: Option<(usize, TextInput)>,
+    /// Sandbox command being typed, with the last command's error.
+    pub command: Option<(TextInput, Option<String>)>,
+    /// Game left behind by the last session, while offering to resume it.
+    pub resume: Option<Autosave>,
+    /// Time since the game was last written out.
+    pub autosave_timer: Duration,
+    /// `pieces_placed` when the game was last staged, while autosaving.
+    pub autosave_pieces: Option<u32>,
+    /// `pieces_placed` when the game was last noted for crash reports.
+    crash_noted_pieces: Option<u32>,
+    /// Global leaderboard, when built with the `online` feature.
+    pub leaderboard: Leaderboard,
+    /// Result to submit once the player has named it.
+    pending_submission: Option<Submission>,
     pub frame: u64,
+    /// Time since launch, for screen animations.
+    pub clock: Duration,
+    /// Whether anything visible changed since the last draw.
+    dirty: bool,
+    /// Frame timings for the debug overlay.
+    pub perf: PerfStats,
+    /// Break down the versus AI's placements in a debug overlay.
+    pub show_ai_explain: bool,
+    /// Live JSON view of the game, with `--stream`.
+    pub stream: Option<StateStream>,
+    /// Words for screen readers, on with the setting or `--announce`.
+    pub announcer: Announcer,
+    /// Whether the keybind help is open over the screen.
+    pub help: bool,
+    /// Terminal size, once known, kept up to date from resize events.
+    pub terminal_size: Option<Rect>,
+    /// Whether the terminal was resized since the last redraw.
+    resized: bool,
+    /// Notice shown over every screen, with the time it has left.
+    pub toast: Option<(String, Duration)>,
+    /// The last seconds of play, for watching a top out again.
+    pub replay: InstantReplay,
+    /// Game time the replay is showing.
+    pub replay_time: Duration,
+    /// Text for the terminal to copy to the clipboard.
+    pub clipboard: Option<String>,
+    /// Whether the terminal bell should ring before the next draw.
+    bell: bool,
+    /// Time left to confirm quitting the game by pressing quit again. Its
+    /// prompt is drawn over any toast rather than replacing it.
+    quit_confirm: Duration,
+    /// Perfect clear searches for the practice hint.
+    pc_worker: PcWorker,
+    /// Position the latest search was asked about, with its id.
+    pc_asked: Option<(u64, PcKey)>,
+    /// Answer for that position, None while the search runs.
+    pc_outcome: Option<PcOutcome>,
 
     // Versus mode specific
     pub versus_mode: Option<VersusMode>,
     pub ai_effects: Effects,
     pub last_player_attack: u32,
+    pub battle_log: BattleLog,
+    /// Run in progress when versus rounds are part of a gauntlet.
+    pub gauntlet: Option<Gauntlet>,
+    /// Time left before the next gauntlet round starts.
+    pub gauntlet_break: Duration,
+    /// Battle royale lobby, which runs without a `GameMode` in `mode`.
+    pub royale: Option<RoyaleMode>,
 
-    // Difficulty selection
-    pub difficulty_selected: usize,
+    // Mode setup and opener selection
+    pub setup_mode: SetupMode,
+    pub setup_selected: usize,
+    pub opener_selected: usize,
+    /// Custom mode scripts, read again each time their list is opened.
+    pub scripts: Vec<ModeScript>,
+    pub script_selected: usize,
 
-    // High scores tab
+    // High scores tab, highlighted entry, and its detail page
     pub hs_tab: usize,
+    pub hs_selected: usize,
+    pub hs_detail: bool,
+    pub hs_view: TableView,
+    /// Highlighted match in the history, counted from the newest.
+    pub history_selected: usize,
+
+    /// Key layout highlighted on the first launch.
+    pub onboarding_selected: usize,
+
+    // Pause menu and settings
+    pub pause_selected: usize,
+    pub settings_selected: usize,
+    /// State to return to when leaving settings (menu or pause).
+    pub settings_return: AppState,
+
+    // Attract mode (AI demo behind the idle menu)
+    pub menu_idle: Duration,
+    pub attract: Option<AttractMode>,
 }
 
 impl App {
     pub fn new(has_key_release: bool) -> Self {
-        Self {
-            state: AppState::Menu,
+        let first_launch = !Config::exists();
+        let (config, mut config_problems) = Config::load();
+        let config_backed_up = !config_problems.is_empty();
+        config_problems.extend(config.piece_script_problems());
+        let mut input = InputHandler::new(has_key_release);
+        input.apply_config(&config);
+        let leaderboard = Leaderboard::new(config.leaderboard_url.clone());
+        let profile = Profile::load();
+        apply_cosmetics(&config, &profile);
+        let resume = Autosave::load().filter(|autosave| resumable_mode(&autosave.mode).is_some());
+        let state = if !config_problems.is_empty() {
+            AppState::ConfigErrors
+        } else if first_launch {
+            AppState::Onboarding
+        } else if resume.is_some() {
+            AppState::ResumePrompt
+        } else {
+            AppState::Menu
+        };
+        crash_report::note_state(&format!("{:?}", state));
+        let mut app = Self {
+            state,
             menu: MenuScreen::new(),
             game: GameState::new(),
             mode: None,
-            input: InputHandler::new(has_key_release),
+            input,
             effects: Effects::new(),
             high_scores: HighScoreStore::load(),
+            ghosts: GhostStore::load(),
+            match_history: MatchHistory::load(),
+            config,
+            seed: None,
+            config_problems,
+            config_backed_up,
+            profile,
             game_result: None,
+            xp_award: None,
+            rating_change: None,
+            show_details: false,
+            timeline_scroll: 0,
+            name_entry: None,
+            command: None,
+            resume,
+            autosave_timer: Duration::ZERO,
+            autosave_pieces: None,
+            crash_noted_pieces: None,
+            leaderboard,
+            pending_submission: None,
             frame: 0,
+            clock: Duration::ZERO,
+            dirty: true,
+            perf: PerfStats::new(),
+            show_ai_explain: false,
+            stream: None,
+            announcer: Announcer::new(),
+            help: false,
+            terminal_size: None,
+            resized: false,
+            toast: None,
+            replay: InstantReplay::new(),
+            replay_time: Duration::ZERO,
+            clipboard: None,
+            bell: false,
+            quit_confirm: Duration::ZERO,
+            pc_worker: PcWorker::new(),
+            pc_asked: None,
+            pc_outcome: None,
             versus_mode: None,
             ai_effects: Effects::new(),
             last_player_attack: 0,
-            difficulty_selected: 1, // Default to Medium
+            battle_log: BattleLog::new(),
+            gauntlet: None,
+            gauntlet_break: Duration::ZERO,
+            royale: None,
+            setup_mode: SetupMode::Sprint,
+            setup_selected: 0,
+            opener_selected: 0,
+            scripts: Vec::new(),
+            script_selected: 0,
             hs_tab: 0,
-        }
+            hs_selected: 0,
+            history_selected: 0,
+            hs_detail: false,
+            hs_view: TableView::default(),
+            onboarding_selected: 0,
+            pause_selected: 0,
+            settings_selected: 0,
+            settings_return: AppState::Menu,
+            menu_idle: Duration::ZERO,
+            attract: None,
+        };
+        app.apply_effect_intensity();
+        app
     }
 
     /// Process one frame. Returns false if the app should exit.
     pub fn update(&mut self, dt: Duration) -> bool {
         self.frame += 1;
+        self.clock += dt;
 
         // Poll input
+        self.input.mirrored = self.game.modifiers.contains(Modifier::Mirror);
         let inputs = self.input.poll();
+        let state_before = self.state;
+        if !inputs.is_empty() || self.input.key_pressed {
+            self.dirty = true;
+        }
+        if let Some((width, height)) = self.input.resized {
+            self.terminal_size = Some(Rect::new(0, 0, width, height));
+            self.resized = true;
+            self.dirty = true;
+        }
+
+        // Any key wakes the menu; the key that dismisses the demo is swallowed
+        if matches!(self.state, AppState::Menu) && self.input.key_pressed {
+            self.menu_idle = Duration::ZERO;
+            if self.attract.take().is_some() {
+                self.menu.transparent = false;
+                return true;
+            }
+        }
 
         // Handle inputs based on state
         for input in inputs {
+            if input == AppInput::TogglePerf {
+                self.perf.visible = !self.perf.visible;
+                continue;
+            }
+            if input == AppInput::ToggleAiExplain {
+                self.show_ai_explain = !self.show_ai_explain;
+                continue;
+            }
+            // Any key closes the help; key releases still reach the game
+            if self.help && input != AppInput::Game(GameAction::SoftDropRelease) {
+                self.help = false;
+                continue;
+            }
+            if input == AppInput::Help {
+                self.help = true;
+                self.input.reset_das();
+                continue;
+            }
+            crash_report::record_input(self.clock, &input);
             if !self.handle_input(input) {
                 return false;
             }
         }
 
+        if self.leaderboard.poll() {
+            self.dirty = true;
+        }
+
         // Game-specific updates
         match &self.state {
+            // The game holds still while a sandbox command is typed or the
+            // help is open
+            AppState::Playing if self.command.is_some() || self.help => {}
+            // Too small to draw the board: pause behind the size warning
+            // rather than play blind
+            AppState::Playing
+                if self
+                    .terminal_size
+                    .is_some_and(|size| !layout::check_size_single(size)) =>
+            {
+                self.pause_game();
+            }
             AppState::Playing => {
+                if self.input.tick_restart(dt) {
+                    self.restart_game();
+                }
+
                 // DAS-driven actions
                 let das_actions = self.input.tick_das(dt);
                 for action in das_actions {
-                    self.game.handle_action(action);
+                    match action {
+                        GameAction::MoveLeft | GameAction::MoveRight => {
+                            self.game.handle_auto_shift(action)
+                        }
+                        _ => self.game.handle_action(action),
+                    };
+                }
+                if let Some(ref mut mode) = self.mode {
+                    for &(action, down) in &self.input.key_edges {
+                        mode.record_input(&self.game, action, down);
+                    }
                 }
 
                 // Game tick
                 let _tick_result = self.game.update(dt);
+                self.replay.record(&self.game);
 
                 // Process game events
                 self.process_events();
 
                 // Update effects
-                self.effects.set_danger(self.game.is_danger());
+                let danger = self.game.danger_tier();
+                if danger == DangerTier::Critical
+                    && self.effects.danger_tier < DangerTier::Critical
+                    && self.config.danger_sting
+                {
+                    self.bell = true;
+                }
+                self.effects.set_danger(danger);
+                self.effects.set_zone(self.game.zone.is_active());
+                self.effects.set_btb(self.game.scoring.btb);
                 self.effects.update(dt);
 
                 // Update versus AI
@@ -117,167 +471,610 @@ impl App {
                         current_player_attack.saturating_sub(self.last_player_attack);
                     self.last_player_attack = current_player_attack;
                     if player_delta > 0 {
+                        tracing::debug!(lines = player_delta, "versus attack to ai");
                         vs.ai_game.garbage.add(player_delta);
                     }
 
-                    vs.update_ai(dt);
+                    let ai_start = Instant::now();
+                    vs.update_ai(&self.game, dt);
+                    self.perf.record_ai(ai_start.elapsed());
+                    for event in vs.ai_game.drain_events() {
+                        self.battle_log
+                            .record(LogSide::Ai, &event, self.game.stats.time);
+                        if let GameEvent::Surge(lines) = event {
+                            self.ai_effects.trigger_surge(lines);
+                            if let Some(clear_type) = vs.ai_game.last_clear_type.clone() {
+                                self.ai_effects
+                                    .trigger_action_text(clear_type, &vs.ai_game.scoring);
+                            }
+                        }
+                    }
 
                     // Feed AI attack to player as garbage
                     let ai_attack = vs.ai.check_attack(&vs.ai_game);
                     if ai_attack > 0 {
+                        tracing::debug!(lines = ai_attack, "versus attack to player");
                         self.game.garbage.add(ai_attack);
                     }
 
-                    self.ai_effects.set_danger(vs.ai_game.is_danger());
+                    self.ai_effects.set_danger(vs.ai_game.danger_tier());
+                    self.ai_effects.set_btb(vs.ai_game.scoring.btb);
                     self.ai_effects.update(dt);
                 }
 
+                // Update the battle royale lobby
+                if let Some(ref mut royale) = self.royale {
+                    let ai_start = Instant::now();
+                    royale.update(&mut self.game, dt);
+                    self.perf.record_ai(ai_start.elapsed());
+                    if let Some(mut result) = royale.check_complete(&self.game) {
+                        result.is_new_high_score = self.check_and_save_high_score(&result);
+                        self.game_result = Some(result);
+                        self.show_result();
+                    }
+                }
+
+                // Mode per-tick hook
+                if let Some(ref mut mode) = self.mode {
+                    mode.on_update(&mut self.game);
+                }
+                self.update_pc_hint();
+                self.tick_autosave(dt);
+
+                // Snapshotting every frame would be wasted work; the game
+                // only changes much as pieces lock
+                let pieces = self.game.stats.pieces_placed;
+                if self.crash_noted_pieces != Some(pieces) {
+                    self.crash_noted_pieces = Some(pieces);
+                    crash_report::note_game(self.mode_name(), &self.game);
+                }
+
                 // Check mode completion
                 if let Some(ref mode) = self.mode {
                     if let Some(mut result) = mode.check_complete(&self.game) {
+                        // Ghosts are only kept for the standard 40 lines,
+                        // played without modifiers
+                        let recording = mode
+                            .recording()
+                            .filter(|_| mode.line_goal() == Some(40))
+                            .filter(|_| self.game.modifiers.is_empty())
+                            .cloned();
                         // Check high scores
                         result.is_new_high_score = self.check_and_save_high_score(&result);
+                        // A personal best becomes the ghost to race next time
+                        if result.is_new_high_score && !self.game.game_over {
+                            if let Some(recording) = recording {
+                                self.ghosts.sprint = Some(recording);
+                                self.ghosts.save();
+                            }
+                        }
                         self.game_result = Some(result);
-                        self.state = AppState::GameOver;
-                        self.input.in_game = false;
+                        self.show_result();
                     }
                 }
 
                 // Also check versus mode completion
                 if let Some(ref vs) = self.versus_mode {
-                    let player_dead = self.game.game_over;
-                    let ai_dead = vs.ai_game.game_over;
-                    if player_dead || ai_dead {
-                        let won = ai_dead && !player_dead;
-                        let mut result = GameResult {
-                            mode_name: format!("Versus AI ({})", vs.difficulty.name()),
-                            primary_label: if won { "VICTORY" } else { "DEFEAT" }.to_string(),
-                            primary_value: format!(
-                                "ATK: {} | RCV: {}",
-                                self.game.stats.attack_sent, self.game.stats.garbage_received
-                            ),
-                            is_new_high_score: false,
-                            stats: self.game.stats.clone(),
-                            won: Some(won),
-                        };
-                        result.is_new_high_score = self.check_and_save_high_score(&result);
-                        self.game_result = Some(result);
-                        self.state = AppState::GameOver;
-                        self.input.in_game = false;
+                    if let Some(mut result) = vs.check_complete(&self.game) {
+                        let won = result.won == Some(true);
+                        let ai_rating = rating::ai_rating(vs.difficulty, vs.ai.skill);
+                        self.rating_change = Some(self.match_history.add(
+                            vs.difficulty,
+                            vs.ai.personality,
+                            ai_rating,
+                            won,
+                            &self.game.stats,
+                        ));
+                        if self.gauntlet.is_some() {
+                            self.finish_gauntlet_round(won);
+                        } else {
+                            result.is_new_high_score = self.check_and_save_high_score(&result);
+                            self.game_result = Some(result);
+                            self.show_result();
+                        }
                     }
                 }
             }
             AppState::Menu => {
-                self.menu.frame = self.frame;
+                self.menu.time = self.clock.as_secs_f32();
+                self.menu.ruleset = self.config.ruleset;
+                self.menu.level = self.profile.level();
+                self.menu.level_progress = self.profile.progress();
+                self.menu.rating =
+                    (!self.match_history.matches.is_empty()).then(|| self.match_history.rating());
+
+                self.menu_idle += dt;
+                if self.attract.is_none() && self.menu_idle >= ATTRACT_DELAY {
+                    self.attract = Some(AttractMode::new());
+                }
+                if let Some(ref mut attract) = self.attract {
+                    let ai_start = Instant::now();
+                    attract.update(dt);
+                    self.perf.record_ai(ai_start.elapsed());
+                }
+                self.menu.transparent = self.attract.is_some();
+            }
+            AppState::GauntletBreak => {
+                self.gauntlet_break = self.gauntlet_break.saturating_sub(dt);
+                if self.gauntlet_break.is_zero() {
+                    self.next_gauntlet_round();
+                }
+            }
+            AppState::Replay => {
+                self.replay_time += dt;
+                let end = self.replay.span().map_or(Duration::ZERO, |(_, end)| end);
+                if self.replay_time >= end + REPLAY_HOLD {
+                    self.state = AppState::GameOver;
+                }
             }
             AppState::GameOver => {}
             _ => {}
         }
 
+        // Say when high scores couldn't be read or written rather than
+        // losing them quietly
+        if let Some(error) = self.high_scores.error.take() {
+            self.toast = Some((error, TOAST_TIME));
+            self.dirty = true;
+        }
+        if !self.quit_confirm.is_zero() {
+            self.quit_confirm = self.quit_confirm.saturating_sub(dt);
+            self.dirty |= self.quit_confirm.is_zero();
+        }
+        if let Some((_, ref mut left)) = self.toast {
+            *left = left.saturating_sub(dt);
+            if left.is_zero() {
+                self.toast = None;
+                self.dirty = true;
+            }
+        }
+
+        // Stream the game while there is one on screen
+        let has_game = matches!(
+            self.state,
+            AppState::Playing
+                | AppState::Paused
+                | AppState::GauntletBreak
+                | AppState::Replay
+                | AppState::GameOver
+        );
+        if let Some(mut stream) = self.stream.take() {
+            if has_game {
+                let state = format!("{:?}", self.state);
+                stream.publish(self.frame, &state, self.mode_name(), &self.game);
+            }
+            self.stream = Some(stream);
+        }
+
+        if self.state != state_before {
+            tracing::info!(from = ?state_before, to = ?self.state, "state change");
+            crash_report::note_state(&format!("{:?}", self.state));
+            // Whatever game is played next gets noted afresh
+            self.crash_noted_pieces = None;
+        }
+        if self.state != state_before || self.is_animated() {
+            self.dirty = true;
+        }
+
+        // A game that ended or was left on purpose isn't offered next launch
+        let in_game = matches!(
+            self.state,
+            AppState::Playing | AppState::Paused | AppState::Settings | AppState::InputDiagnostics
+        );
+        if self.autosave_pieces.is_some() && !in_game {
+            Autosave::clear();
+            self.autosave_pieces = None;
+        }
+
+        if !matches!(self.state, AppState::Menu) {
+            self.menu_idle = Duration::ZERO;
+            self.attract = None;
+            self.menu.transparent = false;
+        }
+
         true
     }
 
     /// Handle a single input event. Returns false to quit.
     fn handle_input(&mut self, input: AppInput) -> bool {
         match &self.state {
+            AppState::ConfigErrors => {
+                if matches!(
+                    input,
+                    AppInput::MenuSelect | AppInput::MenuBack | AppInput::Quit
+                ) {
+                    self.state = if self.resume.is_some() {
+                        AppState::ResumePrompt
+                    } else {
+                        AppState::Menu
+                    };
+                }
+            }
+            AppState::Onboarding => match input {
+                AppInput::MenuUp => {
+                    self.onboarding_selected = self.onboarding_selected.saturating_sub(1)
+                }
+                AppInput::MenuDown => {
+                    self.onboarding_selected =
+                        (self.onboarding_selected + 1).min(KeybindPreset::ALL.len() - 1)
+                }
+                AppInput::MenuSelect => {
+                    self.config.keybind_preset = KeybindPreset::ALL[self.onboarding_selected];
+                    self.finish_onboarding();
+                }
+                // Keep the default, but don't ask again
+                AppInput::MenuBack | AppInput::Quit => self.finish_onboarding(),
+                _ => {}
+            },
+            AppState::ResumePrompt => match input {
+                AppInput::MenuSelect => self.resume_autosave(),
+                AppInput::MenuBack | AppInput::Quit => {
+                    self.resume = None;
+                    Autosave::clear();
+                    self.state = AppState::Menu;
+                }
+                _ => {}
+            },
             AppState::Menu => match input {
                 AppInput::MenuUp => self.menu.move_up(),
                 AppInput::MenuDown => self.menu.move_down(),
+                AppInput::MenuLeft => self.step_ruleset(-1),
+                AppInput::MenuRight => self.step_ruleset(1),
                 AppInput::MenuSelect => match self.menu.selected_choice() {
-                    MenuChoice::Sprint => self.start_sprint(),
-                    MenuChoice::Endless => self.start_endless(),
-                    MenuChoice::Versus => {
-                        self.state = AppState::DifficultySelect;
+                    MenuChoice::Sprint => self.open_setup(SetupMode::Sprint),
+                    MenuChoice::Endless => self.open_setup(SetupMode::Endless),
+                    MenuChoice::Zen => self.open_setup(SetupMode::Zen),
+                    MenuChoice::Versus => self.open_setup(SetupMode::Versus),
+                    MenuChoice::Gauntlet => self.start_gauntlet(),
+                    MenuChoice::Royale => self.start_royale(),
+                    MenuChoice::Trainer => {
+                        self.state = AppState::OpenerSelect;
+                    }
+                    MenuChoice::Custom => {
+                        self.scripts = ModeScript::load_all();
+                        self.script_selected = 0;
+                        self.state = AppState::ScriptSelect;
                     }
                     MenuChoice::HighScores => {
                         self.state = AppState::HighScores;
+                        self.hs_selected = 0;
+                        self.hs_detail = false;
+                    }
+                    MenuChoice::MatchHistory => {
+                        self.state = AppState::MatchHistory;
+                        self.history_selected = 0;
                     }
+                    MenuChoice::Settings => self.open_settings(),
                     MenuChoice::Quit => return false,
                 },
                 AppInput::Quit | AppInput::MenuBack => return false,
                 _ => {}
             },
-            AppState::DifficultySelect => match input {
+            AppState::ModeSetup => match input {
+                AppInput::MenuUp => {
+                    if self.setup_selected > 0 {
+                        self.setup_selected -= 1;
+                    } else {
+                        self.setup_selected = self.setup_mode.items().len() - 1;
+                    }
+                }
+                AppInput::MenuDown => {
+                    self.setup_selected = (self.setup_selected + 1) % self.setup_mode.items().len();
+                }
+                AppInput::MenuLeft => {
+                    self.setup_mode.items()[self.setup_selected].adjust(&mut self.config, -1)
+                }
+                AppInput::MenuRight => {
+                    self.setup_mode.items()[self.setup_selected].adjust(&mut self.config, 1)
+                }
+                AppInput::MenuSelect => {
+                    self.config.save();
+                    match self.setup_mode {
+                        SetupMode::Sprint => self.start_sprint(),
+                        SetupMode::Endless => self.start_endless(),
+                        SetupMode::Zen => self.start_zen(),
+                        SetupMode::Versus => {
+                            self.gauntlet = None;
+                            self.start_versus(
+                                self.config.versus_difficulty,
+                                self.config.versus_personality,
+                                self.config.versus_fair_bag,
+                                self.config.versus_goal,
+                                self.config.modifiers,
+                            );
+                        }
+                    }
+                }
+                AppInput::MenuBack | AppInput::Quit => {
+                    self.config.save();
+                    self.state = AppState::Menu;
+                }
+                _ => {}
+            },
+            AppState::OpenerSelect => match input {
                 AppInput::MenuUp => {
-                    if self.difficulty_selected > 0 {
-                        self.difficulty_selected -= 1;
+                    if self.opener_selected > 0 {
+                        self.opener_selected -= 1;
                     } else {
-                        self.difficulty_selected = 3;
+                        self.opener_selected = OPENERS.len() - 1;
                     }
                 }
                 AppInput::MenuDown => {
-                    self.difficulty_selected = (self.difficulty_selected + 1) % 4;
+                    self.opener_selected = (self.opener_selected + 1) % OPENERS.len();
                 }
                 AppInput::MenuSelect => {
-                    let diff = match self.difficulty_selected {
-                        0 => AiDifficulty::Easy,
-                        1 => AiDifficulty::Medium,
-                        2 => AiDifficulty::Hard,
-                        _ => AiDifficulty::Expert,
-                    };
-                    self.start_versus(diff);
+                    let opener = &OPENERS[self.opener_selected];
+                    self.start_mode(Box::new(TrainerMode::new(opener)), Modifiers::default());
+                }
+                AppInput::MenuBack | AppInput::Quit => {
+                    self.state = AppState::Menu;
+                }
+                _ => {}
+            },
+            AppState::ScriptSelect => match input {
+                AppInput::MenuUp if !self.scripts.is_empty() => {
+                    if self.script_selected > 0 {
+                        self.script_selected -= 1;
+                    } else {
+                        self.script_selected = self.scripts.len() - 1;
+                    }
+                }
+                AppInput::MenuDown if !self.scripts.is_empty() => {
+                    self.script_selected = (self.script_selected + 1) % self.scripts.len();
+                }
+                AppInput::MenuSelect => {
+                    let mode = self
+                        .scripts
+                        .get(self.script_selected)
+                        .and_then(|s| s.mode());
+                    if let Some(mode) = mode {
+                        self.start_mode(Box::new(mode), Modifiers::default());
+                    }
                 }
                 AppInput::MenuBack | AppInput::Quit => {
                     self.state = AppState::Menu;
                 }
                 _ => {}
             },
+            AppState::Playing if self.command.is_some() => match input {
+                AppInput::Char(c) => {
+                    if let Some((ref mut command, _)) = self.command {
+                        command.push(c);
+                    }
+                }
+                AppInput::Backspace => {
+                    if let Some((ref mut command, _)) = self.command {
+                        command.pop();
+                    }
+                }
+                AppInput::MenuSelect => self.run_command(),
+                AppInput::MenuBack => self.close_command(),
+                _ => {}
+            },
             AppState::Playing => match input {
                 AppInput::Game(action) => {
                     self.game.handle_action(action);
                 }
-                AppInput::Pause => {
-                    self.state = AppState::Paused;
+                AppInput::Pause => self.pause_game(),
+                // Held restarts come from the input handler's timer
+                AppInput::Restart if self.input.restart.duration.is_zero() => {
+                    self.restart_game();
+                }
+                AppInput::CycleTarget => {
+                    if let Some(ref mut royale) = self.royale {
+                        royale.cycle_strategy();
+                    }
+                }
+                AppInput::Command if self.mode.as_ref().is_some_and(|m| m.allows_cheats()) => {
+                    self.command = Some((TextInput::new("", COMMAND_MAX_LEN), None));
+                    self.input.text_entry = true;
                     self.input.reset_das();
                 }
-                AppInput::Restart => {
-                    self.restart_game();
+                AppInput::Undo => {
+                    if let Some(ref mut mode) = self.mode {
+                        if mode.undo(&mut self.game) {
+                            self.effects.reset();
+                        }
+                    }
+                }
+                AppInput::Quit if self.config.confirm_quit && self.quit_confirm.is_zero() => {
+                    self.quit_confirm = QUIT_CONFIRM_TIME;
                 }
                 AppInput::Quit => {
+                    self.quit_confirm = Duration::ZERO;
                     self.state = AppState::Menu;
                     self.input.in_game = false;
                     self.input.reset_das();
                 }
                 _ => {}
             },
+            AppState::GauntletBreak => match input {
+                AppInput::MenuSelect => self.next_gauntlet_round(),
+                AppInput::MenuBack | AppInput::Quit => {
+                    self.gauntlet = None;
+                    self.state = AppState::Menu;
+                }
+                _ => {}
+            },
             AppState::Paused => match input {
-                AppInput::Pause => {
-                    self.state = AppState::Playing;
+                AppInput::MenuUp => {
+                    if self.pause_selected > 0 {
+                        self.pause_selected -= 1;
+                    } else {
+                        self.pause_selected = PauseChoice::ALL.len() - 1;
+                    }
                 }
-                AppInput::Restart => {
-                    self.restart_game();
-                    self.state = AppState::Playing;
+                AppInput::MenuDown => {
+                    self.pause_selected = (self.pause_selected + 1) % PauseChoice::ALL.len();
                 }
-                AppInput::Quit | AppInput::MenuBack => {
+                AppInput::MenuSelect => match PauseChoice::ALL[self.pause_selected] {
+                    PauseChoice::Resume => self.resume_game(),
+                    PauseChoice::Restart => self.restart_game(),
+                    PauseChoice::Settings => self.open_settings(),
+                    PauseChoice::Quit => {
+                        self.state = AppState::Menu;
+                    }
+                },
+                AppInput::MenuBack => self.resume_game(),
+                AppInput::Quit => {
                     self.state = AppState::Menu;
-                    self.input.in_game = false;
-                    self.input.reset_das();
                 }
                 _ => {}
             },
+            AppState::Settings => match input {
+                AppInput::MenuUp => {
+                    if self.settings_selected > 0 {
+                        self.settings_selected -= 1;
+                    } else {
+                        self.settings_selected = SettingItem::ALL.len() - 1;
+                    }
+                }
+                AppInput::MenuDown => {
+                    self.settings_selected = (self.settings_selected + 1) % SettingItem::ALL.len();
+                }
+                AppInput::MenuLeft => self.adjust_setting(-1),
+                AppInput::MenuRight => self.adjust_setting(1),
+                AppInput::MenuSelect
+                    if SettingItem::ALL[self.settings_selected] == SettingItem::Diagnostics =>
+                {
+                    self.input.diag.reset();
+                    self.state = AppState::InputDiagnostics;
+                }
+                AppInput::MenuSelect
+                    if SettingItem::ALL[self.settings_selected] == SettingItem::Back =>
+                {
+                    self.close_settings();
+                }
+                AppInput::MenuBack | AppInput::Quit => self.close_settings(),
+                _ => {}
+            },
+            // Every other key is only there to be measured
+            AppState::InputDiagnostics => {
+                if input == AppInput::MenuBack {
+                    self.state = AppState::Settings;
+                }
+            }
+            AppState::Replay => match input {
+                AppInput::MenuLeft | AppInput::MenuRight => {
+                    if let Some((start, end)) = self.replay.span() {
+                        let time = if input == AppInput::MenuLeft {
+                            self.replay_time.saturating_sub(REPLAY_SEEK)
+                        } else {
+                            self.replay_time + REPLAY_SEEK
+                        };
+                        self.replay_time = time.clamp(start, end);
+                    }
+                }
+                AppInput::Restart => self.restart_game(),
+                AppInput::MenuSelect | AppInput::MenuBack | AppInput::Quit => {
+                    self.state = AppState::GameOver;
+                }
+                _ => {}
+            },
+            AppState::GameOver if self.name_entry.is_some() => match input {
+                AppInput::Char(c) => {
+                    if let Some((_, ref mut name)) = self.name_entry {
+                        name.push(c);
+                    }
+                }
+                AppInput::Backspace => {
+                    if let Some((_, ref mut name)) = self.name_entry {
+                        name.pop();
+                    }
+                }
+                AppInput::MenuSelect => self.finish_name_entry(true),
+                // Skip: the entry keeps the remembered name
+                AppInput::MenuBack => self.finish_name_entry(false),
+                _ => {}
+            },
             AppState::GameOver => match input {
                 AppInput::Restart | AppInput::MenuSelect => {
                     self.restart_game();
                 }
+                AppInput::SaveResult => self.save_result(),
+                AppInput::CopyResult => {
+                    if let Some(ref result) = self.game_result {
+                        let line = summary::share_line(result);
+                        self.toast = Some((format!("Copied: {}", line), TOAST_TIME));
+                        self.clipboard = Some(line);
+                    }
+                }
+                AppInput::MenuLeft | AppInput::MenuRight
+                    if self.handling().is_some() || self.versus_mode.is_some() =>
+                {
+                    self.show_details = !self.show_details;
+                    self.timeline_scroll = 0;
+                }
+                AppInput::MenuUp if self.show_details => {
+                    self.timeline_scroll = self.timeline_scroll.saturating_sub(1);
+                }
+                AppInput::MenuDown if self.show_details => {
+                    if let Some(ref vs) = self.versus_mode {
+                        let rows = self.game.exchanges.len() + vs.ai_game.exchanges.len();
+                        self.timeline_scroll = (self.timeline_scroll + 1).min(rows);
+                    }
+                }
                 AppInput::MenuBack | AppInput::Quit => {
                     self.state = AppState::Menu;
                     self.input.in_game = false;
                 }
                 _ => {}
             },
-            AppState::HighScores => match input {
-                AppInput::MenuBack | AppInput::Quit => {
-                    self.state = AppState::Menu;
-                }
-                AppInput::MenuLeft => {
-                    if self.hs_tab > 0 {
+            AppState::HighScores => {
+                let count = self.hs_view.entries(&self.high_scores, self.hs_tab).len();
+                match input {
+                    AppInput::MenuBack | AppInput::Quit if self.hs_detail => {
+                        self.hs_detail = false;
+                    }
+                    AppInput::MenuBack | AppInput::Quit => {
+                        self.state = AppState::Menu;
+                    }
+                    AppInput::MenuUp if self.hs_selected > 0 => {
+                        self.hs_selected -= 1;
+                    }
+                    AppInput::MenuDown if self.hs_selected + 1 < count => {
+                        self.hs_selected += 1;
+                    }
+                    AppInput::MenuSelect if count > 0 => {
+                        self.hs_detail = true;
+                    }
+                    AppInput::MenuLeft if !self.hs_detail && self.hs_tab > 0 => {
                         self.hs_tab -= 1;
+                        self.hs_selected = 0;
                     }
-                }
-                AppInput::MenuRight => {
-                    if self.hs_tab < 2 {
+                    AppInput::MenuRight
+                        if !self.hs_detail && self.hs_tab + 1 < high_scores::TABS.len() =>
+                    {
                         self.hs_tab += 1;
+                        self.hs_selected = 0;
+                        if self.hs_tab == high_scores::GLOBAL_TAB {
+                            self.leaderboard.fetch();
+                        }
+                    }
+                    AppInput::CycleSort if !self.hs_detail && self.hs_tab == 2 => {
+                        self.hs_view.versus_sort = self.hs_view.versus_sort.next();
+                        self.hs_selected = 0;
+                    }
+                    AppInput::CycleFilter if !self.hs_detail && self.hs_tab == 0 => {
+                        self.hs_view.cycle_sprint_length(&self.high_scores);
+                        self.hs_selected = 0;
+                    }
+                    AppInput::CycleFilter if self.hs_tab == high_scores::GLOBAL_TAB => {
+                        self.leaderboard.board = self.leaderboard.board.next();
+                        self.leaderboard.fetch();
                     }
+                    _ => {}
+                }
+            }
+            AppState::MatchHistory => match input {
+                AppInput::MenuBack | AppInput::Quit => self.state = AppState::Menu,
+                AppInput::MenuUp => {
+                    self.history_selected = self.history_selected.saturating_sub(1);
+                }
+                AppInput::MenuDown
+                    if self.history_selected + 1 < self.match_history.matches.len() =>
+                {
+                    self.history_selected += 1;
                 }
                 _ => {}
             },
@@ -286,140 +1083,796 @@ impl App {
         true
     }
 
-    fn start_sprint(&mut self) {
-        self.game = GameState::new();
-        self.effects.reset();
-        let mode = SprintMode::new();
-        self.mode = Some(Box::new(mode));
-        self.versus_mode = None;
-        self.game.start();
-        self.state = AppState::Playing;
-        self.input.in_game = true;
+    fn pause_game(&mut self) {
+        // Pause menu is navigated with menu keys
+        self.state = AppState::Paused;
+        self.pause_selected = 0;
+        self.input.in_game = false;
         self.input.reset_das();
     }
 
-    fn start_endless(&mut self) {
-        self.game = GameState::new();
-        self.effects.reset();
-        let mode = EndlessMode::new();
-        self.mode = Some(Box::new(mode));
-        self.versus_mode = None;
-        self.game.start();
+    fn resume_game(&mut self) {
         self.state = AppState::Playing;
         self.input.in_game = true;
         self.input.reset_das();
     }
 
-    fn start_versus(&mut self, difficulty: AiDifficulty) {
-        self.game = GameState::new();
-        self.effects.reset();
-        self.ai_effects.reset();
-        self.last_player_attack = 0;
-        let mut vs = VersusMode::new(difficulty);
-        self.game.start();
-        vs.ai_game.start();
-        vs.ai.reset();
-        self.versus_mode = Some(vs);
-        self.mode = None; // Versus handles its own completion
-        self.state = AppState::Playing;
-        self.input.in_game = true;
-        self.input.reset_das();
+    /// Save the key layout picked on the first launch and carry on.
+    fn finish_onboarding(&mut self) {
+        self.input.apply_config(&self.config);
+        self.config.save();
+        self.state = if self.resume.is_some() {
+            AppState::ResumePrompt
+        } else {
+            AppState::Menu
+        };
     }
 
-    fn restart_game(&mut self) {
-        self.effects.reset();
-        self.last_player_attack = 0;
-        self.game = GameState::new();
-        self.game.start();
-        if let Some(ref mut vs) = self.versus_mode {
-            self.ai_effects.reset();
-            vs.ai_game = GameState::new();
-            vs.ai_game.start();
-            vs.ai.reset();
-        }
-        self.state = AppState::Playing;
-        self.input.in_game = true;
-        self.input.reset_das();
+    fn open_settings(&mut self) {
+        self.settings_return = self.state;
+        self.settings_selected = 0;
+        self.state = AppState::Settings;
     }
 
-    fn process_events(&mut self) {
-        let events = self.game.drain_events();
-        for event in events {
-            match event {
-                GameEvent::HardDrop { cells } => {
-                    self.effects.trigger_hard_drop(cells);
-                }
-                GameEvent::PieceLocked => {
-                    self.effects.trigger_lock();
-                }
-                GameEvent::LinesClear(rows) => {
-                    self.effects.trigger_line_clear(rows);
-                }
-                GameEvent::PerfectClear => {
-                    self.effects.trigger_pc();
-                }
-                GameEvent::TSpin(_) | GameEvent::Combo(_) | GameEvent::BackToBack(_) => {
-                    self.effects.trigger_action_text();
-                }
-                _ => {}
-            }
-        }
+    fn adjust_setting(&mut self, delta: i64) {
+        let level = self.profile.level();
+        SettingItem::ALL[self.settings_selected].adjust(&mut self.config, delta, level);
+        self.input.apply_config(&self.config);
+        apply_cosmetics(&self.config, &self.profile);
+        self.apply_effect_intensity();
     }
 
-    fn check_and_save_high_score(&mut self, result: &GameResult) -> bool {
-        if result.won == Some(false) {
+    /// Scale both boards' effects by the configured intensities.
+    fn apply_effect_intensity(&mut self) {
+        let intensity = EffectIntensity::from_config(&self.config);
+        self.effects.intensity = intensity;
+        self.ai_effects.intensity = intensity;
+    }
+
+    /// Cycle the ruleset the next game starts with.
+    fn step_ruleset(&mut self, delta: i64) {
+        self.config.ruleset = step_option(&RulesetPreset::ALL, self.config.ruleset, delta);
+        self.config.save();
+    }
+
+    fn open_setup(&mut self, mode: SetupMode) {
+        self.setup_mode = mode;
+        self.setup_selected = mode.items().len() - 1;
+        self.state = AppState::ModeSetup;
+    }
+
+    fn close_settings(&mut self) {
+        self.config.save();
+        self.state = self.settings_return;
+    }
+
+    /// Start a mode given on the command line, skipping the menu and any
+    /// launch prompts.
+    pub fn launch(&mut self, mode: ModeArg) {
+        self.resume = None;
+        if !self.config_problems.is_empty() {
+            self.toast = Some((
+                format!(
+                    "{} problem(s) in the config file; defaults used",
+                    self.config_problems.len()
+                ),
+                TOAST_TIME,
+            ));
+        }
+        match mode {
+            ModeArg::Sprint => self.start_sprint(),
+            ModeArg::Endless => self.start_endless(),
+            ModeArg::Zen => self.start_zen(),
+            ModeArg::Versus { difficulty } => self.start_versus(
+                difficulty.difficulty(),
+                self.config.versus_personality,
+                self.config.versus_fair_bag,
+                self.config.versus_goal,
+                self.config.modifiers,
+            ),
+            ModeArg::Gauntlet => self.start_gauntlet(),
+            ModeArg::Royale => self.start_royale(),
+        }
+        crash_report::note_state(&format!("{:?}", self.state));
+    }
+
+    fn start_sprint(&mut self) {
+        let lines = self.config.sprint_lines;
+        let ghost = self.ghosts.sprint.clone().filter(|_| lines == 40);
+        let mut mode = SprintMode::with_ghost(ghost);
+        mode.target_lines = lines;
+        // Paced against the best run in the category this one ranks in
+        let category = (lines, self.config.modifiers.is_purist());
+        let pb_splits = self
+            .high_scores
+            .sprint
+            .iter()
+            .find(|e| e.category() == category)
+            .map(|pb| pb.splits.clone())
+            .unwrap_or_default();
+        let mode = mode.with_pb_splits(pb_splits);
+        self.start_mode(Box::new(mode), self.config.modifiers);
+    }
+
+    fn start_endless(&mut self) {
+        let level = self.config.endless_level;
+        self.start_mode(
+            Box::new(EndlessMode::with_start_level(level)),
+            self.config.modifiers,
+        );
+    }
+
+    fn start_zen(&mut self) {
+        let level = self.config.zen_level;
+        self.start_mode(
+            Box::new(ZenMode::with_start_level(level)),
+            self.config.modifiers,
+        );
+    }
+
+    /// Pick up the game the last session left behind.
+    fn resume_autosave(&mut self) {
+        let Some(autosave) = self.resume.take() else {
+            return;
+        };
+        let Some(mode) = resumable_mode(&autosave.mode) else {
+            self.state = AppState::Menu;
+            return;
+        };
+        self.start_mode(mode, Modifiers::default());
+        self.game = GameState::from_snapshot(&autosave.game);
+        self.game.garbage.pattern = self.config.garbage_pattern;
+        self.game.garbage.kind = self.config.garbage_kind;
+    }
+
+    /// Keep a resumable game recoverable: staged after every placement for
+    /// the panic hook, and written out every few seconds in case the
+    /// terminal dies without one.
+    fn tick_autosave(&mut self, dt: Duration) {
+        let Some(ref mode) = self.mode else {
+            return;
+        };
+        if !mode.resumable() || self.game.game_over || self.game.current_piece.is_none() {
+            return;
+        }
+
+        let pieces = self.game.stats.pieces_placed;
+        if self.autosave_pieces != Some(pieces) {
+            self.autosave_pieces = Some(pieces);
+            Autosave::new(mode.name(), self.game.snapshot()).stage();
+        }
+        self.autosave_timer += dt;
+        if self.autosave_timer >= AUTOSAVE_INTERVAL {
+            self.autosave_timer = Duration::ZERO;
+            Autosave::new(mode.name(), self.game.snapshot()).save();
+        }
+    }
+
+    /// Fresh game using the ruleset configured for the mode.
+    fn new_game(&self, mode_name: &str) -> GameState {
+        let mut game = match self.seed {
+            Some(seed) => GameState::with_seed(seed),
+            None => GameState::new(),
+        };
+        game.ruleset = self.config.ruleset_for(mode_name);
+        game.script = self.config.piece_script_for(mode_name);
+        game.garbage.pattern = self.config.garbage_pattern;
+        game.garbage.kind = self.config.garbage_kind;
+        game
+    }
+
+    /// Start a single-player game in the given mode.
+    fn start_mode(&mut self, mut mode: Box<dyn GameMode>, modifiers: Modifiers) {
+        self.game = self.new_game(mode.name());
+        self.game.modifiers = modifiers;
+        self.effects.reset();
+        self.announcer.reset();
+        self.replay.clear();
+        self.versus_mode = None;
+        self.gauntlet = None;
+        self.royale = None;
+        self.game.start();
+        mode.on_start(&mut self.game);
+        self.mode = Some(mode);
+        self.show_details = false;
+        self.state = AppState::Playing;
+        self.input.in_game = true;
+        self.input.reset_das();
+    }
+
+    /// Move on to the finished game's result, first playing back the last
+    /// seconds if the player topped out.
+    fn show_result(&mut self) {
+        self.input.in_game = false;
+        match self.replay.span() {
+            Some((start, _)) if self.game.game_over => {
+                self.replay_time = start;
+                self.state = AppState::Replay;
+            }
+            _ => self.state = AppState::GameOver,
+        }
+    }
+
+    /// Write the finished game's summary to a file, saying where.
+    fn save_result(&mut self) {
+        let Some(ref result) = self.game_result else {
+            return;
+        };
+        let message = match summary::save(result, self.game.modifiers, self.game.seed) {
+            Ok(path) => format!("Saved to {}", path.display()),
+            Err(e) => format!("Couldn't save the result: {}", e),
+        };
+        self.toast = Some((message, TOAST_TIME));
+    }
+
+    /// How the last run was handled, for modes that record their inputs.
+    fn handling(&self) -> Option<Handling> {
+        let recording = self.mode.as_ref()?.recording()?;
+        if recording.inputs.is_empty() {
+            return None;
+        }
+        Some(recording.handling(self.input.das.das_delay))
+    }
+
+    fn start_versus(
+        &mut self,
+        difficulty: AiDifficulty,
+        personality: AiPersonality,
+        fair_bag: bool,
+        goal: VersusGoal,
+        modifiers: Modifiers,
+    ) {
+        self.game = self.new_game(VERSUS_MODE_NAME);
+        self.game.modifiers = modifiers;
+        self.effects.reset();
+        self.announcer.reset();
+        self.replay.clear();
+        self.ai_effects.reset();
+        self.battle_log.clear();
+        self.last_player_attack = 0;
+        let mut vs = match self.seed {
+            Some(seed) => VersusMode::with_seed(difficulty, seed),
+            None => VersusMode::new(difficulty),
+        };
+        vs.ai_game.ruleset = self.game.ruleset.clone();
+        vs.ai_game.garbage.pattern = self.game.garbage.pattern;
+        vs.ai_game.garbage.kind = self.game.garbage.kind;
+        // The rising floor and mercy rule are picked on the versus setup
+        // screen; gauntlet rounds always play without them, and mercy is a
+        // help for Easy matches only
+        let setup = self.gauntlet.is_none();
+        let rising = (self.config.rising_floor > 0 && setup)
+            .then(|| Duration::from_secs(self.config.rising_floor as u64));
+        self.game.garbage.solid_interval = rising;
+        let mercy = self.config.versus_mercy && difficulty == AiDifficulty::Easy;
+        self.game.garbage.mercy = (mercy && setup).then_some(MERCY_GRACE);
+        vs.ai_game.garbage.solid_interval = rising;
+        vs.fair_bag = fair_bag;
+        vs.goal = goal;
+        if fair_bag {
+            vs.share_bag(&self.game);
+        }
+        self.game.start();
+        vs.ai_game.start();
+        vs.ai.reset();
+        vs.ai.personality = personality;
+        self.versus_mode = Some(vs);
+        self.royale = None;
+        self.mode = None; // Versus handles its own completion
+        self.show_details = false;
+        self.rating_change = None;
+        self.state = AppState::Playing;
+        self.input.in_game = true;
+        self.input.reset_das();
+    }
+
+    /// Start a battle royale against a full lobby.
+    fn start_royale(&mut self) {
+        let mut royale = match self.seed {
+            Some(seed) => RoyaleMode::with_seed(ROYALE_OPPONENTS, seed),
+            None => RoyaleMode::new(ROYALE_OPPONENTS),
+        };
+        self.game = self.new_game(royale.name());
+        self.effects.reset();
+        self.announcer.reset();
+        self.replay.clear();
+        self.game.start();
+        royale.on_start(&mut self.game);
+        self.royale = Some(royale);
+        self.versus_mode = None;
+        self.gauntlet = None;
+        self.mode = None;
+        self.state = AppState::Playing;
+        self.input.in_game = true;
+        self.input.reset_das();
+    }
+
+    /// Start a fresh gauntlet run against its first opponent.
+    fn start_gauntlet(&mut self) {
+        let gauntlet = Gauntlet::new();
+        let difficulty = gauntlet.difficulty();
+        self.gauntlet = Some(gauntlet);
+        self.start_versus(
+            difficulty,
+            AiPersonality::Balanced,
+            false,
+            VersusGoal::Survival,
+            Modifiers::default(),
+        );
+    }
+
+    /// Score a finished gauntlet round, then take a break or end the run.
+    fn finish_gauntlet_round(&mut self, won: bool) {
+        let Some(ref mut run) = self.gauntlet else {
+            return;
+        };
+        let outcome = run.record_round(won, self.game.stats.time);
+        self.input.in_game = false;
+        self.input.reset_das();
+        if !outcome.is_final() {
+            self.gauntlet_break = gauntlet::BREAK_TIME;
+            self.state = AppState::GauntletBreak;
+            return;
+        }
+
+        let cleared = outcome == RoundOutcome::Cleared;
+        let stages = run.stages_cleared();
+        let time_ms = run.total_time.as_millis() as u64;
+        let mut result = GameResult {
+            mode_name: "AI Gauntlet".to_string(),
+            primary_label: if cleared {
+                "GAUNTLET CLEARED"
+            } else {
+                "ELIMINATED"
+            }
+            .to_string(),
+            primary_value: format!(
+                "Stages: {}/{} | {}",
+                stages,
+                gauntlet::STAGES.len(),
+                high_scores::format_time_ms(time_ms)
+            ),
+            is_new_high_score: false,
+            stats: self.game.stats.clone(),
+            won: Some(cleared),
+            best_ultra: None,
+        };
+        result.is_new_high_score = self.check_and_save_high_score(&result);
+        self.game_result = Some(result);
+        self.state = AppState::GameOver;
+    }
+
+    /// Start the gauntlet's next round after a break.
+    fn next_gauntlet_round(&mut self) {
+        if let Some(difficulty) = self.gauntlet.as_ref().map(Gauntlet::difficulty) {
+            self.start_versus(
+                difficulty,
+                AiPersonality::Balanced,
+                false,
+                VersusGoal::Survival,
+                Modifiers::default(),
+            );
+        }
+    }
+
+    fn restart_game(&mut self) {
+        self.crash_noted_pieces = None;
+        if self.gauntlet.is_some() {
+            self.start_gauntlet();
+            return;
+        }
+        if self.royale.is_some() {
+            self.start_royale();
+            return;
+        }
+        // Pick up a ghost saved by the run just finished
+        if self.mode.as_ref().is_some_and(|m| m.name() == "SPRINT") {
+            self.start_sprint();
+            return;
+        }
+        self.effects.reset();
+        self.announcer.reset();
+        self.replay.clear();
+        self.last_player_attack = 0;
+        let mode_name = match self.mode {
+            Some(ref mode) => mode.name().to_string(),
+            None => VERSUS_MODE_NAME.to_string(),
+        };
+        let modifiers = self.game.modifiers;
+        let rising = self.game.garbage.solid_interval;
+        let mercy = self.game.garbage.mercy;
+        self.game = self.new_game(&mode_name);
+        self.game.modifiers = modifiers;
+        self.game.garbage.solid_interval = rising;
+        self.game.garbage.mercy = mercy;
+        self.game.start();
+        if let Some(ref mut mode) = self.mode {
+            mode.on_start(&mut self.game);
+        }
+        let ai_game = self.new_game(VERSUS_MODE_NAME);
+        if let Some(ref mut vs) = self.versus_mode {
+            self.ai_effects.reset();
+            self.battle_log.clear();
+            vs.ai_game = ai_game;
+            vs.ai_game.garbage.solid_interval = rising;
+            if vs.fair_bag {
+                vs.share_bag(&self.game);
+            }
+            vs.ai_game.start();
+            vs.ai.reset();
+        }
+        self.state = AppState::Playing;
+        self.input.in_game = true;
+        self.input.reset_das();
+    }
+
+    fn process_events(&mut self) {
+        let events = self.game.drain_events();
+        if let Some(ref mut stream) = self.stream {
+            stream.record(&events);
+        }
+        if self.config.announce || self.announcer.has_output() {
+            self.announcer.observe(&events, &self.game);
+        }
+        for event in events {
+            if self.versus_mode.is_some() {
+                self.battle_log
+                    .record(LogSide::Player, &event, self.game.stats.time);
+            }
+            match event {
+                GameEvent::HardDrop { cells } => {
+                    self.effects.trigger_hard_drop(cells);
+                }
+                GameEvent::PieceLocked => {
+                    self.effects.trigger_lock();
+                }
+                GameEvent::LinesClear(rows) => {
+                    self.effects.trigger_line_clear(rows);
+                }
+                GameEvent::Scored { points, row } => {
+                    self.effects.trigger_score_popup(points, row);
+                }
+                GameEvent::PerfectClear => {
+                    self.effects.trigger_pc();
+                }
+                GameEvent::Clear(clear_type) => {
+                    self.effects
+                        .trigger_action_text(clear_type, &self.game.scoring);
+                }
+                GameEvent::Surge(lines) => {
+                    self.effects.trigger_surge(lines);
+                }
+                GameEvent::ZoneStarted => {
+                    self.effects.trigger_zone_start();
+                }
+                GameEvent::ZoneEnded { lines } => {
+                    self.effects.trigger_zone_end(lines);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn check_and_save_high_score(&mut self, result: &GameResult) -> bool {
+        if result.won == Some(false) {
             // Don't save losses that aren't versus
         }
+        // A piece script picks every piece, so its runs aren't ranked,
+        // locally or online, and neither they nor unranked modes earn XP
+        self.xp_award = None;
+        if self.mode.as_ref().is_some_and(|m| !m.ranked()) || self.game.script.is_some() {
+            return false;
+        }
+
+        // Every ranked game comes through here, so it also earns its XP
+        let won = result.won.unwrap_or(false);
+        self.xp_award = Some(self.profile.award(
+            result.stats.score,
+            result.stats.lines_cleared,
+            won,
+        ));
 
         let stats = &result.stats;
-        if result.mode_name.contains("Sprint") {
-            self.high_scores.add_sprint(
-                stats.time.as_millis() as u64,
-                stats.lines_cleared,
-                stats.pieces_placed,
+        let modifiers = self.game.modifiers;
+        let name = self.config.player_name.as_str();
+        // Table on the high scores screen, and whether the entry tops it
+        let (table, is_best) = if result.mode_name.contains("Sprint") {
+            let mode = self.mode.as_ref();
+            let length = mode.and_then(|m| m.line_goal()).unwrap_or(40);
+            let splits = mode.and_then(|m| m.splits()).unwrap_or(&[]);
+            (
+                0,
+                self.high_scores
+                    .add_sprint(name, length, stats, splits, modifiers),
             )
         } else if result.mode_name.contains("Endless") {
-            self.high_scores
-                .add_endless(stats.score, stats.level, stats.lines_cleared)
+            (1, self.high_scores.add_endless(name, stats, modifiers))
+        } else if result.mode_name.contains("Gauntlet") {
+            match self.gauntlet {
+                Some(ref run) => (
+                    3,
+                    self.high_scores.add_gauntlet(
+                        name,
+                        run.stages_cleared(),
+                        run.total_time.as_millis() as u64,
+                        run.lives,
+                        stats,
+                    ),
+                ),
+                None => (3, false),
+            }
         } else if result.mode_name.contains("Versus") {
-            let won = result.won.unwrap_or(false);
             let diff_name = if let Some(ref vs) = self.versus_mode {
                 vs.difficulty.name().to_string()
             } else {
                 "Unknown".to_string()
             };
-            self.high_scores.add_versus(
-                won,
-                &diff_name,
-                stats.time.as_millis() as u64,
-                stats.attack_sent,
+            (
+                2,
+                self.high_scores
+                    .add_versus(name, won, &diff_name, stats, modifiers),
             )
         } else {
-            false
+            (0, false)
+        };
+
+        // Finished 40-line sprints and endless games go to the global
+        // tables, unless played with modifiers. Sprints played without
+        // hold or the ghost have a table of their own. The submission
+        // carries the ruleset, so the server can tell apart runs on
+        // overridden rules or randomizers.
+        let board = if result.mode_name.contains("Sprint") {
+            let goal = self.mode.as_ref().and_then(|m| m.line_goal());
+            let table = if modifiers.is_empty() {
+                Some(Board::Sprint)
+            } else {
+                modifiers.is_purist().then_some(Board::SprintPurist)
+            };
+            table.filter(|_| !self.game.game_over && goal == Some(40))
+        } else if result.mode_name.contains("Endless") && modifiers.is_empty() {
+            Some(Board::Endless)
+        } else {
+            None
+        };
+        if let Some(board) = board.filter(|_| self.leaderboard.is_enabled()) {
+            let recording = self.mode.as_ref().and_then(|m| m.recording());
+            self.pending_submission = Some(Submission::new(
+                board,
+                name,
+                self.game.seed,
+                &self.game.ruleset,
+                stats,
+                recording,
+            ));
+        }
+
+        // Ask who set it, starting from the name used last time
+        if is_best {
+            self.name_entry = Some((table, TextInput::new(name, NAME_MAX_LEN)));
+            self.input.text_entry = true;
+        } else {
+            self.submit_pending();
+        }
+        is_best
+    }
+
+    /// Close the name prompt, saving the typed name and remembering it
+    /// unless skipped.
+    /// Run the typed sandbox command. The prompt stays open on an error.
+    fn run_command(&mut self) {
+        let Some((ref input, ref mut error)) = self.command else {
+            return;
+        };
+        let cheat = match Cheat::parse(&input.value) {
+            Ok(cheat) => cheat,
+            Err(e) => {
+                *error = Some(e);
+                return;
+            }
+        };
+        if let Some(ref mut mode) = self.mode {
+            if mode.cheat(&mut self.game, cheat) {
+                self.effects.reset();
+            }
+        }
+        self.close_command();
+    }
+
+    fn close_command(&mut self) {
+        self.command = None;
+        self.input.text_entry = false;
+    }
+
+    fn finish_name_entry(&mut self, save: bool) {
+        self.input.text_entry = false;
+        if let Some((table, input)) = self.name_entry.take() {
+            let name = input.value.trim();
+            if save && !name.is_empty() {
+                self.high_scores.rename_latest(table, name);
+                if self.config.player_name != name {
+                    self.config.player_name = name.to_string();
+                    self.config.save();
+                }
+            }
+        }
+        self.submit_pending();
+    }
+
+    /// Send the result waiting on the name prompt to the global leaderboard.
+    fn submit_pending(&mut self) {
+        if let Some(mut submission) = self.pending_submission.take() {
+            submission.name = self.config.player_name.clone();
+            self.leaderboard.submit(submission);
+        }
+    }
+
+    /// Whether the screen changes every frame (gameplay or the attract demo),
+    /// as opposed to only on input or slow animations.
+    pub fn is_realtime(&self) -> bool {
+        // Diagnostics are paced like play so they measure what play gets
+        matches!(
+            self.state,
+            AppState::Playing | AppState::InputDiagnostics | AppState::Replay
+        ) || self.attract.is_some()
+    }
+
+    /// Whether the current screen has a slow animation (title shimmer, high
+    /// score pulse, live perf numbers) that needs redrawing even without input.
+    fn is_animated(&self) -> bool {
+        if self.perf.visible {
+            return true;
+        }
+        match self.state {
+            AppState::Menu | AppState::GauntletBreak => true,
+            AppState::GameOver => self
+                .game_result
+                .as_ref()
+                .is_some_and(|r| r.is_new_high_score),
+            _ => false,
+        }
+    }
+
+    /// Name of the game being played, including versus and royale which
+    /// run without a `GameMode`.
+    fn mode_name(&self) -> &str {
+        if self.versus_mode.is_some() {
+            VERSUS_MODE_NAME
+        } else if self.royale.is_some() {
+            "ROYALE"
+        } else {
+            self.mode.as_ref().map_or("GAME", |m| m.name())
         }
     }
 
+    /// Whether the terminal's keyboard support should be checked again
+    /// since this was last asked.
+    pub fn take_reprobe(&mut self) -> bool {
+        std::mem::take(&mut self.input.reprobe)
+    }
+
+    /// Use the result of checking the terminal's keyboard support again.
+    pub fn set_key_release(&mut self, has_key_release: bool) {
+        if self.input.set_key_release(has_key_release) {
+            tracing::info!(has_key_release, "keyboard support changed");
+            self.dirty = true;
+        }
+    }
+
+    /// Text waiting to be put on the clipboard.
+    pub fn take_clipboard(&mut self) -> Option<String> {
+        self.clipboard.take()
+    }
+
+    /// Whether the terminal bell is due, clearing the flag.
+    pub fn take_bell(&mut self) -> bool {
+        std::mem::take(&mut self.bell)
+    }
+
+    /// Whether the terminal was resized since this was last asked, so the
+    /// screen should be cleared before the next draw.
+    pub fn take_resized(&mut self) -> bool {
+        std::mem::take(&mut self.resized)
+    }
+
+    /// Whether a redraw is needed, clearing the flag.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty) || self.is_realtime()
+    }
+
     /// Render the current frame.
     pub fn render(&self, area: Rect, buf: &mut Buffer) {
         match &self.state {
             AppState::Menu => {
+                if let Some(ref attract) = self.attract {
+                    self.render_attract(attract, area, buf);
+                }
                 (&self.menu).render(area, buf);
             }
-            AppState::DifficultySelect => {
-                self.render_difficulty_select(area, buf);
+            AppState::ConfigErrors => {
+                self.render_config_errors(area, buf);
+            }
+            AppState::Onboarding => {
+                self.render_onboarding(area, buf);
+            }
+            AppState::ResumePrompt => {
+                self.render_resume_prompt(area, buf);
+            }
+            AppState::ModeSetup => {
+                ModeSetupScreen {
+                    mode: self.setup_mode,
+                    config: &self.config,
+                    selected: self.setup_selected,
+                }
+                .render(area, buf);
+            }
+            AppState::OpenerSelect => {
+                self.render_opener_select(area, buf);
+            }
+            AppState::ScriptSelect => {
+                self.render_script_select(area, buf);
+            }
+            AppState::GauntletBreak => {
+                self.render_gauntlet_break(area, buf);
             }
             AppState::Playing | AppState::Paused => {
                 if self.versus_mode.is_some() {
                     self.render_versus(area, buf);
+                } else if let Some(ref royale) = self.royale {
+                    self.render_royale(royale, area, buf);
                 } else {
                     self.render_single(area, buf);
                 }
             }
+            AppState::Replay => self.render_replay(area, buf),
             AppState::GameOver => {
                 if let Some(ref result) = self.game_result {
+                    let handling = self.handling();
                     GameOverScreen {
                         result,
-                        frame: self.frame,
+                        time: self.clock.as_secs_f32(),
+                        name_entry: self.name_entry.as_ref().map(|(_, input)| input),
+                        xp: self.xp_award.as_ref(),
+                        rating: self
+                            .versus_mode
+                            .as_ref()
+                            .and(self.rating_change)
+                            .map(|change| (self.match_history.rating(), change)),
+                        modifiers: self.game.modifiers,
+                        handling: handling.as_ref(),
+                        timeline: self.versus_mode.as_ref().map(|vs| {
+                            (
+                                self.game.exchanges.as_slice(),
+                                vs.ai_game.exchanges.as_slice(),
+                            )
+                        }),
+                        timeline_scroll: self.timeline_scroll,
+                        show_details: self.show_details,
+                    }
+                    .render(area, buf);
+                }
+            }
+            AppState::Settings => {
+                SettingsScreen {
+                    config: &self.config,
+                    selected: self.settings_selected,
+                    has_key_release: self.input.has_key_release,
+                }
+                .render(area, buf);
+            }
+            AppState::InputDiagnostics => {
+                InputDiagnosticsScreen {
+                    diag: &self.input.diag,
+                    has_key_release: self.input.has_key_release,
+                }
+                .render(area, buf);
+            }
+            AppState::HighScores if self.hs_detail => {
+                let rows = self.hs_view.entries(&self.high_scores, self.hs_tab);
+                if let Some(&index) = rows.get(self.hs_selected) {
+                    HighScoreDetailScreen {
+                        store: &self.high_scores,
+                        tab: self.hs_tab,
+                        index,
                     }
                     .render(area, buf);
                 }
@@ -428,22 +1881,68 @@ impl App {
                 HighScoresScreen {
                     store: &self.high_scores,
                     selected_tab: self.hs_tab,
+                    selected: self.hs_selected,
+                    view: &self.hs_view,
+                    global: &self.leaderboard,
+                }
+                .render(area, buf);
+            }
+            AppState::MatchHistory => {
+                MatchHistoryScreen {
+                    history: &self.match_history,
+                    selected: self.history_selected,
                 }
                 .render(area, buf);
             }
             AppState::Quitting => {}
         }
+
+        if self.perf.visible {
+            PerfOverlayWidget { stats: &self.perf }.render(area, buf);
+        }
+        if self.help {
+            HelpOverlayWidget {
+                keybinds: &self.input.keybinds,
+            }
+            .render(area, buf);
+        }
+        if let Some((ref message, _)) = self.toast {
+            ToastWidget { message }.render(area, buf);
+        }
+        if !self.quit_confirm.is_zero() {
+            let key = self.input.keybinds.key_display(Action::Quit);
+            let message = format!("Press {} again to quit", key);
+            ToastWidget { message: &message }.render(area, buf);
+        }
+    }
+
+    /// Latest announcement, while they're on.
+    fn announcement(&self) -> Option<&str> {
+        self.announcer
+            .line
+            .as_deref()
+            .filter(|_| self.config.announce || self.announcer.has_output())
+    }
+
+    /// Selected pause menu row, if the game is paused.
+    fn pause_selected(&self) -> Option<usize> {
+        matches!(self.state, AppState::Paused).then_some(self.pause_selected)
     }
 
     fn render_single(&self, area: Rect, buf: &mut Buffer) {
         if !layout::check_size_single(area) {
-            self.render_size_error(area, buf, layout::MIN_WIDTH, layout::MIN_HEIGHT);
+            let (min_w, min_h) = layout::min_size_single(area);
+            self.render_size_error(area, buf, min_w, min_h);
             return;
         }
 
-        let layout = SingleLayout::new(area);
+        let layout = SingleLayout::oriented(area, self.config.layout);
         let mode_name = self.mode.as_ref().map(|m| m.name()).unwrap_or("GAME");
         let mode_info = self.mode.as_ref().and_then(|m| m.info_text(&self.game));
+        let mode_info = match (mode_info, self.pc_text()) {
+            (Some(info), Some(pc)) => Some(format!("{}  {}", info, pc)),
+            (info, pc) => info.or(pc),
+        };
 
         GameScreen {
             game: &self.game,
@@ -451,7 +1950,52 @@ impl App {
             layout: &layout,
             mode_name,
             mode_info: mode_info.as_deref(),
-            paused: matches!(self.state, AppState::Paused),
+            pause_selected: self.pause_selected(),
+            show_attack_graph: false,
+            show_hold_ghost: self.config.hold_ghost,
+            ghost: self.mode.as_ref().and_then(|m| m.ghost()),
+            guide: self.mode.as_ref().and_then(|m| m.guide()),
+            pc_plan: self.pc_plan(),
+            keybinds: &self.input.keybinds,
+            restart_progress: self.input.restart_progress(),
+            announcement: self.announcement(),
+        }
+        .render(area, buf);
+
+        // Sandbox prompt, over the controls hint
+        if let Some((ref input, ref error)) = self.command {
+            let row = Rect::new(area.x, layout.controls.y + 1, area.width, 1);
+            for x in row.x..row.x + row.width {
+                buf.set_string(x, row.y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+            let label = match error {
+                Some(error) => format!("{}  :", error),
+                None => "give/clear/garbage/level  :".to_string(),
+            };
+            TextInputWidget {
+                label: &label,
+                input,
+            }
+            .render(row, buf);
+        }
+    }
+
+    fn render_replay(&self, area: Rect, buf: &mut Buffer) {
+        if !layout::check_size_single(area) {
+            let (min_w, min_h) = layout::min_size_single(area);
+            self.render_size_error(area, buf, min_w, min_h);
+            return;
+        }
+        let Some(frame) = self.replay.at(self.replay_time) else {
+            return;
+        };
+        let end = self.replay.span().map_or(Duration::ZERO, |(_, end)| end);
+        InstantReplayScreen {
+            frame,
+            layout: &SingleLayout::oriented(area, self.config.layout),
+            remaining: end.saturating_sub(self.replay_time),
+            show_ghost: !self.game.modifiers.contains(Modifier::NoGhost),
+            mirrored: self.game.modifiers.contains(Modifier::MirrorView),
         }
         .render(area, buf);
     }
@@ -460,7 +2004,8 @@ impl App {
         // For versus, render two boards side by side
         // If terminal too small, show just the player board
         if !layout::check_size_single(area) {
-            self.render_size_error(area, buf, layout::MIN_WIDTH, layout::MIN_HEIGHT);
+            let (min_w, min_h) = layout::min_size_single(area);
+            self.render_size_error(area, buf, min_w, min_h);
             return;
         }
 
@@ -475,29 +2020,36 @@ impl App {
             let vs_layout = VersusLayout::new(area);
 
             // Player board (left)
-            let mode_info = self.versus_mode.as_ref().map(|_| {
-                format!(
-                    "ATK:{} RCV:{}",
-                    self.game.stats.attack_sent, self.game.stats.garbage_received
-                )
-            });
+            let mode_info = self.versus_mode.as_ref().map(|_| self.versus_info());
+            let player_name = self.gauntlet_stage_name();
 
             GameScreen {
                 game: &self.game,
                 effects: &self.effects,
                 layout: &vs_layout.player,
-                mode_name: "PLAYER",
+                mode_name: player_name.as_deref().unwrap_or("PLAYER"),
                 mode_info: mode_info.as_deref(),
-                paused: matches!(self.state, AppState::Paused),
+                pause_selected: self.pause_selected(),
+                show_attack_graph: true,
+                show_hold_ghost: self.config.hold_ghost,
+                ghost: None,
+                guide: None,
+                pc_plan: None,
+                keybinds: &self.input.keybinds,
+                restart_progress: self.input.restart_progress(),
+                announcement: self.announcement(),
             }
             .render(area, buf);
 
             // AI board (right)
             if let Some(ref vs) = self.versus_mode {
-                let ai_info = format!(
+                let mut ai_info = format!(
                     "ATK:{} RCV:{}",
                     vs.ai_game.stats.attack_sent, vs.ai_game.stats.garbage_received
                 );
+                if vs.difficulty == AiDifficulty::Adaptive {
+                    ai_info += &format!(" SKL:{:.0}%", vs.ai.skill * 100.0);
+                }
 
                 GameScreen {
                     game: &vs.ai_game,
@@ -505,7 +2057,15 @@ impl App {
                     layout: &vs_layout.ai,
                     mode_name: &format!("AI ({})", vs.difficulty.name()),
                     mode_info: Some(&ai_info),
-                    paused: matches!(self.state, AppState::Paused),
+                    pause_selected: self.pause_selected(),
+                    show_attack_graph: true,
+                    show_hold_ghost: false,
+                    ghost: None,
+                    guide: vs.ai.plan().filter(|_| self.config.show_ai_plan),
+                    pc_plan: None,
+                    keybinds: &self.input.keybinds,
+                    restart_progress: None,
+                    announcement: None,
                 }
                 .render(area, buf);
 
@@ -520,53 +2080,515 @@ impl App {
                         .add_modifier(ratatui::style::Modifier::BOLD),
                 );
             }
+
+            if let Some(log_area) = vs_layout.log {
+                BattleLogWidget {
+                    log: &self.battle_log,
+                }
+                .render(log_area, buf);
+            }
         } else {
-            // Fallback: just show player board
-            let layout = SingleLayout::new(area);
-            let mode_info = self.versus_mode.as_ref().map(|_| {
-                format!(
-                    "ATK:{} RCV:{}",
-                    self.game.stats.attack_sent, self.game.stats.garbage_received
-                )
-            });
+            // Fallback: the player's board, with the AI's in miniature in
+            // place of the right sidebar if there's room for it
+            let mut layout = SingleLayout::oriented(area, self.config.layout);
+            let ai_panel = layout.take_right_panel(MINI_BOARD_H + 1);
+            let mode_info = self.versus_mode.as_ref().map(|_| self.versus_info());
+            let player_name = self.gauntlet_stage_name();
 
             GameScreen {
                 game: &self.game,
                 effects: &self.effects,
                 layout: &layout,
-                mode_name: "VERSUS",
+                mode_name: player_name.as_deref().unwrap_or("VERSUS"),
                 mode_info: mode_info.as_deref(),
-                paused: matches!(self.state, AppState::Paused),
+                pause_selected: self.pause_selected(),
+                show_attack_graph: true,
+                show_hold_ghost: self.config.hold_ghost,
+                ghost: None,
+                guide: None,
+                pc_plan: None,
+                keybinds: &self.input.keybinds,
+                restart_progress: self.input.restart_progress(),
+                announcement: self.announcement(),
             }
             .render(area, buf);
+
+            if let (Some(vs), Some(panel)) = (&self.versus_mode, ai_panel) {
+                MiniBoardWidget {
+                    game: &vs.ai_game,
+                    label: &format!("AI {}", vs.difficulty.name()),
+                    place: None,
+                    badges: 0,
+                    targeted: false,
+                    attacking: false,
+                }
+                .render(panel, buf);
+
+                // The AI's incoming garbage, beside its board
+                let bar_x = panel.x + MINI_BOARD_W;
+                if bar_x < area.right() {
+                    GarbageBarWidget {
+                        garbage: &vs.ai_game.garbage,
+                    }
+                    .render(Rect::new(bar_x, panel.y + 1, 1, MINI_BOARD_H - 2), buf);
+                }
+            }
+        }
+
+        if let Some(vs) = self.versus_mode.as_ref().filter(|_| self.show_ai_explain) {
+            AiExplainWidget {
+                explanation: vs.ai.explanation(),
+            }
+            .render(area, buf);
+        }
+    }
+
+    fn render_royale(&self, royale: &RoyaleMode, area: Rect, buf: &mut Buffer) {
+        if !layout::check_size_single(area) {
+            let (min_w, min_h) = layout::min_size_single(area);
+            self.render_size_error(area, buf, min_w, min_h);
+            return;
+        }
+
+        // Clear background
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let count = royale.opponents.len();
+        let royale_layout =
+            layout::check_size_royale(area, count).then(|| RoyaleLayout::new(area, count));
+        // Fallback: just the player board, with the alive count
+        let player_layout = match royale_layout {
+            Some(ref royale_layout) => royale_layout.player.clone(),
+            None => SingleLayout::oriented(area, self.config.layout),
+        };
+        let mode_info = royale.info_text(&self.game);
+
+        GameScreen {
+            game: &self.game,
+            effects: &self.effects,
+            layout: &player_layout,
+            mode_name: royale.name(),
+            mode_info: mode_info.as_deref(),
+            pause_selected: self.pause_selected(),
+            show_attack_graph: true,
+            show_hold_ghost: self.config.hold_ghost,
+            ghost: None,
+            guide: None,
+            pc_plan: None,
+            keybinds: &self.input.keybinds,
+            restart_progress: self.input.restart_progress(),
+            announcement: self.announcement(),
+        }
+        .render(area, buf);
+
+        if let Some(royale_layout) = royale_layout {
+            for (i, (opponent, rect)) in royale
+                .opponents
+                .iter()
+                .zip(&royale_layout.opponents)
+                .enumerate()
+            {
+                MiniBoardWidget {
+                    game: &opponent.game,
+                    label: opponent.ai.difficulty.name(),
+                    place: opponent.place,
+                    badges: opponent.badges,
+                    targeted: royale.target == Some(i),
+                    attacking: opponent.target == Target::Player,
+                }
+                .render(*rect, buf);
+            }
+        }
+    }
+
+    /// Player's attack line under the board, with lives left in a gauntlet.
+    /// Perfect clear hint the player asked for, if this is a practice game.
+    fn pc_hint(&self) -> PcHint {
+        match self.mode {
+            Some(ref mode) if mode.allows_cheats() => self.config.zen_pc_hint,
+            _ => PcHint::Off,
+        }
+    }
+
+    /// Ask for a new perfect clear search whenever the position changes,
+    /// and pick up the answer once it's in.
+    fn update_pc_hint(&mut self) {
+        if self.pc_hint() == PcHint::Off {
+            self.pc_asked = None;
+            self.pc_outcome = None;
+            return;
+        }
+        if let Some(response) = self.pc_worker.poll() {
+            if self
+                .pc_asked
+                .as_ref()
+                .is_some_and(|(id, _)| *id == response.id)
+            {
+                self.pc_outcome = Some(response.outcome);
+            }
+        }
+
+        let Some(ref piece) = self.game.current_piece else {
+            return;
+        };
+        let key = (
+            BitBoard::from_board(&self.game.board),
+            piece.piece_type,
+            self.game.hold.piece,
+            self.game.hold.used_this_turn,
+            self.game.preview(),
+        );
+        if self
+            .pc_asked
+            .as_ref()
+            .is_some_and(|(_, asked)| *asked == key)
+        {
+            return;
         }
+        let id = self.pc_asked.as_ref().map_or(0, |(id, _)| id + 1);
+        self.pc_worker.submit(PcRequest {
+            id,
+            board: self.game.board.clone(),
+            current: key.1,
+            hold: key.2,
+            queue: key.4.clone(),
+            hold_enabled: self.game.hold_enabled(),
+            hold_used: key.3,
+            rotation_system: self.game.ruleset.rotation_system,
+        });
+        self.pc_asked = Some((id, key));
+        self.pc_outcome = None;
     }
 
-    fn render_difficulty_select(&self, area: Rect, buf: &mut Buffer) {
+    /// Perfect clear status for the line under the board.
+    fn pc_text(&self) -> Option<String> {
+        if self.pc_hint() == PcHint::Off {
+            return None;
+        }
+        Some(match self.pc_outcome {
+            None => "PC: ...".to_string(),
+            Some(PcOutcome::Found(ref pieces)) => format!("PC IN {}", pieces.len()),
+            Some(PcOutcome::Impossible) => "NO PC".to_string(),
+            Some(PcOutcome::GaveUp) => "PC: ?".to_string(),
+        })
+    }
+
+    /// Perfect clear to outline on the board, when asked for.
+    fn pc_plan(&self) -> Option<&[PcPiece]> {
+        match self.pc_outcome {
+            Some(PcOutcome::Found(ref pieces)) if self.pc_hint() == PcHint::Solution => {
+                Some(pieces)
+            }
+            _ => None,
+        }
+    }
+
+    fn versus_info(&self) -> String {
+        let info = self
+            .versus_mode
+            .as_ref()
+            .and_then(|vs| vs.info_text(&self.game))
+            .unwrap_or_default();
+        match self.gauntlet {
+            Some(ref run) => format!("{} {}", info, lives_text(run.lives)),
+            None => info,
+        }
+    }
+
+    /// Sidebar title during a gauntlet round, e.g. "STAGE 2/4".
+    fn gauntlet_stage_name(&self) -> Option<String> {
+        self.gauntlet
+            .as_ref()
+            .map(|run| format!("STAGE {}/{}", run.stage_number(), gauntlet::STAGES.len()))
+    }
+
+    fn render_gauntlet_break(&self, area: Rect, buf: &mut Buffer) {
         // Clear background
         for y in area.y..area.y + area.height {
             for x in area.x..area.x + area.width {
                 buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
             }
         }
+        let Some(ref run) = self.gauntlet else {
+            return;
+        };
 
         let center_x = area.x + area.width / 2;
         let mut y = area.y + area.height / 4;
+        let mut center = |y: u16, text: &str, style: Style| {
+            let x = center_x.saturating_sub(text.chars().count() as u16 / 2);
+            buf.set_string(x, y, text, style);
+        };
 
-        let title = "SELECT AI DIFFICULTY";
-        let tx = center_x.saturating_sub(title.len() as u16 / 2);
-        buf.set_string(tx, y, title, theme::title_style());
+        let (title, color) = match run.last_outcome {
+            Some(RoundOutcome::Retry) => ("ROUND LOST", theme::DANGER_COLOR),
+            _ => ("ROUND WON", ratatui::style::Color::Rgb(80, 255, 120)),
+        };
+        center(
+            y,
+            title,
+            Style::default()
+                .fg(color)
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        );
         y += 3;
 
-        let difficulties = [
-            AiDifficulty::Easy,
-            AiDifficulty::Medium,
-            AiDifficulty::Hard,
-            AiDifficulty::Expert,
+        let next = format!(
+            "Next: Stage {}/{} \u{2014} {}",
+            run.stage_number(),
+            gauntlet::STAGES.len(),
+            run.difficulty().name()
+        );
+        center(y, &next, theme::menu_item_style());
+        y += 2;
+        center(
+            y,
+            &lives_text(run.lives),
+            Style::default().fg(theme::DANGER_COLOR),
+        );
+        y += 2;
+        let time = format!(
+            "Total time {}",
+            high_scores::format_time_ms(run.total_time.as_millis() as u64)
+        );
+        center(y, &time, theme::stat_value_style());
+        y += 3;
+
+        let countdown = format!("Starting in {}", self.gauntlet_break.as_secs_f32().ceil());
+        center(y, &countdown, theme::stat_label_style());
+
+        let controls = "Enter: start now  Esc: give up";
+        center(area.y + area.height - 2, controls, theme::menu_desc_style());
+    }
+
+    /// Draw the dimmed attract-mode board behind the menu.
+    fn render_attract(&self, attract: &AttractMode, area: Rect, buf: &mut Buffer) {
+        // Clear background
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let board_area = Rect::new(
+            area.x + area.width.saturating_sub(layout::BOARD_TOTAL_W) / 2,
+            area.y + area.height.saturating_sub(layout::BOARD_TOTAL_H) / 2,
+            layout::BOARD_TOTAL_W,
+            layout::BOARD_TOTAL_H,
+        )
+        .intersection(area);
+
+        let effects = Effects::new();
+        BoardWidget {
+            board: &attract.game.board,
+            current_piece: attract.game.current_piece.as_ref(),
+            effects: &effects,
+            show_grid: false,
+            show_ghost: true,
+            hide_stack: false,
+            zone_lines: 0,
+            ghost_heights: None,
+            guide: None,
+            pc_plan: None,
+            mirrored: false,
+            spawn_preview: None,
+            hold_ghost: None,
+        }
+        .render(board_area, buf);
+
+        // Dim so the menu stays readable on top
+        for y in board_area.y..board_area.y + board_area.height {
+            for x in board_area.x..board_area.x + board_area.width {
+                let cell = &mut buf[(x, y)];
+                cell.fg = blend_color(cell.fg, theme::BG_COLOR, 0.7);
+            }
+        }
+    }
+
+    fn render_config_errors(&self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let center_x = area.x + area.width / 2;
+        let mut y = area.y + area.height / 6;
+        let header = [
+            ("CONFIG PROBLEMS", theme::title_style()),
+            ("", theme::menu_desc_style()),
+            (
+                "These entries in config.json were ignored:",
+                theme::menu_item_style(),
+            ),
+            ("", theme::menu_desc_style()),
+        ];
+        for (line, style) in header {
+            let x = center_x.saturating_sub(line.width() as u16 / 2);
+            buf.set_string(x, y, line, style);
+            y += 1;
+        }
+
+        // Leave room for the footer and controls
+        let max_width = area.width.saturating_sub(4) as usize;
+        let room = (area.y + area.height).saturating_sub(y + 5) as usize;
+        let problems = &self.config_problems;
+        let shown = if problems.len() > room {
+            room.saturating_sub(1)
+        } else {
+            problems.len()
+        };
+        let x = area.x + 2;
+        for problem in &problems[..shown] {
+            let line: String = format!("- {}", problem).chars().take(max_width).collect();
+            buf.set_string(x, y, &line, Style::default().fg(theme::DANGER_COLOR));
+            y += 1;
+        }
+        if shown < problems.len() {
+            let more = format!("...and {} more", problems.len() - shown);
+            buf.set_string(x, y, &more, theme::menu_desc_style());
+            y += 1;
+        }
+
+        let footer = if self.config_backed_up {
+            "Defaults are used instead; the file was copied to config.invalid.bak."
+        } else {
+            "Defaults are used instead."
+        };
+        let fx = center_x.saturating_sub(footer.len() as u16 / 2);
+        buf.set_string(fx, y + 1, footer, theme::menu_desc_style());
+
+        let controls = "Enter: continue";
+        let cx = center_x.saturating_sub(controls.len() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        buf.set_string(cx, cy, controls, theme::menu_desc_style());
+    }
+
+    fn render_resume_prompt(&self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+        let Some(ref autosave) = self.resume else {
+            return;
+        };
+
+        let center_x = area.x + area.width / 2;
+        let stats = &autosave.game.stats;
+        let lines = [
+            ("RESUME GAME?".to_string(), theme::title_style()),
+            (String::new(), theme::menu_desc_style()),
+            (
+                format!("The last {} game didn't finish.", autosave.mode),
+                theme::menu_item_style(),
+            ),
+            (
+                format!(
+                    "Score {}  Lines {}  Time {}",
+                    format_number(stats.score),
+                    stats.lines_cleared,
+                    stats.format_time()
+                ),
+                theme::menu_item_style(),
+            ),
+            (
+                format!(
+                    "Saved {}",
+                    autosave
+                        .date
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                ),
+                theme::menu_desc_style(),
+            ),
+        ];
+        for (y, (line, style)) in (area.y + area.height / 3..).zip(lines) {
+            let x = center_x.saturating_sub(line.width() as u16 / 2);
+            buf.set_string(x, y, &line, style);
+        }
+
+        let controls = "Enter: resume  Esc: discard";
+        let cx = center_x.saturating_sub(controls.len() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        buf.set_string(cx, cy, controls, theme::menu_desc_style());
+    }
+
+    fn render_onboarding(&self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let center_x = area.x + area.width / 2;
+        let mut y = area.y + area.height / 4;
+
+        let header = [
+            ("CHOOSE YOUR CONTROLS", theme::title_style()),
+            ("", theme::menu_desc_style()),
+            (
+                "Change them any time under Settings > Controls",
+                theme::menu_desc_style(),
+            ),
         ];
+        for (line, style) in header {
+            let x = center_x.saturating_sub(line.width() as u16 / 2);
+            buf.set_string(x, y, line, style);
+            y += 1;
+        }
+        y += 1;
+
+        for (i, preset) in KeybindPreset::ALL.iter().enumerate() {
+            let is_selected = i == self.onboarding_selected;
+            let cursor = if is_selected { " ▸ " } else { "   " };
+            let label_style = if is_selected {
+                theme::menu_selected_style()
+            } else {
+                theme::menu_item_style()
+            };
+
+            let x = center_x.saturating_sub(15);
+            buf.set_string(x, y, format!("{}{}", cursor, preset.name()), label_style);
+
+            if is_selected {
+                y += 1;
+                let desc = preset.description();
+                let dx = center_x.saturating_sub(desc.width() as u16 / 2);
+                buf.set_string(dx, y, desc, theme::menu_desc_style());
+            }
+            y += 2;
+        }
+
+        let controls = "↑/↓: navigate  Enter: choose  Esc: keep Vim";
+        let cx = center_x.saturating_sub(controls.width() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        if cy > y {
+            buf.set_string(cx, cy, controls, theme::menu_desc_style());
+        }
+    }
+
+    fn render_opener_select(&self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let center_x = area.x + area.width / 2;
+        let mut y = area.y + area.height / 4;
 
-        for (i, diff) in difficulties.iter().enumerate() {
-            let is_selected = i == self.difficulty_selected;
+        let title = "SELECT OPENER";
+        let tx = center_x.saturating_sub(title.len() as u16 / 2);
+        buf.set_string(tx, y, title, theme::title_style());
+        y += 3;
+
+        for (i, opener) in OPENERS.iter().enumerate() {
+            let is_selected = i == self.opener_selected;
             let cursor = if is_selected { " ▸ " } else { "   " };
 
             let label_style = if is_selected {
@@ -575,32 +2597,123 @@ impl App {
                 theme::menu_item_style()
             };
 
-            let line = format!("{}{}", cursor, diff.name());
+            let line = format!("{}{}", cursor, opener.name);
             let x = center_x.saturating_sub(15);
             buf.set_string(x, y, &line, label_style);
 
             if is_selected {
                 y += 1;
-                let desc = diff.description();
+                let desc = opener.description;
                 let dx = center_x.saturating_sub(desc.len() as u16 / 2);
                 buf.set_string(dx, y, desc, theme::menu_desc_style());
             }
             y += 2;
         }
 
-        let controls = "j/k: navigate  Enter: select  Esc: back";
+        let controls = "j/k: navigate  Enter: start  Esc: back";
+        let cx = center_x.saturating_sub(controls.len() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        buf.set_string(cx, cy, controls, theme::menu_desc_style());
+    }
+
+    fn render_script_select(&self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let center_x = area.x + area.width / 2;
+        let mut y = area.y + area.height / 4;
+
+        let title = "CUSTOM MODES";
+        let tx = center_x.saturating_sub(title.len() as u16 / 2);
+        buf.set_string(tx, y, title, theme::title_style());
+        y += 3;
+
+        if self.scripts.is_empty() {
+            let dir = ModeScript::dir().map_or("modes".to_string(), |d| d.display().to_string());
+            for line in ["No .rhai scripts in", dir.as_str()] {
+                let x = center_x.saturating_sub(line.width() as u16 / 2);
+                buf.set_string(x, y, line, theme::menu_desc_style());
+                y += 1;
+            }
+        }
+
+        for (i, script) in self.scripts.iter().enumerate() {
+            let is_selected = i == self.script_selected;
+            let cursor = if is_selected { " ▸ " } else { "   " };
+
+            let label_style = if is_selected {
+                theme::menu_selected_style()
+            } else {
+                theme::menu_item_style()
+            };
+
+            let line = format!("{}{}", cursor, script.title);
+            let x = center_x.saturating_sub(15);
+            buf.set_string(x, y, &line, label_style);
+
+            if is_selected {
+                y += 1;
+                let (desc, style) = match script.ast {
+                    Ok(_) => (script.description.as_str(), theme::menu_desc_style()),
+                    Err(ref e) => (e.as_str(), theme::danger_style()),
+                };
+                let desc: String = desc.chars().take(area.width as usize).collect();
+                let dx = center_x.saturating_sub(desc.width() as u16 / 2);
+                buf.set_string(dx, y, &desc, style);
+            }
+            y += 2;
+        }
+
+        let controls = "j/k: navigate  Enter: start  Esc: back";
         let cx = center_x.saturating_sub(controls.len() as u16 / 2);
         let cy = area.y + area.height - 2;
         buf.set_string(cx, cy, controls, theme::menu_desc_style());
     }
 
     fn render_size_error(&self, area: Rect, buf: &mut Buffer, min_w: u16, min_h: u16) {
-        let msg = format!(
-            "Terminal too small: {}x{} (need {}x{})",
-            area.width, area.height, min_w, min_h
-        );
-        let x = area.x + area.width.saturating_sub(msg.len() as u16) / 2;
-        let y = area.y + area.height / 2;
-        buf.set_string(x, y, &msg, theme::danger_style());
+        let size = format!("Terminal too small: {}x{}", area.width, area.height);
+        let need = format!("(need {}x{})", min_w, min_h);
+        let one_line = format!("{} {}", size, need);
+        // Split over two lines in a narrow terminal
+        let lines = if one_line.len() as u16 <= area.width {
+            vec![one_line]
+        } else {
+            vec![size, need]
+        };
+        for (y, line) in (area.y + area.height / 2..).zip(lines) {
+            let x = area.x + area.width.saturating_sub(line.len() as u16) / 2;
+            buf.set_string(x, y, &line, theme::danger_style());
+        }
     }
 }
+
+/// Fresh mode for an autosave's mode name, if it's one that can be resumed.
+fn resumable_mode(name: &str) -> Option<Box<dyn GameMode>> {
+    let mode: Box<dyn GameMode> = match name {
+        "ENDLESS" => Box::new(EndlessMode::new()),
+        "ZEN" => Box::new(ZenMode::new()),
+        _ => return None,
+    };
+    mode.resumable().then_some(mode)
+}
+
+/// Draw with the configured cosmetics, or the defaults for any the profile
+/// hasn't unlocked (e.g. after editing the config by hand), and the
+/// configured piece fill.
+fn apply_cosmetics(config: &Config, profile: &Profile) {
+    let level = profile.level();
+    let palette = Some(config.palette).filter(|p| p.unlock_level() <= level);
+    let skin = Some(config.block_skin).filter(|s| s.unlock_level() <= level);
+    theme::set_cosmetics(palette.unwrap_or_default(), skin.unwrap_or_default());
+    theme::set_piece_fill(config.piece_fill);
+}
+
+/// Hearts for gauntlet lives, filled for each one left.
+fn lives_text(lives: u32) -> String {
+    (0..gauntlet::LIVES)
+        .map(|i| if i < lives { '\u{2665}' } else { '\u{2661}' })
+        .collect()
+}
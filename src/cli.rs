@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use tetrs_core::ai::difficulty::AiDifficulty;
+
+/// A modern terminal Tetris. Without a mode, opens the main menu.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Mode to start straight away, skipping the menu.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Seed for the piece sequence and garbage holes, kept across
+    /// restarts. Versus and royale opponents are seeded from it too. With
+    /// `simulate`, the first game's seed, counting up from there.
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// Config file to use instead of the one in the config directory.
+    /// Settings changed in game are saved back to it.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Race a sprint against the run in this file (a `ghosts.json`, or a
+    /// single run) instead of your personal best. Starts a sprint.
+    #[arg(long, global = true, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Publish the game as a line of JSON per frame to readers of a Unix
+    /// socket created at this path, for overlays and other tools.
+    #[arg(long, global = true, value_name = "SOCKET")]
+    pub stream: Option<PathBuf>,
+
+    /// Append a line describing each spawn, clear, garbage warning and
+    /// stack height change to this file or named pipe, for a screen reader
+    /// to speak. Turns on announcements.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub announce: Option<PathBuf>,
+
+    /// Write a debug log to the data directory.
+    #[arg(long, global = true)]
+    pub log: bool,
+}
+
+/// What to run instead of the menu.
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    #[command(flatten)]
+    Mode(ModeArg),
+    /// Play AI games without the UI and print their stats as JSON.
+    Simulate(SimulateArgs),
+}
+
+/// Options for a headless AI run.
+#[derive(Debug, Clone, PartialEq, Args)]
+pub struct SimulateArgs {
+    /// Number of games to play.
+    #[arg(long, default_value_t = 10)]
+    pub games: u32,
+
+    #[arg(long, value_enum, default_value_t = DifficultyArg::Medium)]
+    pub difficulty: DifficultyArg,
+
+    /// JSON file of evaluation weights to play with in place of the
+    /// difficulty's.
+    #[arg(long, value_name = "FILE")]
+    pub weights: Option<PathBuf>,
+
+    /// Game time after which a game that hasn't topped out is stopped, in
+    /// seconds.
+    #[arg(long, default_value_t = 300, value_name = "SECS")]
+    pub time_limit: u64,
+}
+
+/// Mode started from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Subcommand)]
+pub enum ModeArg {
+    /// Line sprint, 40 lines unless another length was picked in the menu.
+    Sprint,
+    /// Score attack until you top out.
+    Endless,
+    /// No gravity pressure and no game over, with undo.
+    Zen,
+    /// Versus the AI.
+    Versus {
+        #[arg(long, value_enum, default_value_t = DifficultyArg::Medium)]
+        difficulty: DifficultyArg,
+    },
+    /// Four versus rounds of rising difficulty.
+    Gauntlet,
+    /// Battle royale against a lobby of AIs.
+    Royale,
+}
+
+/// AI difficulty as typed on the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DifficultyArg {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    Adaptive,
+}
+
+impl DifficultyArg {
+    pub fn difficulty(self) -> AiDifficulty {
+        match self {
+            DifficultyArg::Easy => AiDifficulty::Easy,
+            DifficultyArg::Medium => AiDifficulty::Medium,
+            DifficultyArg::Hard => AiDifficulty::Hard,
+            DifficultyArg::Expert => AiDifficulty::Expert,
+            DifficultyArg::Adaptive => AiDifficulty::Adaptive,
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use tetrs_core::game::snapshot::Snapshot;
+
+/// Game as of its last placement, written out by the panic hook.
+static LATEST: Mutex<Option<Autosave>> = Mutex::new(None);
+
+/// A game in progress saved to disk, offered for resuming if the app
+/// didn't get to end it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Autosave {
+    /// Name of the mode, as `GameMode::name` gives it.
+    pub mode: String,
+    pub game: Snapshot,
+    pub date: DateTime<Utc>,
+}
+
+impl Autosave {
+    pub fn new(mode: &str, game: Snapshot) -> Self {
+        Self {
+            mode: mode.to_string(),
+            game,
+            date: Utc::now(),
+        }
+    }
+
+    /// Get the autosave file path.
+    fn file_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("tetrs").join("autosave.json"))
+    }
+
+    /// The game left behind by the last session, if any.
+    pub fn load() -> Option<Self> {
+        let path = Self::file_path()?;
+        let data = fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&data)
+            .map_err(|e| tracing::warn!("couldn't parse {}: {}", path.display(), e))
+            .ok()
+    }
+
+    /// Save to disk.
+    pub fn save(&self) {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        let temp_path = path.with_extension("tmp");
+        if let Ok(data) = serde_json::to_string(self) {
+            let written = fs::write(&temp_path, &data).and_then(|_| fs::rename(&temp_path, &path));
+            if let Err(e) = written {
+                tracing::warn!("couldn't save {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Keep this as the game to save if the app panics.
+    pub fn stage(self) {
+        if let Ok(mut latest) = LATEST.lock() {
+            *latest = Some(self);
+        }
+    }
+
+    /// Forget the game, on disk and staged, once it has ended normally.
+    pub fn clear() {
+        if let Ok(mut latest) = LATEST.lock() {
+            *latest = None;
+        }
+        if let Some(path) = Self::file_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Write out the staged game; called from the panic hook. `try_lock` so a
+/// panic while the lock is held can't deadlock the hook.
+pub fn flush() {
+    if let Ok(latest) = LATEST.try_lock() {
+        if let Some(ref autosave) = *latest {
+            autosave.save();
+        }
+    }
+}
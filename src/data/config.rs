@@ -1,11 +1,214 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use tetrs_core::ai::difficulty::AiDifficulty;
+use tetrs_core::ai::personality::AiPersonality;
+use tetrs_core::game::attack::{AttackTable, AttackTablePreset};
+use tetrs_core::game::bag::{RandomizerKind, Sequence};
+use tetrs_core::game::garbage::{GarbageKind, GarbagePattern};
+use tetrs_core::game::locking::LockResetMode;
+use tetrs_core::game::modifiers::Modifiers;
+use tetrs_core::game::ruleset::{Ruleset, RulesetPreset, SpinRule};
+use tetrs_core::game::srs::RotationSystem;
+use tetrs_core::modes::cheat::MAX_LEVEL;
+use tetrs_core::modes::versus::VersusGoal;
+
+use crate::data::leaderboard;
+use crate::input::keybinds::KeybindPreset;
+use crate::ui::layout::Orientation;
+use crate::ui::theme::{BlockSkin, Palette, PieceFill};
+
+/// Config file given on the command line, used in place of the default.
+static PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Longest DAS, ARR, soft drop ARR or restart hold the config accepts, in
+/// milliseconds.
+const MAX_DELAY_MS: u64 = 1000;
 
 /// User configuration (persisted).
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub das_delay_ms: u64,
     pub arr_delay_ms: u64,
     pub sd_arr_delay_ms: u64,
+    /// How long restart has to be held during play; 0 restarts on the
+    /// press. Always instant on the game over screen.
+    pub restart_hold_ms: u64,
+    /// Quitting a game in progress needs a second press to go through.
+    pub confirm_quit: bool,
+    /// Keys used to play, picked on the first launch.
+    pub keybind_preset: KeybindPreset,
+    pub fps_cap: FpsCap,
+    /// Strength of the board shake, in percent; 0 keeps the board still.
+    pub shake_percent: u32,
+    /// Strength of the clear and border flashes, in percent.
+    pub flash_percent: u32,
+    /// Strength of the red pulse when the stack gets high, in percent.
+    pub danger_pulse_percent: u32,
+    /// Ring the terminal bell as the stack reaches the critical height.
+    pub danger_sting: bool,
+    /// Describe spawns, clears, garbage and the stack height in words on a
+    /// line under the board, for screen readers.
+    pub announce: bool,
+    /// Also outline where the held piece would land if swapped in now.
+    pub hold_ghost: bool,
+    /// Single player layout; Auto goes portrait in tall, narrow windows.
+    pub layout: Orientation,
+    /// Ruleset new games are played under.
+    pub ruleset: RulesetPreset,
+    /// Kick table override; None uses the ruleset's.
+    pub rotation_system: Option<RotationSystem>,
+    /// Lock delay override; None uses the ruleset's.
+    pub lock_delay_ms: Option<u64>,
+    /// Lock reset override; None uses the ruleset's.
+    pub lock_reset: Option<LockResetMode>,
+    /// Spin rule override; None uses the ruleset's.
+    pub spin_rule: Option<SpinRule>,
+    /// Randomizer per mode, keyed by mode name (e.g. "SPRINT"). Modes not
+    /// listed use the ruleset's.
+    pub randomizers: BTreeMap<String, RandomizerKind>,
+    /// Piece script file per mode, keyed by mode name, dealt in place of
+    /// the randomizer. See `Sequence::parse` for the format.
+    pub piece_scripts: BTreeMap<String, PathBuf>,
+    /// Attack table override; None uses the ruleset's.
+    pub attack_table: Option<AttackTableConfig>,
+    /// Breaking a long back-to-back chain sends its length as extra garbage.
+    pub b2b_surge: bool,
+    /// Hole pattern of incoming garbage.
+    pub garbage_pattern: GarbagePattern,
+    /// What incoming garbage lines are made of.
+    pub garbage_kind: GarbageKind,
+    /// Seconds between rows of solid garbage rising in versus; 0 for none.
+    pub rising_floor: u32,
+    /// Whether line clears charge the zone meter.
+    pub zone: bool,
+    /// Options last picked on the mode setup screens.
+    pub sprint_lines: u32,
+    pub endless_level: u32,
+    pub zen_level: u32,
+    /// Perfect clear help in zen practice.
+    pub zen_pc_hint: PcHint,
+    pub versus_difficulty: AiDifficulty,
+    pub versus_personality: AiPersonality,
+    /// Deal the AI the same pieces as the player.
+    pub versus_fair_bag: bool,
+    /// Hold back garbage for a moment while the stack is near the top, in
+    /// Easy matches.
+    pub versus_mercy: bool,
+    pub versus_goal: VersusGoal,
+    /// Outline the placement the versus AI is heading for.
+    pub show_ai_plan: bool,
+    /// Challenge modifiers for those modes.
+    pub modifiers: Modifiers,
+    /// Name offered when entering a new high score.
+    pub player_name: String,
+    /// Global leaderboard endpoint (`https://` only); results are submitted
+    /// there when set. Needs a build with the `online` feature.
+    pub leaderboard_url: Option<String>,
+    /// Piece colors; only used once the profile level unlocks it.
+    pub palette: Palette,
+    /// Piece characters; only used once the profile level unlocks it.
+    pub block_skin: BlockSkin,
+    /// Fill patterns per piece type, for telling pieces apart without
+    /// color.
+    pub piece_fill: PieceFill,
+}
+
+/// Attack table in the config: a built-in table by name, or a full table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AttackTableConfig {
+    Preset(AttackTablePreset),
+    Custom(Box<AttackTable>),
+}
+
+impl AttackTableConfig {
+    pub fn table(&self) -> AttackTable {
+        match self {
+            AttackTableConfig::Preset(preset) => preset.table(),
+            AttackTableConfig::Custom(table) => (**table).clone(),
+        }
+    }
+}
+
+/// How much the perfect clear solver shows during practice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PcHint {
+    #[default]
+    Off,
+    /// Whether a perfect clear is still reachable with the visible queue.
+    Indicator,
+    /// The indicator, plus one way to get there outlined on the board.
+    Solution,
+}
+
+impl PcHint {
+    pub const ALL: [PcHint; 3] = [PcHint::Off, PcHint::Indicator, PcHint::Solution];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PcHint::Off => "Off",
+            PcHint::Indicator => "Indicator",
+            PcHint::Solution => "Solution",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            PcHint::Off => "No perfect clear help",
+            PcHint::Indicator => "Say whether the queue can still make a perfect clear",
+            PcHint::Solution => "Also outline one perfect clear on the board",
+        }
+    }
+}
+
+/// Frame rate limit while a game or the attract demo is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FpsCap {
+    #[serde(rename = "30")]
+    Fps30,
+    #[serde(rename = "60")]
+    Fps60,
+    #[serde(rename = "120")]
+    Fps120,
+    #[serde(rename = "uncapped")]
+    Uncapped,
+}
+
+impl FpsCap {
+    pub const ALL: [FpsCap; 4] = [
+        FpsCap::Fps30,
+        FpsCap::Fps60,
+        FpsCap::Fps120,
+        FpsCap::Uncapped,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FpsCap::Fps30 => "30 FPS",
+            FpsCap::Fps60 => "60 FPS",
+            FpsCap::Fps120 => "120 FPS",
+            FpsCap::Uncapped => "Uncapped",
+        }
+    }
+
+    /// Target time per frame, or None when uncapped.
+    pub fn frame_duration(self) -> Option<Duration> {
+        let fps = match self {
+            FpsCap::Fps30 => 30,
+            FpsCap::Fps60 => 60,
+            FpsCap::Fps120 => 120,
+            FpsCap::Uncapped => return None,
+        };
+        Some(Duration::from_nanos(1_000_000_000 / fps))
+    }
 }
 
 impl Default for Config {
@@ -14,6 +217,261 @@ impl Default for Config {
             das_delay_ms: 133,
             arr_delay_ms: 0,
             sd_arr_delay_ms: 0,
+            restart_hold_ms: 500,
+            confirm_quit: true,
+            keybind_preset: KeybindPreset::Vim,
+            fps_cap: FpsCap::Fps60,
+            shake_percent: 100,
+            flash_percent: 100,
+            danger_pulse_percent: 100,
+            danger_sting: false,
+            announce: false,
+            hold_ghost: false,
+            layout: Orientation::Auto,
+            ruleset: RulesetPreset::default(),
+            rotation_system: None,
+            lock_delay_ms: None,
+            lock_reset: None,
+            spin_rule: None,
+            randomizers: BTreeMap::new(),
+            piece_scripts: BTreeMap::new(),
+            attack_table: None,
+            b2b_surge: false,
+            garbage_pattern: GarbagePattern::default(),
+            garbage_kind: GarbageKind::default(),
+            rising_floor: 0,
+            zone: false,
+            sprint_lines: 40,
+            endless_level: 0,
+            zen_level: 0,
+            zen_pc_hint: PcHint::Off,
+            versus_difficulty: AiDifficulty::Medium,
+            versus_personality: AiPersonality::Balanced,
+            versus_fair_bag: false,
+            versus_mercy: false,
+            versus_goal: VersusGoal::Survival,
+            show_ai_plan: false,
+            modifiers: Modifiers::default(),
+            player_name: String::new(),
+            leaderboard_url: None,
+            palette: Palette::default(),
+            block_skin: BlockSkin::default(),
+            piece_fill: PieceFill::Color,
+        }
+    }
+}
+
+impl Config {
+    /// Ruleset for a mode: the selected preset with any overrides applied.
+    pub fn ruleset_for(&self, mode_name: &str) -> Ruleset {
+        let mut ruleset = self.ruleset.ruleset();
+        if let Some(rotation_system) = self.rotation_system {
+            ruleset.rotation_system = rotation_system;
+        }
+        if let Some(lock_delay_ms) = self.lock_delay_ms {
+            ruleset.lock_delay_ms = lock_delay_ms;
+        }
+        if let Some(lock_reset) = self.lock_reset {
+            ruleset.lock_reset = lock_reset;
         }
+        if let Some(spin_rule) = self.spin_rule {
+            ruleset.spins = spin_rule;
+        }
+        if let Some(&randomizer) = self.randomizers.get(mode_name) {
+            ruleset.randomizer = randomizer;
+        }
+        if let Some(attack_table) = &self.attack_table {
+            ruleset.attack = attack_table.table();
+        }
+        if self.b2b_surge {
+            ruleset.attack.surge = true;
+        }
+        ruleset.zone = self.zone;
+        ruleset
+    }
+
+    /// Piece script configured for a mode. A script that can't be read or
+    /// parsed is skipped, leaving the randomizer; `piece_script_problems`
+    /// says why.
+    pub fn piece_script_for(&self, mode_name: &str) -> Option<Sequence> {
+        let path = self.piece_scripts.get(mode_name)?;
+        read_piece_script(path)
+            .map_err(|e| tracing::warn!("{}", e))
+            .ok()
+    }
+
+    /// Piece scripts that can't be used, described like the config's own
+    /// problems. Checked apart from `load`, since the file is fine and
+    /// keeps the entries.
+    pub fn piece_script_problems(&self) -> Vec<String> {
+        self.piece_scripts
+            .iter()
+            .filter_map(|(mode, path)| {
+                let e = read_piece_script(path).err()?;
+                Some(format!("piece_scripts.{}: {}; random pieces used", mode, e))
+            })
+            .collect()
+    }
+
+    /// Load and save this file from now on. Only the first call counts.
+    pub fn use_path(path: PathBuf) {
+        let _ = PATH_OVERRIDE.set(path);
+    }
+
+    /// Get the config file path.
+    fn file_path() -> Option<PathBuf> {
+        if let Some(path) = PATH_OVERRIDE.get() {
+            return Some(path.clone());
+        }
+        dirs::config_dir().map(|d| d.join("tetrs").join("config.json"))
+    }
+
+    /// Whether a config file has been saved yet, i.e. this isn't the first
+    /// launch.
+    pub fn exists() -> bool {
+        Self::file_path().is_some_and(|path| path.exists())
+    }
+
+    /// Load from disk, or use defaults if not found. Entries that are
+    /// unknown or invalid are left at their defaults and described in the
+    /// returned problems; the file is then backed up as
+    /// `config.invalid.bak`, since the next save drops them.
+    pub fn load() -> (Self, Vec<String>) {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return (Self::default(), Vec::new()),
+        };
+        let Ok(data) = fs::read_to_string(&path) else {
+            return (Self::default(), Vec::new());
+        };
+
+        let (config, problems) = match serde_json::from_str(&data) {
+            Ok(value) => Self::from_value(value),
+            Err(e) => (Self::default(), vec![format!("not valid JSON: {}", e)]),
+        };
+        if !problems.is_empty() {
+            tracing::warn!("problems in {}: {:?}", path.display(), problems);
+            let _ = fs::copy(&path, path.with_extension("invalid.bak"));
+        }
+        (config, problems)
     }
+
+    /// Build a config from parsed JSON one entry at a time, so a bad entry
+    /// only costs its own setting.
+    fn from_value(value: Value) -> (Self, Vec<String>) {
+        let Value::Object(entries) = value else {
+            return (
+                Self::default(),
+                vec!["expected an object of settings".to_string()],
+            );
+        };
+        let known = match serde_json::to_value(Self::default()) {
+            Ok(Value::Object(defaults)) => defaults,
+            _ => Map::new(),
+        };
+
+        let mut problems = Vec::new();
+        let mut valid = Map::new();
+        for (key, value) in entries {
+            if !known.contains_key(&key) {
+                problems.push(format!("unknown key \"{}\"", key));
+                continue;
+            }
+            let single = Map::from_iter([(key.clone(), value.clone())]);
+            match serde_json::from_value::<Self>(Value::Object(single)) {
+                Ok(_) => {
+                    valid.insert(key, value);
+                }
+                Err(e) => problems.push(format!("{}: {}", key, e)),
+            }
+        }
+
+        let mut config: Self = serde_json::from_value(Value::Object(valid)).unwrap_or_default();
+        let defaults = Self::default();
+        for (key, value, default) in [
+            (
+                "das_delay_ms",
+                &mut config.das_delay_ms,
+                defaults.das_delay_ms,
+            ),
+            (
+                "arr_delay_ms",
+                &mut config.arr_delay_ms,
+                defaults.arr_delay_ms,
+            ),
+            (
+                "sd_arr_delay_ms",
+                &mut config.sd_arr_delay_ms,
+                defaults.sd_arr_delay_ms,
+            ),
+            (
+                "restart_hold_ms",
+                &mut config.restart_hold_ms,
+                defaults.restart_hold_ms,
+            ),
+        ] {
+            if *value > MAX_DELAY_MS {
+                problems.push(format!("{}: {} is over {}ms", key, value, MAX_DELAY_MS));
+                *value = default;
+            }
+        }
+        for (key, value) in [
+            ("endless_level", &mut config.endless_level),
+            ("zen_level", &mut config.zen_level),
+        ] {
+            if *value > MAX_LEVEL {
+                problems.push(format!("{}: {} is over level {}", key, value, MAX_LEVEL));
+                *value = 0;
+            }
+        }
+        for (key, value) in [
+            ("shake_percent", &mut config.shake_percent),
+            ("flash_percent", &mut config.flash_percent),
+            ("danger_pulse_percent", &mut config.danger_pulse_percent),
+        ] {
+            if *value > 100 {
+                problems.push(format!("{}: {} is over 100%", key, value));
+                *value = 100;
+            }
+        }
+        if let Some(url) = config
+            .leaderboard_url
+            .take_if(|url| !leaderboard::is_secure(url))
+        {
+            problems.push(format!("leaderboard_url: {} is not https://", url));
+        }
+        if config.sprint_lines == 0 {
+            problems.push("sprint_lines: a sprint needs at least 1 line".to_string());
+            config.sprint_lines = defaults.sprint_lines;
+        }
+        (config, problems)
+    }
+
+    /// Save to disk.
+    pub fn save(&self) {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        // Write to temp file then rename for atomicity
+        let temp_path = path.with_extension("tmp");
+        if let Ok(data) = serde_json::to_string_pretty(self) {
+            let written = fs::write(&temp_path, &data).and_then(|_| fs::rename(&temp_path, &path));
+            if let Err(e) = written {
+                tracing::warn!("couldn't save {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Read and parse a piece script, saying what went wrong if it can't be.
+fn read_piece_script(path: &Path) -> Result<Sequence, String> {
+    let script =
+        fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+    Sequence::parse(&script).map_err(|e| format!("bad piece script {}: {}", path.display(), e))
 }
@@ -0,0 +1,112 @@
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::Local;
+
+use tetrs_core::game::snapshot::Snapshot;
+use tetrs_core::game::GameState;
+
+/// Inputs kept for the report.
+const RECENT_INPUTS: usize = 50;
+
+/// What the app was doing, kept up to date for the panic hook.
+struct Context {
+    state: String,
+    mode: String,
+    /// Piece in play with its position and rotation, which the snapshot
+    /// leaves out.
+    piece: String,
+    game: Option<Snapshot>,
+    /// App clock time and input, oldest first.
+    inputs: VecDeque<(Duration, String)>,
+}
+
+static CONTEXT: Mutex<Context> = Mutex::new(Context {
+    state: String::new(),
+    mode: String::new(),
+    piece: String::new(),
+    game: None,
+    inputs: VecDeque::new(),
+});
+
+/// Note the app state, e.g. `Playing`.
+pub fn note_state(state: &str) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        context.state = state.to_string();
+    }
+}
+
+/// Note the game being played.
+pub fn note_game(mode: &str, game: &GameState) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        context.mode = mode.to_string();
+        context.piece = format!("{:?}", game.current_piece);
+        context.game = Some(game.snapshot());
+    }
+}
+
+/// Note an input as it's handled.
+pub fn record_input(clock: Duration, input: &impl std::fmt::Debug) {
+    if let Ok(mut context) = CONTEXT.lock() {
+        if context.inputs.len() == RECENT_INPUTS {
+            context.inputs.pop_front();
+        }
+        context.inputs.push_back((clock, format!("{:?}", input)));
+    }
+}
+
+/// Directory crash reports are written to.
+fn dir_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("tetrs"))
+}
+
+/// Write a report for a panic, returning its path. Called from the panic
+/// hook, so nothing here may panic or wait on a lock.
+pub fn write(info: &PanicHookInfo) -> Option<PathBuf> {
+    let now = Local::now();
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_default();
+
+    let mut report = String::new();
+    let _ = writeln!(report, "tetrs {} crash report", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "time: {}", now.to_rfc3339());
+    let _ = writeln!(report, "panic: {} at {}", message, location);
+
+    if let Ok(context) = CONTEXT.try_lock() {
+        let _ = writeln!(report, "state: {}", context.state);
+        let _ = writeln!(report, "mode: {}", context.mode);
+        let _ = writeln!(report, "piece: {}", context.piece);
+        let _ = writeln!(report, "\nrecent inputs (oldest first):");
+        for (clock, input) in &context.inputs {
+            let _ = writeln!(report, "  {:>9.3}s {}", clock.as_secs_f64(), input);
+        }
+        let _ = writeln!(report, "\ngame:");
+        match context.game.as_ref().map(serde_json::to_string) {
+            Some(Ok(game)) => report.push_str(&game),
+            _ => report.push_str("none"),
+        }
+        report.push('\n');
+    }
+
+    let _ = writeln!(report, "\nbacktrace:\n{}", Backtrace::force_capture());
+
+    let dir = dir_path()?;
+    fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+    fs::write(&path, report).ok()?;
+    Some(path)
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use tetrs_core::modes::ghost_run::GhostRun;
+
+/// Personal-best runs kept for racing against.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct GhostStore {
+    pub sprint: Option<GhostRun>,
+}
+
+impl GhostStore {
+    /// Get the ghosts file path.
+    fn file_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("tetrs").join("ghosts.json"))
+    }
+
+    /// Load from disk, or create empty if not found.
+    pub fn load() -> Self {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                tracing::warn!("couldn't parse {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Read a run to race from a file: either a ghosts file, whose sprint
+    /// run is used, or a single run.
+    pub fn load_run(path: &Path) -> Result<GhostRun, String> {
+        let data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        if let Ok(GhostStore { sprint: Some(run) }) = serde_json::from_str(&data) {
+            return Ok(run);
+        }
+        serde_json::from_str(&data).map_err(|_| "not a ghosts file or a recorded run".to_string())
+    }
+
+    /// Save to disk.
+    pub fn save(&self) {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        // Compact rather than pretty: a run is a few hundred frames
+        let temp_path = path.with_extension("tmp");
+        if let Ok(data) = serde_json::to_string(self) {
+            let written = fs::write(&temp_path, &data).and_then(|_| fs::rename(&temp_path, &path));
+            if let Err(e) = written {
+                tracing::warn!("couldn't save {}: {}", path.display(), e);
+            }
+        }
+    }
+}
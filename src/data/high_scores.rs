@@ -1,18 +1,89 @@
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use tetrs_core::game::modifiers::Modifiers;
+use tetrs_core::game::stats::Stats;
 
 const MAX_SCORES: usize = 10;
 
+/// Format version of the high scores file written by this build. Files
+/// without a version are version 1.
+const VERSION: u64 = 2;
+
+/// Upgrades from each older version to the next, oldest first: entry
+/// `i` turns a version `i + 1` file into version `i + 2`.
+const MIGRATIONS: &[fn(&mut Value)] = &[migrate_v1];
+
+/// Version 1 predates player names, run stats, sprint splits and sprint
+/// lengths. Every sprint then was 40 lines.
+fn migrate_v1(value: &mut Value) {
+    let tables = ["sprint", "endless", "versus", "gauntlet"];
+    for table in tables {
+        let Some(entries) = value.get_mut(table).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for entry in entries.iter_mut().filter_map(Value::as_object_mut) {
+            entry.entry("name").or_insert(Value::from(""));
+            entry.entry("stats").or_insert(Value::Null);
+            if table == "sprint" {
+                entry.entry("length").or_insert(Value::from(40));
+                entry.entry("splits").or_insert(Value::Array(Vec::new()));
+            }
+        }
+    }
+    if let Some(store) = value.as_object_mut() {
+        store.entry("gauntlet").or_insert(Value::Array(Vec::new()));
+    }
+}
+
+/// Bring a file of an older `version` up to this build's format, one
+/// version at a time.
+fn migrate(value: &mut Value, version: u64) {
+    for migrate in MIGRATIONS.iter().skip(version.saturating_sub(1) as usize) {
+        migrate(value);
+    }
+}
+
+/// Sprint length assumed for entries saved before lengths were recorded.
+fn default_sprint_length() -> u32 {
+    40
+}
+
 /// Sprint high score entry.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SprintEntry {
     pub time_ms: u64,
+    /// Lines the sprint was to, as opposed to lines cleared.
+    #[serde(default = "default_sprint_length")]
+    pub length: u32,
     pub lines: u32,
     pub pieces: u32,
     pub date: DateTime<Utc>,
+    /// Player name, empty if none was given.
+    #[serde(default)]
+    pub name: String,
+    /// Time in milliseconds at which each line was cleared.
+    #[serde(default)]
+    pub splits: Vec<u64>,
+    /// Full stats of the run; missing from entries saved by older versions.
+    #[serde(default)]
+    pub stats: Option<Stats>,
+    /// Challenge modifiers the run was played with.
+    #[serde(default)]
+    pub modifiers: Modifiers,
+}
+
+impl SprintEntry {
+    /// Length and whether it was a purist run: entries only rank against
+    /// others with the same.
+    pub fn category(&self) -> (u32, bool) {
+        (self.length, self.modifiers.is_purist())
+    }
 }
 
 /// Endless high score entry.
@@ -22,6 +93,13 @@ pub struct EndlessEntry {
     pub level: u32,
     pub lines: u32,
     pub date: DateTime<Utc>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub stats: Option<Stats>,
+    /// Challenge modifiers the run was played with.
+    #[serde(default)]
+    pub modifiers: Modifiers,
 }
 
 /// Versus high score entry.
@@ -32,14 +110,62 @@ pub struct VersusEntry {
     pub time_ms: u64,
     pub damage_sent: u32,
     pub date: DateTime<Utc>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub stats: Option<Stats>,
+    /// Challenge modifiers the run was played with.
+    #[serde(default)]
+    pub modifiers: Modifiers,
+}
+
+/// Gauntlet high score entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GauntletEntry {
+    /// Stages beaten, out of four.
+    pub stages: u32,
+    pub time_ms: u64,
+    pub lives_left: u32,
+    pub date: DateTime<Utc>,
+    #[serde(default)]
+    pub name: String,
+    /// Stats of the final round played.
+    #[serde(default)]
+    pub stats: Option<Stats>,
 }
 
 /// All high scores.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HighScoreStore {
+    #[serde(default)]
+    pub version: u64,
     pub sprint: Vec<SprintEntry>,
     pub endless: Vec<EndlessEntry>,
     pub versus: Vec<VersusEntry>,
+    #[serde(default)]
+    pub gauntlet: Vec<GauntletEntry>,
+    /// Why the last load or save failed, until the UI takes it to show.
+    /// Scores stay in memory for the session either way.
+    #[serde(skip)]
+    pub error: Option<String>,
+    /// Set for a file written by a newer build, which saving would
+    /// overwrite with less than it holds.
+    #[serde(skip)]
+    read_only: bool,
+}
+
+impl Default for HighScoreStore {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            sprint: Vec::new(),
+            endless: Vec::new(),
+            versus: Vec::new(),
+            gauntlet: Vec::new(),
+            error: None,
+            read_only: false,
+        }
+    }
 }
 
 impl HighScoreStore {
@@ -53,72 +179,178 @@ impl HighScoreStore {
         Self::config_path().map(|d| d.join("high_scores.json"))
     }
 
-    /// Load from disk, or create empty if not found.
+    /// Load from disk, or create empty if not found. Older formats are
+    /// migrated; a file that is migrated or unreadable is backed up first so
+    /// the next save can't lose it, and one from a newer build is shown but
+    /// never saved over.
     pub fn load() -> Self {
         let path = match Self::file_path() {
             Some(p) => p,
             None => return Self::default(),
         };
 
-        match fs::read_to_string(&path) {
-            Ok(data) => serde_json::from_str(&data).unwrap_or_default(),
-            Err(_) => Self::default(),
+        let data = match fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => return Self::failed(format!("Couldn't read high scores: {}", e)),
+        };
+        let mut value: Value = match serde_json::from_str(&data) {
+            Ok(value) => value,
+            Err(_) => {
+                Self::backup(&path, "unreadable");
+                return Self::failed(Self::UNREADABLE.to_string());
+            }
+        };
+
+        let version = value.get("version").and_then(Value::as_u64).unwrap_or(1);
+        if version > VERSION {
+            let error = format!(
+                "High scores are from a newer tetrs (v{}); new scores won't be saved",
+                version
+            );
+            let store = serde_json::from_value::<Self>(value).unwrap_or_default();
+            return Self {
+                read_only: true,
+                ..store.with_error(error)
+            };
+        }
+        if version < VERSION {
+            Self::backup(&path, &format!("v{}", version));
+            migrate(&mut value, version);
+        }
+
+        match serde_json::from_value::<Self>(value) {
+            Ok(mut store) => {
+                store.version = VERSION;
+                store
+            }
+            Err(_) => {
+                Self::backup(&path, "unreadable");
+                Self::failed(Self::UNREADABLE.to_string())
+            }
+        }
+    }
+
+    const UNREADABLE: &'static str =
+        "Malformed high scores backed up to high_scores.unreadable.bak";
+
+    /// An empty store carrying a load error.
+    fn failed(error: String) -> Self {
+        Self::default().with_error(error)
+    }
+
+    fn with_error(self, error: String) -> Self {
+        tracing::warn!("{}", error);
+        Self {
+            error: Some(error),
+            ..self
         }
     }
 
-    /// Save to disk.
-    pub fn save(&self) {
+    /// Copy the file aside as `high_scores.<tag>.bak`.
+    fn backup(path: &Path, tag: &str) {
+        tracing::warn!("backing up {} as {}", path.display(), tag);
+        let _ = fs::copy(path, path.with_extension(format!("{}.bak", tag)));
+    }
+
+    /// Save to disk, noting any failure in `error`. Does nothing for a
+    /// file from a newer build.
+    pub fn save(&mut self) {
+        if self.read_only {
+            return;
+        }
         let path = match Self::file_path() {
             Some(p) => p,
             None => return,
         };
+        if let Err(e) = self.write(&path) {
+            tracing::warn!("couldn't save {}: {}", path.display(), e);
+            self.error = Some(format!(
+                "Couldn't save high scores ({}); kept for this session",
+                e
+            ));
+        }
+    }
 
+    fn write(&self, path: &Path) -> io::Result<()> {
         // Ensure directory exists
         if let Some(dir) = path.parent() {
-            let _ = fs::create_dir_all(dir);
+            fs::create_dir_all(dir)?;
         }
 
         // Write to temp file then rename for atomicity
         let temp_path = path.with_extension("tmp");
-        if let Ok(data) = serde_json::to_string_pretty(self) {
-            if fs::write(&temp_path, &data).is_ok() {
-                let _ = fs::rename(&temp_path, &path);
-            }
-        }
+        let data = serde_json::to_string_pretty(self)?;
+        fs::write(&temp_path, &data)?;
+        fs::rename(&temp_path, path)
     }
 
     /// Add a sprint result. Returns true if it's a new high score.
-    pub fn add_sprint(&mut self, time_ms: u64, lines: u32, pieces: u32) -> bool {
+    pub fn add_sprint(
+        &mut self,
+        name: &str,
+        length: u32,
+        stats: &Stats,
+        splits: &[u64],
+        modifiers: Modifiers,
+    ) -> bool {
+        let time_ms = stats.time.as_millis() as u64;
         let entry = SprintEntry {
             time_ms,
-            lines,
-            pieces,
+            length,
+            lines: stats.lines_cleared,
+            pieces: stats.pieces_placed,
             date: Utc::now(),
+            name: name.to_string(),
+            splits: splits.to_vec(),
+            stats: Some(stats.clone()),
+            modifiers,
         };
 
-        let is_best = self.sprint.first().map_or(true, |e| time_ms < e.time_ms);
+        // Purist runs are ranked apart from everything else
+        let category = (length, modifiers.is_purist());
+        let is_best = self
+            .sprint
+            .iter()
+            .filter(|e| e.category() == category)
+            .all(|e| time_ms < e.time_ms);
 
         self.sprint.push(entry);
-        self.sprint.sort_by_key(|e| e.time_ms);
-        self.sprint.truncate(MAX_SCORES);
+        // Fastest first within each length and category, keeping the top
+        // few of each
+        self.sprint.sort_by_key(|e| (e.category(), e.time_ms));
+        let mut kept = 0;
+        let mut category = None;
+        self.sprint.retain(|e| {
+            if category != Some(e.category()) {
+                category = Some(e.category());
+                kept = 0;
+            }
+            kept += 1;
+            kept <= MAX_SCORES
+        });
         self.save();
 
         is_best
     }
 
     /// Add an endless result. Returns true if it's a new high score.
-    pub fn add_endless(&mut self, score: u64, level: u32, lines: u32) -> bool {
+    pub fn add_endless(&mut self, name: &str, stats: &Stats, modifiers: Modifiers) -> bool {
+        let score = stats.score;
         let entry = EndlessEntry {
             score,
-            level,
-            lines,
+            level: stats.level,
+            lines: stats.lines_cleared,
             date: Utc::now(),
+            name: name.to_string(),
+            stats: Some(stats.clone()),
+            modifiers,
         };
 
-        let is_best = self.endless.first().map_or(true, |e| score > e.score);
+        let is_best = self.endless.first().is_none_or(|e| score > e.score);
 
         self.endless.push(entry);
-        self.endless.sort_by(|a, b| b.score.cmp(&a.score));
+        self.endless.sort_by_key(|e| std::cmp::Reverse(e.score));
         self.endless.truncate(MAX_SCORES);
         self.save();
 
@@ -128,20 +360,24 @@ impl HighScoreStore {
     /// Add a versus result. Returns true if it's a new top entry.
     pub fn add_versus(
         &mut self,
+        name: &str,
         won: bool,
         difficulty: &str,
-        time_ms: u64,
-        damage_sent: u32,
+        stats: &Stats,
+        modifiers: Modifiers,
     ) -> bool {
         let entry = VersusEntry {
             won,
             difficulty: difficulty.to_string(),
-            time_ms,
-            damage_sent,
+            time_ms: stats.time.as_millis() as u64,
+            damage_sent: stats.attack_sent,
             date: Utc::now(),
+            name: name.to_string(),
+            stats: Some(stats.clone()),
+            modifiers,
         };
 
-        let is_best = self.versus.first().map_or(true, |e| won && !e.won);
+        let is_best = self.versus.first().is_none_or(|e| won && !e.won);
 
         self.versus.push(entry);
         // Sort: wins first, then by damage sent
@@ -152,4 +388,106 @@ impl HighScoreStore {
 
         is_best
     }
+
+    /// Add a gauntlet run. Returns true if it's a new top entry.
+    pub fn add_gauntlet(
+        &mut self,
+        name: &str,
+        stages: u32,
+        time_ms: u64,
+        lives_left: u32,
+        stats: &Stats,
+    ) -> bool {
+        let entry = GauntletEntry {
+            stages,
+            time_ms,
+            lives_left,
+            date: Utc::now(),
+            name: name.to_string(),
+            stats: Some(stats.clone()),
+        };
+
+        let is_best = self
+            .gauntlet
+            .first()
+            .is_none_or(|e| stages > e.stages || (stages == e.stages && time_ms < e.time_ms));
+
+        self.gauntlet.push(entry);
+        // Sort: furthest stage first, then fastest
+        self.gauntlet
+            .sort_by(|a, b| b.stages.cmp(&a.stages).then(a.time_ms.cmp(&b.time_ms)));
+        self.gauntlet.truncate(MAX_SCORES);
+        self.save();
+
+        is_best
+    }
+
+    /// Rename the most recently added entry of a table, indexed in the
+    /// order of the high scores screen's tabs.
+    pub fn rename_latest(&mut self, table: usize, name: &str) {
+        let latest = match table {
+            0 => self
+                .sprint
+                .iter_mut()
+                .map(|e| (e.date, &mut e.name))
+                .max_by_key(|(d, _)| *d),
+            1 => self
+                .endless
+                .iter_mut()
+                .map(|e| (e.date, &mut e.name))
+                .max_by_key(|(d, _)| *d),
+            2 => self
+                .versus
+                .iter_mut()
+                .map(|e| (e.date, &mut e.name))
+                .max_by_key(|(d, _)| *d),
+            3 => self
+                .gauntlet
+                .iter_mut()
+                .map(|e| (e.date, &mut e.name))
+                .max_by_key(|(d, _)| *d),
+            _ => None,
+        };
+        if let Some((_, entry_name)) = latest {
+            *entry_name = name.to_string();
+            self.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A high scores file as version 1 wrote it: no version, names, stats,
+    /// sprint lengths or splits, and no gauntlet table.
+    const V1_FIXTURE: &str = r#"{
+        "sprint": [
+            {"time_ms": 61250, "lines": 40, "pieces": 101, "date": "2024-03-01T12:00:00Z"}
+        ],
+        "endless": [
+            {"score": 48200, "level": 7, "lines": 63, "date": "2024-03-02T12:00:00Z"}
+        ],
+        "versus": [
+            {"won": true, "difficulty": "Medium", "time_ms": 95000, "damage_sent": 42,
+             "date": "2024-03-03T12:00:00Z"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_migrates_v1_file() {
+        let mut value: Value = serde_json::from_str(V1_FIXTURE).unwrap();
+        migrate(&mut value, 1);
+        assert_eq!(value["sprint"][0]["length"], 40);
+        assert_eq!(value["endless"][0]["name"], "");
+        assert!(value["gauntlet"].as_array().is_some_and(Vec::is_empty));
+
+        let store: HighScoreStore = serde_json::from_value(value).unwrap();
+        let sprint = &store.sprint[0];
+        assert_eq!((sprint.time_ms, sprint.length), (61250, 40));
+        assert!(sprint.splits.is_empty() && sprint.stats.is_none());
+        assert_eq!(store.endless[0].score, 48200);
+        assert!(store.versus[0].won);
+        assert!(store.gauntlet.is_empty());
+    }
 }
@@ -0,0 +1,274 @@
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use tetrs_core::game::bag::RandomizerKind;
+use tetrs_core::game::ruleset::Ruleset;
+use tetrs_core::game::stats::Stats;
+use tetrs_core::modes::ghost_run::GhostRun;
+
+/// Tables kept on the global leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Board {
+    Sprint,
+    /// 40-line sprints played without hold and/or the ghost.
+    SprintPurist,
+    Endless,
+}
+
+impl Board {
+    /// Key used in requests to the endpoint.
+    pub fn key(self) -> &'static str {
+        match self {
+            Board::Sprint => "sprint",
+            Board::SprintPurist => "sprint_purist",
+            Board::Endless => "endless",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Board::Sprint => "40-Line Sprint",
+            Board::SprintPurist => "40-Line Sprint (Purist)",
+            Board::Endless => "Endless",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Board::Sprint => Board::SprintPurist,
+            Board::SprintPurist => Board::Endless,
+            Board::Endless => Board::Sprint,
+        }
+    }
+
+    /// Whether the table is ranked by time rather than score.
+    pub fn is_timed(self) -> bool {
+        matches!(self, Board::Sprint | Board::SprintPurist)
+    }
+}
+
+/// A result sent to the leaderboard. The seed, ruleset and replay hash let
+/// the server regenerate the run's pieces and check it against others
+/// played on the same ones.
+#[derive(Debug, Clone, Serialize)]
+pub struct Submission {
+    pub board: &'static str,
+    pub name: String,
+    pub time_ms: u64,
+    pub score: u64,
+    pub lines: u32,
+    pub seed: u64,
+    /// Also in `ruleset`; kept at the top level since it decides which
+    /// pieces the seed deals.
+    pub randomizer: RandomizerKind,
+    pub ruleset: Ruleset,
+    pub replay_hash: String,
+    pub date: DateTime<Utc>,
+}
+
+impl Submission {
+    pub fn new(
+        board: Board,
+        name: &str,
+        seed: u64,
+        ruleset: &Ruleset,
+        stats: &Stats,
+        recording: Option<&GhostRun>,
+    ) -> Self {
+        Self {
+            board: board.key(),
+            name: name.to_string(),
+            time_ms: stats.time.as_millis() as u64,
+            score: stats.score,
+            lines: stats.lines_cleared,
+            seed,
+            randomizer: ruleset.randomizer,
+            ruleset: ruleset.clone(),
+            replay_hash: replay_hash(seed, ruleset, stats, recording),
+            date: Utc::now(),
+        }
+    }
+}
+
+/// One row of a global table, as returned by the endpoint.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GlobalEntry {
+    pub name: String,
+    #[serde(default)]
+    pub time_ms: u64,
+    #[serde(default)]
+    pub score: u64,
+    pub date: DateTime<Utc>,
+}
+
+/// Top entries of the table being viewed.
+#[derive(Debug, Clone)]
+pub enum Tops {
+    Idle,
+    Loading,
+    Loaded(Vec<GlobalEntry>),
+    Failed(String),
+}
+
+/// Client for the global leaderboard. Requests run on background threads so
+/// a slow server never stalls a frame.
+pub struct Leaderboard {
+    endpoint: Option<String>,
+    pub board: Board,
+    pub tops: Tops,
+    pending: Option<Receiver<Result<Vec<GlobalEntry>, String>>>,
+}
+
+impl Leaderboard {
+    /// Client for `endpoint`, disabled without one, for one that isn't
+    /// `https://`, or when built without the `online` feature.
+    pub fn new(endpoint: Option<String>) -> Self {
+        Self {
+            endpoint: endpoint.filter(|url| cfg!(feature = "online") && is_secure(url)),
+            board: Board::Sprint,
+            tops: Tops::Idle,
+            pending: None,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.endpoint.is_some()
+    }
+
+    /// Send a result in the background; failures are dropped.
+    pub fn submit(&self, submission: Submission) {
+        let Some(endpoint) = self.endpoint.clone() else {
+            return;
+        };
+        let Ok(body) = serde_json::to_string(&submission) else {
+            return;
+        };
+        thread::spawn(move || {
+            if let Err(e) = http::request("POST", &format!("{}/scores", endpoint), Some(&body)) {
+                tracing::warn!("leaderboard submission failed: {}", e);
+            }
+        });
+    }
+
+    /// Start loading the current board's top entries.
+    pub fn fetch(&mut self) {
+        let Some(endpoint) = self.endpoint.clone() else {
+            return;
+        };
+        let url = format!("{}/scores?board={}", endpoint, self.board.key());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = http::request("GET", &url, None).and_then(|body| {
+                serde_json::from_str(&body).map_err(|e| format!("bad response: {}", e))
+            });
+            let _ = tx.send(result);
+        });
+        self.tops = Tops::Loading;
+        self.pending = Some(rx);
+    }
+
+    /// Pick up a finished fetch. Returns true if the tops changed.
+    pub fn poll(&mut self) -> bool {
+        let Some(ref rx) = self.pending else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.tops = match result {
+                    Ok(entries) => Tops::Loaded(entries),
+                    Err(e) => {
+                        tracing::warn!("leaderboard fetch failed: {}", e);
+                        Tops::Failed(e)
+                    }
+                };
+                self.pending = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.tops = Tops::Failed("request failed".to_string());
+                self.pending = None;
+                true
+            }
+        }
+    }
+}
+
+/// Whether `url` is an endpoint results may be sent to.
+pub fn is_secure(url: &str) -> bool {
+    url.starts_with("https://")
+}
+
+/// FNV-1a hash of the seed, ruleset, final stats, and recorded run, as hex.
+/// There's no input replay to hash, so the run's progress trace and key
+/// timings stand in for it.
+fn replay_hash(
+    seed: u64,
+    ruleset: &Ruleset,
+    stats: &Stats,
+    recording: Option<&GhostRun>,
+) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |bytes: &[u8]| {
+        for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    feed(&seed.to_le_bytes());
+    feed(
+        serde_json::to_string(ruleset)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    feed(serde_json::to_string(stats).unwrap_or_default().as_bytes());
+    if let Some(recording) = recording {
+        feed(
+            serde_json::to_string(recording)
+                .unwrap_or_default()
+                .as_bytes(),
+        );
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(feature = "online")]
+mod http {
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// HTTPS request, returning the body of a 2xx response. Plain `http://`
+    /// is refused so names and results never go out unencrypted.
+    pub fn request(method: &str, url: &str, body: Option<&str>) -> Result<String, String> {
+        if !super::is_secure(url) {
+            return Err("only https:// endpoints are supported".to_string());
+        }
+        let agent = ureq::AgentBuilder::new()
+            .timeout(TIMEOUT)
+            .https_only(true)
+            .build();
+        let request = agent
+            .request(method, url)
+            .set("Content-Type", "application/json");
+        let response = match body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response.into_string().map_err(|e| e.to_string()),
+            Err(ureq::Error::Status(status, _)) => Err(format!("server returned {}", status)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+#[cfg(not(feature = "online"))]
+mod http {
+    pub fn request(_method: &str, _url: &str, _body: Option<&str>) -> Result<String, String> {
+        Err("built without online support".to_string())
+    }
+}
@@ -0,0 +1,174 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use tetrs_core::ai::difficulty::AiDifficulty;
+use tetrs_core::ai::personality::AiPersonality;
+use tetrs_core::ai::rating::{self, START_RATING};
+use tetrs_core::game::stats::Stats;
+
+/// Matches kept; the oldest go once there are more.
+const MAX_MATCHES: usize = 500;
+
+/// One finished versus match or gauntlet round.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRecord {
+    pub difficulty: AiDifficulty,
+    #[serde(default)]
+    pub personality: AiPersonality,
+    pub won: bool,
+    pub time_ms: u64,
+    pub apm: f64,
+    pub attack_sent: u32,
+    pub attack_received: u32,
+    pub date: DateTime<Utc>,
+    /// The player's rating after the match; missing from matches saved
+    /// before ratings were kept.
+    #[serde(default)]
+    pub rating: Option<f64>,
+}
+
+/// Wins and losses against one difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Record {
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl Record {
+    /// Share of matches won, or None before any were played.
+    pub fn win_rate(&self) -> Option<f64> {
+        let played = self.wins + self.losses;
+        (played > 0).then(|| self.wins as f64 / played as f64)
+    }
+}
+
+/// Every versus match played, oldest first. Unlike the versus high scores
+/// this keeps losses and doesn't rank anything.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MatchHistory {
+    pub matches: Vec<MatchRecord>,
+}
+
+impl MatchHistory {
+    /// Get the match history file path.
+    fn file_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("tetrs").join("match_history.json"))
+    }
+
+    /// Load from disk, or create empty if not found. A file that can't be
+    /// read is backed up first so the next save can't lose it.
+    pub fn load() -> Self {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                tracing::warn!("couldn't parse {}: {}", path.display(), e);
+                Self::backup(&path);
+                Self::default()
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                tracing::warn!("couldn't read {}: {}", path.display(), e);
+                Self::backup(&path);
+                Self::default()
+            }
+        }
+    }
+
+    /// Copy the file aside as `match_history.unreadable.bak`.
+    fn backup(path: &Path) {
+        tracing::warn!("backing up {} as unreadable", path.display());
+        let _ = fs::copy(path, path.with_extension("unreadable.bak"));
+    }
+
+    /// Save to disk.
+    pub fn save(&self) {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        let temp_path = path.with_extension("tmp");
+        if let Ok(data) = serde_json::to_string(self) {
+            let written = fs::write(&temp_path, &data).and_then(|_| fs::rename(&temp_path, &path));
+            if let Err(e) = written {
+                tracing::warn!("couldn't save {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// The player's current rating.
+    pub fn rating(&self) -> f64 {
+        self.matches
+            .iter()
+            .rev()
+            .find_map(|m| m.rating)
+            .unwrap_or(START_RATING)
+    }
+
+    /// Highest rating reached, if any match has been rated.
+    pub fn peak_rating(&self) -> Option<f64> {
+        self.matches
+            .iter()
+            .filter_map(|m| m.rating)
+            .reduce(f64::max)
+    }
+
+    /// Record a finished match from the player's stats against an AI rated
+    /// `ai_rating`, and save. Returns how far the player's rating moved.
+    pub fn add(
+        &mut self,
+        difficulty: AiDifficulty,
+        personality: AiPersonality,
+        ai_rating: f64,
+        won: bool,
+        stats: &Stats,
+    ) -> f64 {
+        let before = self.rating();
+        let after = rating::rate(before, ai_rating, won);
+        self.matches.push(MatchRecord {
+            difficulty,
+            personality,
+            won,
+            time_ms: stats.time.as_millis() as u64,
+            apm: stats.apm(),
+            attack_sent: stats.attack_sent,
+            attack_received: stats.garbage_received,
+            date: Utc::now(),
+            rating: Some(after),
+        });
+        if self.matches.len() > MAX_MATCHES {
+            let excess = self.matches.len() - MAX_MATCHES;
+            self.matches.drain(..excess);
+        }
+        self.save();
+        after - before
+    }
+
+    /// Wins and losses against `difficulty`.
+    pub fn record(&self, difficulty: AiDifficulty) -> Record {
+        self.matches
+            .iter()
+            .filter(|m| m.difficulty == difficulty)
+            .fold(Record::default(), |mut record, m| {
+                if m.won {
+                    record.wins += 1;
+                } else {
+                    record.losses += 1;
+                }
+                record
+            })
+    }
+}
@@ -1,2 +1,9 @@
+pub mod autosave;
 pub mod config;
+pub mod crash_report;
+pub mod ghosts;
 pub mod high_scores;
+pub mod leaderboard;
+pub mod match_history;
+pub mod profile;
+pub mod summary;
@@ -0,0 +1,129 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ui::theme::{BlockSkin, Palette};
+
+/// XP between level 1 and level 2; each level after needs this much more
+/// than the one before.
+const XP_PER_LEVEL: u64 = 500;
+
+/// XP for a win in versus or a gauntlet stage.
+const WIN_XP: u64 = 250;
+
+/// Player progression across all modes (persisted).
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub xp: u64,
+    pub games: u32,
+    pub wins: u32,
+}
+
+/// What a finished game earned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XpAward {
+    pub xp: u64,
+    pub level: u32,
+    pub level_up: bool,
+    /// Names of cosmetics the new level unlocked.
+    pub unlocks: Vec<&'static str>,
+}
+
+impl Profile {
+    /// Total XP needed to reach `level`.
+    fn xp_for_level(level: u32) -> u64 {
+        let level = level.max(1) as u64;
+        XP_PER_LEVEL * level * (level - 1) / 2
+    }
+
+    pub fn level(&self) -> u32 {
+        let mut level = 1;
+        while self.xp >= Self::xp_for_level(level + 1) {
+            level += 1;
+        }
+        level
+    }
+
+    /// XP into the current level, and XP the level takes in total.
+    pub fn progress(&self) -> (u64, u64) {
+        let level = self.level();
+        let start = Self::xp_for_level(level);
+        (self.xp - start, Self::xp_for_level(level + 1) - start)
+    }
+
+    /// Credit a finished game and save.
+    pub fn award(&mut self, score: u64, lines: u32, won: bool) -> XpAward {
+        let xp = lines as u64 * 10 + score / 100 + if won { WIN_XP } else { 0 };
+        let before = self.level();
+        self.xp += xp;
+        self.games += 1;
+        if won {
+            self.wins += 1;
+        }
+        self.save();
+
+        let level = self.level();
+        let unlocked = |unlock: u32| unlock > before && unlock <= level;
+        let unlocks = Palette::ALL
+            .iter()
+            .filter(|p| unlocked(p.unlock_level()))
+            .map(|p| p.name())
+            .chain(
+                BlockSkin::ALL
+                    .iter()
+                    .filter(|s| unlocked(s.unlock_level()))
+                    .map(|s| s.name()),
+            )
+            .collect();
+        XpAward {
+            xp,
+            level,
+            level_up: level > before,
+            unlocks,
+        }
+    }
+
+    /// Get the profile file path.
+    fn file_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("tetrs").join("profile.json"))
+    }
+
+    /// Load from disk, or start a new profile if not found.
+    pub fn load() -> Self {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return Self::default(),
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|e| {
+                tracing::warn!("couldn't parse {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save to disk.
+    pub fn save(&self) {
+        let path = match Self::file_path() {
+            Some(p) => p,
+            None => return,
+        };
+
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        // Write to temp file then rename for atomicity
+        let temp_path = path.with_extension("tmp");
+        if let Ok(data) = serde_json::to_string_pretty(self) {
+            let written = fs::write(&temp_path, &data).and_then(|_| fs::rename(&temp_path, &path));
+            if let Err(e) = written {
+                tracing::warn!("couldn't save {}: {}", path.display(), e);
+            }
+        }
+    }
+}
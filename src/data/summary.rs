@@ -0,0 +1,103 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+
+use tetrs_core::game::modifiers::Modifiers;
+use tetrs_core::modes::{format_number, GameResult};
+
+/// Directory saved summaries go in.
+fn dir_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|d| d.join("tetrs").join("results"))
+}
+
+/// A finished game as plain text: what was played, when and with which
+/// seed, then every stat.
+pub fn format(
+    result: &GameResult,
+    modifiers: Modifiers,
+    seed: u64,
+    date: DateTime<Local>,
+) -> String {
+    let stats = &result.stats;
+    let mut mode = result.mode_name.clone();
+    if !modifiers.is_empty() {
+        mode.push_str(&format!(" [{}]", modifiers.label()));
+    }
+    let outcome = match result.won {
+        Some(true) => "won",
+        Some(false) => "lost",
+        None => "finished",
+    };
+
+    let mut rows = vec![
+        ("Mode", mode),
+        ("Date", date.format("%Y-%m-%d %H:%M:%S").to_string()),
+        ("Seed", seed.to_string()),
+        ("Outcome", outcome.to_string()),
+        (result.primary_label.as_str(), result.primary_value.clone()),
+        ("", String::new()),
+        ("Score", format_number(stats.score)),
+        ("Time", stats.format_time()),
+        ("Level", stats.level.to_string()),
+        ("Lines", stats.lines_cleared.to_string()),
+        ("Pieces", stats.pieces_placed.to_string()),
+        ("PPS", format!("{:.2}", stats.pps())),
+        ("APM", format!("{:.1}", stats.apm())),
+        ("KPS", format!("{:.2}", stats.kps())),
+        ("Inputs", stats.inputs.to_string()),
+        ("Singles", stats.singles.to_string()),
+        ("Doubles", stats.doubles.to_string()),
+        ("Triples", stats.triples.to_string()),
+        ("Quads", stats.quads.to_string()),
+        ("T-Spins", stats.tspins.to_string()),
+        ("Mini T-Spins", stats.mini_tspins.to_string()),
+        ("All-Spins", stats.all_spins.to_string()),
+        ("Perfect Clears", stats.perfect_clears.to_string()),
+        ("Max Combo", stats.max_combo.to_string()),
+        ("Max B2B", stats.max_btb.to_string()),
+        ("Attack Sent", stats.attack_sent.to_string()),
+        ("Garbage Taken", stats.garbage_received.to_string()),
+        ("Garbage Cleared", stats.garbage_cleared.to_string()),
+    ];
+    if result.won.is_some() {
+        rows.push(("VS", format!("{:.1}", stats.vs())));
+    }
+
+    let mut text = format!("tetrs {} game summary\n\n", env!("CARGO_PKG_VERSION"));
+    for (label, value) in rows {
+        if label.is_empty() {
+            text.push('\n');
+        } else {
+            let _ = writeln!(text, "{:<16} {}", label, value);
+        }
+    }
+    text
+}
+
+/// One line to paste elsewhere, e.g. "tetrs 40-Line Sprint — 01:02.451, 1.82 PPS".
+pub fn share_line(result: &GameResult) -> String {
+    let outcome = match result.won {
+        Some(true) => "won, ",
+        Some(false) => "lost, ",
+        None => "",
+    };
+    format!(
+        "tetrs {} — {}{}, {:.2} PPS",
+        result.mode_name,
+        outcome,
+        result.primary_value,
+        result.stats.pps()
+    )
+}
+
+/// Write a summary of `result` to the data directory, returning its path.
+pub fn save(result: &GameResult, modifiers: Modifiers, seed: u64) -> Result<PathBuf, String> {
+    let now = Local::now();
+    let dir = dir_path().ok_or("no data directory")?;
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("result-{}.txt", now.format("%Y%m%d-%H%M%S")));
+    fs::write(&path, format(result, modifiers, seed, now)).map_err(|e| e.to_string())?;
+    Ok(path)
+}
@@ -1,5 +1,7 @@
 use std::time::Duration;
 
+use crate::input::FIRST_REPEAT_WAIT;
+
 /// DAS (Delayed Auto-Shift) configuration.
 pub const DAS_DELAY: Duration = Duration::from_millis(133);
 /// ARR (Auto Repeat Rate). 0 = instant.
@@ -7,12 +9,91 @@ pub const ARR_DELAY: Duration = Duration::from_millis(0);
 /// Soft drop ARR.
 pub const SD_ARR_DELAY: Duration = Duration::from_millis(0);
 
+/// Without key release events, a held key is taken as let go once its
+/// repeats stop for this long.
+pub const REPEAT_TIMEOUT: Duration = Duration::from_millis(150);
+
 /// DAS state for a single direction.
 #[derive(Debug, Clone)]
 pub struct DasState {
     pub pressed: bool,
     pub phase: DasPhase,
     pub timer: Duration,
+    /// Press and repeat tracking for terminals without release events.
+    repeat: KeyRepeat,
+}
+
+/// What a key event means on a terminal without release events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RepeatEvent {
+    /// A press, or one that can't yet be told from a quick second tap:
+    /// act once.
+    Press,
+    /// Events close enough together to be OS repeats, so the key is being
+    /// held and has been since the first press this long ago.
+    Held(Duration),
+    /// A later repeat of a key already held.
+    Repeat,
+}
+
+/// Guesses a key's releases from the gaps between its OS repeats. The
+/// OS waits longer before the first repeat than between the rest, so a
+/// second event alone might be a tap; a third close behind one that came
+/// after such a wait means the key is held. Quick taps never leave that
+/// long gap followed by a short one.
+#[derive(Debug, Clone, Default)]
+struct KeyRepeat {
+    /// Whether the key is taken as down.
+    down: bool,
+    /// Time since the last press or repeat.
+    quiet: Duration,
+    /// Time between the last event and the one before it, None after a
+    /// first press.
+    last_gap: Option<Duration>,
+    repeating: bool,
+}
+
+impl KeyRepeat {
+    fn event(&mut self) -> RepeatEvent {
+        let quiet = std::mem::take(&mut self.quiet);
+        if !self.down {
+            *self = Self {
+                down: true,
+                ..Self::default()
+            };
+            return RepeatEvent::Press;
+        }
+        if self.repeating {
+            return RepeatEvent::Repeat;
+        }
+        match self.last_gap.replace(quiet) {
+            // The OS's first repeat, then one close behind: held since the
+            // press before that first repeat
+            Some(gap) if gap > REPEAT_TIMEOUT && quiet <= REPEAT_TIMEOUT => {
+                self.repeating = true;
+                RepeatEvent::Held(gap + quiet)
+            }
+            _ => RepeatEvent::Press,
+        }
+    }
+
+    /// Returns true when the key is taken as released.
+    fn tick(&mut self, dt: Duration) -> bool {
+        if !self.down {
+            return false;
+        }
+        self.quiet += dt;
+        let wait = if self.repeating {
+            REPEAT_TIMEOUT
+        } else {
+            FIRST_REPEAT_WAIT
+        };
+        if self.quiet > wait {
+            *self = Self::default();
+            return true;
+        }
+        false
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -30,9 +111,32 @@ impl DasState {
             pressed: false,
             phase: DasPhase::Idle,
             timer: Duration::ZERO,
+            repeat: KeyRepeat::default(),
         }
     }
 
+    /// Key pressed or repeated on a terminal without release events. Auto
+    /// shift starts only once the OS repeats the key, counting the time it
+    /// has already been held towards the DAS delay.
+    pub fn press_without_release(&mut self) -> RepeatEvent {
+        let event = self.repeat.event();
+        if let RepeatEvent::Held(down) = event {
+            self.press();
+            self.timer = down;
+        }
+        event
+    }
+
+    /// Advance the release guess on a terminal without release events.
+    /// Returns true when the key is taken as released.
+    pub fn tick_without_release(&mut self, dt: Duration) -> bool {
+        let released = self.repeat.tick(dt);
+        if released {
+            self.release();
+        }
+        released
+    }
+
     /// Key pressed.
     pub fn press(&mut self) {
         self.pressed = true;
@@ -97,6 +201,7 @@ impl DasState {
         self.pressed = false;
         self.phase = DasPhase::Idle;
         self.timer = Duration::ZERO;
+        self.repeat = KeyRepeat::default();
     }
 }
 
@@ -131,6 +236,16 @@ impl DasHandler {
         (left, right, sd)
     }
 
+    /// Advance the release guesses on a terminal without release events.
+    /// Returns (left, right, soft_drop) taken as released this tick.
+    pub fn tick_without_release(&mut self, dt: Duration) -> (bool, bool, bool) {
+        (
+            self.left.tick_without_release(dt),
+            self.right.tick_without_release(dt),
+            self.soft_drop.tick_without_release(dt),
+        )
+    }
+
     /// Reset all DAS state.
     pub fn reset(&mut self) {
         self.left.reset();
@@ -172,6 +287,70 @@ mod tests {
         assert_eq!(moves, 20); // Instant = 20 (capped)
     }
 
+    #[test]
+    fn test_tap_without_release_does_not_shift() {
+        let mut das = DasState::new();
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+        let mut moves = 0;
+        for _ in 0..50 {
+            das.tick_without_release(Duration::from_millis(16));
+            moves += das.tick(Duration::from_millis(16), DAS_DELAY, Duration::ZERO);
+        }
+        assert_eq!(moves, 0);
+        // Long forgotten, so the next press is a new tap
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+    }
+
+    #[test]
+    fn test_hold_without_release_shifts_then_stops() {
+        let mut das = DasState::new();
+        das.press_without_release();
+        das.tick_without_release(Duration::from_millis(300));
+        // The first OS repeat could still be a second tap
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+        das.tick_without_release(Duration::from_millis(30));
+        // The next one close behind isn't: held for 330 ms, past the DAS delay
+        assert_eq!(
+            das.press_without_release(),
+            RepeatEvent::Held(Duration::from_millis(330))
+        );
+        assert!(das.tick(Duration::from_millis(16), DAS_DELAY, Duration::ZERO) > 0);
+        assert_eq!(das.press_without_release(), RepeatEvent::Repeat);
+
+        // Repeats stop: released
+        let released = (0..20).any(|_| das.tick_without_release(Duration::from_millis(16)));
+        assert!(released);
+        assert!(!das.is_active());
+    }
+
+    #[test]
+    fn test_double_tap_without_release_is_not_held() {
+        let mut das = DasState::new();
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+        das.tick_without_release(Duration::from_millis(100));
+        // Too soon for an OS repeat, so a second tap
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+        das.tick_without_release(Duration::from_millis(90));
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+        let moves: u32 = (0..10)
+            .map(|_| {
+                das.tick_without_release(Duration::from_millis(16));
+                das.tick(Duration::from_millis(16), DAS_DELAY, Duration::ZERO)
+            })
+            .sum();
+        assert_eq!(moves, 0);
+
+        // Held after the taps: timed from the press the OS started
+        // repeating, 300 ms before its first repeat
+        das.tick_without_release(Duration::from_millis(140));
+        assert_eq!(das.press_without_release(), RepeatEvent::Press);
+        das.tick_without_release(Duration::from_millis(30));
+        assert_eq!(
+            das.press_without_release(),
+            RepeatEvent::Held(Duration::from_millis(330))
+        );
+    }
+
     #[test]
     fn test_das_release() {
         let mut das = DasState::new();
@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+
+use crate::input::FIRST_REPEAT_WAIT;
+
+/// Samples kept for each rolling figure, about two seconds of frames.
+const WINDOW: usize = 120;
+
+/// Durations over the last `WINDOW` samples.
+#[derive(Debug, Clone, Default)]
+pub struct Samples(VecDeque<Duration>);
+
+impl Samples {
+    fn push(&mut self, sample: Duration) {
+        if self.0.len() == WINDOW {
+            self.0.pop_front();
+        }
+        self.0.push_back(sample);
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        if self.0.is_empty() {
+            return None;
+        }
+        Some(self.0.iter().sum::<Duration>() / self.0.len() as u32)
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.0.iter().max().copied()
+    }
+
+    /// Mean distance of a sample from the mean.
+    pub fn jitter(&self) -> Option<Duration> {
+        let mean = self.mean()?;
+        let spread: Duration = self.0.iter().map(|&s| s.abs_diff(mean)).sum();
+        Some(spread / self.0.len() as u32)
+    }
+}
+
+/// What the input diagnostics screen shows: how evenly input is polled,
+/// how long a key waits between being read and drawn, and which kinds of
+/// key events the terminal actually sends.
+#[derive(Debug, Clone, Default)]
+pub struct InputDiagnostics {
+    /// Gaps between consecutive polls; a key pressed just after one waits
+    /// up to a whole gap to be read.
+    pub polls: Samples,
+    last_poll: Option<Instant>,
+    /// Time from reading a key to the frame that shows it being drawn.
+    pub read_to_draw: Samples,
+    /// When the oldest key not yet drawn was read.
+    unread_since: Option<Instant>,
+    pub presses: u64,
+    pub repeats: u64,
+    pub releases: u64,
+    /// Wait before the OS starts repeating a held key.
+    pub repeat_delay: Option<Duration>,
+    /// Gaps between later repeats of a held key.
+    pub repeat_gaps: Samples,
+    /// Key and time of the last press or repeat, to time the next repeat.
+    last_down: Option<(KeyCode, Instant, bool)>,
+    pub last_key: Option<(KeyCode, KeyEventKind)>,
+}
+
+impl InputDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Input was polled at `now`.
+    pub fn record_poll(&mut self, now: Instant) {
+        if let Some(last) = self.last_poll {
+            self.polls.push(now - last);
+        }
+        self.last_poll = Some(now);
+    }
+
+    /// A key event was read at `now`.
+    pub fn record_key(&mut self, key: &KeyEvent, now: Instant) {
+        self.last_key = Some((key.code, key.kind));
+        self.unread_since.get_or_insert(now);
+        match key.kind {
+            KeyEventKind::Press => {
+                self.presses += 1;
+                self.last_down = Some((key.code, now, false));
+            }
+            KeyEventKind::Repeat => self.record_repeat(key.code, now),
+            KeyEventKind::Release => {
+                self.releases += 1;
+                self.last_down = None;
+            }
+        }
+    }
+
+    fn record_repeat(&mut self, code: KeyCode, now: Instant) {
+        self.repeats += 1;
+        match self.last_down {
+            Some((last, at, repeating)) if last == code => {
+                if repeating {
+                    self.repeat_gaps.push(now - at);
+                } else {
+                    self.repeat_delay = Some(now - at);
+                }
+            }
+            _ => {}
+        }
+        self.last_down = Some((code, now, true));
+    }
+
+    /// A key press from a terminal without release or repeat events, where
+    /// the same key again soon after is taken as the OS repeating it.
+    pub fn record_key_without_kinds(&mut self, key: &KeyEvent, now: Instant) {
+        let same = self
+            .last_down
+            .is_some_and(|(last, at, _)| last == key.code && now - at <= FIRST_REPEAT_WAIT);
+        if same {
+            self.last_key = Some((key.code, KeyEventKind::Repeat));
+            self.unread_since.get_or_insert(now);
+            self.record_repeat(key.code, now);
+        } else {
+            self.record_key(key, now);
+        }
+    }
+
+    /// A frame was drawn at `now`, showing any keys read since the last.
+    pub fn record_drawn(&mut self, now: Instant) {
+        if let Some(read) = self.unread_since.take() {
+            self.read_to_draw.push(now - read);
+        }
+    }
+
+    /// Rough time from pressing a key to seeing it: on average half a poll
+    /// gap waiting to be read, then the time to draw it.
+    pub fn estimated_latency(&self) -> Option<Duration> {
+        Some(self.polls.mean()? / 2 + self.read_to_draw.mean()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventState, KeyModifiers};
+
+    fn key(kind: KeyEventKind) -> KeyEvent {
+        KeyEvent {
+            code: KeyCode::Char('h'),
+            modifiers: KeyModifiers::NONE,
+            kind,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn test_poll_jitter() {
+        let mut diag = InputDiagnostics::new();
+        let start = Instant::now();
+        for ms in [0, 10, 30, 40, 60] {
+            diag.record_poll(start + Duration::from_millis(ms));
+        }
+        // Gaps of 10, 20, 10, 20 ms
+        assert_eq!(diag.polls.mean(), Some(Duration::from_millis(15)));
+        assert_eq!(diag.polls.max(), Some(Duration::from_millis(20)));
+        assert_eq!(diag.polls.jitter(), Some(Duration::from_millis(5)));
+    }
+
+    #[test]
+    fn test_repeat_timing() {
+        let mut diag = InputDiagnostics::new();
+        let start = Instant::now();
+        diag.record_key(&key(KeyEventKind::Press), start);
+        diag.record_key(
+            &key(KeyEventKind::Repeat),
+            start + Duration::from_millis(300),
+        );
+        diag.record_key(
+            &key(KeyEventKind::Repeat),
+            start + Duration::from_millis(330),
+        );
+        diag.record_key(
+            &key(KeyEventKind::Release),
+            start + Duration::from_millis(340),
+        );
+        assert_eq!(diag.repeat_delay, Some(Duration::from_millis(300)));
+        assert_eq!(diag.repeat_gaps.mean(), Some(Duration::from_millis(30)));
+        assert_eq!((diag.presses, diag.repeats, diag.releases), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_read_to_draw_counts_oldest_key() {
+        let mut diag = InputDiagnostics::new();
+        let start = Instant::now();
+        diag.record_key(&key(KeyEventKind::Press), start);
+        diag.record_key(
+            &key(KeyEventKind::Release),
+            start + Duration::from_millis(2),
+        );
+        diag.record_drawn(start + Duration::from_millis(5));
+        // Nothing new read, so this frame isn't counted
+        diag.record_drawn(start + Duration::from_millis(20));
+        assert_eq!(diag.read_to_draw.mean(), Some(Duration::from_millis(5)));
+    }
+}
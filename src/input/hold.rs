@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use crate::input::FIRST_REPEAT_WAIT;
+
+/// Time restart has to be held for during play.
+pub const RESTART_HOLD: Duration = Duration::from_millis(500);
+
+/// A key that has to be held for a while before it acts, such as restart
+/// during play.
+#[derive(Debug, Clone)]
+pub struct HoldTimer {
+    /// Time the key has to be held; zero acts on the press.
+    pub duration: Duration,
+    /// Time since the press, while the key is down.
+    held: Option<Duration>,
+    /// Held time at the last press or repeat. Without release events, only
+    /// this much is known to have been held.
+    seen: Duration,
+}
+
+impl HoldTimer {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            held: None,
+            seen: Duration::ZERO,
+        }
+    }
+
+    /// Key pressed, or repeated by a terminal without release events.
+    pub fn press(&mut self) {
+        match self.held {
+            Some(held) => self.seen = held,
+            None => {
+                self.held = Some(Duration::ZERO);
+                self.seen = Duration::ZERO;
+            }
+        }
+    }
+
+    /// Key released, or the hold cancelled.
+    pub fn release(&mut self) {
+        self.held = None;
+        self.seen = Duration::ZERO;
+    }
+
+    /// Time held that counts towards the duration.
+    fn counted(&self, has_key_release: bool) -> Option<Duration> {
+        let held = self.held?;
+        Some(if has_key_release { held } else { self.seen })
+    }
+
+    /// Advance the timer. Returns true once when the key has been held long
+    /// enough.
+    pub fn tick(&mut self, dt: Duration, has_key_release: bool) -> bool {
+        let Some(held) = self.held.as_mut() else {
+            return false;
+        };
+        *held += dt;
+        if !has_key_release && *held - self.seen > FIRST_REPEAT_WAIT {
+            self.release();
+            return false;
+        }
+        let done = self
+            .counted(has_key_release)
+            .is_some_and(|counted| counted >= self.duration);
+        if done {
+            self.release();
+        }
+        done
+    }
+
+    /// How far through the hold the key is, from 0 to 1, while it's down.
+    pub fn progress(&self, has_key_release: bool) -> Option<f32> {
+        let counted = self.counted(has_key_release)?;
+        if self.duration.is_zero() {
+            return None;
+        }
+        Some((counted.as_secs_f32() / self.duration.as_secs_f32()).min(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    #[test]
+    fn test_fires_after_holding() {
+        let mut timer = HoldTimer::new(Duration::from_millis(100));
+        timer.press();
+        let mut fired = 0;
+        for _ in 0..20 {
+            if timer.tick(FRAME, true) {
+                fired += 1;
+            }
+        }
+        assert_eq!(fired, 1);
+        assert_eq!(timer.progress(true), None);
+    }
+
+    #[test]
+    fn test_release_cancels() {
+        let mut timer = HoldTimer::new(Duration::from_millis(100));
+        timer.press();
+        for _ in 0..4 {
+            assert!(!timer.tick(FRAME, true));
+        }
+        assert!(timer.progress(true).unwrap() > 0.5);
+        timer.release();
+        for _ in 0..10 {
+            assert!(!timer.tick(FRAME, true));
+        }
+    }
+
+    #[test]
+    fn test_tap_without_release_events_does_not_fire() {
+        let mut timer = HoldTimer::new(Duration::from_millis(100));
+        timer.press();
+        for _ in 0..60 {
+            assert!(!timer.tick(FRAME, false));
+        }
+        assert_eq!(timer.progress(false), None);
+    }
+
+    #[test]
+    fn test_repeats_without_release_events_fire() {
+        let mut timer = HoldTimer::new(Duration::from_millis(100));
+        timer.press();
+        let mut fired = false;
+        for _ in 0..20 {
+            timer.press();
+            fired |= timer.tick(FRAME * 2, false);
+        }
+        assert!(fired);
+    }
+}
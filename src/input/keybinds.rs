@@ -1,4 +1,7 @@
 use crossterm::event::KeyCode;
+use serde::{Deserialize, Serialize};
+
+use tetrs_core::game::GameAction;
 
 /// All bindable actions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -11,9 +14,26 @@ pub enum Action {
     RotateCCW,
     Rotate180,
     Hold,
+    Zone,
+    CycleTarget,
     Pause,
     Quit,
     Restart,
+    Undo,
+    /// Open the sandbox command prompt.
+    Command,
+    TogglePerf,
+    /// Break down the versus AI's last placement.
+    ToggleAiExplain,
+    /// Show every binding.
+    Help,
+    /// Next order or filter for a high score table.
+    CycleSort,
+    CycleFilter,
+    /// Write the finished game's summary to a file.
+    SaveResult,
+    /// Copy a one-line summary of the finished game to the clipboard.
+    CopyResult,
     MenuUp,
     MenuDown,
     MenuLeft,
@@ -22,6 +42,98 @@ pub enum Action {
     MenuBack,
 }
 
+impl Action {
+    /// Actions bound during play, in the order the help overlay lists them.
+    pub const GAME: [Action; 18] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::SoftDrop,
+        Action::HardDrop,
+        Action::RotateCW,
+        Action::RotateCCW,
+        Action::Rotate180,
+        Action::Hold,
+        Action::Zone,
+        Action::CycleTarget,
+        Action::Pause,
+        Action::Restart,
+        Action::Undo,
+        Action::Command,
+        Action::Quit,
+        Action::TogglePerf,
+        Action::ToggleAiExplain,
+        Action::Help,
+    ];
+
+    /// Actions bound on menu screens.
+    pub const MENU: [Action; 13] = [
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::MenuLeft,
+        Action::MenuRight,
+        Action::MenuSelect,
+        Action::MenuBack,
+        Action::CycleSort,
+        Action::CycleFilter,
+        Action::SaveResult,
+        Action::CopyResult,
+        Action::Quit,
+        Action::TogglePerf,
+        Action::Help,
+    ];
+
+    /// The game action a key bound to this performs when pressed, for
+    /// actions that act on the piece.
+    pub fn game_action(self) -> Option<GameAction> {
+        match self {
+            Action::MoveLeft => Some(GameAction::MoveLeft),
+            Action::MoveRight => Some(GameAction::MoveRight),
+            Action::SoftDrop => Some(GameAction::SoftDrop),
+            Action::HardDrop => Some(GameAction::HardDrop),
+            Action::RotateCW => Some(GameAction::RotateCW),
+            Action::RotateCCW => Some(GameAction::RotateCCW),
+            Action::Rotate180 => Some(GameAction::Rotate180),
+            Action::Hold => Some(GameAction::Hold),
+            Action::Zone => Some(GameAction::Zone),
+            _ => None,
+        }
+    }
+
+    /// What the action does, for the help overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::SoftDrop => "Soft drop",
+            Action::HardDrop => "Hard drop",
+            Action::RotateCW => "Rotate clockwise",
+            Action::RotateCCW => "Rotate counter-cw",
+            Action::Rotate180 => "Rotate 180",
+            Action::Hold => "Hold",
+            Action::Zone => "Zone",
+            Action::CycleTarget => "Royale targeting",
+            Action::Pause => "Pause",
+            Action::Quit => "Quit",
+            Action::Restart => "Restart",
+            Action::Undo => "Undo (Zen)",
+            Action::Command => "Command (Zen)",
+            Action::TogglePerf => "Perf overlay",
+            Action::ToggleAiExplain => "AI decision overlay",
+            Action::Help => "This help",
+            Action::CycleSort => "Sort scores",
+            Action::CycleFilter => "Filter scores",
+            Action::SaveResult => "Save result",
+            Action::CopyResult => "Copy result",
+            Action::MenuUp => "Up",
+            Action::MenuDown => "Down",
+            Action::MenuLeft => "Left",
+            Action::MenuRight => "Right",
+            Action::MenuSelect => "Select",
+            Action::MenuBack => "Back",
+        }
+    }
+}
+
 /// A key binding entry.
 #[derive(Debug, Clone)]
 pub struct Keybind {
@@ -64,6 +176,14 @@ pub fn default_keybinds() -> Vec<Keybind> {
             action: Action::Hold,
             keys: vec![KeyCode::Char('g'), KeyCode::Char('c')],
         },
+        Keybind {
+            action: Action::Zone,
+            keys: vec![KeyCode::Char('e'), KeyCode::Char('v')],
+        },
+        Keybind {
+            action: Action::CycleTarget,
+            keys: vec![KeyCode::Tab, KeyCode::Char('t')],
+        },
         Keybind {
             action: Action::Pause,
             keys: vec![KeyCode::Esc, KeyCode::Char('p')],
@@ -76,6 +196,42 @@ pub fn default_keybinds() -> Vec<Keybind> {
             action: Action::Restart,
             keys: vec![KeyCode::Char('r')],
         },
+        Keybind {
+            action: Action::Undo,
+            keys: vec![KeyCode::Char('u')],
+        },
+        Keybind {
+            action: Action::Command,
+            keys: vec![KeyCode::Char(':')],
+        },
+        Keybind {
+            action: Action::TogglePerf,
+            keys: vec![KeyCode::F(3)],
+        },
+        Keybind {
+            action: Action::ToggleAiExplain,
+            keys: vec![KeyCode::F(4)],
+        },
+        Keybind {
+            action: Action::Help,
+            keys: vec![KeyCode::Char('?')],
+        },
+        Keybind {
+            action: Action::CycleSort,
+            keys: vec![KeyCode::Char('s')],
+        },
+        Keybind {
+            action: Action::CycleFilter,
+            keys: vec![KeyCode::Char('f')],
+        },
+        Keybind {
+            action: Action::SaveResult,
+            keys: vec![KeyCode::Char('w')],
+        },
+        Keybind {
+            action: Action::CopyResult,
+            keys: vec![KeyCode::Char('y')],
+        },
         Keybind {
             action: Action::MenuUp,
             keys: vec![KeyCode::Char('k'), KeyCode::Up],
@@ -103,6 +259,102 @@ pub fn default_keybinds() -> Vec<Keybind> {
     ]
 }
 
+/// Layout of the keys used to play. Each only changes the playing keys;
+/// the rest keep their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum KeybindPreset {
+    #[default]
+    Vim,
+    Guideline,
+    Wasd,
+    LeftHanded,
+}
+
+impl KeybindPreset {
+    pub const ALL: [KeybindPreset; 4] = [
+        KeybindPreset::Vim,
+        KeybindPreset::Guideline,
+        KeybindPreset::Wasd,
+        KeybindPreset::LeftHanded,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            KeybindPreset::Vim => "Vim",
+            KeybindPreset::Guideline => "Guideline",
+            KeybindPreset::Wasd => "WASD",
+            KeybindPreset::LeftHanded => "Left-handed",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            KeybindPreset::Vim => "h/l move, j/k drop, d/f rotate, g hold (arrows work too)",
+            KeybindPreset::Guideline => "Arrows move, Space drops, z/x rotate, c holds",
+            KeybindPreset::Wasd => "a/d move, s/w drop, j/k rotate, f holds",
+            KeybindPreset::LeftHanded => "s/f move, d/e drop, / and . rotate, , holds",
+        }
+    }
+
+    /// Playing keys that differ from the Vim defaults. Every playing
+    /// action is listed so no default key is left bound twice.
+    fn game_keys(self) -> Option<[(Action, Vec<KeyCode>); 9]> {
+        use KeyCode::Char;
+
+        Some(match self {
+            KeybindPreset::Vim => return None,
+            KeybindPreset::Guideline => [
+                (Action::MoveLeft, vec![KeyCode::Left]),
+                (Action::MoveRight, vec![KeyCode::Right]),
+                (Action::SoftDrop, vec![KeyCode::Down]),
+                (Action::HardDrop, vec![Char(' ')]),
+                (Action::RotateCW, vec![KeyCode::Up, Char('x')]),
+                (Action::RotateCCW, vec![Char('z')]),
+                (Action::Rotate180, vec![Char('a')]),
+                (Action::Hold, vec![Char('c')]),
+                (Action::Zone, vec![Char('v')]),
+            ],
+            KeybindPreset::Wasd => [
+                (Action::MoveLeft, vec![Char('a')]),
+                (Action::MoveRight, vec![Char('d')]),
+                (Action::SoftDrop, vec![Char('s')]),
+                (Action::HardDrop, vec![Char('w'), Char(' ')]),
+                (Action::RotateCW, vec![Char('k')]),
+                (Action::RotateCCW, vec![Char('j')]),
+                (Action::Rotate180, vec![Char('l')]),
+                (Action::Hold, vec![Char('f')]),
+                (Action::Zone, vec![Char('e')]),
+            ],
+            // Guideline mirrored: moves under the left hand, rotations
+            // under the right
+            KeybindPreset::LeftHanded => [
+                (Action::MoveLeft, vec![Char('s')]),
+                (Action::MoveRight, vec![Char('f')]),
+                (Action::SoftDrop, vec![Char('d')]),
+                (Action::HardDrop, vec![Char('e'), Char(' ')]),
+                (Action::RotateCW, vec![Char('.')]),
+                (Action::RotateCCW, vec![Char('/')]),
+                (Action::Rotate180, vec![Char(';')]),
+                (Action::Hold, vec![Char(',')]),
+                (Action::Zone, vec![Char('m')]),
+            ],
+        })
+    }
+
+    /// Full set of bindings for the preset.
+    pub fn keybinds(self) -> Vec<Keybind> {
+        let mut binds = default_keybinds();
+        if let Some(game_keys) = self.game_keys() {
+            for (action, keys) in game_keys {
+                if let Some(bind) = binds.iter_mut().find(|bind| bind.action == action) {
+                    bind.keys = keys;
+                }
+            }
+        }
+        binds
+    }
+}
+
 /// Keybind resolver.
 pub struct KeybindMap {
     binds: Vec<Keybind>,
@@ -110,52 +362,31 @@ pub struct KeybindMap {
 
 impl KeybindMap {
     pub fn new() -> Self {
+        Self::with_preset(KeybindPreset::default())
+    }
+
+    pub fn with_preset(preset: KeybindPreset) -> Self {
         Self {
-            binds: default_keybinds(),
+            binds: preset.keybinds(),
         }
     }
 
     /// Look up the action for a key code in game context.
     pub fn resolve_game(&self, key: KeyCode) -> Option<Action> {
-        for bind in &self.binds {
-            if bind.keys.contains(&key) {
-                match bind.action {
-                    // Only return game actions during play
-                    Action::MoveLeft
-                    | Action::MoveRight
-                    | Action::SoftDrop
-                    | Action::HardDrop
-                    | Action::RotateCW
-                    | Action::RotateCCW
-                    | Action::Rotate180
-                    | Action::Hold
-                    | Action::Pause
-                    | Action::Quit
-                    | Action::Restart => return Some(bind.action),
-                    _ => {}
-                }
-            }
-        }
-        None
+        self.resolve(key, &Action::GAME)
     }
 
     /// Look up the action for a key code in menu context.
     pub fn resolve_menu(&self, key: KeyCode) -> Option<Action> {
-        for bind in &self.binds {
-            if bind.keys.contains(&key) {
-                match bind.action {
-                    Action::MenuUp
-                    | Action::MenuDown
-                    | Action::MenuLeft
-                    | Action::MenuRight
-                    | Action::MenuSelect
-                    | Action::MenuBack
-                    | Action::Quit => return Some(bind.action),
-                    _ => {}
-                }
-            }
-        }
-        None
+        self.resolve(key, &Action::MENU)
+    }
+
+    /// First action among `actions` bound to the key.
+    fn resolve(&self, key: KeyCode, actions: &[Action]) -> Option<Action> {
+        self.binds
+            .iter()
+            .find(|bind| bind.keys.contains(&key) && actions.contains(&bind.action))
+            .map(|bind| bind.action)
     }
 
     /// Get the display string for an action's primary key.
@@ -169,6 +400,21 @@ impl KeybindMap {
         }
         "?".to_string()
     }
+
+    /// Display string for every key bound to an action, e.g. `h ←`.
+    pub fn keys_display(&self, action: Action) -> String {
+        let keys: Vec<String> = self
+            .binds
+            .iter()
+            .filter(|bind| bind.action == action)
+            .flat_map(|bind| bind.keys.iter().map(|&key| format_key(key)))
+            .collect();
+        if keys.is_empty() {
+            "-".to_string()
+        } else {
+            keys.join(" ")
+        }
+    }
 }
 
 /// Format a key code for display.
@@ -184,6 +430,38 @@ pub fn format_key(key: KeyCode) -> String {
         KeyCode::Esc => "Esc".to_string(),
         KeyCode::Tab => "Tab".to_string(),
         KeyCode::Backspace => "Bksp".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
         _ => "?".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_bind_each_game_key_once() {
+        for preset in KeybindPreset::ALL {
+            let binds = preset.keybinds();
+            let mut seen = Vec::new();
+            for bind in binds
+                .iter()
+                .filter(|bind| Action::GAME.contains(&bind.action))
+            {
+                for key in &bind.keys {
+                    assert!(!seen.contains(key), "{:?} binds {:?} twice", preset, key);
+                    seen.push(*key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_preset_resolves_its_keys() {
+        let map = KeybindMap::with_preset(KeybindPreset::Wasd);
+        assert_eq!(map.resolve_game(KeyCode::Char('a')), Some(Action::MoveLeft));
+        assert_eq!(map.resolve_game(KeyCode::Char('h')), None);
+        // Menus keep their keys
+        assert_eq!(map.resolve_menu(KeyCode::Char('h')), Some(Action::MenuLeft));
+    }
+}
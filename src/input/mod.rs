@@ -1,13 +1,25 @@
 pub mod das;
+pub mod diag;
+pub mod hold;
 pub mod keybinds;
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 
-use self::das::DasHandler;
+use self::das::{DasHandler, RepeatEvent};
+use self::diag::InputDiagnostics;
+use self::hold::{HoldTimer, RESTART_HOLD};
 use self::keybinds::{Action, KeybindMap};
-use crate::game::GameAction;
+use tetrs_core::game::GameAction;
+
+use crate::data::config::Config;
+
+/// Longest the OS waits before it starts repeating a held key; the usual
+/// delays run from 250 to 600 ms. Without release or repeat events, a key
+/// seen again within this long may be repeating, and one quiet for longer
+/// has been let go.
+pub const FIRST_REPEAT_WAIT: Duration = Duration::from_millis(600);
 
 /// Input event for the application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,12 +28,26 @@ pub enum AppInput {
     Pause,
     Quit,
     Restart,
+    Undo,
+    Command,
+    /// Next battle royale targeting strategy.
+    CycleTarget,
+    TogglePerf,
+    ToggleAiExplain,
+    Help,
+    CycleSort,
+    CycleFilter,
+    SaveResult,
+    CopyResult,
     MenuUp,
     MenuDown,
     MenuLeft,
     MenuRight,
     MenuSelect,
     MenuBack,
+    /// Character typed into a text field.
+    Char(char),
+    Backspace,
     None,
 }
 
@@ -29,11 +55,30 @@ pub enum AppInput {
 pub struct InputHandler {
     pub keybinds: KeybindMap,
     pub das: DasHandler,
+    /// Restart during play, which has to be held so a stray press doesn't
+    /// end a run.
+    pub restart: HoldTimer,
     /// Whether we're in game mode (DAS active) or menu mode.
     pub in_game: bool,
     /// Whether the terminal supports key release events (Kitty keyboard protocol).
-    /// When false, DAS is disabled and each press/repeat is treated as a single move.
+    /// When false, held keys are guessed from the OS key repeat.
     pub has_key_release: bool,
+    /// Whether the terminal may have changed (resized or refocused, as on a
+    /// tmux reattach) so its keyboard support should be checked again.
+    pub reprobe: bool,
+    /// Whether any key was pressed during the last poll, bound or not.
+    pub key_pressed: bool,
+    /// New terminal size, if it was resized during the last poll.
+    pub resized: Option<(u16, u16)>,
+    /// Whether keys are being typed into a text field rather than bound.
+    pub text_entry: bool,
+    /// Whether the left and right keys are swapped, for the Mirror modifier.
+    pub mirrored: bool,
+    /// Timing and event counts for the input diagnostics screen.
+    pub diag: InputDiagnostics,
+    /// Game keys that went down (true) or up during the last poll and DAS
+    /// tick, for recording a run's input timings.
+    pub key_edges: Vec<(GameAction, bool)>,
 }
 
 impl InputHandler {
@@ -41,8 +86,16 @@ impl InputHandler {
         Self {
             keybinds: KeybindMap::new(),
             das: DasHandler::new(),
+            restart: HoldTimer::new(RESTART_HOLD),
             in_game: false,
             has_key_release,
+            reprobe: false,
+            key_pressed: false,
+            resized: None,
+            text_entry: false,
+            mirrored: false,
+            diag: InputDiagnostics::new(),
+            key_edges: Vec::new(),
         }
     }
 
@@ -50,11 +103,32 @@ impl InputHandler {
     /// Should be called once per frame.
     pub fn poll(&mut self) -> Vec<AppInput> {
         let mut actions = Vec::new();
+        self.key_pressed = false;
+        self.resized = None;
+        self.key_edges.clear();
+        self.diag.record_poll(Instant::now());
 
         // Poll all available events (non-blocking, 1ms timeout)
         while let Ok(true) = event::poll(Duration::from_millis(1)) {
-            if let Ok(Event::Key(key_event)) = event::read() {
-                self.process_key(key_event, &mut actions);
+            match event::read() {
+                Ok(Event::Key(key_event)) => {
+                    if key_event.kind == KeyEventKind::Press {
+                        self.key_pressed = true;
+                    }
+                    if self.has_key_release {
+                        self.diag.record_key(&key_event, Instant::now());
+                    } else {
+                        self.diag
+                            .record_key_without_kinds(&key_event, Instant::now());
+                    }
+                    self.process_key(key_event, &mut actions);
+                }
+                Ok(Event::Resize(width, height)) => {
+                    self.resized = Some((width, height));
+                    self.reprobe = true;
+                }
+                Ok(Event::FocusGained) => self.reprobe = true,
+                _ => {}
             }
         }
 
@@ -64,12 +138,29 @@ impl InputHandler {
     /// Process DAS ticks. Returns additional game actions from auto-repeat.
     /// Should be called once per frame after poll().
     pub fn tick_das(&mut self, dt: Duration) -> Vec<GameAction> {
-        if !self.in_game || !self.has_key_release {
+        if !self.in_game {
             return Vec::new();
         }
 
-        let (left, right, _sd) = self.das.tick(dt);
         let mut actions = Vec::new();
+        if !self.has_key_release {
+            let (left, right, soft_drop) = self.das.tick_without_release(dt);
+            if soft_drop {
+                actions.push(GameAction::SoftDropRelease);
+            }
+            let released = [
+                (GameAction::MoveLeft, left),
+                (GameAction::MoveRight, right),
+                (GameAction::SoftDrop, soft_drop),
+            ];
+            for (action, released) in released {
+                if released {
+                    self.key_edges.push((action, false));
+                }
+            }
+        }
+
+        let (left, right, _sd) = self.das.tick(dt);
 
         for _ in 0..left {
             actions.push(GameAction::MoveLeft);
@@ -81,10 +172,29 @@ impl InputHandler {
         actions
     }
 
+    /// Advance a held restart. Returns true when it has been held long
+    /// enough. Should be called once per frame during play.
+    pub fn tick_restart(&mut self, dt: Duration) -> bool {
+        if !self.in_game {
+            self.restart.release();
+            return false;
+        }
+        self.restart.tick(dt, self.has_key_release)
+    }
+
+    /// How far through holding restart the player is, if they are.
+    pub fn restart_progress(&self) -> Option<f32> {
+        self.restart.progress(self.has_key_release)
+    }
+
     fn process_key(&mut self, key_event: KeyEvent, actions: &mut Vec<AppInput>) {
         let code = key_event.code;
 
-        if self.in_game {
+        if self.text_entry {
+            if let Some(a) = self.process_text_key(code, key_event.kind) {
+                actions.push(a);
+            }
+        } else if self.in_game {
             self.process_game_key(code, key_event.kind, actions);
         } else if let Some(a) = self.process_menu_key(code, key_event.kind) {
             actions.push(a);
@@ -99,6 +209,16 @@ impl InputHandler {
         }
     }
 
+    /// Game action for a key, with left and right swapped while mirrored so
+    /// the game and its replays only ever see the moves actually made.
+    fn resolve_game(&self, code: KeyCode) -> Option<Action> {
+        self.keybinds.resolve_game(code).map(|action| match action {
+            Action::MoveLeft if self.mirrored => Action::MoveRight,
+            Action::MoveRight if self.mirrored => Action::MoveLeft,
+            _ => action,
+        })
+    }
+
     /// Input handling for terminals that support key release events.
     /// Uses DAS for auto-repeat with proper press/release tracking.
     fn process_game_key_with_release(
@@ -109,9 +229,12 @@ impl InputHandler {
     ) {
         match kind {
             KeyEventKind::Press => {
-                let Some(action) = self.keybinds.resolve_game(code) else {
+                let Some(action) = self.resolve_game(code) else {
                     return;
                 };
+                if let Some(game_action) = action.game_action() {
+                    self.key_edges.push((game_action, true));
+                }
                 match action {
                     Action::MoveLeft => {
                         // Cancel opposite direction
@@ -133,14 +256,32 @@ impl InputHandler {
                     Action::RotateCCW => actions.push(AppInput::Game(GameAction::RotateCCW)),
                     Action::Rotate180 => actions.push(AppInput::Game(GameAction::Rotate180)),
                     Action::Hold => actions.push(AppInput::Game(GameAction::Hold)),
+                    Action::Zone => actions.push(AppInput::Game(GameAction::Zone)),
+                    Action::CycleTarget => actions.push(AppInput::CycleTarget),
                     Action::Pause => actions.push(AppInput::Pause),
                     Action::Quit => actions.push(AppInput::Quit),
-                    Action::Restart => actions.push(AppInput::Restart),
+                    Action::Restart => {
+                        self.restart.press();
+                        actions.push(AppInput::Restart);
+                    }
+                    Action::Undo => actions.push(AppInput::Undo),
+                    Action::Command => actions.push(AppInput::Command),
+                    Action::TogglePerf => actions.push(AppInput::TogglePerf),
+                    Action::ToggleAiExplain => actions.push(AppInput::ToggleAiExplain),
+                    Action::Help => actions.push(AppInput::Help),
                     _ => {}
                 }
             }
             KeyEventKind::Release => {
-                let action = self.keybinds.resolve_game(code);
+                let action = self.resolve_game(code);
+                if let Some(
+                    game_action @ (GameAction::MoveLeft
+                    | GameAction::MoveRight
+                    | GameAction::SoftDrop),
+                ) = action.and_then(Action::game_action)
+                {
+                    self.key_edges.push((game_action, false));
+                }
                 match action {
                     Some(Action::MoveLeft) => {
                         self.das.left.release();
@@ -152,6 +293,7 @@ impl InputHandler {
                         self.das.soft_drop.release();
                         actions.push(AppInput::Game(GameAction::SoftDropRelease));
                     }
+                    Some(Action::Restart) => self.restart.release(),
                     _ => {}
                 }
             }
@@ -161,9 +303,11 @@ impl InputHandler {
     }
 
     /// Input handling for terminals without key release events.
-    /// Each Press/Repeat generates exactly one action; DAS is not used.
-    /// For SoftDrop, we emit both SoftDrop and SoftDropRelease so the
-    /// persistent soft_dropping flag doesn't stay on between frames.
+    /// Movement and soft drop guess from the OS key repeats whether a key
+    /// is held: a tap acts once, a held key charges DAS as usual and is
+    /// taken as released once its repeats stop. A tapped SoftDrop emits
+    /// both SoftDrop and SoftDropRelease so the persistent soft_dropping
+    /// flag doesn't stay on between frames.
     fn process_game_key_no_release(
         &mut self,
         code: KeyCode,
@@ -172,35 +316,83 @@ impl InputHandler {
     ) {
         match kind {
             KeyEventKind::Press | KeyEventKind::Repeat => {
-                let Some(action) = self.keybinds.resolve_game(code) else {
+                let Some(action) = self.resolve_game(code) else {
                     return;
                 };
+                // Held directions and soft drop note their first press below
+                let guessed = matches!(
+                    action,
+                    Action::MoveLeft | Action::MoveRight | Action::SoftDrop
+                );
+                if let Some(game_action) = action.game_action().filter(|_| !guessed) {
+                    if kind == KeyEventKind::Press {
+                        self.key_edges.push((game_action, true));
+                    }
+                }
                 match action {
-                    Action::MoveLeft => actions.push(AppInput::Game(GameAction::MoveLeft)),
-                    Action::MoveRight => actions.push(AppInput::Game(GameAction::MoveRight)),
-                    Action::SoftDrop => {
-                        // Move down one cell, then immediately release so gravity
-                        // doesn't stay in soft-drop mode forever.
-                        actions.push(AppInput::Game(GameAction::SoftDrop));
-                        actions.push(AppInput::Game(GameAction::SoftDropRelease));
+                    Action::MoveLeft => {
+                        self.das.right.reset();
+                        // Once held, DAS does the moving
+                        if self.das.left.press_without_release() == RepeatEvent::Press {
+                            self.key_edges.push((GameAction::MoveLeft, true));
+                            actions.push(AppInput::Game(GameAction::MoveLeft));
+                        }
+                    }
+                    Action::MoveRight => {
+                        self.das.left.reset();
+                        if self.das.right.press_without_release() == RepeatEvent::Press {
+                            self.key_edges.push((GameAction::MoveRight, true));
+                            actions.push(AppInput::Game(GameAction::MoveRight));
+                        }
                     }
-                    Action::HardDrop => {
+                    Action::SoftDrop => match self.das.soft_drop.press_without_release() {
+                        RepeatEvent::Press => {
+                            self.key_edges.push((GameAction::SoftDrop, true));
+                            // Move down one cell, then immediately release so gravity
+                            // doesn't stay in soft-drop mode forever.
+                            actions.push(AppInput::Game(GameAction::SoftDrop));
+                            actions.push(AppInput::Game(GameAction::SoftDropRelease));
+                        }
+                        // Stays on until tick_das takes the key as released
+                        RepeatEvent::Held(_) => {
+                            actions.push(AppInput::Game(GameAction::SoftDrop));
+                        }
+                        RepeatEvent::Repeat => {}
+                    },
+                    Action::HardDrop
                         // Only on initial press, not repeats, to avoid accidental hard drops
-                        if kind == KeyEventKind::Press {
+                        if kind == KeyEventKind::Press => {
                             actions.push(AppInput::Game(GameAction::HardDrop));
                         }
-                    }
                     Action::RotateCW => actions.push(AppInput::Game(GameAction::RotateCW)),
                     Action::RotateCCW => actions.push(AppInput::Game(GameAction::RotateCCW)),
                     Action::Rotate180 => actions.push(AppInput::Game(GameAction::Rotate180)),
-                    Action::Hold => {
-                        if kind == KeyEventKind::Press {
+                    Action::Hold
+                        if kind == KeyEventKind::Press => {
                             actions.push(AppInput::Game(GameAction::Hold));
                         }
-                    }
+                    Action::Zone
+                        if kind == KeyEventKind::Press => {
+                            actions.push(AppInput::Game(GameAction::Zone));
+                        }
+                    Action::CycleTarget
+                        if kind == KeyEventKind::Press => {
+                            actions.push(AppInput::CycleTarget);
+                        }
                     Action::Pause => actions.push(AppInput::Pause),
                     Action::Quit => actions.push(AppInput::Quit),
-                    Action::Restart => actions.push(AppInput::Restart),
+                    Action::Restart => {
+                        self.restart.press();
+                        actions.push(AppInput::Restart);
+                    }
+                    Action::Undo => actions.push(AppInput::Undo),
+                    Action::Command => actions.push(AppInput::Command),
+                    Action::TogglePerf => actions.push(AppInput::TogglePerf),
+                    Action::ToggleAiExplain => actions.push(AppInput::ToggleAiExplain),
+                    Action::Help
+                        if kind == KeyEventKind::Press => {
+                            actions.push(AppInput::Help);
+                        }
                     _ => {}
                 }
             }
@@ -209,6 +401,21 @@ impl InputHandler {
         }
     }
 
+    /// Keys typed into a text field: Enter and Esc still confirm and cancel.
+    fn process_text_key(&mut self, code: KeyCode, kind: KeyEventKind) -> Option<AppInput> {
+        if kind == KeyEventKind::Release {
+            return None;
+        }
+
+        match code {
+            KeyCode::Char(c) => Some(AppInput::Char(c)),
+            KeyCode::Backspace => Some(AppInput::Backspace),
+            KeyCode::Enter => Some(AppInput::MenuSelect),
+            KeyCode::Esc => Some(AppInput::MenuBack),
+            _ => None,
+        }
+    }
+
     fn process_menu_key(&mut self, code: KeyCode, kind: KeyEventKind) -> Option<AppInput> {
         if kind != KeyEventKind::Press {
             return None;
@@ -222,13 +429,60 @@ impl InputHandler {
             Action::MenuRight => Some(AppInput::MenuRight),
             Action::MenuSelect => Some(AppInput::MenuSelect),
             Action::MenuBack => Some(AppInput::MenuBack),
+            Action::CycleSort => Some(AppInput::CycleSort),
+            Action::CycleFilter => Some(AppInput::CycleFilter),
+            Action::SaveResult => Some(AppInput::SaveResult),
+            Action::CopyResult => Some(AppInput::CopyResult),
             Action::Quit => Some(AppInput::Quit),
+            Action::TogglePerf => Some(AppInput::TogglePerf),
+            Action::Help => Some(AppInput::Help),
             _ => None,
         }
     }
 
-    /// Reset DAS state (e.g., on pause/resume).
+    /// Apply handling settings from the user config.
+    pub fn apply_config(&mut self, config: &Config) {
+        self.das.das_delay = Duration::from_millis(config.das_delay_ms);
+        self.das.arr_delay = Duration::from_millis(config.arr_delay_ms);
+        self.das.sd_arr_delay = Duration::from_millis(config.sd_arr_delay_ms);
+        self.restart.duration = Duration::from_millis(config.restart_hold_ms);
+        self.keybinds = KeybindMap::with_preset(config.keybind_preset);
+    }
+
+    /// Switch input strategy after the terminal's keyboard support was
+    /// checked again. Keys held under the old one are dropped, since their
+    /// releases may never arrive. Returns whether it changed.
+    pub fn set_key_release(&mut self, has_key_release: bool) -> bool {
+        if has_key_release == self.has_key_release {
+            return false;
+        }
+        self.has_key_release = has_key_release;
+        self.reset_das();
+        true
+    }
+
+    /// Reset DAS state and any restart being held (e.g., on pause/resume).
     pub fn reset_das(&mut self) {
         self.das.reset();
+        self.restart.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mirrored_swaps_left_and_right() {
+        let mut input = InputHandler::new(true);
+        input.mirrored = true;
+        let mut actions = Vec::new();
+        input.process_game_key_with_release(KeyCode::Left, KeyEventKind::Press, &mut actions);
+        assert_eq!(actions, [AppInput::Game(GameAction::MoveRight)]);
+        assert_eq!(input.key_edges, [(GameAction::MoveRight, true)]);
+        assert!(input.das.right.is_active());
+
+        input.process_game_key_with_release(KeyCode::Left, KeyEventKind::Release, &mut actions);
+        assert!(!input.das.right.is_active());
     }
 }
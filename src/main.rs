@@ -1,56 +1,164 @@
 #![allow(dead_code)]
 
-mod ai;
+mod announce;
 mod app;
+mod cli;
+mod clipboard;
 mod data;
-mod game;
 mod input;
-mod modes;
+mod script;
+mod simulate;
+mod stream;
 mod ui;
 
-use std::io;
+use std::io::{self, Write};
 use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
+use clap::{CommandFactory, Parser};
 use crossterm::event::{
-    DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
-    PushKeyboardEnhancementFlags,
+    self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
+    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
 };
 use crossterm::execute;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
 use ratatui::Terminal;
 
-use app::App;
+use announce::Announcer;
+use app::{App, AppState};
+use cli::{Cli, Command, ModeArg};
+use data::config::Config;
+use data::ghosts::GhostStore;
+use stream::StateStream;
 
-const TARGET_FPS: u64 = 60;
-const FRAME_DURATION: Duration = Duration::from_nanos(1_000_000_000 / TARGET_FPS);
+/// How long to block waiting for input outside of gameplay. Kept under the
+/// 100ms dt cap so idle timers (attract mode) still advance in real time.
+const IDLE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// How long after a resize or focus change to check the terminal's keyboard
+/// support again, so dragging a window edge only checks once.
+const REPROBE_DELAY: Duration = Duration::from_millis(300);
+
+/// Whether our keyboard enhancement flags are on the terminal's stack, so
+/// only ours are ever popped, never ones pushed by whatever runs us.
+static KEYBOARD_FLAGS_PUSHED: AtomicBool = AtomicBool::new(false);
 
 fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let mode = match cli.command {
+        Some(Command::Mode(mode)) => Some(mode),
+        Some(Command::Simulate(ref args)) => {
+            if cli.log {
+                init_logging();
+            }
+            return simulate::run(args, cli.seed);
+        }
+        None => None,
+    };
+    // Problems with the arguments are reported before the screen is taken
+    let replay = match cli.replay {
+        Some(_) if mode.is_some_and(|mode| mode != ModeArg::Sprint) => Cli::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "--replay races a sprint; use it alone or with `sprint`",
+            )
+            .exit(),
+        Some(ref path) => match GhostStore::load_run(path) {
+            Ok(run) => Some(run),
+            Err(e) => Cli::command()
+                .error(
+                    clap::error::ErrorKind::ValueValidation,
+                    format!("can't read replay {}: {}", path.display(), e),
+                )
+                .exit(),
+        },
+        None => None,
+    };
+    if let Some(ref path) = cli.config {
+        Config::use_path(path.clone());
+    }
+
     // Set up panic hook to restore terminal on crash
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |info| {
         let _ = restore_terminal();
+        data::autosave::flush();
+        let report = data::crash_report::write(info);
         original_hook(info);
+        if let Some(path) = report {
+            eprintln!("\nA crash report was written to {}", path.display());
+            eprintln!("Please attach it when reporting this bug.");
+        }
     }));
 
+    if cli.log {
+        init_logging();
+    }
+    let stream = match cli.stream {
+        Some(ref path) => match StateStream::bind(path) {
+            Ok(stream) => Some(stream),
+            Err(e) => Cli::command()
+                .error(
+                    clap::error::ErrorKind::Io,
+                    format!("can't stream to {}: {}", path.display(), e),
+                )
+                .exit(),
+        },
+        None => None,
+    };
+    let announcer = match cli.announce {
+        Some(ref path) => match Announcer::open(path) {
+            Ok(announcer) => Some(announcer),
+            Err(e) => Cli::command()
+                .error(
+                    clap::error::ErrorKind::Io,
+                    format!("can't announce to {}: {}", path.display(), e),
+                )
+                .exit(),
+        },
+        None => None,
+    };
+
     // Check if the terminal supports keyboard enhancement (key release events)
     let has_key_release = crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
 
+    let mut app = App::new(has_key_release);
+    app.seed = cli.seed;
+    app.stream = stream;
+    if let Some(announcer) = announcer {
+        app.announcer = announcer;
+    }
+    let mode = match replay {
+        Some(run) => {
+            app.ghosts.sprint = Some(run);
+            Some(ModeArg::Sprint)
+        }
+        None => mode,
+    };
+    if let Some(mode) = mode {
+        app.launch(mode);
+    }
+
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
     if has_key_release {
         execute!(
             stdout,
-            EnterAlternateScreen,
-            EnableMouseCapture,
             PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
         )?;
-    } else {
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        KEYBOARD_FLAGS_PUSHED.store(true, Ordering::Relaxed);
     }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
@@ -58,7 +166,7 @@ fn main() -> io::Result<()> {
     terminal.clear()?;
 
     // Run the app
-    let result = run_app(&mut terminal, has_key_release);
+    let result = run_app(&mut terminal, &mut app);
 
     // Restore terminal
     restore_terminal()?;
@@ -66,51 +174,134 @@ fn main() -> io::Result<()> {
     result
 }
 
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    has_key_release: bool,
-) -> io::Result<()> {
-    let mut app = App::new(has_key_release);
+fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> io::Result<()> {
     let mut last_frame = Instant::now();
+    // When the next frame should start; advanced by a fixed step so sleep
+    // inaccuracy doesn't accumulate into drift
+    let mut deadline = Instant::now();
+    let size = terminal.size()?;
+    app.terminal_size = Some(Rect::new(0, 0, size.width, size.height));
+    let mut reprobe_at: Option<Instant> = None;
 
     loop {
         let now = Instant::now();
-        let dt = now.duration_since(last_frame);
+        let frame_time = now.duration_since(last_frame);
         last_frame = now;
 
         // Cap dt to prevent huge jumps (e.g., after a debugger pause)
-        let dt = dt.min(Duration::from_millis(100));
+        let dt = frame_time.min(Duration::from_millis(100));
 
         // Update app state
         if !app.update(dt) {
             break;
         }
+        let update_time = now.elapsed();
 
-        // Render
-        terminal.draw(|frame| {
-            let area = frame.area();
-            app.render(area, frame.buffer_mut());
-        })?;
+        // The terminal may have changed under us (tmux reattach, SSH), so
+        // check its keyboard support again once things settle. The check
+        // waits on the terminal to answer, so it's put off while a game is
+        // being played.
+        if app.take_reprobe() {
+            reprobe_at = Some(now + REPROBE_DELAY);
+        }
+        if reprobe_at.is_some_and(|at| now >= at) && app.state != AppState::Playing {
+            reprobe_at = None;
+            app.set_key_release(negotiate_keyboard()?);
+        }
 
-        // Frame timing
+        // Render, skipped when nothing changed. After a resize the screen
+        // is cleared first so nothing from the old layout is left behind.
+        if let Some(text) = app.take_clipboard() {
+            clipboard::copy(terminal.backend_mut(), &text)?;
+        }
+        if app.take_bell() {
+            let out = terminal.backend_mut();
+            out.write_all(b"\x07")?;
+            out.flush()?;
+        }
+        if app.take_resized() {
+            terminal.autoresize()?;
+            terminal.clear()?;
+        }
+        if app.take_dirty() {
+            terminal.draw(|frame| {
+                let area = frame.area();
+                app.render(area, frame.buffer_mut());
+            })?;
+            app.input.diag.record_drawn(Instant::now());
+        }
         let elapsed = now.elapsed();
-        if elapsed < FRAME_DURATION {
-            std::thread::sleep(FRAME_DURATION - elapsed);
+        app.perf
+            .record_frame(frame_time, update_time, elapsed - update_time);
+
+        // Frame timing: paced in realtime screens, otherwise sleep until input
+        if !app.is_realtime() {
+            event::poll(IDLE_TIMEOUT.saturating_sub(elapsed))?;
+            deadline = Instant::now();
+        } else if let Some(frame_duration) = app.config.fps_cap.frame_duration() {
+            deadline += frame_duration;
+            let now = Instant::now();
+            if deadline > now {
+                std::thread::sleep(deadline - now);
+            } else {
+                // Overran the budget: start a fresh schedule rather than
+                // rushing extra frames to catch up
+                app.perf.record_dropped();
+                deadline = now;
+            }
+        } else {
+            deadline = Instant::now();
         }
     }
 
     Ok(())
 }
 
+/// Log to a file in the data directory, rolled over daily, to debug what
+/// the fullscreen UI hides.
+fn init_logging() {
+    let Some(dir) = dirs::data_dir().map(|d| d.join("tetrs").join("logs")) else {
+        return;
+    };
+    tracing_subscriber::fmt()
+        .with_writer(tracing_appender::rolling::daily(dir, "tetrs.log"))
+        .with_max_level(tracing::Level::DEBUG)
+        .init();
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "logging started");
+}
+
+/// Ask the terminal whether it reports key releases and, if it does, turn
+/// them on. A newly attached terminal hasn't seen the flags pushed at
+/// startup, so ours are popped and pushed again rather than stacked.
+fn negotiate_keyboard() -> io::Result<bool> {
+    let supported = crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+    pop_keyboard_flags()?;
+    if supported {
+        execute!(
+            io::stdout(),
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+        KEYBOARD_FLAGS_PUSHED.store(true, Ordering::Relaxed);
+    }
+    Ok(supported)
+}
+
+/// Pop the keyboard enhancement flags, if we pushed any.
+fn pop_keyboard_flags() -> io::Result<()> {
+    if KEYBOARD_FLAGS_PUSHED.swap(false, Ordering::Relaxed) {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
+    Ok(())
+}
+
 fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
-    // PopKeyboardEnhancementFlags is safe to call even if we didn't push;
-    // it will just be ignored by terminals that don't support it.
+    pop_keyboard_flags()?;
     execute!(
         io::stdout(),
         LeaveAlternateScreen,
         DisableMouseCapture,
-        PopKeyboardEnhancementFlags
+        DisableFocusChange
     )?;
     Ok(())
 }
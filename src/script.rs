@@ -0,0 +1,324 @@
+use std::cell::RefCell;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+
+use rhai::{CallFnOptions, Dynamic, Engine, Map, Scope, AST};
+
+use tetrs_core::game::scoring::ComboTimer;
+use tetrs_core::game::GameState;
+use tetrs_core::modes::cheat::{MAX_GARBAGE, MAX_LEVEL};
+use tetrs_core::modes::{format_number, GameMode, GameResult};
+
+/// Operations a single hook call may run, so a runaway loop can't hang the
+/// game.
+const MAX_OPERATIONS: u64 = 200_000;
+
+/// Longest script error shown on the game over screen; the full one is
+/// logged.
+const ERROR_MAX_LEN: usize = 48;
+
+/// Longest combo timer a script may set, in milliseconds.
+const MAX_COMBO_TIMER_MS: u32 = 60_000;
+
+/// A `.rhai` file in the modes folder, as listed on the custom modes screen.
+pub struct ModeScript {
+    pub title: String,
+    pub description: String,
+    /// Compiled script, or why it didn't compile.
+    pub ast: Result<AST, String>,
+}
+
+impl ModeScript {
+    /// Folder scripts are loaded from.
+    pub fn dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("tetrs").join("modes"))
+    }
+
+    /// Every script in the modes folder, by file name.
+    pub fn load_all() -> Vec<Self> {
+        let Some(dir) = Self::dir() else {
+            return Vec::new();
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+            .collect();
+        paths.sort();
+        paths.into_iter().map(Self::load).collect()
+    }
+
+    /// Compile a script and read its `TITLE` and `DESCRIPTION` constants,
+    /// falling back to the file name.
+    fn load(path: PathBuf) -> Self {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let engine = engine(Rc::default());
+        let ast = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|source| engine.compile(source).map_err(|e| e.to_string()));
+        let mut scope = Scope::new();
+        if let Ok(ref ast) = ast {
+            if let Err(e) = engine.run_ast_with_scope(&mut scope, ast) {
+                tracing::warn!("script {} failed to load: {}", path.display(), e);
+            }
+        }
+        if let Err(ref e) = ast {
+            tracing::warn!("script {} didn't compile: {}", path.display(), e);
+        }
+        Self {
+            title: scope.get_value("TITLE").unwrap_or(stem),
+            description: scope.get_value("DESCRIPTION").unwrap_or_default(),
+            ast,
+        }
+    }
+
+    /// Mode running this script, if it compiled.
+    pub fn mode(&self) -> Option<ScriptMode> {
+        let ast = self.ast.as_ref().ok()?.clone();
+        Some(ScriptMode::new(&self.title, ast))
+    }
+}
+
+/// Change to the game asked for by a script through a registered function.
+enum Command {
+    Garbage(u32),
+    Gravity(u32),
+    ComboTimer(u32),
+}
+
+/// Engine with the functions scripts use to change the game, which queue
+/// into `commands`. Arguments are capped like the practice commands', so a
+/// script can't flood the board or overflow the garbage queue.
+fn engine(commands: Rc<RefCell<Vec<Command>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    let queue = commands.clone();
+    engine.register_fn("add_garbage", move |lines: i64| {
+        queue
+            .borrow_mut()
+            .push(Command::Garbage(lines.clamp(0, MAX_GARBAGE as i64) as u32));
+    });
+    let queue = commands.clone();
+    engine.register_fn("set_gravity", move |level: i64| {
+        queue
+            .borrow_mut()
+            .push(Command::Gravity(level.clamp(0, MAX_LEVEL as i64) as u32));
+    });
+    engine.register_fn("set_combo_timer", move |ms: i64| {
+        commands.borrow_mut().push(Command::ComboTimer(
+            ms.clamp(0, MAX_COMBO_TIMER_MS as i64) as u32
+        ));
+    });
+    engine
+}
+
+/// What a script sees of the game, passed to every hook.
+fn view(game: &GameState) -> Map {
+    let stats = &game.stats;
+    let mut map = Map::new();
+    let mut set = |key: &str, value: Dynamic| {
+        map.insert(key.into(), value);
+    };
+    set("lines", (stats.lines_cleared as i64).into());
+    set("pieces", (stats.pieces_placed as i64).into());
+    set("score", (game.scoring.score as i64).into());
+    set("level", (game.scoring.level as i64).into());
+    set("time", stats.time.as_secs_f64().into());
+    set("attack", (stats.attack_sent as i64).into());
+    set("garbage_received", (stats.garbage_received as i64).into());
+    set("pending_garbage", (game.garbage.pending() as i64).into());
+    set("combo", (game.scoring.combo as i64).into());
+    set("b2b", (game.scoring.btb as i64).into());
+    set("height", (game.board.max_height() as i64).into());
+    set("game_over", game.game_over.into());
+    map
+}
+
+/// A custom mode whose hooks are functions in a Rhai script. Each hook
+/// takes the game's `view`, with the script's own state as `this`.
+pub struct ScriptMode {
+    title: String,
+    name: String,
+    engine: Engine,
+    ast: AST,
+    commands: Rc<RefCell<Vec<Command>>>,
+    /// The script's `this`, a map it keeps anything in between hooks.
+    state: Dynamic,
+    /// `check_complete` and `info_text` from the last update, since those
+    /// are asked for without mutable access.
+    result: Option<(String, String, Option<bool>)>,
+    info: Option<String>,
+    error: Option<String>,
+}
+
+impl ScriptMode {
+    pub fn new(title: &str, ast: AST) -> Self {
+        let commands = Rc::default();
+        Self {
+            title: title.to_string(),
+            name: title.to_uppercase(),
+            engine: engine(Rc::clone(&commands)),
+            ast,
+            commands,
+            state: Dynamic::from_map(Map::new()),
+            result: None,
+            info: None,
+            error: None,
+        }
+    }
+
+    fn has_hook(&self, hook: &str) -> bool {
+        self.ast.iter_functions().any(|f| f.name == hook)
+    }
+
+    /// Run a hook if the script has one, then apply what it queued. A
+    /// failing hook stops the script for the rest of the game.
+    fn call(&mut self, hook: &str, game: &mut GameState) -> Option<Dynamic> {
+        if self.error.is_some() || !self.has_hook(hook) {
+            return None;
+        }
+        let options = CallFnOptions::new()
+            .eval_ast(false)
+            .bind_this_ptr(&mut self.state);
+        let value = self.engine.call_fn_with_options::<Dynamic>(
+            options,
+            &mut Scope::new(),
+            &self.ast,
+            hook,
+            (view(game),),
+        );
+        for command in self.commands.borrow_mut().drain(..) {
+            match command {
+                Command::Garbage(lines) => game.garbage.add(lines),
+                Command::Gravity(level) => game.gravity.level = level,
+                Command::ComboTimer(ms) => {
+                    game.scoring.combo_timer =
+                        (ms > 0).then(|| ComboTimer::new(Duration::from_millis(ms as u64)));
+                }
+            }
+        }
+        match value {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.fail(hook, e.to_string());
+                None
+            }
+        }
+    }
+
+    fn fail(&mut self, hook: &str, error: String) {
+        tracing::warn!("script mode {} failed in {}: {}", self.title, hook, error);
+        self.error = Some(error);
+    }
+}
+
+/// A `check_complete` return value: `()` to keep going, or a map with the
+/// result's `label`, `value` and optionally `won`.
+fn parse_result(value: Dynamic) -> Result<Option<(String, String, Option<bool>)>, String> {
+    if value.is_unit() {
+        return Ok(None);
+    }
+    let Some(map) = value.try_cast::<Map>() else {
+        return Err("check_complete should return () or a map".to_string());
+    };
+    let field = |key: &str| map.get(key).filter(|v| !v.is_unit());
+    let label = field("label").map_or("SCORE".to_string(), |v| v.to_string());
+    let value = field("value").map_or(String::new(), |v| v.to_string());
+    let won = field("won").and_then(|v| v.as_bool().ok());
+    Ok(Some((label, value, won)))
+}
+
+impl GameMode for ScriptMode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_start(&mut self, game: &mut GameState) {
+        self.state = Dynamic::from_map(Map::new());
+        self.result = None;
+        self.info = None;
+        self.error = None;
+        self.call("on_start", game);
+    }
+
+    fn on_update(&mut self, game: &mut GameState) {
+        self.call("on_update", game);
+        if let Some(value) = self.call("check_complete", game) {
+            match parse_result(value) {
+                Ok(result) => self.result = result,
+                Err(e) => self.fail("check_complete", e),
+            }
+        }
+        self.info = self
+            .call("info_text", game)
+            .filter(|v| !v.is_unit())
+            .map(|v| v.to_string());
+    }
+
+    fn check_complete(&self, game: &GameState) -> Option<GameResult> {
+        let (label, value, won) = match (&self.error, &self.result) {
+            (Some(error), _) => (
+                "SCRIPT ERROR".to_string(),
+                error.chars().take(ERROR_MAX_LEN).collect(),
+                None,
+            ),
+            (None, Some(result)) => result.clone(),
+            // Topping out ends the game whether or not the script says so
+            (None, None) if game.game_over => {
+                ("SCORE".to_string(), format_number(game.scoring.score), None)
+            }
+            (None, None) => return None,
+        };
+        Some(GameResult {
+            mode_name: self.title.clone(),
+            primary_label: label,
+            primary_value: value,
+            is_new_high_score: false,
+            stats: game.stats.clone(),
+            won,
+            best_ultra: None,
+        })
+    }
+
+    fn info_text(&self, _game: &GameState) -> Option<String> {
+        self.info.clone()
+    }
+
+    fn ranked(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_stay_bounded() {
+        let commands: Rc<RefCell<Vec<Command>>> = Rc::default();
+        let engine = engine(commands.clone());
+        engine
+            .run(
+                "add_garbage(9223372036854775807);
+                 set_gravity(9223372036854775807);
+                 set_combo_timer(9223372036854775807);
+                 add_garbage(-5);",
+            )
+            .unwrap();
+        let commands = commands.borrow();
+        assert!(matches!(commands[0], Command::Garbage(MAX_GARBAGE)));
+        assert!(matches!(commands[1], Command::Gravity(MAX_LEVEL)));
+        assert!(matches!(
+            commands[2],
+            Command::ComboTimer(MAX_COMBO_TIMER_MS)
+        ));
+        assert!(matches!(commands[3], Command::Garbage(0)));
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::io;
+use std::panic;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use tetrs_core::ai::difficulty::{AiDifficulty, EvalWeights};
+use tetrs_core::ai::simulate::{simulate_game, GameSummary, SimReport};
+
+use crate::cli::SimulateArgs;
+
+/// Everything `tetrs simulate` prints.
+#[derive(Serialize)]
+struct Output {
+    difficulty: AiDifficulty,
+    weights: EvalWeights,
+    first_seed: u64,
+    time_limit_secs: u64,
+    report: SimReport,
+    games: Vec<GameSummary>,
+}
+
+/// Play the games spread over the available cores and print the results
+/// as JSON on stdout.
+pub fn run(args: &SimulateArgs, seed: Option<u64>) -> io::Result<()> {
+    let difficulty = args.difficulty.difficulty();
+    let mut params = difficulty.params();
+    if let Some(ref path) = args.weights {
+        let data = fs::read_to_string(path)?;
+        params.weights = serde_json::from_str(&data)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    }
+    let first_seed = seed.unwrap_or_else(rand_seed);
+    let time_limit = Duration::from_secs(args.time_limit);
+
+    let seeds: Vec<u64> = (0..args.games as u64)
+        .map(|i| first_seed.wrapping_add(i))
+        .collect();
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = seeds.len().div_ceil(threads).max(1);
+    let games: Vec<GameSummary> = thread::scope(|scope| {
+        let handles: Vec<_> = seeds
+            .chunks(chunk)
+            .map(|seeds| {
+                scope.spawn(move || {
+                    seeds
+                        .iter()
+                        .map(|&seed| simulate_game(difficulty, params, seed, time_limit))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        // A worker that panicked would take its share of the games with it
+        // and skew the report, so the whole run fails instead
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    });
+
+    let output = Output {
+        difficulty,
+        weights: params.weights,
+        first_seed,
+        time_limit_secs: args.time_limit,
+        report: SimReport::new(&games),
+        games,
+    };
+    let json = serde_json::to_string_pretty(&output)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Seed for a run that wasn't given one, printed with the results so the
+/// run can be repeated.
+fn rand_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
@@ -0,0 +1,172 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use tetrs_core::game::piece::Piece;
+use tetrs_core::game::snapshot::Snapshot;
+use tetrs_core::game::{GameEvent, GameState};
+
+/// Frames waiting to be written; newer ones are dropped while it's full.
+const BACKLOG: usize = 4;
+
+/// One line of the stream.
+#[derive(Serialize)]
+struct Frame<'a> {
+    frame: u64,
+    /// App state, e.g. `Playing`.
+    state: &'a str,
+    mode: &'a str,
+    /// Piece in play with its position and rotation.
+    piece: Option<&'a Piece>,
+    game: Snapshot,
+    /// Events since the previous frame.
+    events: &'a [GameEvent],
+}
+
+/// Read-only view of the game for overlays and other tools: every frame is
+/// written as a line of JSON to each reader connected to a Unix socket.
+pub struct StateStream {
+    path: PathBuf,
+    /// Device and inode of the socket bound at `path`, so only that one is
+    /// removed again.
+    socket_id: (u64, u64),
+    lines: SyncSender<String>,
+    /// Readers connected, kept by the writer thread.
+    readers: Arc<AtomicUsize>,
+    events: Vec<GameEvent>,
+}
+
+impl StateStream {
+    /// Listen on a socket at `path`, replacing one left by an earlier run.
+    /// Anything else already at `path` is left alone and is an error.
+    #[cfg(unix)]
+    pub fn bind(path: &Path) -> io::Result<Self> {
+        use std::os::unix::fs::{FileTypeExt, MetadataExt};
+        use std::os::unix::net::UnixListener;
+
+        match fs::symlink_metadata(path) {
+            Ok(meta) if meta.file_type().is_socket() => fs::remove_file(path)?,
+            Ok(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    "something other than a socket is there",
+                ))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let listener = UnixListener::bind(path)?;
+        let meta = fs::symlink_metadata(path)?;
+        listener.set_nonblocking(true)?;
+        let (lines, rx) = mpsc::sync_channel(BACKLOG);
+        let readers = Arc::new(AtomicUsize::new(0));
+        let count = readers.clone();
+        std::thread::Builder::new()
+            .name("tetrs-stream".to_string())
+            .spawn(move || serve(listener, rx, count))?;
+        tracing::info!("streaming state to {}", path.display());
+        Ok(Self {
+            path: path.to_path_buf(),
+            socket_id: (meta.dev(), meta.ino()),
+            lines,
+            readers,
+            events: Vec::new(),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn bind(_path: &Path) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "state streaming needs Unix domain sockets",
+        ))
+    }
+
+    fn has_readers(&self) -> bool {
+        self.readers.load(Ordering::Relaxed) > 0
+    }
+
+    /// Keep a frame's events to send with it.
+    pub fn record(&mut self, events: &[GameEvent]) {
+        if self.has_readers() {
+            self.events.extend_from_slice(events);
+        }
+    }
+
+    /// Send the game as it is now. Does nothing while no one is reading.
+    pub fn publish(&mut self, frame: u64, state: &str, mode: &str, game: &GameState) {
+        if self.has_readers() {
+            let line = Frame {
+                frame,
+                state,
+                mode,
+                piece: game.current_piece.as_ref(),
+                game: game.snapshot(),
+                events: &self.events,
+            };
+            if let Ok(line) = serde_json::to_string(&line) {
+                let _ = self.lines.try_send(line);
+            }
+        }
+        self.events.clear();
+    }
+}
+
+impl Drop for StateStream {
+    /// Remove the socket, unless something else has since replaced it.
+    #[cfg(unix)]
+    fn drop(&mut self) {
+        use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+        let ours = fs::symlink_metadata(&self.path).is_ok_and(|meta| {
+            meta.file_type().is_socket() && (meta.dev(), meta.ino()) == self.socket_id
+        });
+        if ours {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn drop(&mut self) {}
+}
+
+/// Writer thread: picks up new readers and writes each line to all of
+/// them, dropping any that hang up or fall behind. Ends with the stream.
+#[cfg(unix)]
+fn serve(
+    listener: std::os::unix::net::UnixListener,
+    lines: Receiver<String>,
+    count: Arc<AtomicUsize>,
+) {
+    use std::io::Write;
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::time::Duration;
+
+    let mut readers = Vec::new();
+    loop {
+        let line = match lines.recv_timeout(Duration::from_millis(100)) {
+            Ok(line) => Some(line),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        while let Ok((reader, _)) = listener.accept() {
+            let ready = reader.set_nonblocking(false).is_ok()
+                && reader
+                    .set_write_timeout(Some(Duration::from_millis(20)))
+                    .is_ok();
+            if ready {
+                tracing::info!("stream reader connected");
+                readers.push(reader);
+            }
+        }
+        if let Some(line) = line {
+            readers.retain_mut(|reader| writeln!(reader, "{}", line).is_ok());
+        }
+        count.store(readers.len(), Ordering::Relaxed);
+    }
+}
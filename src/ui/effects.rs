@@ -1,7 +1,92 @@
+use std::collections::VecDeque;
 use std::time::Duration;
 
 use ratatui::style::Color;
 
+use tetrs_core::game::clear::ClearType;
+use tetrs_core::game::scoring::Scoring;
+use tetrs_core::game::DangerTier;
+
+use crate::data::config::Config;
+use crate::ui::theme;
+use crate::ui::widgets::board::blend_color;
+
+/// Most action texts on screen at once; older ones are dropped.
+const MAX_ACTION_TEXTS: usize = 3;
+
+/// Rows a score popup rises per second.
+const POPUP_RISE: f32 = 3.0;
+
+/// Time a score popup stays up, in seconds.
+const POPUP_LIFE: f32 = 1.2;
+
+/// Back-to-back chain at which the border warms up and flames start.
+pub const BTB_WARM: i32 = 4;
+/// Chain at which the border runs hot and the flames climb higher.
+pub const BTB_HOT: i32 = 8;
+
+/// Points scored by a clear, floating up from the cleared rows.
+#[derive(Debug, Clone)]
+pub struct ScorePopup {
+    pub points: u64,
+    /// Board row it started on, 0 at the bottom.
+    pub row: usize,
+    /// Seconds since the clear.
+    pub age: f32,
+}
+
+impl ScorePopup {
+    /// Rows risen since the clear.
+    pub fn rise(&self) -> f32 {
+        self.age * POPUP_RISE
+    }
+
+    /// 1.0 when new, fading to 0.0 as it expires.
+    pub fn opacity(&self) -> f32 {
+        (1.0 - self.age / POPUP_LIFE).clamp(0.0, 1.0)
+    }
+}
+
+/// Text announcing one clear, with what the scoring looked like right after.
+#[derive(Debug, Clone)]
+pub struct ActionText {
+    pub clear_type: ClearType,
+    pub combo: i32,
+    pub btb: i32,
+    pub surge: u32,
+    /// 1.0 = fully visible.
+    pub opacity: f32,
+}
+
+/// How strongly each kind of effect is shown, from 0.0 (off) to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EffectIntensity {
+    pub shake: f32,
+    pub flash: f32,
+    pub danger: f32,
+}
+
+impl EffectIntensity {
+    pub fn from_config(config: &Config) -> Self {
+        let scale = |percent: u32| percent.min(100) as f32 / 100.0;
+        Self {
+            shake: scale(config.shake_percent),
+            flash: scale(config.flash_percent),
+            danger: scale(config.danger_pulse_percent),
+        }
+    }
+}
+
+impl Default for EffectIntensity {
+    fn default() -> Self {
+        Self {
+            shake: 1.0,
+            flash: 1.0,
+            danger: 1.0,
+        }
+    }
+}
+
 /// Active visual effects.
 #[derive(Debug, Clone)]
 pub struct Effects {
@@ -12,7 +97,7 @@ pub struct Effects {
 
     /// Danger flash intensity (0.0 - 1.0).
     pub danger_flash: f32,
-    pub in_danger: bool,
+    pub danger_tier: DangerTier,
 
     /// Line clear flash: (rows, progress 0.0-1.0).
     pub line_clear_flash: Option<(Vec<usize>, f32)>,
@@ -26,11 +111,32 @@ pub struct Effects {
     /// Perfect clear celebration timer.
     pub pc_timer: Duration,
 
-    /// Action text opacity (1.0 = fully visible).
-    pub action_text_opacity: f32,
+    /// Recent clears still fading out, newest first.
+    pub action_texts: VecDeque<ActionText>,
+
+    /// Points from recent clears, rising over the board.
+    pub score_popups: Vec<ScorePopup>,
+
+    /// Back-to-back surge flash timer.
+    pub surge_flash: Duration,
+
+    /// Zone start/release flash timer.
+    pub zone_flash: Duration,
+    pub in_zone: bool,
+
+    /// Current back-to-back chain, -1 for none.
+    pub btb: i32,
+
+    /// Time since the last reset, for steady blinking.
+    pub clock: Duration,
+
+    /// Scale applied when effects are drawn; kept across resets.
+    pub intensity: EffectIntensity,
 }
 
 const SPRING_CONSTANT: f32 = 0.02;
+/// Length of each on and off phase of a blinking warning.
+const BLINK_MS: u128 = 150;
 const FRICTION: f32 = 0.75;
 
 impl Effects {
@@ -39,12 +145,19 @@ impl Effects {
             shake: (0.0, 0.0),
             shake_vel: (0.0, 0.0),
             danger_flash: 0.0,
-            in_danger: false,
+            danger_tier: DangerTier::Safe,
             line_clear_flash: None,
             hard_drop_flash: Duration::ZERO,
             lock_flash: Duration::ZERO,
             pc_timer: Duration::ZERO,
-            action_text_opacity: 0.0,
+            action_texts: VecDeque::new(),
+            score_popups: Vec::new(),
+            surge_flash: Duration::ZERO,
+            zone_flash: Duration::ZERO,
+            in_zone: false,
+            btb: -1,
+            clock: Duration::ZERO,
+            intensity: EffectIntensity::default(),
         }
     }
 
@@ -70,7 +183,7 @@ impl Effects {
         }
 
         // Danger flash
-        if self.in_danger {
+        if self.danger_tier > DangerTier::Safe {
             self.danger_flash = (self.danger_flash + dt_f * 3.0).min(1.0);
         } else {
             self.danger_flash = (self.danger_flash - dt_f * 3.0).max(0.0);
@@ -85,12 +198,24 @@ impl Effects {
         }
 
         // Timers
+        self.clock += dt;
         self.hard_drop_flash = self.hard_drop_flash.saturating_sub(dt);
         self.lock_flash = self.lock_flash.saturating_sub(dt);
         self.pc_timer = self.pc_timer.saturating_sub(dt);
+        self.surge_flash = self.surge_flash.saturating_sub(dt);
+        self.zone_flash = self.zone_flash.saturating_sub(dt);
+
+        // Action text fade, each on its own clock
+        for text in &mut self.action_texts {
+            text.opacity -= dt_f * 0.5;
+        }
+        self.action_texts.retain(|text| text.opacity > 0.01);
 
-        // Action text fade
-        self.action_text_opacity = (self.action_text_opacity - dt_f * 0.5).max(0.0);
+        // Score popups
+        for popup in &mut self.score_popups {
+            popup.age += dt_f;
+        }
+        self.score_popups.retain(|popup| popup.age < POPUP_LIFE);
     }
 
     /// Trigger hard drop visual feedback.
@@ -113,19 +238,150 @@ impl Effects {
         self.pc_timer = Duration::from_millis(2000);
     }
 
-    /// Show action text.
-    pub fn trigger_action_text(&mut self) {
-        self.action_text_opacity = 1.0;
+    /// Float the points a clear scored up from its rows.
+    pub fn trigger_score_popup(&mut self, points: u64, row: usize) {
+        self.score_popups.push(ScorePopup {
+            points,
+            row,
+            age: 0.0,
+        });
+    }
+
+    /// Announce a clear above any that are still showing.
+    pub fn trigger_action_text(&mut self, clear_type: ClearType, scoring: &Scoring) {
+        self.action_texts.push_front(ActionText {
+            clear_type,
+            combo: scoring.combo,
+            btb: scoring.btb,
+            surge: scoring.last_surge,
+            opacity: 1.0,
+        });
+        self.action_texts.truncate(MAX_ACTION_TEXTS);
+    }
+
+    /// Flash and jolt the board sideways when a back-to-back chain surges.
+    pub fn trigger_surge(&mut self, lines: u32) {
+        self.surge_flash = Duration::from_millis(500);
+        self.shake_vel.0 += 0.15 + (lines as f32 * 0.02).min(0.25);
+    }
+
+    /// Flash the board as the zone starts.
+    pub fn trigger_zone_start(&mut self) {
+        self.zone_flash = Duration::from_millis(400);
+    }
+
+    /// Flash and knock the board down as the zone releases its lines,
+    /// harder for bigger releases.
+    pub fn trigger_zone_end(&mut self, lines: u32) {
+        self.zone_flash = Duration::from_millis(600);
+        if lines > 0 {
+            self.shake_vel.1 += 0.15 + (lines as f32 * 0.02).min(0.25);
+        }
+    }
+
+    /// Set zone state.
+    pub fn set_zone(&mut self, in_zone: bool) {
+        self.in_zone = in_zone;
+    }
+
+    /// Set the back-to-back chain the border reflects.
+    pub fn set_btb(&mut self, btb: i32) {
+        self.btb = btb;
+    }
+
+    /// Border color for the back-to-back chain: `base` below `BTB_WARM`,
+    /// gold from there, orange from `BTB_HOT`.
+    pub fn btb_border(&self, base: Color) -> Color {
+        if self.btb >= BTB_HOT {
+            blend_color(base, theme::SURGE_COLOR, 0.45)
+        } else if self.btb >= BTB_WARM {
+            blend_color(base, theme::B2B_WARM_COLOR, 0.3)
+        } else {
+            base
+        }
+    }
+
+    /// Color of a side wall cell `row` cells up from the bottom of the
+    /// board with the chain's flames over `wall`, or None where they don't
+    /// reach. They flicker to a height set by the chain, separately on each
+    /// `side`, and shrink with the flash setting.
+    pub fn btb_flame(&self, wall: Color, row: u16, side: u16) -> Option<Color> {
+        let reach = if self.btb >= BTB_HOT {
+            7.0
+        } else if self.btb >= BTB_WARM {
+            3.0
+        } else {
+            return None;
+        };
+        let t = self.clock.as_secs_f32() * 6.0 + side as f32 * 2.3;
+        let flicker = 0.75 + 0.15 * t.sin() + 0.1 * (t * 2.7).sin();
+        let height = reach * flicker * self.intensity.flash;
+        let row = row as f32;
+        if row >= height {
+            return None;
+        }
+        // Hottest at the base, fading into the wall at the tip
+        let heat = 1.0 - row / height.max(f32::EPSILON);
+        let flame = blend_color(theme::B2B_WARM_COLOR, theme::SURGE_COLOR, heat);
+        Some(blend_color(wall, flame, 0.3 + 0.7 * heat))
+    }
+
+    /// Set how close the stack is to the top.
+    pub fn set_danger(&mut self, tier: DangerTier) {
+        self.danger_tier = tier;
+    }
+
+    /// Border color for the danger tier: `base` when safe, a slow amber
+    /// pulse at warning, a fast red throb at critical.
+    pub fn danger_border(&self, base: Color) -> Color {
+        let t = self.clock.as_secs_f32();
+        match self.danger_tier {
+            DangerTier::Safe => base,
+            DangerTier::Warning => {
+                let pulse = 0.5 + 0.5 * (t * 2.5).sin();
+                let amount = self.danger() * (0.25 + 0.25 * pulse);
+                blend_color(base, theme::DANGER_WARN_COLOR, amount)
+            }
+            DangerTier::Critical => {
+                let pulse = 0.5 + 0.5 * (t * 9.0).sin();
+                let amount = self.danger() * (0.5 + 0.4 * pulse);
+                blend_color(base, theme::DANGER_BRIGHT, amount)
+            }
+        }
     }
 
-    /// Set danger state.
-    pub fn set_danger(&mut self, danger: bool) {
-        self.in_danger = danger;
+    /// How far the top rows' blocks are tinted red at the danger tier.
+    pub fn danger_tint(&self) -> f32 {
+        match self.danger_tier {
+            DangerTier::Safe => 0.0,
+            DangerTier::Warning => self.danger() * 0.15,
+            DangerTier::Critical => self.danger() * 0.35,
+        }
     }
 
     /// Get shake offset in terminal cells.
     pub fn shake_offset(&self) -> (i16, i16) {
-        (self.shake.0.round() as i16, self.shake.1.round() as i16)
+        let scale = self.intensity.shake;
+        (
+            (self.shake.0 * scale).round() as i16,
+            (self.shake.1 * scale).round() as i16,
+        )
+    }
+
+    /// Danger pulse strength (0.0 - 1.0) after the intensity setting.
+    pub fn danger(&self) -> f32 {
+        self.danger_flash * self.intensity.danger
+    }
+
+    /// Blend `base` toward a flash color by the flash intensity.
+    pub fn flash_color(&self, base: Color, flash: Color) -> Color {
+        blend_color(base, flash, self.intensity.flash)
+    }
+
+    /// Whether a blinking warning is in its lit phase. Always lit with
+    /// flashes turned off.
+    pub fn blink_on(&self) -> bool {
+        self.intensity.flash <= 0.0 || (self.clock.as_millis() / BLINK_MS).is_multiple_of(2)
     }
 
     /// Get flash color for line clear animation.
@@ -139,6 +395,9 @@ impl Effects {
     }
 
     pub fn reset(&mut self) {
-        *self = Self::new();
+        *self = Self {
+            intensity: self.intensity,
+            ..Self::new()
+        };
     }
 }
@@ -1,15 +1,37 @@
 use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
 
-use crate::game::board::{BOARD_WIDTH, VISIBLE_HEIGHT};
+use tetrs_core::game::board::{BOARD_WIDTH, VISIBLE_HEIGHT};
 
-/// Minimum terminal size for single player.
-pub const MIN_WIDTH: u16 = 56;
+use crate::ui::widgets::mini_board::{MINI_BOARD_H, MINI_BOARD_W};
+
+/// Minimum terminal size for single player. The width is that of the
+/// landscape layout, sidebars either side of the board; anything narrower
+/// goes compact. Versus and royale lay boards out at exactly this width.
+pub const MIN_WIDTH: u16 = PANEL_W + 1 + BOARD_TOTAL_W + 1 + PANEL_W; // 48
 pub const MIN_HEIGHT: u16 = 26;
 
+/// Minimum terminal size for the compact single player layout, used when
+/// there isn't room for the sidebars.
+pub const COMPACT_MIN_WIDTH: u16 = 30;
+pub const COMPACT_MIN_HEIGHT: u16 = 29;
+
+/// Height the portrait layout needs; it's as narrow as the compact one.
+pub const PORTRAIT_MIN_HEIGHT: u16 = 44;
+
+/// Rows for hold and next above the board in the narrow layouts.
+const STACKED_PANEL_H: u16 = 5;
+
+/// Rows for the stats under the board in portrait.
+const PORTRAIT_STATS_H: u16 = 14;
+
 /// Minimum terminal size for versus mode.
 pub const VS_MIN_WIDTH: u16 = 106;
 pub const VS_MIN_HEIGHT: u16 = 26;
 
+/// Rows of miniature boards on each side of the player in battle royale.
+pub const ROYALE_ROWS: u16 = 2;
+
 /// Board dimensions in terminal cells.
 pub const BOARD_CELL_W: u16 = BOARD_WIDTH as u16 * 2; // 20
 pub const BOARD_CELL_H: u16 = VISIBLE_HEIGHT as u16; // 20
@@ -19,6 +41,10 @@ pub const BOARD_TOTAL_H: u16 = BOARD_CELL_H + 2; // +border
 /// Panel width for sidebars.
 pub const PANEL_W: u16 = 12;
 
+/// Battle log height range (rows, including header) in versus mode.
+pub const LOG_MIN_H: u16 = 4;
+pub const LOG_MAX_H: u16 = 8;
+
 /// Layout positions for single-player mode.
 #[derive(Debug, Clone)]
 pub struct SingleLayout {
@@ -28,22 +54,81 @@ pub struct SingleLayout {
     pub left_sidebar: Rect,
     pub right_sidebar: Rect,
     pub garbage_bar: Rect,
+    /// Zone meter, in the gap right of the board.
+    pub zone_bar: Rect,
     pub action_text: Rect,
     pub controls: Rect,
 }
 
+/// Which way round the single player layout goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Orientation {
+    /// Sidebars beside the board, or stats under it in a window too narrow
+    /// for them but tall enough.
+    #[default]
+    Auto,
+    /// Sidebars beside the board; dropped when too narrow.
+    Landscape,
+    /// Stats under the board whenever the window is tall enough.
+    Portrait,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 3] = [
+        Orientation::Auto,
+        Orientation::Landscape,
+        Orientation::Portrait,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Orientation::Auto => "Auto",
+            Orientation::Landscape => "Landscape",
+            Orientation::Portrait => "Portrait",
+        }
+    }
+}
+
 /// Layout positions for versus mode.
 #[derive(Debug, Clone)]
 pub struct VersusLayout {
     pub player: SingleLayout,
     pub ai: SingleLayout,
     pub center: Rect,
+    /// Battle log below the boards, if the terminal is tall enough.
+    pub log: Option<Rect>,
+}
+
+/// Layout positions for battle royale.
+#[derive(Debug, Clone)]
+pub struct RoyaleLayout {
+    pub player: SingleLayout,
+    /// One miniature board (plus label row) per opponent, left side first.
+    pub opponents: Vec<Rect>,
 }
 
 impl SingleLayout {
-    /// Calculate layout centered in the given area.
+    /// Calculate layout centered in the given area, compact if it's
+    /// narrower than the sidebars need.
     pub fn new(area: Rect) -> Self {
-        let total_w = PANEL_W + 1 + BOARD_TOTAL_W + 1 + PANEL_W;
+        Self::oriented(area, Orientation::Landscape)
+    }
+
+    /// Calculate layout centered in the given area the way round asked
+    /// for, falling back to one that fits.
+    pub fn oriented(area: Rect, orientation: Orientation) -> Self {
+        let narrow = area.width < MIN_WIDTH;
+        let portrait_fits = area.height >= PORTRAIT_MIN_HEIGHT;
+        match orientation {
+            Orientation::Portrait if portrait_fits => Self::stacked(area, true),
+            Orientation::Auto if narrow && portrait_fits => Self::stacked(area, true),
+            _ if narrow => Self::stacked(area, false),
+            _ => Self::landscape(area),
+        }
+    }
+
+    fn landscape(area: Rect) -> Self {
+        let total_w = MIN_WIDTH;
         let total_h = BOARD_TOTAL_H + 2; // +controls row
 
         let start_x = area.x + area.width.saturating_sub(total_w) / 2;
@@ -63,6 +148,7 @@ impl SingleLayout {
             ),
             board: Rect::new(board_x, start_y, BOARD_TOTAL_W, BOARD_TOTAL_H),
             garbage_bar: Rect::new(board_x.saturating_sub(1), start_y + 1, 1, BOARD_CELL_H),
+            zone_bar: Rect::new(board_x + BOARD_TOTAL_W, start_y + 1, 1, BOARD_CELL_H),
             next: Rect::new(right_x, start_y, PANEL_W, 12),
             right_sidebar: Rect::new(
                 right_x,
@@ -70,10 +156,65 @@ impl SingleLayout {
                 PANEL_W,
                 BOARD_TOTAL_H.saturating_sub(12),
             ),
-            action_text: Rect::new(board_x + 2, start_y + BOARD_TOTAL_H / 2, BOARD_CELL_W, 4),
+            action_text: Rect::new(board_x + 2, start_y + BOARD_TOTAL_H / 2, BOARD_CELL_W, 6),
             controls: Rect::new(start_x, start_y + BOARD_TOTAL_H, total_w, 2),
         }
     }
+
+    /// Hand the right sidebar over to a panel `height` rows tall, taking
+    /// rows off the bottom of the next queue if the sidebar is too short.
+    /// Returns where the panel goes, or None if there's no room.
+    pub fn take_right_panel(&mut self, height: u16) -> Option<Rect> {
+        let sidebar = self.right_sidebar;
+        let short = height.saturating_sub(sidebar.height);
+        let panel = if short == 0 {
+            Rect::new(sidebar.x, sidebar.y, sidebar.width, height)
+        } else {
+            // Only when the queue sits right above the sidebar, keeping
+            // room for its first piece
+            let stacked = self.next.x == sidebar.x && self.next.bottom() == sidebar.y;
+            if !stacked || self.next.height < short + 5 {
+                return None;
+            }
+            self.next.height -= short;
+            Rect::new(sidebar.x, self.next.bottom(), sidebar.width, height)
+        };
+        if panel.width == 0 {
+            return None;
+        }
+        self.right_sidebar = Rect::new(sidebar.x, sidebar.y, 0, 0);
+        Some(panel)
+    }
+
+    /// Narrow layout for split panes: hold and next side by side above the
+    /// board. Compact drops the sidebars; portrait puts them side by side
+    /// under the board, below the mode info row.
+    fn stacked(area: Rect, portrait: bool) -> Self {
+        // Stats start a row down, leaving the mode info under the board
+        let stats_h = if portrait { PORTRAIT_STATS_H + 1 } else { 0 };
+        let total_h = STACKED_PANEL_H + BOARD_TOTAL_H + stats_h + 2; // +controls row
+
+        let board_x = area.x + area.width.saturating_sub(BOARD_TOTAL_W) / 2;
+        let start_y = area.y + area.height.saturating_sub(total_h) / 2;
+        let board_y = start_y + STACKED_PANEL_H;
+        let board_right = board_x + BOARD_TOTAL_W;
+        let left_x = board_x.saturating_sub(1);
+        let right_x = (board_right + 1).saturating_sub(PANEL_W);
+        let stats_y = board_y + BOARD_TOTAL_H + 1;
+
+        SingleLayout {
+            hold: Rect::new(left_x, start_y, PANEL_W, STACKED_PANEL_H),
+            board: Rect::new(board_x, board_y, BOARD_TOTAL_W, BOARD_TOTAL_H),
+            // Shows the first piece of the queue
+            next: Rect::new(right_x, start_y, PANEL_W, STACKED_PANEL_H),
+            left_sidebar: Rect::new(left_x, stats_y, PANEL_W, stats_h.saturating_sub(1)),
+            right_sidebar: Rect::new(right_x, stats_y, PANEL_W, stats_h.saturating_sub(1)),
+            garbage_bar: Rect::new(left_x, board_y + 1, 1, BOARD_CELL_H),
+            zone_bar: Rect::new(board_right, board_y + 1, 1, BOARD_CELL_H),
+            action_text: Rect::new(board_x + 2, board_y + BOARD_TOTAL_H / 2, BOARD_CELL_W, 6),
+            controls: Rect::new(area.x, board_y + BOARD_TOTAL_H + stats_h, area.width, 2),
+        }
+    }
 }
 
 impl VersusLayout {
@@ -83,27 +224,109 @@ impl VersusLayout {
         let gap = 4;
         let total_w = single_w * 2 + gap;
 
+        let boards_h = BOARD_TOTAL_H + 2; // +controls row
+        let spare_h = area.height.saturating_sub(boards_h);
+        let log_h = if spare_h >= LOG_MIN_H {
+            spare_h.min(LOG_MAX_H)
+        } else {
+            0
+        };
+
         let start_x = area.x + area.width.saturating_sub(total_w) / 2;
-        let start_y = area.y + area.height.saturating_sub(BOARD_TOTAL_H + 2) / 2;
+        let start_y = area.y + area.height.saturating_sub(boards_h + log_h) / 2;
 
-        let player_area = Rect::new(start_x, start_y, single_w, area.height);
-        let ai_area = Rect::new(start_x + single_w + gap, start_y, single_w, area.height);
+        let player_area = Rect::new(start_x, start_y, single_w, boards_h);
+        let ai_area = Rect::new(start_x + single_w + gap, start_y, single_w, boards_h);
         let center_area = Rect::new(start_x + single_w, start_y + BOARD_TOTAL_H / 2 - 2, gap, 5);
+        let log_area = (log_h > 0).then(|| Rect::new(start_x, start_y + boards_h, total_w, log_h));
 
         VersusLayout {
             player: SingleLayout::new(player_area),
             ai: SingleLayout::new(ai_area),
             center: center_area,
+            log: log_area,
         }
     }
 }
 
-/// Check if the terminal is big enough for single player.
+impl RoyaleLayout {
+    /// Columns of miniature boards on each side for `count` opponents.
+    fn side_columns(count: usize) -> u16 {
+        let per_side = count.div_ceil(2) as u16;
+        per_side.div_ceil(ROYALE_ROWS).max(1)
+    }
+
+    /// Terminal size needed to fit `count` opponents around the player.
+    pub fn min_size(count: usize) -> (u16, u16) {
+        let cols = Self::side_columns(count);
+        let side_w = cols * (MINI_BOARD_W + 1);
+        let single_w = PANEL_W + 1 + BOARD_TOTAL_W + 1 + PANEL_W;
+        let minis_h = ROYALE_ROWS * (MINI_BOARD_H + 1);
+        (side_w * 2 + 2 + single_w, minis_h.max(BOARD_TOTAL_H + 2))
+    }
+
+    /// Calculate battle royale layout centered in the given area.
+    pub fn new(area: Rect, count: usize) -> Self {
+        let cols = Self::side_columns(count);
+        let side_w = cols * (MINI_BOARD_W + 1);
+        let single_w = PANEL_W + 1 + BOARD_TOTAL_W + 1 + PANEL_W;
+        let (total_w, total_h) = Self::min_size(count);
+
+        let start_x = area.x + area.width.saturating_sub(total_w) / 2;
+        let start_y = area.y + area.height.saturating_sub(total_h) / 2;
+        let player_x = start_x + side_w + 1;
+        let player_area = Rect::new(player_x, start_y, single_w, total_h);
+
+        let per_side = count.div_ceil(2);
+        let opponents = (0..count)
+            .map(|i| {
+                let (side_x, slot) = if i < per_side {
+                    (start_x, i)
+                } else {
+                    (player_x + single_w + 1, i - per_side)
+                };
+                let col = slot as u16 % cols;
+                let row = slot as u16 / cols;
+                Rect::new(
+                    side_x + col * (MINI_BOARD_W + 1),
+                    start_y + row * (MINI_BOARD_H + 1),
+                    MINI_BOARD_W,
+                    MINI_BOARD_H + 1,
+                )
+            })
+            .collect();
+
+        RoyaleLayout {
+            player: SingleLayout::new(player_area),
+            opponents,
+        }
+    }
+}
+
+/// Check if the terminal is big enough for single player, in either
+/// layout.
 pub fn check_size_single(area: Rect) -> bool {
-    area.width >= MIN_WIDTH && area.height >= MIN_HEIGHT
+    let (min_w, min_h) = min_size_single(area);
+    area.width >= min_w && area.height >= min_h
+}
+
+/// Size single player needs: the full layout's, or the compact one's in a
+/// terminal too narrow for the full one.
+pub fn min_size_single(area: Rect) -> (u16, u16) {
+    if area.width < MIN_WIDTH {
+        (COMPACT_MIN_WIDTH, COMPACT_MIN_HEIGHT)
+    } else {
+        (MIN_WIDTH, MIN_HEIGHT)
+    }
 }
 
 /// Check if the terminal is big enough for versus.
 pub fn check_size_versus(area: Rect) -> bool {
     area.width >= VS_MIN_WIDTH && area.height >= VS_MIN_HEIGHT
 }
+
+/// Check if the terminal is big enough for a battle royale lobby of `count`.
+pub fn check_size_royale(area: Rect, count: usize) -> bool {
+    let (w, h) = RoyaleLayout::min_size(count);
+    area.width >= w && area.height >= h
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
+
+use crate::input::diag::{InputDiagnostics, Samples};
+use crate::input::keybinds::format_key;
+use crate::ui::theme;
+
+/// Poll jitter above this makes input timing feel uneven.
+const JITTER_WARN: Duration = Duration::from_millis(4);
+
+/// Live input timing, opened from the settings, for tuning a terminal for
+/// competitive play. Keys pressed here only feed the figures.
+pub struct InputDiagnosticsScreen<'a> {
+    pub diag: &'a InputDiagnostics,
+    pub has_key_release: bool,
+}
+
+fn ms(d: Duration) -> String {
+    format!("{:.1} ms", d.as_secs_f64() * 1000.0)
+}
+
+fn mean_max(samples: &Samples) -> String {
+    match (samples.mean(), samples.max()) {
+        (Some(mean), Some(max)) => format!("{} (max {})", ms(mean), ms(max)),
+        _ => "-".to_string(),
+    }
+}
+
+impl<'a> InputDiagnosticsScreen<'a> {
+    /// The most useful thing to change, if anything stands out.
+    fn hint(&self) -> &'static str {
+        let d = self.diag;
+        if !self.has_key_release {
+            "No key releases: a terminal with the kitty keyboard protocol gives exact DAS"
+        } else if d.presses > 0 && d.releases == 0 {
+            "Releases enabled but none seen yet; hold and let go of a key"
+        } else if d.polls.jitter().is_some_and(|j| j > JITTER_WARN) {
+            "Uneven polling: try a lower frame rate cap or close busy programs"
+        } else if d.presses == 0 {
+            "Press and hold keys to measure them"
+        } else {
+            "Input looks healthy"
+        }
+    }
+}
+
+impl<'a> Widget for InputDiagnosticsScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            buf.set_string(
+                area.x,
+                y,
+                " ".repeat(area.width as usize),
+                Style::default().bg(theme::BG_COLOR),
+            );
+        }
+
+        let center_x = area.x + area.width / 2;
+        let mut y = area.y + area.height.saturating_sub(20) / 3;
+
+        let title = "INPUT DIAGNOSTICS";
+        let tx = center_x.saturating_sub(title.width() as u16 / 2);
+        buf.set_string(tx, y, title, theme::title_style());
+        y += 3;
+
+        let d = self.diag;
+        let mode = if self.has_key_release {
+            "key release events"
+        } else {
+            "key repeat, holds guessed"
+        };
+        let last_key = match d.last_key {
+            Some((code, kind)) => {
+                let kind = match kind {
+                    KeyEventKind::Press => "press",
+                    KeyEventKind::Repeat => "repeat",
+                    KeyEventKind::Release => "release",
+                };
+                format!("{} {}", format_key(code), kind)
+            }
+            None => "-".to_string(),
+        };
+        let rows = [
+            ("Input mode", mode.to_string()),
+            ("Poll interval", mean_max(&d.polls)),
+            ("Poll jitter", d.polls.jitter().map_or("-".to_string(), ms)),
+            ("Read to screen", mean_max(&d.read_to_draw)),
+            (
+                "Est. latency",
+                d.estimated_latency().map_or("-".to_string(), ms),
+            ),
+            (
+                "Key events",
+                format!(
+                    "{} press  {} repeat  {} release",
+                    d.presses, d.repeats, d.releases
+                ),
+            ),
+            ("Repeat delay", d.repeat_delay.map_or("-".to_string(), ms)),
+            (
+                "Repeat rate",
+                match d.repeat_gaps.mean() {
+                    Some(gap) if !gap.is_zero() => {
+                        format!("{} ({:.0}/s)", ms(gap), 1.0 / gap.as_secs_f64())
+                    }
+                    _ => "-".to_string(),
+                },
+            ),
+            ("Last key", last_key),
+        ];
+
+        let x = center_x.saturating_sub(22);
+        for (label, value) in rows {
+            if y + 4 >= area.y + area.height {
+                break;
+            }
+            buf.set_string(x, y, label, theme::stat_label_style());
+            buf.set_string(x + 16, y, &value, theme::stat_value_style());
+            y += 1;
+        }
+
+        y += 1;
+        let hint = self.hint();
+        if y + 2 < area.y + area.height {
+            let hx = center_x.saturating_sub(hint.width() as u16 / 2);
+            buf.set_string(hx, y, hint, theme::menu_desc_style());
+        }
+
+        let controls = "Press keys to test  Esc: back";
+        let cx = center_x.saturating_sub(controls.width() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        if cy > y {
+            buf.set_string(cx, cy, controls, theme::menu_desc_style());
+        }
+    }
+}
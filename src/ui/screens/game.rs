@@ -2,17 +2,54 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
 
-use crate::game::GameState;
+use tetrs_core::ai::perfect_clear::PcPiece;
+use tetrs_core::game::modifiers;
+use tetrs_core::game::piece::PieceType;
+use tetrs_core::game::GameState;
+use tetrs_core::modes::ghost_run::GhostRun;
+
+use crate::input::keybinds::{Action, KeybindMap};
 use crate::ui::effects::Effects;
 use crate::ui::layout::SingleLayout;
 use crate::ui::theme;
 use crate::ui::widgets::action_text::ActionTextWidget;
+use crate::ui::widgets::attack_graph::AttackGraphWidget;
 use crate::ui::widgets::board::BoardWidget;
 use crate::ui::widgets::garbage_bar::GarbageBarWidget;
 use crate::ui::widgets::hold_box::HoldBoxWidget;
 use crate::ui::widgets::next_queue::NextQueueWidget;
-use crate::ui::widgets::sidebar::SidebarWidget;
+use crate::ui::widgets::pace_bar::PaceBarWidget;
+use crate::ui::widgets::sidebar::{draw_rolling_stat, SidebarWidget};
+use crate::ui::widgets::zone_meter::ZoneMeterWidget;
+
+/// Pause menu selections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseChoice {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
+
+impl PauseChoice {
+    pub const ALL: [PauseChoice; 4] = [
+        PauseChoice::Resume,
+        PauseChoice::Restart,
+        PauseChoice::Settings,
+        PauseChoice::Quit,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PauseChoice::Resume => "Resume",
+            PauseChoice::Restart => "Restart",
+            PauseChoice::Settings => "Settings",
+            PauseChoice::Quit => "Quit to Menu",
+        }
+    }
+}
 
 /// Widget that renders the full game screen (single-player layout).
 pub struct GameScreen<'a> {
@@ -21,7 +58,26 @@ pub struct GameScreen<'a> {
     pub layout: &'a SingleLayout,
     pub mode_name: &'a str,
     pub mode_info: Option<&'a str>,
-    pub paused: bool,
+    /// Selected pause menu row while paused, None while playing.
+    pub pause_selected: Option<usize>,
+    /// Show the attack sent/received graph in the right sidebar (versus).
+    pub show_attack_graph: bool,
+    /// Outline where the held piece would land if swapped in.
+    pub show_hold_ghost: bool,
+    /// Earlier run being raced.
+    pub ghost: Option<&'a GhostRun>,
+    /// Placement an opener trainer is asking for.
+    pub guide: Option<(PieceType, [(i32, i32); 4])>,
+    /// Perfect clear to outline, when practice asks for one.
+    pub pc_plan: Option<&'a [PcPiece]>,
+    /// Bindings the controls hint is written from.
+    pub keybinds: &'a KeybindMap,
+    /// How far through holding restart the player is, shown in place of
+    /// the controls hint.
+    pub restart_progress: Option<f32>,
+    /// Latest screen reader announcement, shown in place of the controls
+    /// hint.
+    pub announcement: Option<&'a str>,
 }
 
 impl<'a> Widget for GameScreen<'a> {
@@ -36,25 +92,60 @@ impl<'a> Widget for GameScreen<'a> {
         // Hold box
         HoldBoxWidget {
             piece: self.game.hold.piece,
-            available: !self.game.hold.used_this_turn,
+            available: self.game.hold_enabled() && !self.game.hold.used_this_turn,
         }
         .render(self.layout.hold, buf);
 
         // Board
+        let ghost_frame = self.ghost.and_then(|ghost| ghost.at(self.game.stats.time));
         BoardWidget {
             board: &self.game.board,
             current_piece: self.game.current_piece.as_ref(),
             effects: self.effects,
             show_grid: true,
+            show_ghost: !self.game.modifiers.contains(modifiers::Modifier::NoGhost),
+            hide_stack: self.game.modifiers.contains(modifiers::Modifier::Invisible),
+            zone_lines: self.game.zone.lines as usize,
+            ghost_heights: ghost_frame.map(|f| &f.heights),
+            guide: self.guide,
+            pc_plan: self.pc_plan,
+            mirrored: self
+                .game
+                .modifiers
+                .contains(modifiers::Modifier::MirrorView),
+            spawn_preview: self
+                .game
+                .preview()
+                .first()
+                .filter(|_| !self.game.game_over)
+                .map(|&next| (next, self.game.next_spawn_blocked())),
+            hold_ghost: self.game.hold_ghost().filter(|_| self.show_hold_ghost),
         }
         .render(self.layout.board, buf);
 
         // Garbage bar
         GarbageBarWidget {
-            pending: self.game.garbage.pending(),
+            garbage: &self.game.garbage,
         }
         .render(self.layout.garbage_bar, buf);
 
+        // Zone meter
+        if self.game.ruleset.zone {
+            ZoneMeterWidget {
+                zone: &self.game.zone,
+            }
+            .render(self.layout.zone_bar, buf);
+        } else if let Some(ghost) = self.ghost {
+            // Pace against the ghost in the zone meter's spot, out of the
+            // lines the ghost finished with
+            PaceBarWidget {
+                lines: self.game.scoring.lines_cleared,
+                ghost_lines: ghost_frame.map_or(0, |f| f.lines),
+                target: ghost.frames.last().map_or(0, |f| f.lines),
+            }
+            .render(self.layout.zone_bar, buf);
+        }
+
         // Next queue
         NextQueueWidget {
             pieces: self.game.preview(),
@@ -73,14 +164,10 @@ impl<'a> Widget for GameScreen<'a> {
         self.render_right_sidebar(buf);
 
         // Action text
-        if self.effects.action_text_opacity > 0.01 {
-            ActionTextWidget {
-                clear_type: self.game.last_clear_type.as_ref(),
-                scoring: &self.game.scoring,
-                opacity: self.effects.action_text_opacity,
-            }
-            .render(self.layout.action_text, buf);
+        ActionTextWidget {
+            texts: &self.effects.action_texts,
         }
+        .render(self.layout.action_text, buf);
 
         // Mode-specific info (like lines remaining for sprint)
         if let Some(info) = self.mode_info {
@@ -90,11 +177,15 @@ impl<'a> Widget for GameScreen<'a> {
         }
 
         // Controls bar
-        self.render_controls(buf);
+        match (self.restart_progress, self.announcement) {
+            (Some(progress), _) => self.render_restart_progress(buf, progress),
+            (None, Some(text)) => self.render_announcement(buf, text),
+            (None, None) => self.render_controls(buf),
+        }
 
         // Pause overlay
-        if self.paused {
-            self.render_pause_overlay(area, buf);
+        if let Some(selected) = self.pause_selected {
+            self.render_pause_overlay(area, buf, selected);
         }
     }
 }
@@ -110,24 +201,48 @@ impl<'a> GameScreen<'a> {
         let mut y = area.y;
 
         // APM
-        buf.set_string(x + 1, y, "APM", theme::stat_label_style());
-        y += 1;
-        buf.set_string(
-            x + 1,
+        draw_rolling_stat(
+            buf,
+            x,
             y,
-            &format!("{:.1}", self.game.stats.apm()),
-            theme::stat_value_style(),
+            "APM",
+            self.game.stats.apm(),
+            self.game.stats.rolling_apm(),
+            1,
         );
-        y += 2;
+        y += 3;
 
-        // Max combo
+        // Keys per second, and how much of the sideways movement DAS did
         if y + 1 < area.y + area.height {
+            draw_rolling_stat(
+                buf,
+                x,
+                y,
+                "KPS",
+                self.game.stats.kps(),
+                self.game.stats.rolling_kps(),
+                1,
+            );
+            y += 2;
+        }
+        if y < area.y + area.height {
+            let auto = match self.game.stats.rolling_auto_shift() {
+                Some(share) => format!("{:.0}%", share * 100.0),
+                None => "-".to_string(),
+            };
+            buf.set_string(x + 1, y, "DAS", theme::stat_label_style());
+            buf.set_string(x + 7, y, auto, theme::stat_value_style());
+            y += 2;
+        }
+
+        // Max combo (replaced by the attack graph in versus)
+        if !self.show_attack_graph && y + 1 < area.y + area.height {
             buf.set_string(x + 1, y, "MAX CMB", theme::stat_label_style());
             y += 1;
             buf.set_string(
                 x + 1,
                 y,
-                &self.game.stats.max_combo.to_string(),
+                self.game.stats.max_combo.to_string(),
                 theme::stat_value_style(),
             );
             y += 2;
@@ -140,9 +255,21 @@ impl<'a> GameScreen<'a> {
             buf.set_string(
                 x + 1,
                 y,
-                &self.game.stats.attack_sent.to_string(),
+                self.game.stats.attack_sent.to_string(),
                 theme::stat_value_style(),
             );
+            y += 2;
+        }
+
+        // Attack graph
+        if self.show_attack_graph && y + 2 < area.y + area.height {
+            buf.set_string(x + 1, y, "ATK/RCV", theme::stat_label_style());
+            y += 1;
+            AttackGraphWidget {
+                sent: &self.game.stats.attack_timeline,
+                received: &self.game.stats.received_timeline,
+            }
+            .render(Rect::new(x + 1, y, area.width.saturating_sub(2), 2), buf);
         }
     }
 
@@ -152,12 +279,67 @@ impl<'a> GameScreen<'a> {
             return;
         }
 
-        let controls = "h/l:←→  j:↓  k/Space:drop  d/f:rot  s:180  g:hold  Esc:pause";
-        let x = area.x + area.width.saturating_sub(controls.len() as u16) / 2;
-        buf.set_string(x, area.y + 1, controls, theme::menu_desc_style());
+        let key = |action| self.keybinds.key_display(action);
+        let mut hints = vec![
+            format!("{}/{}:←→", key(Action::MoveLeft), key(Action::MoveRight)),
+            format!("{}:↓", key(Action::SoftDrop)),
+            format!("{}:drop", key(Action::HardDrop)),
+            format!("{}/{}:rot", key(Action::RotateCCW), key(Action::RotateCW)),
+            format!("{}:180", key(Action::Rotate180)),
+            format!("{}:hold", key(Action::Hold)),
+        ];
+        if self.game.ruleset.zone {
+            hints.push(format!("{}:zone", key(Action::Zone)));
+        }
+        let tail = [
+            format!("{}:pause", key(Action::Pause)),
+            format!("{}:help", key(Action::Help)),
+        ];
+        hints.extend(tail.clone());
+        let mut controls = hints.join("  ");
+        // Just pause and help when the rest won't fit
+        if controls.width() > area.width as usize {
+            controls = tail.join("  ");
+        }
+        let x = area.x + area.width.saturating_sub(controls.width() as u16) / 2;
+        buf.set_string(x, area.y + 1, &controls, theme::menu_desc_style());
     }
 
-    fn render_pause_overlay(&self, area: Rect, buf: &mut Buffer) {
+    fn render_announcement(&self, buf: &mut Buffer, text: &str) {
+        let area = self.layout.controls;
+        if area.height < 1 {
+            return;
+        }
+        // Left-aligned so a screen reader finds it in the same place
+        let text: String = text.chars().take(area.width as usize).collect();
+        buf.set_string(area.x, area.y + 1, &text, theme::stat_value_style());
+    }
+
+    fn render_restart_progress(&self, buf: &mut Buffer, progress: f32) {
+        let area = self.layout.controls;
+        if area.width < 30 || area.height < 1 {
+            return;
+        }
+
+        const BAR_WIDTH: usize = 10;
+        let filled = ((progress * BAR_WIDTH as f32).round() as usize).min(BAR_WIDTH);
+        let label = "RESTART ";
+        let bar = format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled));
+        let x = area.x
+            + area
+                .width
+                .saturating_sub((label.width() + BAR_WIDTH) as u16)
+                / 2;
+        buf.set_string(x, area.y + 1, label, theme::stat_label_style());
+        buf.set_string(
+            x + label.width() as u16,
+            area.y + 1,
+            &bar,
+            theme::danger_style(),
+        );
+    }
+
+    fn render_pause_overlay(&self, area: Rect, buf: &mut Buffer, selected: usize) {
         // Semi-transparent overlay
         for y in area.y..area.y + area.height {
             for x in area.x..area.x + area.width {
@@ -165,23 +347,42 @@ impl<'a> GameScreen<'a> {
             }
         }
 
+        const INNER_W: usize = 18;
+        let box_h = PauseChoice::ALL.len() as u16 + 5;
         let center_x = area.x + area.width / 2;
-        let center_y = area.y + area.height / 2;
+        let top = (area.y + area.height / 2).saturating_sub(box_h / 2);
+        let x = center_x.saturating_sub(INNER_W as u16 / 2 + 1);
 
-        let text = "║  PAUSED  ║";
-        let x = center_x.saturating_sub(text.len() as u16 / 2);
         let style = Style::default()
             .fg(theme::TEXT_BRIGHT)
             .add_modifier(Modifier::BOLD);
+        let bar = "═".repeat(INNER_W);
+
+        buf.set_string(x, top, format!("╔{}╗", bar), style);
+        buf.set_string(x, top + 1, format!("║{:^INNER_W$}║", "PAUSED"), style);
+        buf.set_string(x, top + 2, format!("╠{}╣", bar), style);
 
-        buf.set_string(x, center_y - 2, "╔══════════╗", style);
-        buf.set_string(x, center_y - 1, "║          ║", style);
-        buf.set_string(x, center_y, text, style);
-        buf.set_string(x, center_y + 1, "║          ║", style);
-        buf.set_string(x, center_y + 2, "╚══════════╝", style);
+        let mut y = top + 3;
+        for (i, choice) in PauseChoice::ALL.iter().enumerate() {
+            buf.set_string(x, y, format!("║{:INNER_W$}║", ""), style);
+            let (cursor, item_style) = if i == selected {
+                (" ▸ ", theme::menu_selected_style())
+            } else {
+                ("   ", theme::menu_item_style())
+            };
+            buf.set_string(
+                x + 1,
+                y,
+                format!("{}{}", cursor, choice.label()),
+                item_style,
+            );
+            y += 1;
+        }
+        buf.set_string(x, y, format!("║{:INNER_W$}║", ""), style);
+        buf.set_string(x, y + 1, format!("╚{}╝", bar), style);
 
-        let resume = "[Esc] Resume   [R] Restart   [Q] Quit";
-        let rx = center_x.saturating_sub(resume.len() as u16 / 2);
-        buf.set_string(rx, center_y + 4, resume, theme::menu_desc_style());
+        let hint = "j/k: navigate  Enter: select  Esc: resume";
+        let hx = center_x.saturating_sub(hint.width() as u16 / 2);
+        buf.set_string(hx, y + 3, hint, theme::menu_desc_style());
     }
 }
@@ -1,27 +1,65 @@
+use std::time::Duration;
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
 
-use crate::game::stats::Stats;
-use crate::ui::theme;
-use crate::ui::widgets::sidebar::format_number;
-
-/// Result to display on game over.
-#[derive(Debug, Clone)]
-pub struct GameResult {
-    pub mode_name: String,
-    pub primary_label: String,
-    pub primary_value: String,
-    pub is_new_high_score: bool,
-    pub stats: Stats,
-    pub won: Option<bool>, // For versus: Some(true/false), else None
-}
+use tetrs_core::game::clear::ClearType;
+use tetrs_core::game::garbage::Exchange;
+use tetrs_core::game::modifiers::Modifiers;
+use tetrs_core::game::piece::PieceType;
+use tetrs_core::modes::ghost_run::Handling;
+use tetrs_core::modes::{format_number, GameResult};
+
+use crate::data::profile::XpAward;
+use crate::ui::theme::{self, PieceColors};
+use crate::ui::widgets::attack_graph::AttackGraphWidget;
+use crate::ui::widgets::bar_chart::{Bar, BarChartWidget};
+use crate::ui::widgets::exchange_timeline::ExchangeTimelineWidget;
+use crate::ui::widgets::height_graph::HeightGraphWidget;
+use crate::ui::widgets::text_input::{TextInput, TextInputWidget};
+
+/// Minimum width to show the breakdown charts beside the stats.
+const BREAKDOWN_MIN_W: u16 = 90;
+/// Width of each breakdown chart.
+const CHART_W: u16 = 24;
 
 /// Game over screen widget.
 pub struct GameOverScreen<'a> {
     pub result: &'a GameResult,
-    pub frame: u64,
+    /// Seconds since launch; drives the high score pulse.
+    pub time: f32,
+    /// Name being typed for a new high score.
+    pub name_entry: Option<&'a TextInput>,
+    /// XP the game earned toward the profile.
+    pub xp: Option<&'a XpAward>,
+    /// Versus rating after the match, and how far it moved.
+    pub rating: Option<(f64, f64)>,
+    /// Challenge modifiers the game was played with, tagged on the mode.
+    pub modifiers: Modifiers,
+    /// How the run was handled, for modes that record their inputs.
+    pub handling: Option<&'a Handling>,
+    /// The player's and the AI's garbage exchanges, after a versus match.
+    pub timeline: Option<(&'a [Exchange], &'a [Exchange])>,
+    /// Rows of the timeline scrolled past.
+    pub timeline_scroll: usize,
+    /// Show the handling breakdown or exchange timeline in place of the stats.
+    pub show_details: bool,
+}
+
+/// Rule between the result and the stats, which the attack graph matches.
+const SEPARATOR: &str = "─────────────────────────";
+/// Rows of the stack height graph.
+const HEIGHT_GRAPH_H: u16 = 3;
+/// Width of the exchange timeline.
+const TIMELINE_W: u16 = 44;
+/// Shortest area the title and result fit in, with every optional line.
+const MIN_HEIGHT: u16 = 18;
+
+fn ms(d: Duration) -> String {
+    format!("{} ms", d.as_millis())
 }
 
 impl<'a> Widget for GameOverScreen<'a> {
@@ -32,6 +70,9 @@ impl<'a> Widget for GameOverScreen<'a> {
                 buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
             }
         }
+        if area.height < MIN_HEIGHT {
+            return;
+        }
 
         let center_x = area.x + area.width / 2;
         let mut y = area.y + area.height / 6;
@@ -65,8 +106,13 @@ impl<'a> Widget for GameOverScreen<'a> {
 
         // Mode name
         let mode_style = Style::default().fg(theme::TEXT_DIM);
-        let mx = center_x.saturating_sub(self.result.mode_name.len() as u16 / 2);
-        buf.set_string(mx, y, &self.result.mode_name, mode_style);
+        let mode_name = if self.modifiers.is_empty() {
+            self.result.mode_name.clone()
+        } else {
+            format!("{} [{}]", self.result.mode_name, self.modifiers.label())
+        };
+        let mx = center_x.saturating_sub(mode_name.width() as u16 / 2);
+        buf.set_string(mx, y, &mode_name, mode_style);
         y += 2;
 
         // Primary result (highlighted)
@@ -87,7 +133,7 @@ impl<'a> Widget for GameOverScreen<'a> {
             y += 1;
             let hs_text = "★ NEW HIGH SCORE ★";
             let hsx = center_x.saturating_sub(hs_text.len() as u16 / 2);
-            let phase = (self.frame as f32 * 0.1).sin() * 0.5 + 0.5;
+            let phase = (self.time * 6.0).sin() * 0.5 + 0.5;
             let hs_color = ratatui::style::Color::Rgb(
                 (255.0 * phase) as u8,
                 (215.0 * phase + 40.0 * (1.0 - phase)) as u8,
@@ -100,49 +146,282 @@ impl<'a> Widget for GameOverScreen<'a> {
                 Style::default().fg(hs_color).add_modifier(Modifier::BOLD),
             );
             y += 1;
+
+            if let Some(input) = self.name_entry {
+                y += 1;
+                TextInputWidget {
+                    label: "NAME",
+                    input,
+                }
+                .render(Rect::new(area.x, y, area.width, 1), buf);
+                y += 1;
+            }
+        }
+
+        // XP earned, and any level reached along with it
+        if let Some(award) = self.xp {
+            y += 1;
+            let mut text = format!("+{} XP", award.xp);
+            if award.level_up {
+                text.push_str(&format!("  ·  LEVEL {}!", award.level));
+                if !award.unlocks.is_empty() {
+                    text.push_str(&format!(" Unlocked {}", award.unlocks.join(", ")));
+                }
+            }
+            let style = if award.level_up {
+                theme::stat_value_style()
+            } else {
+                theme::stat_label_style()
+            };
+            let xx = center_x.saturating_sub(text.width() as u16 / 2);
+            buf.set_string(xx, y, &text, style);
+            y += 1;
+        }
+
+        if let Some((rating, change)) = self.rating {
+            let text = format!("Rating {:.0} ({:+.0})", rating, change);
+            let style = if change >= 0.0 {
+                theme::stat_value_style()
+            } else {
+                Style::default().fg(theme::DANGER_COLOR)
+            };
+            let rx = center_x.saturating_sub(text.width() as u16 / 2);
+            buf.set_string(rx, y, &text, style);
+            y += 1;
         }
 
         y += 1;
 
         // Separator
-        let sep = "─────────────────────────";
-        let sx = center_x.saturating_sub(sep.len() as u16 / 2);
-        buf.set_string(sx, y, sep, Style::default().fg(theme::PANEL_COLOR));
+        let sx = center_x.saturating_sub(SEPARATOR.width() as u16 / 2);
+        buf.set_string(sx, y, SEPARATOR, Style::default().fg(theme::PANEL_COLOR));
         y += 2;
 
-        // Stats
-        let stats_data = [
-            ("Score", format_number(self.result.stats.score)),
-            ("Lines", self.result.stats.lines_cleared.to_string()),
-            ("Level", self.result.stats.level.to_string()),
-            ("Pieces", self.result.stats.pieces_placed.to_string()),
-            ("Time", self.result.stats.format_time()),
-            ("PPS", format!("{:.2}", self.result.stats.pps())),
-            ("APM", format!("{:.1}", self.result.stats.apm())),
-            ("Max Combo", self.result.stats.max_combo.to_string()),
-            ("Max B2B", self.result.stats.max_btb.to_string()),
-            ("Quads", self.result.stats.quads.to_string()),
-            ("T-Spins", self.result.stats.tspins.to_string()),
-            ("PCs", self.result.stats.perfect_clears.to_string()),
-        ];
+        // The exchange timeline takes the place of everything below
+        if let Some((player, ai)) = self.timeline.filter(|_| self.show_details) {
+            let bottom = (area.y + area.height).saturating_sub(3);
+            ExchangeTimelineWidget {
+                player,
+                ai,
+                scroll: self.timeline_scroll,
+            }
+            .render(
+                Rect::new(
+                    center_x.saturating_sub(TIMELINE_W / 2),
+                    y,
+                    TIMELINE_W,
+                    bottom.saturating_sub(y),
+                ),
+                buf,
+            );
+        } else {
+            y = self.render_summary(area, buf, y);
+        }
 
+        // Controls
+        let controls = if self.name_entry.is_some() {
+            "[Enter] Save name   [Esc] Skip".to_string()
+        } else {
+            let toggle = match (self.handling, self.timeline, self.show_details) {
+                (Some(_), _, false) => "   [→] Handling",
+                (_, Some(_), false) => "   [→] Timeline",
+                (None, Some(_), true) => "   [↑↓] Scroll   [←] Stats",
+                (Some(_), _, true) => "   [←] Stats",
+                (None, None, _) => "",
+            };
+            format!(
+                "[R] Restart   [W] Save   [Y] Copy   [Esc] Menu   [Q] Quit{}",
+                toggle
+            )
+        };
+        let cx = center_x.saturating_sub(controls.width() as u16 / 2);
+        let cy = (area.y + area.height).saturating_sub(2);
+        if cy > y + 1 {
+            buf.set_string(cx, cy, &controls, theme::menu_desc_style());
+        }
+    }
+}
+
+impl<'a> GameOverScreen<'a> {
+    /// Stats or handling from `y` down, with the breakdown charts, the
+    /// versus attack graph and the stack height graph. Returns the row below the last one drawn.
+    fn render_summary(&self, area: Rect, buf: &mut Buffer, mut y: u16) -> u16 {
+        let center_x = area.x + area.width / 2;
+
+        // Piece and clear breakdown flanking the stats, if there is room
+        let handling = self.handling.filter(|_| self.show_details);
+        if area.width >= BREAKDOWN_MIN_W && handling.is_none() {
+            self.render_breakdown(buf, center_x, y, (area.y + area.height).saturating_sub(3));
+        }
+
+        // Stats, or how the run was handled
+        let stats_data = match handling {
+            Some(handling) => handling_rows(handling),
+            None => self.stat_rows(),
+        };
+
+        // One column for all rows, centered on the widest
+        let widest = stats_data
+            .iter()
+            .map(|(_, value)| 13 + value.width())
+            .max()
+            .unwrap_or(0);
+        let stat_x = center_x.saturating_sub(widest as u16 / 2);
         for (label, value) in &stats_data {
-            if y + 1 >= area.y + area.height - 2 {
+            if y + 1 >= (area.y + area.height).saturating_sub(2) {
                 break;
             }
-            let stat_str = format!("{:<12} {}", label, value);
-            let stat_x = center_x.saturating_sub(stat_str.len() as u16 / 2);
             buf.set_string(stat_x, y, label, theme::stat_label_style());
             buf.set_string(stat_x + 13, y, value, theme::stat_value_style());
             y += 1;
         }
 
-        // Controls
-        let controls = "[R] Restart   [Esc] Menu   [Q] Quit";
-        let cx = center_x.saturating_sub(controls.len() as u16 / 2);
-        let cy = area.y + area.height - 2;
-        if cy > y + 1 {
-            buf.set_string(cx, cy, controls, theme::menu_desc_style());
+        // Attack graph (versus)
+        if self.result.won.is_some() && y + 4 < (area.y + area.height).saturating_sub(2) {
+            y += 1;
+            let graph_w = SEPARATOR.width() as u16;
+            let gx = center_x.saturating_sub(graph_w / 2);
+            let label = "Attack sent / received";
+            let lx = center_x.saturating_sub(label.len() as u16 / 2);
+            buf.set_string(lx, y, label, theme::stat_label_style());
+            y += 1;
+            AttackGraphWidget {
+                sent: &self.result.stats.attack_timeline,
+                received: &self.result.stats.received_timeline,
+            }
+            .render(Rect::new(gx, y, graph_w, 2), buf);
+            y += 2;
+        }
+
+        // Stack height, to spot where the run got away
+        let heights = &self.result.stats.height_timeline;
+        if !heights.is_empty() && y + HEIGHT_GRAPH_H + 3 < (area.y + area.height).saturating_sub(2)
+        {
+            y += 1;
+            let graph_w = SEPARATOR.width() as u16;
+            let label = "Stack height";
+            let lx = center_x.saturating_sub(label.len() as u16 / 2);
+            buf.set_string(lx, y, label, theme::stat_label_style());
+            y += 1;
+            HeightGraphWidget { heights }.render(
+                Rect::new(
+                    center_x.saturating_sub(graph_w / 2),
+                    y,
+                    graph_w,
+                    HEIGHT_GRAPH_H,
+                ),
+                buf,
+            );
+            y += HEIGHT_GRAPH_H;
+        }
+        y
+    }
+
+    /// The run's headline stats, one label and value per row.
+    fn stat_rows(&self) -> Vec<(&'static str, String)> {
+        let stats = &self.result.stats;
+        let mut rows = vec![("Score", format_number(stats.score))];
+        // A marathon's best two minutes, to line up against an Ultra score
+        if let Some(best) = self.result.best_ultra {
+            rows.push(("Best 2 Min", format_number(best)));
+        }
+        rows.extend([
+            ("Lines", stats.lines_cleared.to_string()),
+            ("Level", stats.level.to_string()),
+            ("Pieces", stats.pieces_placed.to_string()),
+            ("Time", stats.format_time()),
+            ("PPS", format!("{:.2}", stats.pps())),
+            ("APM", format!("{:.1}", stats.apm())),
+        ]);
+        // Attack and digging together only mean something against someone
+        if self.result.won.is_some() {
+            rows.push(("VS", format!("{:.1}", stats.vs())));
+        }
+        rows.extend([
+            ("Max Combo", stats.max_combo.to_string()),
+            ("Max B2B", stats.max_btb.to_string()),
+            ("Quads", stats.quads.to_string()),
+            ("T-Spins", stats.tspins.to_string()),
+            ("PCs", stats.perfect_clears.to_string()),
+        ]);
+        rows
+    }
+
+    /// Piece distribution on the left of the stats, clear types on the right.
+    fn render_breakdown(&self, buf: &mut Buffer, center_x: u16, top: u16, bottom: u16) {
+        if bottom <= top {
+            return;
+        }
+        let height = bottom - top;
+        let stats = &self.result.stats;
+
+        let piece_bars: Vec<Bar> = PieceType::ALL
+            .iter()
+            .map(|&pt| Bar {
+                label: pt.name(),
+                value: stats.pieces_by_type[pt.index()],
+                color: pt.color(),
+            })
+            .collect();
+        BarChartWidget {
+            title: "PIECES",
+            bars: &piece_bars,
+        }
+        .render(
+            Rect::new(center_x.saturating_sub(16 + CHART_W), top, CHART_W, height),
+            buf,
+        );
+
+        let clear_bars = [
+            Bar {
+                label: "Single",
+                value: stats.singles,
+                color: theme::clear_type_color(&ClearType::Single),
+            },
+            Bar {
+                label: "Double",
+                value: stats.doubles,
+                color: theme::clear_type_color(&ClearType::Double),
+            },
+            Bar {
+                label: "Triple",
+                value: stats.triples,
+                color: theme::clear_type_color(&ClearType::Triple),
+            },
+            Bar {
+                label: "Quad",
+                value: stats.quads,
+                color: theme::clear_type_color(&ClearType::Quad),
+            },
+            Bar {
+                label: "T-Spin",
+                value: stats.tspins,
+                color: theme::clear_type_color(&ClearType::TSpinDouble),
+            },
+        ];
+        BarChartWidget {
+            title: "CLEARS",
+            bars: &clear_bars,
         }
+        .render(Rect::new(center_x + 16, top, CHART_W, height), buf);
     }
 }
+
+/// Tap speed, DAS use and hard drop pace, in place of the stats.
+fn handling_rows(handling: &Handling) -> Vec<(&'static str, String)> {
+    let none = || "-".to_string();
+    vec![
+        ("Taps", handling.taps.to_string()),
+        (
+            "Tap Speed",
+            handling.tap_interval.map_or_else(none, |gap| {
+                format!("{} ({:.1}/s)", ms(gap), 1.0 / gap.as_secs_f64().max(0.001))
+            }),
+        ),
+        ("DAS Holds", handling.das_holds.to_string()),
+        ("DAS Charge", handling.das_charge.map_or_else(none, ms)),
+        ("Hard Drops", handling.hard_drops.to_string()),
+        ("Drop Pace", handling.drop_interval.map_or_else(none, ms)),
+        ("Fastest", handling.fastest_drop.map_or_else(none, ms)),
+    ]
+}
@@ -3,17 +3,203 @@ use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::widgets::Widget;
 
-use crate::data::high_scores::HighScoreStore;
-use crate::ui::theme;
-use crate::ui::widgets::sidebar::format_number;
+use chrono::{DateTime, Utc};
+
+use tetrs_core::ai::difficulty::AiDifficulty;
+use tetrs_core::game::modifiers::Modifiers;
+use tetrs_core::game::piece::PieceType;
+use tetrs_core::game::stats::Stats;
+use tetrs_core::modes::format_number;
+use tetrs_core::modes::gauntlet::STAGES;
+
+use crate::data::high_scores::{HighScoreStore, SprintEntry};
+use crate::data::leaderboard::{Leaderboard, Tops};
+use crate::ui::theme::{self, PieceColors};
 
 /// High scores screen.
 pub struct HighScoresScreen<'a> {
     pub store: &'a HighScoreStore,
     pub selected_tab: usize,
+    /// Highlighted row in the current tab.
+    pub selected: usize,
+    pub view: &'a TableView,
+    pub global: &'a Leaderboard,
+}
+
+#[cfg(not(feature = "online"))]
+pub const TABS: &[&str] = &["SPRINT", "ENDLESS", "VERSUS", "GAUNTLET"];
+#[cfg(feature = "online")]
+pub const TABS: &[&str] = &["SPRINT", "ENDLESS", "VERSUS", "GAUNTLET", "GLOBAL"];
+
+/// Tab showing the global leaderboard, with the `online` feature.
+pub const GLOBAL_TAB: usize = 4;
+
+/// Orders the versus tab can be shown in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VersusSort {
+    /// Wins first, then by damage: the order entries are kept in.
+    #[default]
+    Rank,
+    /// Newest first.
+    Date,
+    /// Easiest AI first.
+    Difficulty,
+    /// Most damage sent first.
+    Damage,
+    /// Highest VS score first.
+    Vs,
+}
+
+impl VersusSort {
+    pub const ALL: [VersusSort; 5] = [
+        VersusSort::Rank,
+        VersusSort::Date,
+        VersusSort::Difficulty,
+        VersusSort::Damage,
+        VersusSort::Vs,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            VersusSort::Rank => "Rank",
+            VersusSort::Date => "Date",
+            VersusSort::Difficulty => "Difficulty",
+            VersusSort::Damage => "Damage",
+            VersusSort::Vs => "VS",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// Sorting and filtering applied to the tables.
+#[derive(Debug, Clone, Default)]
+pub struct TableView {
+    pub versus_sort: VersusSort,
+    /// Sprint length shown; None shows every length.
+    pub sprint_length: Option<u32>,
+    /// Show purist sprints, played without hold or the ghost, instead of
+    /// the rest.
+    pub sprint_purist: bool,
+}
+
+impl TableView {
+    /// Indices into a tab's entries, in the order they're shown.
+    pub fn entries(&self, store: &HighScoreStore, tab: usize) -> Vec<usize> {
+        match tab {
+            0 => (0..store.sprint.len())
+                .filter(|&i| {
+                    let (length, purist) = store.sprint[i].category();
+                    purist == self.sprint_purist && self.sprint_length.is_none_or(|l| length == l)
+                })
+                .collect(),
+            1 => (0..store.endless.len()).collect(),
+            2 => {
+                let mut rows: Vec<usize> = (0..store.versus.len()).collect();
+                let versus = &store.versus;
+                match self.versus_sort {
+                    VersusSort::Rank => {}
+                    VersusSort::Date => rows.sort_by_key(|&i| std::cmp::Reverse(versus[i].date)),
+                    VersusSort::Difficulty => rows.sort_by_key(|&i| {
+                        AiDifficulty::ALL
+                            .iter()
+                            .position(|d| d.name() == versus[i].difficulty)
+                            .unwrap_or(AiDifficulty::ALL.len())
+                    }),
+                    VersusSort::Damage => {
+                        rows.sort_by_key(|&i| std::cmp::Reverse(versus[i].damage_sent))
+                    }
+                    VersusSort::Vs => rows.sort_by(|&a, &b| {
+                        let vs = |i: usize| versus[i].stats.as_ref().map_or(-1.0, Stats::vs);
+                        vs(b).total_cmp(&vs(a))
+                    }),
+                }
+                rows
+            }
+            3 => (0..store.gauntlet.len()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Step to the next sprint view: every length, then each recorded
+    /// length in turn, then the same for purist runs once there are any.
+    pub fn cycle_sprint_length(&mut self, store: &HighScoreStore) {
+        let views = sprint_views(store);
+        let current = (self.sprint_purist, self.sprint_length);
+        let next = views
+            .iter()
+            .position(|&v| v == current)
+            .map_or(0, |i| (i + 1) % views.len());
+        (self.sprint_purist, self.sprint_length) = views[next];
+    }
+
+    /// Name of the sprint view, when there's more than one to pick from.
+    fn sprint_label(&self, store: &HighScoreStore) -> Option<String> {
+        if sprint_views(store).len() < 2 {
+            return None;
+        }
+        let lengths = match self.sprint_length {
+            Some(length) => format!("{}-line sprints", length),
+            None => "All lengths".to_string(),
+        };
+        Some(if self.sprint_purist {
+            format!("Purist: {}", lengths.to_lowercase())
+        } else {
+            lengths
+        })
+    }
+}
+
+/// Distinct lengths of standard or purist sprints, shortest first.
+fn sprint_lengths(store: &HighScoreStore, purist: bool) -> Vec<u32> {
+    let mut lengths: Vec<u32> = store
+        .sprint
+        .iter()
+        .map(SprintEntry::category)
+        .filter(|&(_, p)| p == purist)
+        .map(|(length, _)| length)
+        .collect();
+    lengths.sort_unstable();
+    lengths.dedup();
+    lengths
+}
+
+/// Sprint views that can be cycled through, as (purist, length). The
+/// standard view is always first; a length is only picked out when the
+/// category has more than one.
+fn sprint_views(store: &HighScoreStore) -> Vec<(bool, Option<u32>)> {
+    let mut views = Vec::new();
+    for purist in [false, true] {
+        let lengths = sprint_lengths(store, purist);
+        if purist && lengths.is_empty() {
+            continue;
+        }
+        views.push((purist, None));
+        if lengths.len() > 1 {
+            views.extend(lengths.into_iter().map(|l| (purist, Some(l))));
+        }
+    }
+    views
 }
 
-const TABS: &[&str] = &["SPRINT", "ENDLESS", "VERSUS"];
+/// Position of an entry within its table, counting sprints only against
+/// those of the same length and category.
+fn rank(store: &HighScoreStore, tab: usize, index: usize) -> usize {
+    match tab {
+        0 => {
+            let category = store.sprint[index].category();
+            store.sprint[..index]
+                .iter()
+                .filter(|e| e.category() == category)
+                .count()
+                + 1
+        }
+        _ => index + 1,
+    }
+}
 
 impl<'a> Widget for HighScoresScreen<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
@@ -34,7 +220,8 @@ impl<'a> Widget for HighScoresScreen<'a> {
         y += 2;
 
         // Tabs
-        let mut tab_x = center_x.saturating_sub(15);
+        let tabs_w = TABS.iter().map(|t| t.len() as u16 + 3).sum::<u16>() - 3;
+        let mut tab_x = center_x.saturating_sub(tabs_w / 2);
         for (i, tab) in TABS.iter().enumerate() {
             let style = if i == self.selected_tab {
                 Style::default()
@@ -46,7 +233,24 @@ impl<'a> Widget for HighScoresScreen<'a> {
             buf.set_string(tab_x, y, tab, style);
             tab_x += tab.len() as u16 + 3;
         }
-        y += 2;
+        y += 1;
+
+        // Current sort or filter, where the tab has one
+        let view_label = match self.selected_tab {
+            0 => self.view.sprint_label(self.store),
+            2 => Some(format!(
+                "Sorted by {}",
+                self.view.versus_sort.name().to_lowercase()
+            )),
+            GLOBAL_TAB => Some(self.global.board.name().to_string()),
+            _ => None,
+        };
+        if let Some(label) = view_label {
+            let lx = center_x.saturating_sub(label.len() as u16 / 2);
+            buf.set_string(lx, y, &label, Style::default().fg(theme::TEXT_DIM));
+        }
+        y += 1;
+        let rows = self.view.entries(self.store, self.selected_tab);
 
         // Separator
         let sep = "─────────────────────────────────────";
@@ -57,31 +261,42 @@ impl<'a> Widget for HighScoresScreen<'a> {
         // Header
         match self.selected_tab {
             0 => {
-                // Sprint: rank, time, lines, pieces, date
+                // Sprint: rank, time, lines, pieces, date, modifiers
+                let mods = badge_width(rows.iter().map(|&i| self.store.sprint[i].modifiers));
                 let header = format!(
-                    " {:<4} {:<12} {:<6} {:<6} {}",
-                    "#", "TIME", "LINES", "PCS", "DATE"
+                    " {:<4} {:<10} {:<12} {:<6} {:<6} {}",
+                    "#",
+                    "NAME",
+                    "TIME",
+                    "LINES",
+                    "PCS",
+                    with_badges("DATE".to_string(), "MODS", mods)
                 );
                 let hx = center_x.saturating_sub(header.len() as u16 / 2);
                 buf.set_string(hx, y, &header, theme::stat_label_style());
                 y += 1;
 
-                for (i, entry) in self.store.sprint.iter().enumerate() {
+                for (row, &i) in rows.iter().enumerate() {
+                    let entry = &self.store.sprint[i];
                     if y >= area.y + area.height - 2 {
                         break;
                     }
-                    let rank = format!("{:>2}.", i + 1);
+                    let rank = rank(self.store, self.selected_tab, i);
+                    let rank_str = format!("{:>2}.", rank);
                     let time = format_time_ms(entry.time_ms);
+                    let tags = badges(entry.modifiers);
                     let line = format!(
-                        " {:<4} {:<12} {:<6} {:<6} {}",
-                        rank,
+                        " {:<4} {:<10.10} {:<12} {:<6} {:<6} {}",
+                        rank_str,
+                        display_name(&entry.name),
                         time,
                         entry.lines,
                         entry.pieces,
-                        entry.date.format("%Y-%m-%d")
+                        with_badges(entry.date.format("%Y-%m-%d").to_string(), &tags, mods)
                     );
-                    let lx = center_x.saturating_sub(line.len() as u16 / 2);
-                    let style = if i == 0 {
+                    let line = mark_selected(line, row == self.selected);
+                    let lx = center_x.saturating_sub(line.chars().count() as u16 / 2);
+                    let style = if rank == 1 {
                         Style::default()
                             .fg(ratatui::style::Color::Rgb(255, 215, 60))
                             .add_modifier(Modifier::BOLD)
@@ -89,34 +304,46 @@ impl<'a> Widget for HighScoresScreen<'a> {
                         theme::stat_value_style()
                     };
                     buf.set_string(lx, y, &line, style);
+                    draw_badges(buf, lx, y, &line, &tags, mods);
                     y += 1;
                 }
             }
             1 => {
-                // Endless: rank, score, level, lines, date
+                // Endless: rank, score, level, lines, date, modifiers
+                let mods = badge_width(rows.iter().map(|&i| self.store.endless[i].modifiers));
                 let header = format!(
-                    " {:<4} {:<12} {:<6} {:<6} {}",
-                    "#", "SCORE", "LEVEL", "LINES", "DATE"
+                    " {:<4} {:<10} {:<12} {:<6} {:<6} {}",
+                    "#",
+                    "NAME",
+                    "SCORE",
+                    "LEVEL",
+                    "LINES",
+                    with_badges("DATE".to_string(), "MODS", mods)
                 );
                 let hx = center_x.saturating_sub(header.len() as u16 / 2);
                 buf.set_string(hx, y, &header, theme::stat_label_style());
                 y += 1;
 
-                for (i, entry) in self.store.endless.iter().enumerate() {
+                for (row, &i) in rows.iter().enumerate() {
+                    let entry = &self.store.endless[i];
                     if y >= area.y + area.height - 2 {
                         break;
                     }
-                    let rank = format!("{:>2}.", i + 1);
+                    let rank = rank(self.store, self.selected_tab, i);
+                    let rank_str = format!("{:>2}.", rank);
+                    let tags = badges(entry.modifiers);
                     let line = format!(
-                        " {:<4} {:<12} {:<6} {:<6} {}",
-                        rank,
+                        " {:<4} {:<10.10} {:<12} {:<6} {:<6} {}",
+                        rank_str,
+                        display_name(&entry.name),
                         format_number(entry.score),
                         entry.level,
                         entry.lines,
-                        entry.date.format("%Y-%m-%d")
+                        with_badges(entry.date.format("%Y-%m-%d").to_string(), &tags, mods)
                     );
-                    let lx = center_x.saturating_sub(line.len() as u16 / 2);
-                    let style = if i == 0 {
+                    let line = mark_selected(line, row == self.selected);
+                    let lx = center_x.saturating_sub(line.chars().count() as u16 / 2);
+                    let style = if rank == 1 {
                         Style::default()
                             .fg(ratatui::style::Color::Rgb(255, 215, 60))
                             .add_modifier(Modifier::BOLD)
@@ -124,42 +351,63 @@ impl<'a> Widget for HighScoresScreen<'a> {
                         theme::stat_value_style()
                     };
                     buf.set_string(lx, y, &line, style);
+                    draw_badges(buf, lx, y, &line, &tags, mods);
                     y += 1;
                 }
             }
             2 => {
-                // Versus: rank, result, difficulty, damage, time, date
+                // Versus: rank, result, difficulty, damage, VS score, time,
+                // date, modifiers
+                let mods = badge_width(rows.iter().map(|&i| self.store.versus[i].modifiers));
                 let header = format!(
-                    " {:<4} {:<6} {:<8} {:<6} {:<10} {}",
-                    "#", "RESULT", "DIFF", "DMG", "TIME", "DATE"
+                    " {:<4} {:<10} {:<6} {:<8} {:<6} {:<6} {:<10} {}",
+                    "#",
+                    "NAME",
+                    "RESULT",
+                    "DIFF",
+                    "DMG",
+                    "VS",
+                    "TIME",
+                    with_badges("DATE".to_string(), "MODS", mods)
                 );
                 let hx = center_x.saturating_sub(header.len() as u16 / 2);
                 buf.set_string(hx, y, &header, theme::stat_label_style());
                 y += 1;
 
-                for (i, entry) in self.store.versus.iter().enumerate() {
+                for (row, &i) in rows.iter().enumerate() {
+                    let entry = &self.store.versus[i];
                     if y >= area.y + area.height - 2 {
                         break;
                     }
-                    let rank = format!("{:>2}.", i + 1);
+                    let rank = rank(self.store, self.selected_tab, i);
+                    let rank_str = format!("{:>2}.", rank);
                     let result_str = if entry.won { "WIN" } else { "LOSS" };
                     let time = format_time_ms(entry.time_ms);
+                    // Entries saved before full stats were kept have none
+                    let vs = entry
+                        .stats
+                        .as_ref()
+                        .map_or("-".to_string(), |s| format!("{:.1}", s.vs()));
+                    let tags = badges(entry.modifiers);
                     let line = format!(
-                        " {:<4} {:<6} {:<8} {:<6} {:<10} {}",
-                        rank,
+                        " {:<4} {:<10.10} {:<6} {:<8} {:<6} {:<6} {:<10} {}",
+                        rank_str,
+                        display_name(&entry.name),
                         result_str,
                         entry.difficulty,
                         entry.damage_sent,
+                        vs,
                         time,
-                        entry.date.format("%Y-%m-%d")
+                        with_badges(entry.date.format("%Y-%m-%d").to_string(), &tags, mods)
                     );
-                    let lx = center_x.saturating_sub(line.len() as u16 / 2);
+                    let line = mark_selected(line, row == self.selected);
+                    let lx = center_x.saturating_sub(line.chars().count() as u16 / 2);
                     let result_color = if entry.won {
                         ratatui::style::Color::Rgb(80, 255, 120)
                     } else {
                         ratatui::style::Color::Rgb(255, 80, 80)
                     };
-                    let style = if i == 0 {
+                    let style = if rank == 1 {
                         Style::default()
                             .fg(ratatui::style::Color::Rgb(255, 215, 60))
                             .add_modifier(Modifier::BOLD)
@@ -167,34 +415,401 @@ impl<'a> Widget for HighScoresScreen<'a> {
                         Style::default().fg(result_color)
                     };
                     buf.set_string(lx, y, &line, style);
+                    draw_badges(buf, lx, y, &line, &tags, mods);
+                    y += 1;
+                }
+            }
+            3 => {
+                // Gauntlet: rank, stages beaten, lives left, total time, date
+                let header = format!(
+                    " {:<4} {:<10} {:<7} {:<6} {:<10} {}",
+                    "#", "NAME", "STAGES", "LIVES", "TIME", "DATE"
+                );
+                let hx = center_x.saturating_sub(header.len() as u16 / 2);
+                buf.set_string(hx, y, &header, theme::stat_label_style());
+                y += 1;
+
+                for (row, &i) in rows.iter().enumerate() {
+                    let entry = &self.store.gauntlet[i];
+                    if y >= area.y + area.height - 2 {
+                        break;
+                    }
+                    let rank = rank(self.store, self.selected_tab, i);
+                    let rank_str = format!("{:>2}.", rank);
+                    let stages = format!("{}/{}", entry.stages, STAGES.len());
+                    let line = format!(
+                        " {:<4} {:<10.10} {:<7} {:<6} {:<10} {}",
+                        rank_str,
+                        display_name(&entry.name),
+                        stages,
+                        entry.lives_left,
+                        format_time_ms(entry.time_ms),
+                        entry.date.format("%Y-%m-%d")
+                    );
+                    let line = mark_selected(line, row == self.selected);
+                    let lx = center_x.saturating_sub(line.chars().count() as u16 / 2);
+                    let style = if rank == 1 {
+                        Style::default()
+                            .fg(ratatui::style::Color::Rgb(255, 215, 60))
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        theme::stat_value_style()
+                    };
+                    buf.set_string(lx, y, &line, style);
                     y += 1;
                 }
             }
+            GLOBAL_TAB => self.render_global(area, buf, center_x, y),
             _ => {}
         }
 
         // Show empty message if no scores
-        let is_empty = match self.selected_tab {
-            0 => self.store.sprint.is_empty(),
-            1 => self.store.endless.is_empty(),
-            2 => self.store.versus.is_empty(),
-            _ => true,
-        };
-        if is_empty && y < area.y + area.height - 3 {
+        if rows.is_empty() && self.selected_tab != GLOBAL_TAB && y < area.y + area.height - 3 {
             let msg = "No scores yet. Play some games!";
             let mx = center_x.saturating_sub(msg.len() as u16 / 2);
             buf.set_string(mx, y + 2, msg, theme::menu_desc_style());
         }
 
         // Controls
-        let controls = "h/l: switch tab   Esc: back";
+        let controls = match self.selected_tab {
+            0 if sprint_views(self.store).len() > 1 => {
+                "j/k: select   Enter: details   f: filter   h/l: switch tab   Esc: back"
+            }
+            2 => "j/k: select   Enter: details   s: sort   h/l: switch tab   Esc: back",
+            GLOBAL_TAB => "f: switch board   h/l: switch tab   Esc: back",
+            _ => "j/k: select   Enter: details   h/l: switch tab   Esc: back",
+        };
         let cx = center_x.saturating_sub(controls.len() as u16 / 2);
         let cy = area.y + area.height - 2;
         buf.set_string(cx, cy, controls, theme::menu_desc_style());
     }
 }
 
-fn format_time_ms(ms: u64) -> String {
+impl<'a> HighScoresScreen<'a> {
+    /// Top entries fetched from the global leaderboard.
+    fn render_global(&self, area: Rect, buf: &mut Buffer, center_x: u16, mut y: u16) {
+        let message = |buf: &mut Buffer, msg: &str| {
+            let mx = center_x.saturating_sub(msg.chars().count() as u16 / 2);
+            buf.set_string(mx, y + 1, msg, theme::menu_desc_style());
+        };
+        if !self.global.is_enabled() {
+            message(
+                buf,
+                "Set leaderboard_url in config.json to see global scores.",
+            );
+            return;
+        }
+        let entries = match self.global.tops {
+            Tops::Idle | Tops::Loading => return message(buf, "Loading\u{2026}"),
+            Tops::Failed(ref e) => return message(buf, &format!("Couldn't load scores: {}", e)),
+            Tops::Loaded(ref entries) => entries,
+        };
+        if entries.is_empty() {
+            return message(buf, "No scores on this board yet.");
+        }
+
+        let sprint = self.global.board.is_timed();
+        let header = format!(
+            " {:<4} {:<16} {:<12} {}",
+            "#",
+            "NAME",
+            if sprint { "TIME" } else { "SCORE" },
+            "DATE"
+        );
+        let hx = center_x.saturating_sub(header.len() as u16 / 2);
+        buf.set_string(hx, y, &header, theme::stat_label_style());
+        y += 1;
+
+        for (i, entry) in entries.iter().enumerate() {
+            if y >= area.y + area.height - 2 {
+                break;
+            }
+            let value = if sprint {
+                format_time_ms(entry.time_ms)
+            } else {
+                format_number(entry.score)
+            };
+            let line = format!(
+                " {:<4} {:<16.16} {:<12} {}",
+                format!("{:>2}.", i + 1),
+                display_name(&entry.name),
+                value,
+                entry.date.format("%Y-%m-%d")
+            );
+            let lx = center_x.saturating_sub(line.chars().count() as u16 / 2);
+            let style = if i == 0 {
+                Style::default()
+                    .fg(ratatui::style::Color::Rgb(255, 215, 60))
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                theme::stat_value_style()
+            };
+            buf.set_string(lx, y, &line, style);
+            y += 1;
+        }
+    }
+}
+
+/// Full stats of one high score entry.
+pub struct HighScoreDetailScreen<'a> {
+    pub store: &'a HighScoreStore,
+    pub tab: usize,
+    pub index: usize,
+}
+
+impl<'a> Widget for HighScoreDetailScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear background
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let Some(entry) = entry_detail(self.store, self.tab, self.index) else {
+            return;
+        };
+        let center_x = area.x + area.width / 2;
+        let bottom = area.y + area.height.saturating_sub(2);
+        let mut y = area.y + 2;
+
+        // Title, date, and the value the entry is ranked by
+        let title = format!(
+            "{} #{}",
+            TABS[self.tab],
+            rank(self.store, self.tab, self.index)
+        );
+        let tx = center_x.saturating_sub(title.len() as u16 / 2);
+        buf.set_string(tx, y, &title, theme::title_style());
+        y += 2;
+
+        let date = format!(
+            "{} \u{00B7} {}",
+            display_name(entry.name),
+            entry.date.format("%Y-%m-%d %H:%M")
+        );
+        let dx = center_x.saturating_sub(date.chars().count() as u16 / 2);
+        buf.set_string(dx, y, &date, Style::default().fg(theme::TEXT_DIM));
+        y += 1;
+
+        let px = center_x.saturating_sub(entry.summary.chars().count() as u16 / 2);
+        let summary_style = Style::default()
+            .fg(ratatui::style::Color::Rgb(100, 220, 255))
+            .add_modifier(Modifier::BOLD);
+        buf.set_string(px, y, &entry.summary, summary_style);
+        y += 1;
+
+        if !entry.modifiers.is_empty() {
+            let mods = entry.modifiers.label();
+            let mx = center_x.saturating_sub(mods.chars().count() as u16 / 2);
+            buf.set_string(mx, y, &mods, Style::default().fg(theme::BADGE_COLOR));
+            y += 1;
+        }
+
+        // Separator
+        let sep = "─────────────────────────────────────";
+        let sx = center_x.saturating_sub(sep.chars().count() as u16 / 2);
+        buf.set_string(sx, y, sep, Style::default().fg(theme::PANEL_COLOR));
+        y += 2;
+
+        match entry.stats {
+            Some(stats) => {
+                let rows = stat_rows(stats, entry.versus);
+                let half = rows.len().div_ceil(2);
+                let left_x = center_x.saturating_sub(19);
+                for (i, (label, value)) in rows.iter().enumerate() {
+                    let (x, row) = if i < half {
+                        (left_x, i)
+                    } else {
+                        (center_x + 2, i - half)
+                    };
+                    let ry = y + row as u16;
+                    if ry >= bottom {
+                        continue;
+                    }
+                    buf.set_string(x, ry, label, theme::stat_label_style());
+                    buf.set_string(x + 10, ry, value, theme::stat_value_style());
+                }
+                y += half as u16 + 1;
+
+                // Piece distribution on one line, in piece colors
+                if y < bottom {
+                    let width = PieceType::ALL.len() as u16 * 6 - 2;
+                    let mut x = center_x.saturating_sub(width / 2);
+                    for pt in PieceType::ALL {
+                        let count = format!("{} {}", pt.name(), stats.pieces_by_type[pt.index()]);
+                        buf.set_string(x, y, &count, Style::default().fg(pt.color()));
+                        x += 6;
+                    }
+                }
+            }
+            None => {
+                let msg = "No detailed stats were saved with this entry.";
+                let mx = center_x.saturating_sub(msg.len() as u16 / 2);
+                buf.set_string(mx, y, msg, theme::menu_desc_style());
+            }
+        }
+
+        // Controls
+        let controls = "j/k: previous/next   Esc: back";
+        let cx = center_x.saturating_sub(controls.len() as u16 / 2);
+        buf.set_string(cx, bottom, controls, theme::menu_desc_style());
+    }
+}
+
+/// What the detail page shows for an entry.
+struct EntryDetail<'a> {
+    name: &'a str,
+    summary: String,
+    date: DateTime<Utc>,
+    stats: Option<&'a Stats>,
+    modifiers: Modifiers,
+    /// Whether the run was played against an AI, as on the game over screen.
+    versus: bool,
+}
+
+fn entry_detail(store: &HighScoreStore, tab: usize, index: usize) -> Option<EntryDetail<'_>> {
+    match tab {
+        0 => store.sprint.get(index).map(|e| EntryDetail {
+            summary: format!("{} for {} lines", format_time_ms(e.time_ms), e.lines),
+            name: &e.name,
+            date: e.date,
+            stats: e.stats.as_ref(),
+            modifiers: e.modifiers,
+            versus: false,
+        }),
+        1 => store.endless.get(index).map(|e| EntryDetail {
+            summary: format!("{} points, level {}", format_number(e.score), e.level),
+            name: &e.name,
+            date: e.date,
+            stats: e.stats.as_ref(),
+            modifiers: e.modifiers,
+            versus: false,
+        }),
+        2 => store.versus.get(index).map(|e| EntryDetail {
+            summary: format!("{} vs {}", if e.won { "Win" } else { "Loss" }, e.difficulty),
+            name: &e.name,
+            date: e.date,
+            stats: e.stats.as_ref(),
+            modifiers: e.modifiers,
+            versus: true,
+        }),
+        3 => store.gauntlet.get(index).map(|e| EntryDetail {
+            summary: format!(
+                "{}/{} stages in {} (final round below)",
+                e.stages,
+                STAGES.len(),
+                format_time_ms(e.time_ms)
+            ),
+            name: &e.name,
+            date: e.date,
+            stats: e.stats.as_ref(),
+            // Gauntlet rounds are always played plain
+            modifiers: Modifiers::default(),
+            versus: true,
+        }),
+        _ => None,
+    }
+}
+
+/// Every stat worth showing, as label and value.
+fn stat_rows(stats: &Stats, versus: bool) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        ("Score", format_number(stats.score)),
+        ("Time", stats.format_time()),
+        ("Level", stats.level.to_string()),
+        ("Lines", stats.lines_cleared.to_string()),
+        ("Pieces", stats.pieces_placed.to_string()),
+        ("PPS", format!("{:.2}", stats.pps())),
+        ("LPM", format!("{:.1}", stats.lpm())),
+        ("APM", format!("{:.1}", stats.apm())),
+    ];
+    // Attack and digging together only mean something against someone
+    if versus {
+        rows.push(("VS", format!("{:.1}", stats.vs())));
+    }
+    rows.extend([
+        ("KPP", format!("{:.2}", stats.kpp())),
+        ("Inputs", stats.inputs.to_string()),
+        ("Attack", stats.attack_sent.to_string()),
+        ("Received", stats.garbage_received.to_string()),
+        ("Dug", stats.garbage_cleared.to_string()),
+        ("Singles", stats.singles.to_string()),
+        ("Doubles", stats.doubles.to_string()),
+        ("Triples", stats.triples.to_string()),
+        ("Quads", stats.quads.to_string()),
+        ("T-Spins", stats.tspins.to_string()),
+        ("TSS", stats.tspin_singles.to_string()),
+        ("TSD", stats.tspin_doubles.to_string()),
+        ("TST", stats.tspin_triples.to_string()),
+        ("Minis", stats.mini_tspins.to_string()),
+        ("All-Spins", stats.all_spins.to_string()),
+        ("PCs", stats.perfect_clears.to_string()),
+        ("Max Combo", stats.max_combo.to_string()),
+        ("Max B2B", stats.max_btb.to_string()),
+    ]);
+    rows
+}
+
+/// Short tags for a run's modifiers, e.g. "NH MIR"; empty for a plain run.
+fn badges(modifiers: Modifiers) -> String {
+    modifiers
+        .iter()
+        .map(|m| m.badge())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Width of the modifier column for these entries, or None to leave it
+/// out when every one is a plain run.
+fn badge_width(modifiers: impl Iterator<Item = Modifiers>) -> Option<usize> {
+    modifiers
+        .map(|m| badges(m).len())
+        .max()
+        .filter(|&w| w > 0)
+        .map(|w| w.max("MODS".len()))
+}
+
+/// A table's last column, the date, followed by the modifier column if
+/// the table has one.
+fn with_badges(date: String, tags: &str, width: Option<usize>) -> String {
+    match width {
+        Some(width) => format!("{:<10} {:<width$}", date, tags),
+        None => date,
+    }
+}
+
+/// Redraw the tags ending a row in their own color, so modified runs
+/// stand out from plain ones.
+fn draw_badges(buf: &mut Buffer, x: u16, y: u16, line: &str, tags: &str, width: Option<usize>) {
+    let Some(width) = width.filter(|_| !tags.is_empty()) else {
+        return;
+    };
+    let bx = x + (line.chars().count() - width) as u16;
+    if bx < buf.area.right() {
+        buf.set_string(bx, y, tags, Style::default().fg(theme::BADGE_COLOR));
+    }
+}
+
+/// Name shown for an entry, with a placeholder for unnamed ones.
+fn display_name(name: &str) -> &str {
+    if name.is_empty() {
+        "-"
+    } else {
+        name
+    }
+}
+
+/// Swap a row's leading space for a marker when it's highlighted.
+pub fn mark_selected(line: String, selected: bool) -> String {
+    if selected {
+        format!("\u{25B8}{}", &line[1..])
+    } else {
+        line
+    }
+}
+
+pub fn format_time_ms(ms: u64) -> String {
     let minutes = ms / 60_000;
     let seconds = (ms % 60_000) / 1000;
     let millis = ms % 1000;
@@ -0,0 +1,118 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use tetrs_core::ai::difficulty::AiDifficulty;
+
+use crate::data::match_history::MatchHistory;
+use crate::ui::screens::high_scores::{format_time_ms, mark_selected};
+use crate::ui::theme;
+
+/// Every versus match played, newest first, under the win/loss record
+/// against each difficulty.
+pub struct MatchHistoryScreen<'a> {
+    pub history: &'a MatchHistory,
+    /// Highlighted match, counted from the newest.
+    pub selected: usize,
+}
+
+impl<'a> Widget for MatchHistoryScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear background
+        for y in area.y..area.y + area.height {
+            buf.set_string(
+                area.x,
+                y,
+                " ".repeat(area.width as usize),
+                Style::default().bg(theme::BG_COLOR),
+            );
+        }
+
+        let center_x = area.x + area.width / 2;
+        let bottom = area.y + area.height.saturating_sub(2);
+        let mut y = area.y + 2;
+
+        let title = "MATCH HISTORY";
+        let tx = center_x.saturating_sub(title.len() as u16 / 2);
+        buf.set_string(tx, y, title, theme::title_style());
+        y += 2;
+
+        if let Some(peak) = self.history.peak_rating() {
+            let rating = format!("Rating {:.0}   Peak {:.0}", self.history.rating(), peak);
+            let rx = center_x.saturating_sub(rating.len() as u16 / 2);
+            buf.set_string(rx, y, &rating, theme::stat_value_style());
+            y += 2;
+        }
+
+        // Record against each difficulty
+        let header = format!(" {:<10} {:>4} {:>4} {:>6}", "AI", "W", "L", "WIN%");
+        let rx = center_x.saturating_sub(header.len() as u16 / 2);
+        buf.set_string(rx, y, &header, theme::stat_label_style());
+        y += 1;
+        for difficulty in AiDifficulty::ALL {
+            let record = self.history.record(difficulty);
+            let rate = record
+                .win_rate()
+                .map_or("-".to_string(), |r| format!("{:.0}%", r * 100.0));
+            let line = format!(
+                " {:<10} {:>4} {:>4} {:>6}",
+                difficulty.name(),
+                record.wins,
+                record.losses,
+                rate
+            );
+            buf.set_string(rx, y, &line, theme::stat_value_style());
+            y += 1;
+        }
+        y += 1;
+
+        let header = format!(
+            " {:<16} {:<8} {:<14} {:<6} {:<9} {:>5} {:>8} {:>6}",
+            "DATE", "AI", "STYLE", "RESULT", "TIME", "APM", "SENT/RCV", "RATING"
+        );
+        let hx = center_x.saturating_sub(header.len() as u16 / 2);
+        let sep = "─".repeat(header.len());
+        buf.set_string(hx, y, &sep, Style::default().fg(theme::PANEL_COLOR));
+        y += 1;
+
+        let matches = &self.history.matches;
+        if matches.is_empty() {
+            let empty = "No versus matches yet";
+            let ex = center_x.saturating_sub(empty.len() as u16 / 2);
+            buf.set_string(ex, y + 1, empty, theme::menu_desc_style());
+        } else {
+            buf.set_string(hx, y, &header, theme::stat_label_style());
+            y += 1;
+
+            // Scroll just far enough to keep the selection on screen
+            let visible = bottom.saturating_sub(y + 1).max(1) as usize;
+            let first = self.selected.saturating_sub(visible - 1);
+            for (row, m) in matches.iter().rev().enumerate().skip(first).take(visible) {
+                let line = format!(
+                    " {:<16} {:<8} {:<14} {:<6} {:<9} {:>5.1} {:>8} {:>6}",
+                    m.date.format("%Y-%m-%d %H:%M"),
+                    m.difficulty.name(),
+                    m.personality.name(),
+                    if m.won { "WIN" } else { "LOSS" },
+                    format_time_ms(m.time_ms),
+                    m.apm,
+                    format!("{}/{}", m.attack_sent, m.attack_received),
+                    m.rating.map_or("-".to_string(), |r| format!("{:.0}", r)),
+                );
+                let line = mark_selected(line, row == self.selected);
+                let style = if m.won {
+                    theme::stat_value_style()
+                } else {
+                    Style::default().fg(theme::TEXT_DIM)
+                };
+                buf.set_string(hx, y, &line, style);
+                y += 1;
+            }
+        }
+
+        let controls = "j/k: scroll   Esc: back";
+        let cx = center_x.saturating_sub(controls.len() as u16 / 2);
+        buf.set_string(cx, bottom, controls, theme::menu_desc_style());
+    }
+}
@@ -4,6 +4,8 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Widget;
 use unicode_width::UnicodeWidthStr;
 
+use tetrs_core::game::ruleset::RulesetPreset;
+
 use crate::ui::theme;
 
 /// Menu item definition.
@@ -19,8 +21,15 @@ pub struct MenuItem {
 pub enum MenuChoice {
     Sprint,
     Endless,
+    Zen,
     Versus,
+    Gauntlet,
+    Royale,
+    Trainer,
+    Custom,
     HighScores,
+    MatchHistory,
+    Settings,
     Quit,
 }
 
@@ -28,7 +37,17 @@ pub enum MenuChoice {
 pub struct MenuScreen {
     pub selected: usize,
     pub items: Vec<MenuItem>,
-    pub frame: u64,
+    /// Seconds since launch; drives the title shimmer.
+    pub time: f32,
+    /// Skip clearing the background so an attract-mode demo shows through.
+    pub transparent: bool,
+    /// Ruleset the next game starts with, cycled with left/right.
+    pub ruleset: RulesetPreset,
+    /// Profile level, and XP into it out of what it takes.
+    pub level: u32,
+    pub level_progress: (u64, u64),
+    /// Versus rating, once a match has been played.
+    pub rating: Option<f64>,
 }
 
 impl MenuScreen {
@@ -46,23 +65,63 @@ impl MenuScreen {
                     description: "Play forever, maximize your score",
                     id: MenuChoice::Endless,
                 },
+                MenuItem {
+                    label: "Zen Practice",
+                    description: "No speed-up, no game over, undo with u",
+                    id: MenuChoice::Zen,
+                },
                 MenuItem {
                     label: "Versus AI",
                     description: "Battle against a computer opponent",
                     id: MenuChoice::Versus,
                 },
+                MenuItem {
+                    label: "AI Gauntlet",
+                    description: "Beat every AI from Easy to Expert in one run",
+                    id: MenuChoice::Gauntlet,
+                },
+                MenuItem {
+                    label: "Battle Royale",
+                    description: "Last one standing against a lobby of AI boards",
+                    id: MenuChoice::Royale,
+                },
+                MenuItem {
+                    label: "Opener Trainer",
+                    description: "Learn TKI, DT Cannon, and PCO step by step",
+                    id: MenuChoice::Trainer,
+                },
+                MenuItem {
+                    label: "Custom Modes",
+                    description: "Your own modes, scripted in Rhai",
+                    id: MenuChoice::Custom,
+                },
                 MenuItem {
                     label: "High Scores",
                     description: "View your best performances",
                     id: MenuChoice::HighScores,
                 },
+                MenuItem {
+                    label: "Match History",
+                    description: "Every versus match and your record per AI",
+                    id: MenuChoice::MatchHistory,
+                },
+                MenuItem {
+                    label: "Settings",
+                    description: "Adjust handling (DAS/ARR)",
+                    id: MenuChoice::Settings,
+                },
                 MenuItem {
                     label: "Quit",
                     description: "Exit the game",
                     id: MenuChoice::Quit,
                 },
             ],
-            frame: 0,
+            time: 0.0,
+            transparent: false,
+            ruleset: RulesetPreset::default(),
+            level: 1,
+            level_progress: (0, 0),
+            rating: None,
         }
     }
 
@@ -86,19 +145,42 @@ impl MenuScreen {
 impl Widget for &MenuScreen {
     fn render(self, area: Rect, buf: &mut Buffer) {
         // Clear background
-        for y in area.y..area.y + area.height {
-            for x in area.x..area.x + area.width {
-                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+        if !self.transparent {
+            for y in area.y..area.y + area.height {
+                for x in area.x..area.x + area.width {
+                    buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+                }
             }
         }
 
+        // Profile level in the top-right corner
+        let (xp, needed) = self.level_progress;
+        let level = format!("LV {}", self.level);
+        let progress = format!("  {}/{} XP", xp, needed);
+        let w = (level.width() + progress.width()) as u16;
+        if area.width > w + 2 && area.height > 1 {
+            let x = area.x + area.width - w - 2;
+            buf.set_string(x, area.y + 1, &level, theme::stat_value_style());
+            buf.set_string(
+                x + level.width() as u16,
+                area.y + 1,
+                &progress,
+                theme::stat_label_style(),
+            );
+        }
+        if let Some(rating) = self.rating.filter(|_| area.height > 2) {
+            let text = format!("RATING {:.0}", rating);
+            let x = (area.x + area.width).saturating_sub(text.width() as u16 + 2);
+            buf.set_string(x, area.y + 2, &text, theme::stat_label_style());
+        }
+
         let center_x = area.x + area.width / 2;
         let mut y = area.y + area.height / 6;
 
         // Title
         let title_lines = TITLE_ART;
         for (i, line) in title_lines.iter().enumerate() {
-            let hue = ((self.frame as f32 * 2.0 + i as f32 * 20.0) % 360.0) / 360.0;
+            let hue = ((self.time * 120.0 + i as f32 * 20.0) % 360.0) / 360.0;
             let color = hsl_to_rgb(hue, 0.7, 0.65);
             let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
             let x = center_x.saturating_sub(line.width() as u16 / 2);
@@ -109,7 +191,13 @@ impl Widget for &MenuScreen {
         }
         y += 2;
 
-        // Menu items
+        // Menu items, dropping the blank rows between them if space is short
+        let spaced_h = self.items.len() as u16 * 2 + 1;
+        let spacing = if y + spaced_h + 2 <= area.y + area.height {
+            1
+        } else {
+            0
+        };
         for (i, item) in self.items.iter().enumerate() {
             if y + 1 >= area.y + area.height {
                 break;
@@ -139,15 +227,27 @@ impl Widget for &MenuScreen {
                 let desc_x = center_x.saturating_sub(item.description.width() as u16 / 2);
                 buf.set_string(desc_x, y, item.description, theme::menu_desc_style());
             }
-            y += 1;
-            if !is_selected {
-                // Add spacing between non-selected items
-            }
-            y += 1;
+            y += 1 + spacing;
+        }
+
+        // Ruleset selector
+        y += 1 - spacing;
+        if y + 2 < area.y + area.height {
+            let label = "Ruleset ";
+            let value = format!("\u{25c2} {} \u{25b8}", self.ruleset.name());
+            let w = (label.width() + value.width()) as u16;
+            let x = center_x.saturating_sub(w / 2);
+            buf.set_string(x, y, label, theme::stat_label_style());
+            buf.set_string(
+                x + label.width() as u16,
+                y,
+                &value,
+                theme::stat_value_style(),
+            );
         }
 
         // Controls help at bottom
-        let controls = "j/k: navigate  Enter/Space: select  q: quit";
+        let controls = "j/k: navigate  h/l: ruleset  Enter/Space: select  q: quit";
         let ctrl_x = center_x.saturating_sub(controls.width() as u16 / 2);
         let ctrl_y = area.y + area.height - 2;
         if ctrl_y > y {
@@ -1,4 +1,9 @@
+pub mod diagnostics;
 pub mod game;
 pub mod game_over;
 pub mod high_scores;
+pub mod match_history;
 pub mod menu;
+pub mod mode_setup;
+pub mod replay;
+pub mod settings;
@@ -0,0 +1,293 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
+
+use tetrs_core::ai::difficulty::AiDifficulty;
+use tetrs_core::ai::personality::AiPersonality;
+use tetrs_core::game::garbage::{GarbageKind, GarbagePattern};
+use tetrs_core::game::modifiers::Modifier;
+use tetrs_core::modes::cheat::MAX_LEVEL;
+use tetrs_core::modes::versus::VersusGoal;
+
+use crate::data::config::{Config, PcHint};
+use crate::ui::screens::settings::{on_off, step_option};
+use crate::ui::theme;
+
+/// Sprint lengths to pick from.
+pub const SPRINT_LENGTHS: [u32; 3] = [20, 40, 100];
+/// Seconds between solid rows a versus match can rise at; 0 is off.
+pub const RISING_FLOOR_SECS: [u32; 4] = [0, 30, 20, 10];
+
+/// A toggle for each modifier, shared by every setup screen.
+const MODIFIER_ITEMS: [SetupItem; 6] = [
+    SetupItem::Modifier(Modifier::NoHold),
+    SetupItem::Modifier(Modifier::NoGhost),
+    SetupItem::Modifier(Modifier::Invisible),
+    SetupItem::Modifier(Modifier::DoubleGravity),
+    SetupItem::Modifier(Modifier::Mirror),
+    SetupItem::Modifier(Modifier::MirrorView),
+];
+
+/// Mode whose options are being set before it starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupMode {
+    Sprint,
+    Endless,
+    Zen,
+    Versus,
+}
+
+impl SetupMode {
+    pub fn title(self) -> &'static str {
+        match self {
+            SetupMode::Sprint => "SPRINT",
+            SetupMode::Endless => "ENDLESS MARATHON",
+            SetupMode::Zen => "ZEN PRACTICE",
+            SetupMode::Versus => "VERSUS AI",
+        }
+    }
+
+    /// Rows on the setup screen: the mode's own options, the modifiers,
+    /// then the one that starts the game.
+    pub fn items(self) -> Vec<SetupItem> {
+        let options: &[SetupItem] = match self {
+            SetupMode::Sprint => &[SetupItem::SprintLines],
+            SetupMode::Endless => &[SetupItem::EndlessLevel],
+            SetupMode::Zen => &[SetupItem::ZenLevel, SetupItem::PcHint],
+            SetupMode::Versus => &[
+                SetupItem::Difficulty,
+                SetupItem::Personality,
+                SetupItem::FairBag,
+                SetupItem::Goal,
+                SetupItem::Garbage,
+                SetupItem::GarbageKind,
+                SetupItem::RisingFloor,
+                SetupItem::Mercy,
+                SetupItem::AiPlan,
+            ],
+        };
+        options
+            .iter()
+            .copied()
+            .chain(MODIFIER_ITEMS)
+            .chain([SetupItem::Start])
+            .collect()
+    }
+}
+
+/// A single row on a mode's setup screen. Choices are kept in the config
+/// so the next game starts the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupItem {
+    SprintLines,
+    EndlessLevel,
+    ZenLevel,
+    PcHint,
+    Difficulty,
+    Personality,
+    FairBag,
+    Goal,
+    Garbage,
+    GarbageKind,
+    RisingFloor,
+    Mercy,
+    AiPlan,
+    Modifier(Modifier),
+    Start,
+}
+
+impl SetupItem {
+    pub fn label(self) -> &'static str {
+        match self {
+            SetupItem::SprintLines => "Lines",
+            SetupItem::EndlessLevel => "Starting Level",
+            SetupItem::ZenLevel => "Gravity Level",
+            SetupItem::PcHint => "PC Hint",
+            SetupItem::Difficulty => "Difficulty",
+            SetupItem::Personality => "AI Style",
+            SetupItem::FairBag => "Fair Bag",
+            SetupItem::Goal => "Win By",
+            SetupItem::Garbage => "Garbage",
+            SetupItem::GarbageKind => "Garbage Type",
+            SetupItem::RisingFloor => "Rising Floor",
+            SetupItem::Mercy => "Mercy Rule",
+            SetupItem::AiPlan => "Show AI Plan",
+            SetupItem::Modifier(modifier) => modifier.name(),
+            SetupItem::Start => "Start",
+        }
+    }
+
+    pub fn description(self, config: &Config) -> &'static str {
+        match self {
+            SetupItem::SprintLines => "Lines to clear; each length has its own records",
+            SetupItem::EndlessLevel => "Start faster; level n is still reached at 10n lines",
+            SetupItem::ZenLevel => "Gravity stays at this level the whole session",
+            SetupItem::PcHint => config.zen_pc_hint.description(),
+            SetupItem::Difficulty => config.versus_difficulty.description(),
+            SetupItem::Personality => config.versus_personality.description(),
+            SetupItem::FairBag => "Both boards get the same pieces in the same order",
+            SetupItem::Goal => config.versus_goal.description(),
+            SetupItem::Garbage => "Where the holes in incoming garbage lines go",
+            SetupItem::GarbageKind => match config.garbage_kind {
+                GarbageKind::Standard => "One hole in every garbage line",
+                GarbageKind::Cheese => "Two holes per line, for a messier dig",
+                GarbageKind::Bomb => "A bomb beside each hole blasts the cells around it",
+            },
+            SetupItem::RisingFloor => "Unclearable rows rise under both boards on a timer",
+            SetupItem::Mercy => "On Easy, garbage waits up to 3s while your stack is near the top",
+            SetupItem::AiPlan => "Outline where the AI is about to put its piece",
+            SetupItem::Modifier(modifier) => modifier.description(),
+            SetupItem::Start => "Play with these options",
+        }
+    }
+
+    /// Current value as display text, or None for the start row.
+    pub fn value_text(self, config: &Config) -> Option<String> {
+        match self {
+            SetupItem::SprintLines => Some(config.sprint_lines.to_string()),
+            SetupItem::EndlessLevel => Some(config.endless_level.to_string()),
+            SetupItem::ZenLevel => Some(config.zen_level.to_string()),
+            SetupItem::PcHint => Some(config.zen_pc_hint.label().to_string()),
+            SetupItem::Difficulty => Some(config.versus_difficulty.name().to_string()),
+            SetupItem::Personality => Some(config.versus_personality.name().to_string()),
+            SetupItem::FairBag => Some(on_off(config.versus_fair_bag)),
+            SetupItem::Goal => Some(config.versus_goal.name().to_string()),
+            SetupItem::Garbage => Some(config.garbage_pattern.name().to_string()),
+            SetupItem::GarbageKind => Some(config.garbage_kind.name().to_string()),
+            SetupItem::Mercy => Some(on_off(config.versus_mercy)),
+            SetupItem::RisingFloor => Some(match config.rising_floor {
+                0 => "Off".to_string(),
+                secs => format!("Every {}s", secs),
+            }),
+            SetupItem::AiPlan => Some(on_off(config.show_ai_plan)),
+            SetupItem::Modifier(modifier) => Some(on_off(config.modifiers.contains(modifier))),
+            SetupItem::Start => None,
+        }
+    }
+
+    /// Step the value left (-1) or right (+1).
+    pub fn adjust(self, config: &mut Config, delta: i64) {
+        let levels: Vec<u32> = (0..=MAX_LEVEL).collect();
+        match self {
+            SetupItem::SprintLines => {
+                config.sprint_lines = step_option(&SPRINT_LENGTHS, config.sprint_lines, delta)
+            }
+            SetupItem::EndlessLevel => {
+                config.endless_level = step_option(&levels, config.endless_level, delta)
+            }
+            SetupItem::ZenLevel => config.zen_level = step_option(&levels, config.zen_level, delta),
+            SetupItem::PcHint => {
+                config.zen_pc_hint = step_option(&PcHint::ALL, config.zen_pc_hint, delta)
+            }
+            SetupItem::Difficulty => {
+                config.versus_difficulty =
+                    step_option(&AiDifficulty::ALL, config.versus_difficulty, delta)
+            }
+            SetupItem::Personality => {
+                config.versus_personality =
+                    step_option(&AiPersonality::ALL, config.versus_personality, delta)
+            }
+            SetupItem::FairBag => {
+                config.versus_fair_bag = step_option(&[false, true], config.versus_fair_bag, delta)
+            }
+            SetupItem::Goal => {
+                config.versus_goal = step_option(&VersusGoal::ALL, config.versus_goal, delta)
+            }
+            SetupItem::Garbage => {
+                config.garbage_pattern =
+                    step_option(&GarbagePattern::ALL, config.garbage_pattern, delta)
+            }
+            SetupItem::GarbageKind => {
+                config.garbage_kind = step_option(&GarbageKind::ALL, config.garbage_kind, delta)
+            }
+            SetupItem::RisingFloor => {
+                config.rising_floor = step_option(&RISING_FLOOR_SECS, config.rising_floor, delta)
+            }
+            SetupItem::Mercy => {
+                config.versus_mercy = step_option(&[false, true], config.versus_mercy, delta)
+            }
+            SetupItem::AiPlan => {
+                config.show_ai_plan = step_option(&[false, true], config.show_ai_plan, delta)
+            }
+            SetupItem::Modifier(modifier) => {
+                let on = step_option(&[false, true], config.modifiers.contains(modifier), delta);
+                config.modifiers.set(modifier, on);
+            }
+            SetupItem::Start => {}
+        }
+    }
+}
+
+/// Options screen shown between the menu and the start of a game.
+pub struct ModeSetupScreen<'a> {
+    pub mode: SetupMode,
+    pub config: &'a Config,
+    pub selected: usize,
+}
+
+impl<'a> Widget for ModeSetupScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear background
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let center_x = area.x + area.width / 2;
+        // Drop the blank rows between options if space is short
+        let rows = self.mode.items().len() as u16;
+        let spacing = if rows * 2 + 8 <= area.height { 1 } else { 0 };
+        let content_h = rows * (1 + spacing) + 4;
+        let top = (area.height / 4).min(area.height.saturating_sub(content_h + 2) / 2);
+        let mut y = area.y + top;
+
+        let title = self.mode.title();
+        let tx = center_x.saturating_sub(title.width() as u16 / 2);
+        buf.set_string(tx, y, title, theme::title_style());
+        y += 3;
+
+        for (i, item) in self.mode.items().iter().enumerate() {
+            if y + 1 >= area.y + area.height {
+                break;
+            }
+
+            let is_selected = i == self.selected;
+            let cursor = if is_selected { " ▸ " } else { "   " };
+            let label_style = if is_selected {
+                theme::menu_selected_style()
+            } else {
+                theme::menu_item_style()
+            };
+
+            let x = center_x.saturating_sub(15);
+            buf.set_string(x, y, format!("{}{}", cursor, item.label()), label_style);
+
+            if let Some(value) = item.value_text(self.config) {
+                let value = if is_selected {
+                    format!("◂ {} ▸", value)
+                } else {
+                    format!("  {}  ", value)
+                };
+                buf.set_string(x + 20, y, &value, theme::stat_value_style());
+            }
+
+            if is_selected {
+                y += 1;
+                let desc = item.description(self.config);
+                let dx = center_x.saturating_sub(desc.width() as u16 / 2);
+                buf.set_string(dx, y, desc, theme::menu_desc_style());
+            }
+            y += 1 + spacing;
+        }
+
+        let controls = "j/k: navigate  h/l: change  Enter: start  Esc: back";
+        let cx = center_x.saturating_sub(controls.width() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        if cy > y {
+            buf.set_string(cx, cy, controls, theme::menu_desc_style());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
+
+use tetrs_core::modes::instant_replay::ReplayFrame;
+
+use crate::ui::effects::Effects;
+use crate::ui::layout::SingleLayout;
+use crate::ui::theme;
+use crate::ui::widgets::board::BoardWidget;
+use crate::ui::widgets::hold_box::HoldBoxWidget;
+use crate::ui::widgets::next_queue::NextQueueWidget;
+
+/// The last seconds before a top out played back on the usual layout,
+/// before the results.
+pub struct InstantReplayScreen<'a> {
+    pub frame: &'a ReplayFrame,
+    pub layout: &'a SingleLayout,
+    /// Time left until the top out.
+    pub remaining: Duration,
+    pub show_ghost: bool,
+    pub mirrored: bool,
+}
+
+impl<'a> Widget for InstantReplayScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.y..area.y + area.height {
+            buf.set_string(
+                area.x,
+                y,
+                " ".repeat(area.width as usize),
+                Style::default().bg(theme::BG_COLOR),
+            );
+        }
+
+        HoldBoxWidget {
+            piece: self.frame.hold,
+            available: true,
+        }
+        .render(self.layout.hold, buf);
+        BoardWidget {
+            board: &self.frame.board,
+            current_piece: self.frame.piece.as_ref(),
+            effects: &Effects::new(),
+            show_grid: true,
+            show_ghost: self.show_ghost,
+            hide_stack: false,
+            zone_lines: 0,
+            ghost_heights: None,
+            guide: None,
+            pc_plan: None,
+            mirrored: self.mirrored,
+            spawn_preview: None,
+            hold_ghost: None,
+        }
+        .render(self.layout.board, buf);
+        NextQueueWidget {
+            pieces: self.frame.next.clone(),
+        }
+        .render(self.layout.next, buf);
+
+        let side = self.layout.left_sidebar;
+        if side.width >= 10 && side.height >= 6 {
+            let title_style = Style::default()
+                .fg(theme::DANGER_COLOR)
+                .add_modifier(Modifier::BOLD);
+            buf.set_string(side.x, side.y, "REPLAY", title_style);
+            let countdown = format!("-{:.1}s", self.remaining.as_secs_f64());
+            buf.set_string(side.x, side.y + 2, &countdown, theme::stat_value_style());
+            if self.frame.garbage > 0 {
+                let incoming = format!("{} incoming", self.frame.garbage);
+                buf.set_string(side.x, side.y + 4, &incoming, theme::stat_label_style());
+            }
+        }
+
+        let controls = self.layout.controls;
+        let hint = "h/l: seek  Enter: results";
+        if controls.height > 1 {
+            let x = controls.x + controls.width.saturating_sub(hint.width() as u16) / 2;
+            buf.set_string(x, controls.y + 1, hint, theme::menu_desc_style());
+        }
+    }
+}
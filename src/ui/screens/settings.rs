@@ -0,0 +1,403 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
+
+use tetrs_core::game::garbage::GarbagePattern;
+use tetrs_core::game::locking::LockResetMode;
+use tetrs_core::game::ruleset::{RulesetPreset, SpinRule};
+use tetrs_core::game::srs::RotationSystem;
+
+use crate::data::config::{Config, FpsCap};
+use crate::input::keybinds::KeybindPreset;
+use crate::ui::layout::Orientation;
+use crate::ui::theme::{self, BlockSkin, Palette, PieceFill};
+
+/// Lock delay choices in 50 ms steps, up to 2 s.
+const LOCK_DELAY_STEPS: u64 = 40;
+
+/// Effect strengths to pick from, in percent.
+const PERCENT_STEPS: [u32; 5] = [0, 25, 50, 75, 100];
+
+/// A single row on the settings screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingItem {
+    Das,
+    Arr,
+    SoftDropArr,
+    RestartHold,
+    ConfirmQuit,
+    Keybinds,
+    FrameRate,
+    Shake,
+    Flashes,
+    DangerPulse,
+    DangerSting,
+    Announce,
+    Layout,
+    Ruleset,
+    Rotation,
+    LockDelay,
+    LockReset,
+    Spins,
+    HoldGhost,
+    Garbage,
+    Surge,
+    Zone,
+    Palette,
+    BlockSkin,
+    PieceFill,
+    Diagnostics,
+    Back,
+}
+
+impl SettingItem {
+    pub const ALL: [SettingItem; 27] = [
+        SettingItem::Das,
+        SettingItem::Arr,
+        SettingItem::SoftDropArr,
+        SettingItem::RestartHold,
+        SettingItem::ConfirmQuit,
+        SettingItem::Keybinds,
+        SettingItem::FrameRate,
+        SettingItem::Shake,
+        SettingItem::Flashes,
+        SettingItem::DangerPulse,
+        SettingItem::DangerSting,
+        SettingItem::Announce,
+        SettingItem::Layout,
+        SettingItem::Ruleset,
+        SettingItem::Rotation,
+        SettingItem::LockDelay,
+        SettingItem::LockReset,
+        SettingItem::Spins,
+        SettingItem::HoldGhost,
+        SettingItem::Garbage,
+        SettingItem::Surge,
+        SettingItem::Zone,
+        SettingItem::Palette,
+        SettingItem::BlockSkin,
+        SettingItem::PieceFill,
+        SettingItem::Diagnostics,
+        SettingItem::Back,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingItem::Das => "DAS",
+            SettingItem::Arr => "ARR",
+            SettingItem::SoftDropArr => "Soft Drop ARR",
+            SettingItem::RestartHold => "Restart Hold",
+            SettingItem::ConfirmQuit => "Confirm Quit",
+            SettingItem::Keybinds => "Controls",
+            SettingItem::FrameRate => "Frame Rate",
+            SettingItem::Shake => "Screen Shake",
+            SettingItem::Flashes => "Flashes",
+            SettingItem::DangerPulse => "Danger Pulse",
+            SettingItem::DangerSting => "Danger Sting",
+            SettingItem::Announce => "Announcements",
+            SettingItem::Layout => "Layout",
+            SettingItem::Ruleset => "Ruleset",
+            SettingItem::Rotation => "Rotation",
+            SettingItem::LockDelay => "Lock Delay",
+            SettingItem::LockReset => "Lock Reset",
+            SettingItem::Spins => "Spins",
+            SettingItem::HoldGhost => "Hold Ghost",
+            SettingItem::Garbage => "Garbage",
+            SettingItem::Surge => "B2B Surge",
+            SettingItem::Zone => "Zone",
+            SettingItem::Palette => "Theme",
+            SettingItem::BlockSkin => "Blocks",
+            SettingItem::PieceFill => "Piece Fill",
+            SettingItem::Diagnostics => "Input Diagnostics",
+            SettingItem::Back => "Back",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            SettingItem::Das => "Delay before a held direction starts repeating",
+            SettingItem::Arr => "Delay between auto-repeat moves (0 = instant)",
+            SettingItem::SoftDropArr => "Delay between soft drop repeats (0 = instant)",
+            SettingItem::RestartHold => "Hold restart this long to use it mid-game (0 = instant)",
+            SettingItem::ConfirmQuit => "Quitting mid-game takes a second press",
+            SettingItem::Keybinds => "Key layout for playing; ? lists every binding",
+            SettingItem::FrameRate => "Frame rate limit during play",
+            SettingItem::Shake => "How hard the board jolts on surges and zone releases",
+            SettingItem::Flashes => "Strength of line clear and board border flashes",
+            SettingItem::DangerPulse => "Red pulse on the board when the stack gets high",
+            SettingItem::DangerSting => "Terminal bell when the stack gets critically high",
+            SettingItem::Announce => "Spawns, clears, garbage and stack height in words",
+            SettingItem::Layout => "Stats beside the board, or under it (Auto: by window shape)",
+            SettingItem::Ruleset => "Randomizer, kicks, lock delay, hold, spins, and attack",
+            SettingItem::Rotation => "Kick table used when rotating",
+            SettingItem::LockDelay => "Time a grounded piece waits before locking",
+            SettingItem::LockReset => "Lock timer restarts on moves (limited or not) or new rows",
+            SettingItem::Spins => "Which pieces can spin, and how spins are detected",
+            SettingItem::HoldGhost => "A second ghost: where the held piece lands if swapped in",
+            SettingItem::Garbage => "Where the holes in incoming garbage lines go",
+            SettingItem::Surge => "Breaking a B2B chain of 4+ sends its length as garbage",
+            SettingItem::Zone => "Line clears charge a meter that stops time (e to use)",
+            SettingItem::Palette => "Piece colors; more unlock as your profile levels up",
+            SettingItem::BlockSkin => "How pieces are drawn; more unlock with profile level",
+            SettingItem::PieceFill => "A pattern per piece, for monochrome terminals",
+            SettingItem::Diagnostics => "Measure input latency and the key events that arrive",
+            SettingItem::Back => "Save and return",
+        }
+    }
+
+    /// Current value as display text, or None for action rows.
+    pub fn value_text(self, config: &Config) -> Option<String> {
+        match self {
+            SettingItem::Das => Some(format!("{} ms", config.das_delay_ms)),
+            SettingItem::Arr => Some(format!("{} ms", config.arr_delay_ms)),
+            SettingItem::SoftDropArr => Some(format!("{} ms", config.sd_arr_delay_ms)),
+            SettingItem::RestartHold => Some(format!("{} ms", config.restart_hold_ms)),
+            SettingItem::ConfirmQuit => Some(on_off(config.confirm_quit)),
+            SettingItem::Keybinds => Some(config.keybind_preset.name().to_string()),
+            SettingItem::FrameRate => Some(config.fps_cap.label().to_string()),
+            SettingItem::Shake => Some(percent(config.shake_percent)),
+            SettingItem::Flashes => Some(percent(config.flash_percent)),
+            SettingItem::DangerPulse => Some(percent(config.danger_pulse_percent)),
+            SettingItem::DangerSting => Some(on_off(config.danger_sting)),
+            SettingItem::Announce => Some(on_off(config.announce)),
+            SettingItem::Layout => Some(config.layout.name().to_string()),
+            SettingItem::Ruleset => Some(config.ruleset.name().to_string()),
+            SettingItem::Rotation => Some(match config.rotation_system {
+                Some(rotation_system) => rotation_system.name().to_string(),
+                None => format!(
+                    "{} (Ruleset)",
+                    config.ruleset.ruleset().rotation_system.name()
+                ),
+            }),
+            SettingItem::LockDelay => Some(match config.lock_delay_ms {
+                Some(ms) => format!("{} ms", ms),
+                None => format!("{} ms (Ruleset)", config.ruleset.ruleset().lock_delay_ms),
+            }),
+            SettingItem::LockReset => Some(match config.lock_reset {
+                Some(mode) => mode.name().to_string(),
+                None => format!("{} (Ruleset)", config.ruleset.ruleset().lock_reset.name()),
+            }),
+            SettingItem::Spins => Some(match config.spin_rule {
+                Some(rule) => rule.name().to_string(),
+                None => format!("{} (Ruleset)", config.ruleset.ruleset().spins.name()),
+            }),
+            SettingItem::HoldGhost => Some(on_off(config.hold_ghost)),
+            SettingItem::Garbage => Some(config.garbage_pattern.name().to_string()),
+            SettingItem::Surge => Some(on_off(config.b2b_surge)),
+            SettingItem::Zone => Some(on_off(config.zone)),
+            SettingItem::Palette => Some(config.palette.name().to_string()),
+            SettingItem::BlockSkin => Some(config.block_skin.name().to_string()),
+            SettingItem::PieceFill => Some(config.piece_fill.name().to_string()),
+            SettingItem::Diagnostics | SettingItem::Back => None,
+        }
+    }
+
+    /// Step the value left (-1) or right (+1). Cosmetics step only through
+    /// those unlocked at the profile `level`.
+    pub fn adjust(self, config: &mut Config, delta: i64, level: u32) {
+        match self {
+            SettingItem::Das => step_ms(&mut config.das_delay_ms, delta * 5, 0, 500),
+            SettingItem::Arr => step_ms(&mut config.arr_delay_ms, delta, 0, 100),
+            SettingItem::SoftDropArr => step_ms(&mut config.sd_arr_delay_ms, delta, 0, 100),
+            SettingItem::RestartHold => step_ms(&mut config.restart_hold_ms, delta * 100, 0, 1000),
+            SettingItem::ConfirmQuit => {
+                config.confirm_quit = step_option(&[false, true], config.confirm_quit, delta)
+            }
+            SettingItem::Keybinds => {
+                config.keybind_preset =
+                    step_option(&KeybindPreset::ALL, config.keybind_preset, delta)
+            }
+            SettingItem::FrameRate => {
+                config.fps_cap = step_option(&FpsCap::ALL, config.fps_cap, delta)
+            }
+            SettingItem::Shake => {
+                config.shake_percent = step_option(&PERCENT_STEPS, config.shake_percent, delta)
+            }
+            SettingItem::Flashes => {
+                config.flash_percent = step_option(&PERCENT_STEPS, config.flash_percent, delta)
+            }
+            SettingItem::DangerPulse => {
+                config.danger_pulse_percent =
+                    step_option(&PERCENT_STEPS, config.danger_pulse_percent, delta)
+            }
+            SettingItem::DangerSting => {
+                config.danger_sting = step_option(&[false, true], config.danger_sting, delta)
+            }
+            SettingItem::Announce => {
+                config.announce = step_option(&[false, true], config.announce, delta)
+            }
+            SettingItem::Layout => {
+                config.layout = step_option(&Orientation::ALL, config.layout, delta)
+            }
+            SettingItem::Ruleset => {
+                config.ruleset = step_option(&RulesetPreset::ALL, config.ruleset, delta)
+            }
+            SettingItem::Rotation => {
+                let options: Vec<_> = std::iter::once(None)
+                    .chain(RotationSystem::ALL.map(Some))
+                    .collect();
+                config.rotation_system = step_option(&options, config.rotation_system, delta)
+            }
+            SettingItem::LockDelay => {
+                let options: Vec<_> = std::iter::once(None)
+                    .chain((0..=LOCK_DELAY_STEPS).map(|i| Some(i * 50)))
+                    .collect();
+                config.lock_delay_ms = step_option(&options, config.lock_delay_ms, delta)
+            }
+            SettingItem::LockReset => {
+                let options: Vec<_> = std::iter::once(None)
+                    .chain(LockResetMode::ALL.map(Some))
+                    .collect();
+                config.lock_reset = step_option(&options, config.lock_reset, delta)
+            }
+            SettingItem::Spins => {
+                let options: Vec<_> = std::iter::once(None)
+                    .chain(SpinRule::ALL.map(Some))
+                    .collect();
+                config.spin_rule = step_option(&options, config.spin_rule, delta)
+            }
+            SettingItem::HoldGhost => {
+                config.hold_ghost = step_option(&[false, true], config.hold_ghost, delta)
+            }
+            SettingItem::Garbage => {
+                config.garbage_pattern =
+                    step_option(&GarbagePattern::ALL, config.garbage_pattern, delta)
+            }
+            SettingItem::Surge => {
+                config.b2b_surge = step_option(&[false, true], config.b2b_surge, delta)
+            }
+            SettingItem::Zone => config.zone = step_option(&[false, true], config.zone, delta),
+            SettingItem::Palette => {
+                let options: Vec<_> = Palette::ALL
+                    .into_iter()
+                    .filter(|p| p.unlock_level() <= level)
+                    .collect();
+                config.palette = step_option(&options, config.palette, delta)
+            }
+            SettingItem::BlockSkin => {
+                let options: Vec<_> = BlockSkin::ALL
+                    .into_iter()
+                    .filter(|s| s.unlock_level() <= level)
+                    .collect();
+                config.block_skin = step_option(&options, config.block_skin, delta)
+            }
+            SettingItem::PieceFill => {
+                config.piece_fill = step_option(&PieceFill::ALL, config.piece_fill, delta)
+            }
+            SettingItem::Diagnostics | SettingItem::Back => {}
+        }
+    }
+}
+
+/// Step through a list of options, stopping at either end.
+pub fn step_option<T: Copy + PartialEq>(options: &[T], current: T, delta: i64) -> T {
+    let index = options.iter().position(|&o| o == current).unwrap_or(0) as i64;
+    let stepped = (index + delta).clamp(0, options.len() as i64 - 1);
+    options[stepped as usize]
+}
+
+/// Effect strength as display text, with 0 spelled out as off.
+fn percent(value: u32) -> String {
+    if value == 0 {
+        "Off".to_string()
+    } else {
+        format!("{}%", value)
+    }
+}
+
+pub fn on_off(value: bool) -> String {
+    if value { "On" } else { "Off" }.to_string()
+}
+
+fn step_ms(value: &mut u64, delta: i64, min: u64, max: u64) {
+    let stepped = (*value as i64 + delta).clamp(min as i64, max as i64);
+    *value = stepped as u64;
+}
+
+/// Settings screen widget, shared by the main menu and the pause menu.
+pub struct SettingsScreen<'a> {
+    pub config: &'a Config,
+    pub selected: usize,
+    /// Input mode in use, which the terminal decides.
+    pub has_key_release: bool,
+}
+
+impl<'a> Widget for SettingsScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Clear background
+        for y in area.y..area.y + area.height {
+            for x in area.x..area.x + area.width {
+                buf.set_string(x, y, " ", Style::default().bg(theme::BG_COLOR));
+            }
+        }
+
+        let center_x = area.x + area.width / 2;
+        // Rows, the selected row's description, and the title; drop the
+        // blank rows between settings and move up if space is short
+        let rows = SettingItem::ALL.len() as u16;
+        let spaced_h = rows * 2 + 4;
+        let spacing = if spaced_h + 4 <= area.height { 1 } else { 0 };
+        let content_h = rows * (1 + spacing) + 4;
+        let top = (area.height / 4).min(area.height.saturating_sub(content_h + 2) / 2);
+        let mut y = area.y + top;
+
+        let title = "SETTINGS";
+        let tx = center_x.saturating_sub(title.width() as u16 / 2);
+        buf.set_string(tx, y, title, theme::title_style());
+        let mode = if self.has_key_release {
+            "Input: key release events"
+        } else {
+            "Input: key repeat, holds guessed"
+        };
+        let mx = center_x.saturating_sub(mode.width() as u16 / 2);
+        buf.set_string(mx, y + 1, mode, theme::menu_desc_style());
+        y += 3;
+
+        // Scroll so the selected row and its description stay on screen
+        let fit = ((area.y + area.height).saturating_sub(y + 2) / (1 + spacing)) as usize;
+        let first = (self.selected + 1).saturating_sub(fit.max(1));
+
+        for (i, item) in SettingItem::ALL.iter().enumerate().skip(first) {
+            if y + 1 >= area.y + area.height {
+                break;
+            }
+
+            let is_selected = i == self.selected;
+            let cursor = if is_selected { " ▸ " } else { "   " };
+            let label_style = if is_selected {
+                theme::menu_selected_style()
+            } else {
+                theme::menu_item_style()
+            };
+
+            let x = center_x.saturating_sub(15);
+            buf.set_string(x, y, format!("{}{}", cursor, item.label()), label_style);
+
+            if let Some(value) = item.value_text(self.config) {
+                let value = if is_selected {
+                    format!("◂ {} ▸", value)
+                } else {
+                    format!("  {}  ", value)
+                };
+                buf.set_string(x + 20, y, &value, theme::stat_value_style());
+            }
+
+            if is_selected {
+                y += 1;
+                let desc = item.description();
+                let dx = center_x.saturating_sub(desc.width() as u16 / 2);
+                buf.set_string(dx, y, desc, theme::menu_desc_style());
+            }
+            y += 1 + spacing;
+        }
+
+        let controls = "j/k: navigate  h/l: adjust  Esc: back";
+        let cx = center_x.saturating_sub(controls.width() as u16 / 2);
+        let cy = area.y + area.height - 2;
+        if cy > y {
+            buf.set_string(cx, cy, controls, theme::menu_desc_style());
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
 use ratatui::style::{Color, Modifier, Style};
+use serde::{Deserialize, Serialize};
 
-use crate::game::clear::ClearType;
+use tetrs_core::game::clear::ClearType;
+use tetrs_core::game::piece::PieceType;
 
 // Block characters
 pub const BLOCK_FULL: &str = "██";
@@ -39,13 +43,329 @@ pub const TEXT_BRIGHT: Color = Color::Rgb(240, 245, 255);
 pub const GHOST_COLOR: Color = Color::Rgb(60, 62, 70);
 pub const GARBAGE_COLOR: Color = Color::Rgb(100, 100, 110);
 pub const GARBAGE_DARK: Color = Color::Rgb(65, 65, 75);
+/// Solid garbage, darker than any clearable row.
+pub const SOLID_COLOR: Color = Color::Rgb(45, 45, 52);
+pub const BOMB_COLOR: Color = Color::Rgb(255, 110, 50);
 pub const DANGER_COLOR: Color = Color::Rgb(180, 40, 40);
+/// First sign of a high stack, before it turns red.
+pub const DANGER_WARN_COLOR: Color = Color::Rgb(210, 120, 40);
+/// Warnings that need to catch the eye, like a blocked spawn.
+pub const DANGER_BRIGHT: Color = Color::Rgb(255, 60, 60);
 pub const GRID_DOT_COLOR: Color = Color::Rgb(30, 32, 40);
 
 // Garbage bar colors
 pub const GARBAGE_BAR_COLOR: Color = Color::Rgb(200, 50, 50);
 pub const GARBAGE_BAR_BG: Color = Color::Rgb(35, 35, 45);
 
+// Back-to-back surge color, also the hot end of long chain flames
+pub const SURGE_COLOR: Color = Color::Rgb(255, 150, 40);
+pub const B2B_WARM_COLOR: Color = Color::Rgb(230, 190, 70);
+
+// Racing a ghost run
+pub const GHOST_RUN_COLOR: Color = Color::Rgb(70, 60, 110);
+pub const PACE_AHEAD_COLOR: Color = Color::Rgb(80, 220, 120);
+pub const PACE_BEHIND_COLOR: Color = Color::Rgb(230, 90, 80);
+
+// Challenge modifier tags on high score entries
+pub const BADGE_COLOR: Color = Color::Rgb(230, 120, 255);
+
+// Battle royale targeting
+pub const TARGET_COLOR: Color = Color::Rgb(255, 90, 90);
+pub const ATTACKER_COLOR: Color = Color::Rgb(255, 170, 60);
+
+// Zone colors
+pub const ZONE_COLOR: Color = Color::Rgb(90, 160, 255);
+pub const ZONE_BRIGHT: Color = Color::Rgb(190, 225, 255);
+pub const ZONE_LINE_COLOR: Color = Color::Rgb(220, 230, 245);
+
+// Cosmetics unlocked by profile level
+/// Set of piece colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Guideline,
+    Pastel,
+    Neon,
+    Retro,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 4] = [
+        Palette::Guideline,
+        Palette::Pastel,
+        Palette::Neon,
+        Palette::Retro,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::Guideline => "Guideline",
+            Palette::Pastel => "Pastel",
+            Palette::Neon => "Neon",
+            Palette::Retro => "Retro",
+        }
+    }
+
+    /// Profile level the palette becomes available at.
+    pub fn unlock_level(self) -> u32 {
+        match self {
+            Palette::Guideline => 1,
+            Palette::Pastel => 5,
+            Palette::Neon => 10,
+            Palette::Retro => 15,
+        }
+    }
+}
+
+/// Characters pieces are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BlockSkin {
+    #[default]
+    Solid,
+    Brackets,
+    Bricks,
+}
+
+impl BlockSkin {
+    pub const ALL: [BlockSkin; 3] = [BlockSkin::Solid, BlockSkin::Brackets, BlockSkin::Bricks];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockSkin::Solid => "Solid",
+            BlockSkin::Brackets => "Brackets",
+            BlockSkin::Bricks => "Bricks",
+        }
+    }
+
+    pub fn glyph(self) -> &'static str {
+        match self {
+            BlockSkin::Solid => BLOCK_FULL,
+            BlockSkin::Brackets => "[]",
+            BlockSkin::Bricks => "▇▇",
+        }
+    }
+
+    /// Profile level the skin becomes available at.
+    pub fn unlock_level(self) -> u32 {
+        match self {
+            BlockSkin::Solid => 1,
+            BlockSkin::Brackets => 3,
+            BlockSkin::Bricks => 8,
+        }
+    }
+}
+
+/// How pieces are told apart: by color, by a fill pattern for each piece
+/// type as well, or by pattern alone for monochrome terminals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PieceFill {
+    #[default]
+    Color,
+    Patterns,
+    Monochrome,
+}
+
+impl PieceFill {
+    pub const ALL: [PieceFill; 3] = [PieceFill::Color, PieceFill::Patterns, PieceFill::Monochrome];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceFill::Color => "Color",
+            PieceFill::Patterns => "Patterns",
+            PieceFill::Monochrome => "Monochrome",
+        }
+    }
+
+    fn has_patterns(self) -> bool {
+        self != PieceFill::Color
+    }
+}
+
+// Cosmetics in use, set once from the config rather than threaded through
+// every widget
+static PALETTE: AtomicU8 = AtomicU8::new(0);
+static SKIN: AtomicU8 = AtomicU8::new(0);
+static FILL: AtomicU8 = AtomicU8::new(0);
+
+/// Switch the palette and block skin everything is drawn with.
+pub fn set_cosmetics(palette: Palette, skin: BlockSkin) {
+    let palette = Palette::ALL.iter().position(|&p| p == palette).unwrap_or(0);
+    let skin = BlockSkin::ALL.iter().position(|&s| s == skin).unwrap_or(0);
+    PALETTE.store(palette as u8, Ordering::Relaxed);
+    SKIN.store(skin as u8, Ordering::Relaxed);
+}
+
+/// Switch how pieces are told apart. Not a cosmetic: it's never locked.
+pub fn set_piece_fill(fill: PieceFill) {
+    let fill = PieceFill::ALL.iter().position(|&f| f == fill).unwrap_or(0);
+    FILL.store(fill as u8, Ordering::Relaxed);
+}
+
+pub fn piece_fill() -> PieceFill {
+    PieceFill::ALL[FILL.load(Ordering::Relaxed) as usize]
+}
+
+/// Characters a piece is drawn with: the block skin's, or one pattern per
+/// piece type when patterns are on.
+pub fn piece_glyph(piece_type: PieceType) -> &'static str {
+    if !piece_fill().has_patterns() {
+        return block_skin().glyph();
+    }
+    match piece_type {
+        PieceType::I => "██",
+        PieceType::O => "[]",
+        PieceType::T => "<>",
+        PieceType::S => "//",
+        PieceType::Z => "\\\\",
+        PieceType::J => "((",
+        PieceType::L => "))",
+    }
+}
+
+/// Characters garbage is drawn with, a hatch unlike any piece's pattern
+/// when patterns are on.
+pub fn garbage_glyph() -> &'static str {
+    if piece_fill().has_patterns() {
+        "##"
+    } else {
+        BLOCK_GARBAGE
+    }
+}
+
+/// Characters solid garbage is drawn with, bars unlike clearable garbage's
+/// hatch when patterns are on.
+pub fn solid_glyph() -> &'static str {
+    if piece_fill().has_patterns() {
+        "=="
+    } else {
+        BLOCK_FULL
+    }
+}
+
+/// Characters a bomb is drawn with, so it stands out from the garbage
+/// around it without its color.
+pub fn bomb_glyph() -> &'static str {
+    if piece_fill().has_patterns() {
+        "**"
+    } else {
+        BLOCK_FULL
+    }
+}
+
+pub fn palette() -> Palette {
+    Palette::ALL[PALETTE.load(Ordering::Relaxed) as usize]
+}
+
+pub fn block_skin() -> BlockSkin {
+    BlockSkin::ALL[SKIN.load(Ordering::Relaxed) as usize]
+}
+
+/// Scale a color toward white (positive `t`) or black (negative `t`).
+fn shade(color: Color, t: f32) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    let shade = |c: u8| {
+        if t >= 0.0 {
+            (c as f32 + (255.0 - c as f32) * t) as u8
+        } else {
+            (c as f32 * (1.0 + t)) as u8
+        }
+    };
+    Color::Rgb(shade(r), shade(g), shade(b))
+}
+
+// Piece colors
+/// Display colors for each tetromino, kept out of the engine crate.
+pub trait PieceColors {
+    fn color(self) -> Color;
+    fn bright_color(self) -> Color;
+    fn dim_color(self) -> Color;
+}
+
+impl PieceColors for PieceType {
+    fn color(self) -> Color {
+        if piece_fill() == PieceFill::Monochrome {
+            return TEXT_COLOR;
+        }
+        match palette() {
+            Palette::Guideline => {}
+            Palette::Pastel => {
+                return match self {
+                    PieceType::I => Color::Rgb(160, 225, 220),
+                    PieceType::O => Color::Rgb(250, 235, 160),
+                    PieceType::T => Color::Rgb(210, 170, 230),
+                    PieceType::S => Color::Rgb(180, 225, 160),
+                    PieceType::Z => Color::Rgb(240, 160, 160),
+                    PieceType::J => Color::Rgb(160, 180, 240),
+                    PieceType::L => Color::Rgb(245, 200, 150),
+                }
+            }
+            Palette::Neon => {
+                return match self {
+                    PieceType::I => Color::Rgb(0, 255, 255),
+                    PieceType::O => Color::Rgb(255, 255, 0),
+                    PieceType::T => Color::Rgb(255, 0, 255),
+                    PieceType::S => Color::Rgb(57, 255, 20),
+                    PieceType::Z => Color::Rgb(255, 20, 60),
+                    PieceType::J => Color::Rgb(30, 90, 255),
+                    PieceType::L => Color::Rgb(255, 140, 0),
+                }
+            }
+            Palette::Retro => {
+                return match self {
+                    PieceType::I => Color::Rgb(200, 230, 120),
+                    PieceType::O => Color::Rgb(170, 210, 90),
+                    PieceType::T => Color::Rgb(140, 190, 70),
+                    PieceType::S => Color::Rgb(190, 220, 100),
+                    PieceType::Z => Color::Rgb(120, 170, 60),
+                    PieceType::J => Color::Rgb(100, 150, 50),
+                    PieceType::L => Color::Rgb(160, 200, 80),
+                }
+            }
+        }
+        match self {
+            PieceType::I => Color::Rgb(101, 219, 200), // Cyan
+            PieceType::O => Color::Rgb(242, 215, 76),  // Yellow
+            PieceType::T => Color::Rgb(193, 50, 208),  // Purple
+            PieceType::S => Color::Rgb(122, 205, 68),  // Green
+            PieceType::Z => Color::Rgb(216, 58, 40),   // Red
+            PieceType::J => Color::Rgb(51, 88, 221),   // Blue
+            PieceType::L => Color::Rgb(237, 169, 63),  // Orange
+        }
+    }
+
+    fn bright_color(self) -> Color {
+        if palette() != Palette::Guideline || piece_fill() == PieceFill::Monochrome {
+            return shade(self.color(), 0.35);
+        }
+        match self {
+            PieceType::I => Color::Rgb(140, 240, 225),
+            PieceType::O => Color::Rgb(255, 235, 120),
+            PieceType::T => Color::Rgb(225, 100, 240),
+            PieceType::S => Color::Rgb(160, 235, 110),
+            PieceType::Z => Color::Rgb(245, 100, 85),
+            PieceType::J => Color::Rgb(100, 135, 245),
+            PieceType::L => Color::Rgb(255, 200, 110),
+        }
+    }
+
+    fn dim_color(self) -> Color {
+        if palette() != Palette::Guideline || piece_fill() == PieceFill::Monochrome {
+            return shade(self.color(), -0.5);
+        }
+        match self {
+            PieceType::I => Color::Rgb(50, 110, 100),
+            PieceType::O => Color::Rgb(121, 107, 38),
+            PieceType::T => Color::Rgb(96, 25, 104),
+            PieceType::S => Color::Rgb(61, 102, 34),
+            PieceType::Z => Color::Rgb(108, 29, 20),
+            PieceType::J => Color::Rgb(25, 44, 110),
+            PieceType::L => Color::Rgb(118, 84, 31),
+        }
+    }
+}
+
 // Level colors (progression)
 pub fn level_color(level: u32) -> Color {
     match level {
@@ -1,57 +1,68 @@
+use std::collections::VecDeque;
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Widget;
 
-use crate::game::clear::ClearType;
-use crate::game::scoring::Scoring;
+use tetrs_core::game::clear::ClearType;
+
+use crate::ui::effects::ActionText;
 use crate::ui::theme;
+use crate::ui::widgets::board::blend_color;
 
-/// Widget that shows the last clear type, combo, and BTB text.
+/// Widget that shows recent clears stacked newest first: the newest with
+/// its BTB and combo text, older ones as a single fading line beneath.
 pub struct ActionTextWidget<'a> {
-    pub clear_type: Option<&'a ClearType>,
-    pub scoring: &'a Scoring,
-    pub opacity: f32,
+    pub texts: &'a VecDeque<ActionText>,
 }
 
 impl<'a> Widget for ActionTextWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if self.opacity <= 0.01 || area.width < 10 || area.height < 1 {
+        if area.width < 10 || area.height < 1 {
             return;
         }
 
         let x = area.x;
+        let bottom = area.y + area.height;
         let mut y = area.y;
 
-        // BTB indicator
-        if self.scoring.btb > 0 {
-            let btb_text = format!("B2B ×{}", self.scoring.btb);
-            let color = theme::btb_color(self.scoring.btb as u32);
-            let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
-            buf.set_string(x, y, &btb_text, style);
-            y += 1;
-        }
+        for (i, text) in self.texts.iter().enumerate() {
+            if y >= bottom {
+                break;
+            }
+            let fade = |color: Color| {
+                Style::default()
+                    .fg(blend_color(theme::BG_COLOR, color, text.opacity))
+                    .add_modifier(Modifier::BOLD)
+            };
 
-        // Clear type
-        if let Some(clear_type) = self.clear_type {
-            if !matches!(clear_type, ClearType::None) {
-                let text = clear_type.display_name();
-                let color = theme::clear_type_color(clear_type);
-                let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
-                if y < area.y + area.height {
-                    buf.set_string(x, y, text, style);
+            if i == 0 {
+                // BTB indicator
+                if text.btb > 0 {
+                    let btb_text = format!("B2B ×{}", text.btb);
+                    buf.set_string(x, y, &btb_text, fade(theme::btb_color(text.btb as u32)));
+                    y += 1;
+                } else if text.surge > 0 {
+                    let surge_text = format!("SURGE +{}", text.surge);
+                    buf.set_string(x, y, &surge_text, fade(theme::SURGE_COLOR));
                     y += 1;
                 }
             }
-        }
 
-        // Combo
-        if self.scoring.combo > 0 {
-            let combo_text = format!("{} COMBO", self.scoring.combo);
-            let color = theme::combo_color(self.scoring.combo as u32);
-            let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
-            if y < area.y + area.height {
-                buf.set_string(x, y, &combo_text, style);
+            // Clear type
+            if !matches!(text.clear_type, ClearType::None) && y < bottom {
+                let color = theme::clear_type_color(&text.clear_type);
+                buf.set_string(x, y, text.clear_type.display_name(), fade(color));
+                y += 1;
+            }
+
+            // Combo
+            if i == 0 && text.combo > 0 && y < bottom {
+                let combo_text = format!("{} COMBO", text.combo);
+                let color = theme::combo_color(text.combo as u32);
+                buf.set_string(x, y, &combo_text, fade(color));
+                y += 1;
             }
         }
     }
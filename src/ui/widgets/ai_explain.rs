@@ -0,0 +1,118 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use tetrs_core::ai::explain::{Candidate, Explanation};
+use tetrs_core::game::piece::RotationState;
+
+use crate::ui::theme;
+
+/// Overlay size in cells.
+const OVERLAY_W: u16 = 34;
+const OVERLAY_H: u16 = 13;
+/// Columns of the chosen and runner-up values, from the left edge.
+const CHOSEN_X: u16 = 13;
+const RUNNER_UP_X: u16 = 24;
+
+/// Shows one figure of a candidate.
+type Figure = fn(&Candidate) -> String;
+
+/// Rows of the breakdown, top to bottom.
+const ROWS: [(&str, Figure); 9] = [
+    ("Place", placement_text),
+    ("Height", |c| c.features.aggregate_height.to_string()),
+    ("Holes", |c| c.features.holes.to_string()),
+    ("Bumpiness", |c| c.features.bumpiness.to_string()),
+    ("Lines", |c| {
+        let pc = if c.features.perfect_clear { " PC" } else { "" };
+        format!("{}{pc}", c.features.lines_cleared)
+    }),
+    ("Wells", |c| c.features.wells.to_string()),
+    ("Col/Row tr", |c| {
+        let f = &c.features;
+        format!("{}/{}", f.column_transitions, f.row_transitions)
+    }),
+    ("Style bias", |c| format!("{:+.2}", c.bias())),
+    ("Score", |c| format!("{:.2}", c.score)),
+];
+
+/// Debug panel in the top-left corner breaking down the versus AI's last
+/// placement against the runner-up, for tuning the evaluator.
+pub struct AiExplainWidget<'a> {
+    pub explanation: Option<&'a Explanation>,
+}
+
+fn rotation_name(rotation: RotationState) -> &'static str {
+    match rotation {
+        RotationState::R0 => "0",
+        RotationState::R1 => "R",
+        RotationState::R2 => "2",
+        RotationState::R3 => "L",
+    }
+}
+
+fn placement_text(c: &Candidate) -> String {
+    let hold = if c.use_hold { "H:" } else { "" };
+    format!(
+        "{hold}{} {} @{}",
+        c.piece_type.name(),
+        rotation_name(c.rotation),
+        c.column + 1
+    )
+}
+
+impl<'a> Widget for AiExplainWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < OVERLAY_W || area.height < OVERLAY_H {
+            return;
+        }
+
+        let x = area.x;
+        let mut y = area.y;
+        let bg = Style::default().bg(theme::BG_COLOR);
+        for row in y..y + OVERLAY_H {
+            buf.set_string(x, row, " ".repeat(OVERLAY_W as usize), bg);
+        }
+
+        buf.set_string(x + 1, y, "AI DECISION", theme::header_style());
+        y += 1;
+        let sep: String = theme::PANEL_H.repeat(OVERLAY_W as usize);
+        buf.set_string(x, y, &sep, Style::default().fg(theme::PANEL_COLOR));
+        y += 1;
+
+        let Some(explanation) = self.explanation else {
+            buf.set_string(x + 1, y, "No placement yet", theme::menu_desc_style());
+            return;
+        };
+
+        buf.set_string(x + CHOSEN_X, y, "Chosen", theme::stat_label_style());
+        buf.set_string(x + RUNNER_UP_X, y, "Runner-up", theme::stat_label_style());
+        y += 1;
+
+        let chosen = &explanation.chosen;
+        let runner_up = explanation.runner_up.as_ref();
+        for (label, value) in ROWS {
+            let style = if label == "Score" {
+                theme::header_style()
+            } else {
+                theme::stat_value_style()
+            };
+            buf.set_string(x + 1, y, label, theme::stat_label_style());
+            buf.set_string(x + CHOSEN_X, y, value(chosen), style);
+            if let Some(runner_up) = runner_up {
+                buf.set_string(x + RUNNER_UP_X, y, value(runner_up), style);
+            }
+            y += 1;
+        }
+
+        if explanation.mistake {
+            buf.set_string(
+                x + 1,
+                y,
+                "Deliberate mistake",
+                Style::default().fg(theme::DANGER_COLOR),
+            );
+        }
+    }
+}
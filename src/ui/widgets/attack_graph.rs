@@ -0,0 +1,61 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Widget;
+
+use crate::ui::theme;
+
+const SPARK_CHARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+const SENT_COLOR: Color = Color::Rgb(80, 255, 120);
+const RECEIVED_COLOR: Color = Color::Rgb(255, 90, 90);
+
+/// Two-row sparkline of attack sent (top) and garbage received (bottom) over time.
+///
+/// Both rows share one scale so momentum swings are comparable. Timelines
+/// longer than the area are merged into wider buckets to fit.
+pub struct AttackGraphWidget<'a> {
+    pub sent: &'a [u32],
+    pub received: &'a [u32],
+}
+
+impl<'a> Widget for AttackGraphWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height < 2 {
+            return;
+        }
+
+        let width = area.width as usize;
+        let len = self.sent.len().max(self.received.len());
+        let group = len.div_ceil(width).max(1);
+        let sent = merge_buckets(self.sent, len, group);
+        let received = merge_buckets(self.received, len, group);
+        let max = sent.iter().chain(&received).copied().max().unwrap_or(0);
+
+        draw_row(buf, area.x, area.y, &sent, max, SENT_COLOR);
+        draw_row(buf, area.x, area.y + 1, &received, max, RECEIVED_COLOR);
+    }
+}
+
+/// Sum groups of adjacent buckets, padding the shorter timeline to `len`.
+fn merge_buckets(values: &[u32], len: usize, group: usize) -> Vec<u32> {
+    (0..len)
+        .step_by(group)
+        .map(|start| {
+            (start..(start + group).min(len))
+                .map(|i| values.get(i).copied().unwrap_or(0))
+                .sum()
+        })
+        .collect()
+}
+
+fn draw_row(buf: &mut Buffer, x: u16, y: u16, values: &[u32], max: u32, color: Color) {
+    for (i, &v) in values.iter().enumerate() {
+        let (ch, style) = if v == 0 || max == 0 {
+            (SPARK_CHARS[0], Style::default().fg(theme::PANEL_COLOR))
+        } else {
+            let level = (v as usize * (SPARK_CHARS.len() - 1)).div_ceil(max as usize);
+            (SPARK_CHARS[level], Style::default().fg(color))
+        };
+        buf.set_string(x + i as u16, y, ch, style);
+    }
+}
@@ -0,0 +1,75 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Widget;
+
+use crate::ui::theme;
+
+/// Width reserved for bar labels.
+const LABEL_W: u16 = 7;
+/// Width reserved for the count after each bar.
+const COUNT_W: u16 = 5;
+
+/// A single labelled bar.
+pub struct Bar<'a> {
+    pub label: &'a str,
+    pub value: u32,
+    pub color: Color,
+}
+
+/// Horizontal bar chart with a header, one row per bar.
+pub struct BarChartWidget<'a> {
+    pub title: &'a str,
+    pub bars: &'a [Bar<'a>],
+}
+
+impl<'a> Widget for BarChartWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < LABEL_W + COUNT_W + 4 || area.height < 2 {
+            return;
+        }
+
+        let x = area.x;
+        let mut y = area.y;
+
+        buf.set_string(x, y, self.title, theme::header_style());
+        y += 1;
+
+        let bar_w = area.width - LABEL_W - COUNT_W;
+        let max = self.bars.iter().map(|b| b.value).max().unwrap_or(0);
+
+        for bar in self.bars {
+            if y >= area.y + area.height {
+                break;
+            }
+
+            buf.set_string(x, y, bar.label, theme::stat_label_style());
+
+            let filled = if max > 0 {
+                (bar.value as u64 * bar_w as u64).div_ceil(max as u64) as usize
+            } else {
+                0
+            };
+            let track = bar_w as usize - filled;
+            buf.set_string(
+                x + LABEL_W,
+                y,
+                "█".repeat(filled),
+                Style::default().fg(bar.color),
+            );
+            buf.set_string(
+                x + LABEL_W + filled as u16,
+                y,
+                "░".repeat(track),
+                Style::default().fg(theme::PANEL_COLOR),
+            );
+            buf.set_string(
+                x + LABEL_W + bar_w + 1,
+                y,
+                bar.value.to_string(),
+                theme::stat_value_style(),
+            );
+            y += 1;
+        }
+    }
+}
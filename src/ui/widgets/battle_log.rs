@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Widget;
+
+use tetrs_core::game::GameEvent;
+
+use crate::ui::theme;
+
+/// Maximum number of entries kept in the log.
+const MAX_ENTRIES: usize = 50;
+
+/// Which board an entry came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSide {
+    Player,
+    Ai,
+}
+
+impl LogSide {
+    pub fn name(self) -> &'static str {
+        match self {
+            LogSide::Player => "You",
+            LogSide::Ai => "AI",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            LogSide::Player => Color::Rgb(100, 220, 255),
+            LogSide::Ai => Color::Rgb(255, 120, 120),
+        }
+    }
+}
+
+/// A single line in the battle log.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: Duration,
+    pub side: LogSide,
+    pub text: String,
+}
+
+/// Scrolling record of the attack exchange in versus.
+#[derive(Debug, Clone, Default)]
+pub struct BattleLog {
+    pub entries: VecDeque<LogEntry>,
+}
+
+impl BattleLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a game event from one side. Events without a log line are ignored.
+    pub fn record(&mut self, side: LogSide, event: &GameEvent, time: Duration) {
+        let who = side.name();
+        let text = match event {
+            GameEvent::AttackSent(lines) => format!("{} sent {}", who, lines),
+            GameEvent::GarbageReceived(lines) => format!("{} received {} garbage", who, lines),
+            GameEvent::Clear(clear_type) if clear_type.is_difficult() => {
+                format!("{}: {}", who, clear_type.display_name())
+            }
+            GameEvent::PerfectClear => format!("{}: PERFECT CLEAR", who),
+            GameEvent::Surge(lines) => format!("{}: B2B SURGE +{}", who, lines),
+            GameEvent::GameOver => format!("{} topped out", who),
+            _ => return,
+        };
+
+        self.entries.push_back(LogEntry { time, side, text });
+        while self.entries.len() > MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Widget that renders the most recent battle log entries, newest at the bottom.
+pub struct BattleLogWidget<'a> {
+    pub log: &'a BattleLog,
+}
+
+impl<'a> Widget for BattleLogWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 20 || area.height < 3 {
+            return;
+        }
+
+        let x = area.x;
+        let mut y = area.y;
+
+        // Header
+        buf.set_string(x + 1, y, "BATTLE LOG", theme::header_style());
+        y += 1;
+        let sep: String = theme::PANEL_H.repeat(area.width as usize);
+        buf.set_string(x, y, &sep, Style::default().fg(theme::PANEL_COLOR));
+        y += 1;
+
+        let rows = (area.y + area.height).saturating_sub(y) as usize;
+        let skip = self.log.entries.len().saturating_sub(rows);
+        for entry in self.log.entries.iter().skip(skip) {
+            let secs = entry.time.as_secs();
+            let stamp = format!("{}:{:02}", secs / 60, secs % 60);
+            buf.set_string(x + 1, y, &stamp, theme::stat_label_style());
+            buf.set_string(
+                x + 8,
+                y,
+                &entry.text,
+                Style::default().fg(entry.side.color()),
+            );
+            y += 1;
+        }
+    }
+}
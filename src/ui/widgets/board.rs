@@ -3,11 +3,15 @@ use ratatui::layout::Rect;
 use ratatui::style::{Color, Style};
 use ratatui::widgets::Widget;
 
-use crate::game::board::{Board, Cell, BOARD_WIDTH, VISIBLE_HEIGHT};
-use crate::game::ghost;
-use crate::game::piece::Piece;
+use tetrs_core::ai::perfect_clear::PcPiece;
+use tetrs_core::game::board::{Board, Cell, BOARD_WIDTH, VISIBLE_HEIGHT};
+use tetrs_core::game::ghost;
+use tetrs_core::game::piece::{Piece, PieceType};
+use tetrs_core::game::DangerTier;
+use tetrs_core::modes::format_number;
+
 use crate::ui::effects::Effects;
-use crate::ui::theme;
+use crate::ui::theme::{self, PieceColors};
 
 /// Widget that renders the Tetris playfield.
 pub struct BoardWidget<'a> {
@@ -15,6 +19,25 @@ pub struct BoardWidget<'a> {
     pub current_piece: Option<&'a Piece>,
     pub effects: &'a Effects,
     pub show_grid: bool,
+    /// Outline where the current piece would land.
+    pub show_ghost: bool,
+    /// Leave locked cells blank, for the invisible modifier.
+    pub hide_stack: bool,
+    /// Rows at the bottom banked by an active zone.
+    pub zone_lines: usize,
+    /// Column heights of a ghost run, drawn as a silhouette in empty cells.
+    pub ghost_heights: Option<&'a [u8; BOARD_WIDTH]>,
+    /// Cells a trainer wants the current piece locked into.
+    pub guide: Option<(PieceType, [(i32, i32); 4])>,
+    /// Perfect clear the solver found, outlined in empty cells.
+    pub pc_plan: Option<&'a [PcPiece]>,
+    /// Draw column 0 on the right, for the mirror view modifier.
+    pub mirrored: bool,
+    /// Next piece and whether it would top out, marked where it will spawn
+    /// just above the playfield.
+    pub spawn_preview: Option<(PieceType, bool)>,
+    /// The held piece where it would land if swapped in now.
+    pub hold_ghost: Option<Piece>,
 }
 
 impl<'a> Widget for BoardWidget<'a> {
@@ -50,45 +73,77 @@ impl<'a> Widget for BoardWidget<'a> {
                     continue;
                 }
 
-                let (text, style) = self.cell_display(col as i32, board_row as i32);
+                let board_col = if self.mirrored {
+                    BOARD_WIDTH - 1 - col
+                } else {
+                    col
+                };
+                let (text, style) = self.cell_display(board_col as i32, board_row as i32);
 
                 if screen_col < buf.area.width && screen_row < buf.area.height {
                     buf.set_string(screen_col, screen_row, text, style);
                 }
             }
         }
+
+        self.draw_score_popups(board_x, board_y, area, buf);
     }
 }
 
 impl<'a> BoardWidget<'a> {
+    /// Draw clear points over the board, centered and rising from their rows.
+    fn draw_score_popups(&self, board_x: u16, board_y: u16, area: Rect, buf: &mut Buffer) {
+        let board_width = BOARD_WIDTH as u16 * 2;
+        for popup in &self.effects.score_popups {
+            let row = popup.row as f32 + popup.rise();
+            if row >= VISIBLE_HEIGHT as f32 {
+                continue;
+            }
+            let screen_row = board_y + (VISIBLE_HEIGHT - 1 - row as usize) as u16;
+            if screen_row >= area.y + area.height || screen_row >= buf.area.height {
+                continue;
+            }
+            let text = format!("+{}", format_number(popup.points));
+            let x = board_x + board_width.saturating_sub(text.len() as u16) / 2;
+            let color = blend_color(theme::BG_COLOR, theme::TEXT_BRIGHT, popup.opacity());
+            buf.set_string(x, screen_row, &text, Style::default().fg(color));
+        }
+    }
+
     fn cell_display(&self, col: i32, row: i32) -> (&'static str, Style) {
         // Check line clear flash
         if let Some((ref flash_rows, progress)) = self.effects.line_clear_flash {
-            if flash_rows.contains(&(row as usize)) {
-                let color = self.effects.line_clear_color(progress);
+            if flash_rows.contains(&(row as usize)) && self.effects.intensity.flash > 0.0 {
+                let color = self
+                    .effects
+                    .flash_color(theme::BG_COLOR, self.effects.line_clear_color(progress));
                 return (theme::BLOCK_FLASH, Style::default().fg(color));
             }
         }
 
+        // Lines banked by the zone
+        if (row as usize) < self.zone_lines {
+            return (
+                theme::BLOCK_FULL,
+                Style::default().fg(theme::ZONE_LINE_COLOR),
+            );
+        }
+
         // Check active piece
         if let Some(piece) = self.current_piece {
             let cells = piece.cells();
             for &(cx, cy) in &cells {
                 if cx == col && cy == row {
-                    // Lock flash effect
-                    let color = if !self.effects.lock_flash.is_zero() {
-                        piece.piece_type.bright_color()
-                    } else {
-                        piece.piece_type.bright_color()
-                    };
-                    return (theme::BLOCK_FULL, Style::default().fg(color));
+                    let color = piece.piece_type.bright_color();
+                    let glyph = theme::piece_glyph(piece.piece_type);
+                    return (glyph, Style::default().fg(color));
                 }
             }
 
             // Check ghost piece
             let ghost_cells = ghost::ghost_cells(self.board, piece);
             let ghost_y = ghost::ghost_y(self.board, piece);
-            if ghost_y != piece.y {
+            if self.show_ghost && ghost_y != piece.y {
                 for &(gx, gy) in &ghost_cells {
                     if gx == col && gy == row {
                         return (theme::BLOCK_GHOST, Style::default().fg(theme::GHOST_COLOR));
@@ -98,10 +153,47 @@ impl<'a> BoardWidget<'a> {
         }
 
         // Check board cells
-        match self.board.get(col, row) {
+        let cell = match self.board.get(col, row) {
+            _ if self.hide_stack => Cell::Empty,
+            cell => cell,
+        };
+        match cell {
             Cell::Empty => {
-                // Grid pattern
-                if self.show_grid {
+                let guided = self.guide.filter(|(_, cells)| cells.contains(&(col, row)));
+                if let Some((piece_type, _)) = guided {
+                    return (
+                        theme::BLOCK_GARBAGE,
+                        Style::default().fg(piece_type.color()),
+                    );
+                }
+                let planned = self
+                    .pc_plan
+                    .and_then(|plan| plan.iter().find(|p| p.cells.contains(&(col, row))));
+                if let Some(piece) = planned {
+                    return (
+                        theme::BLOCK_GHOST,
+                        Style::default().fg(piece.piece_type.color()),
+                    );
+                }
+                let held = self
+                    .hold_ghost
+                    .as_ref()
+                    .filter(|p| p.cells().contains(&(col, row)));
+                if let Some(piece) = held {
+                    // Fainter than the main ghost, in the held piece's color
+                    let color = blend_color(theme::BG_COLOR, piece.piece_type.color(), 0.45);
+                    return (theme::BLOCK_GHOST, Style::default().fg(color));
+                }
+                let in_ghost_run = self
+                    .ghost_heights
+                    .is_some_and(|heights| row < heights[col as usize] as i32);
+                if in_ghost_run {
+                    (
+                        theme::BLOCK_GHOST,
+                        Style::default().fg(theme::GHOST_RUN_COLOR),
+                    )
+                } else if self.show_grid {
+                    // Grid pattern
                     (theme::BLOCK_DOT, Style::default().fg(theme::GRID_DOT_COLOR))
                 } else {
                     (theme::BLOCK_EMPTY, Style::default())
@@ -110,12 +202,12 @@ impl<'a> BoardWidget<'a> {
             Cell::Filled(piece_type) => {
                 let color = piece_type.color();
                 // Danger zone tint for high rows
-                let color = if row >= (VISIBLE_HEIGHT as i32 - 4) && self.effects.in_danger {
-                    blend_color(color, theme::DANGER_COLOR, self.effects.danger_flash * 0.3)
+                let color = if row >= (VISIBLE_HEIGHT as i32 - 4) {
+                    blend_color(color, theme::DANGER_COLOR, self.effects.danger_tint())
                 } else {
                     color
                 };
-                (theme::BLOCK_FULL, Style::default().fg(color))
+                (theme::piece_glyph(piece_type), Style::default().fg(color))
             }
             Cell::Garbage => {
                 let color = if row % 2 == 0 {
@@ -123,8 +215,13 @@ impl<'a> BoardWidget<'a> {
                 } else {
                     theme::GARBAGE_DARK
                 };
-                (theme::BLOCK_GARBAGE, Style::default().fg(color))
+                (theme::garbage_glyph(), Style::default().fg(color))
             }
+            Cell::Solid => (
+                theme::solid_glyph(),
+                Style::default().fg(theme::SOLID_COLOR),
+            ),
+            Cell::Bomb => (theme::bomb_glyph(), Style::default().fg(theme::BOMB_COLOR)),
         }
     }
 
@@ -132,16 +229,22 @@ impl<'a> BoardWidget<'a> {
         let w = BOARD_WIDTH as u16 * 2 + 2;
         let h = VISIBLE_HEIGHT as u16 + 2;
 
-        let border_color = if self.effects.in_danger {
-            blend_color(
-                theme::BORDER_COLOR,
-                theme::DANGER_COLOR,
-                self.effects.danger_flash * 0.6,
-            )
+        let in_danger = self.effects.danger_tier > DangerTier::Safe;
+        let border_color = if in_danger {
+            self.effects.danger_border(theme::BORDER_COLOR)
+        } else if !self.effects.surge_flash.is_zero() {
+            self.effects
+                .flash_color(theme::BORDER_COLOR, theme::SURGE_COLOR)
+        } else if !self.effects.zone_flash.is_zero() {
+            self.effects
+                .flash_color(theme::BORDER_COLOR, theme::ZONE_BRIGHT)
+        } else if self.effects.in_zone {
+            theme::ZONE_COLOR
         } else if !self.effects.hard_drop_flash.is_zero() {
-            theme::BORDER_BRIGHT
+            self.effects
+                .flash_color(theme::BORDER_COLOR, theme::BORDER_BRIGHT)
         } else {
-            theme::BORDER_COLOR
+            self.effects.btb_border(theme::BORDER_COLOR)
         };
         let style = Style::default().fg(border_color);
 
@@ -155,22 +258,48 @@ impl<'a> BoardWidget<'a> {
         }
         buf.set_string(x + w - 1, y, theme::BORDER_TR, style);
 
+        // The bottom row of the next piece's spawn lines up with the top
+        // border, so its footprint is drawn there
+        if let Some((piece_type, blocked)) = self.spawn_preview {
+            let color = if blocked && self.effects.blink_on() {
+                theme::DANGER_BRIGHT
+            } else {
+                blend_color(border_color, piece_type.color(), 0.45)
+            };
+            let spawn_row = VISIBLE_HEIGHT as i32;
+            for (col, row) in Piece::new(piece_type).cells() {
+                if row != spawn_row {
+                    continue;
+                }
+                let col = if self.mirrored {
+                    BOARD_WIDTH as i32 - 1 - col
+                } else {
+                    col
+                };
+                let cx = x + 1 + col as u16 * 2;
+                let top = theme::BORDER_H_TOP.repeat(2);
+                buf.set_string(cx, y, &top, Style::default().fg(color));
+            }
+        }
+
         // Side borders (half blocks — fill toward the playfield inside)
         for row in 1..h - 1 {
             // Gradient: brighter near top
             let t = row as f32 / (h - 2) as f32;
             let grad_color = blend_color(theme::BORDER_BRIGHT, theme::BORDER_COLOR, t);
-            let grad_style = Style::default().fg(if self.effects.in_danger {
-                blend_color(
-                    grad_color,
-                    theme::DANGER_COLOR,
-                    self.effects.danger_flash * 0.4,
-                )
+            let grad_color = if in_danger {
+                self.effects.danger_border(grad_color)
             } else {
-                grad_color
-            });
-            buf.set_string(x, y + row, theme::BORDER_V_LEFT, grad_style);
-            buf.set_string(x + w - 1, y + row, theme::BORDER_V_RIGHT, grad_style);
+                self.effects.btb_border(grad_color)
+            };
+            // Flames lick up the walls during a long back-to-back chain
+            let from_bottom = h - 2 - row;
+            let wall = |side| {
+                let color = self.effects.btb_flame(grad_color, from_bottom, side);
+                Style::default().fg(color.unwrap_or(grad_color))
+            };
+            buf.set_string(x, y + row, theme::BORDER_V_LEFT, wall(0));
+            buf.set_string(x + w - 1, y + row, theme::BORDER_V_RIGHT, wall(1));
         }
 
         // Bottom border (upper half blocks — fill toward the playfield above)
@@ -0,0 +1,43 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use crate::ui::theme;
+
+/// Widget that renders the time left on a timed combo as a horizontal bar,
+/// draining from the right and colored by the combo's size.
+pub struct ComboBarWidget {
+    /// Share of the combo timer left, from 0 to 1.
+    pub fraction: f32,
+    pub combo: u32,
+}
+
+impl Widget for ComboBarWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 2 || area.height < 1 {
+            return;
+        }
+
+        let width = area.width as usize;
+        // Eighth blocks let the bar drain smoothly between cells
+        let eighths = (self.fraction.clamp(0.0, 1.0) * width as f32 * 8.0).round() as usize;
+        let full = eighths / 8;
+        let partial = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"][eighths % 8];
+
+        let color = if self.fraction < 0.25 {
+            theme::DANGER_COLOR
+        } else {
+            theme::combo_color(self.combo)
+        };
+        let bar = format!("{}{}", "█".repeat(full), partial);
+        let track = "─".repeat(width);
+        buf.set_string(
+            area.x,
+            area.y,
+            &track,
+            Style::default().fg(theme::GARBAGE_BAR_BG),
+        );
+        buf.set_string(area.x, area.y, &bar, Style::default().fg(color));
+    }
+}
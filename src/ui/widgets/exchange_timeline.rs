@@ -0,0 +1,116 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use tetrs_core::game::garbage::{Exchange, ExchangeKind};
+
+use crate::ui::theme;
+use crate::ui::widgets::battle_log::LogSide;
+
+/// Every garbage exchange of a versus match in time order, with the
+/// player's running balance of lines sent against lines taken.
+pub struct ExchangeTimelineWidget<'a> {
+    pub player: &'a [Exchange],
+    pub ai: &'a [Exchange],
+    /// Rows scrolled past at the top.
+    pub scroll: usize,
+}
+
+impl<'a> ExchangeTimelineWidget<'a> {
+    /// Both sides' exchanges merged by time, the player's first on a tie.
+    fn merged(&self) -> Vec<(LogSide, &'a Exchange)> {
+        let mut rows: Vec<_> = self
+            .player
+            .iter()
+            .map(|e| (LogSide::Player, e))
+            .chain(self.ai.iter().map(|e| (LogSide::Ai, e)))
+            .collect();
+        rows.sort_by_key(|(_, e)| e.time);
+        rows
+    }
+
+    /// Lines of each kind on one side, as "sent 12  cancelled 3  took 8".
+    fn totals(exchanges: &[Exchange]) -> String {
+        let total = |kind| -> u32 {
+            exchanges
+                .iter()
+                .filter(|e| e.kind == kind)
+                .map(|e| e.lines)
+                .sum()
+        };
+        format!(
+            "sent {}  cancelled {}  took {}",
+            total(ExchangeKind::Sent),
+            total(ExchangeKind::Cancelled),
+            total(ExchangeKind::Received)
+        )
+    }
+}
+
+impl<'a> Widget for ExchangeTimelineWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 36 || area.height < 5 {
+            return;
+        }
+        let x = area.x;
+        let bottom = area.y + area.height;
+        let mut y = area.y;
+
+        for (side, exchanges) in [(LogSide::Player, self.player), (LogSide::Ai, self.ai)] {
+            buf.set_string(x, y, side.name(), Style::default().fg(side.color()));
+            buf.set_string(x + 5, y, Self::totals(exchanges), theme::stat_label_style());
+            y += 1;
+        }
+        y += 1;
+        buf.set_string(x, y, "Time    Exchange", theme::stat_label_style());
+        buf.set_string(x + 30, y, "Net", theme::stat_label_style());
+        y += 1;
+
+        let rows = self.merged();
+        if rows.is_empty() {
+            buf.set_string(x, y, "No garbage traded", theme::menu_desc_style());
+            return;
+        }
+
+        // The balance runs from the start, so add up the rows scrolled past
+        let visible = (bottom - y) as usize;
+        let skip = self.scroll.min(rows.len().saturating_sub(visible));
+        let mut balance: i64 = 0;
+        for (i, (side, exchange)) in rows.into_iter().enumerate() {
+            let lines = exchange.lines as i64;
+            let verb = match (exchange.kind, side) {
+                (ExchangeKind::Sent, LogSide::Player) => {
+                    balance += lines;
+                    "sent"
+                }
+                (ExchangeKind::Received, LogSide::Player) => {
+                    balance -= lines;
+                    "took"
+                }
+                (ExchangeKind::Sent, LogSide::Ai) => "sent",
+                (ExchangeKind::Received, LogSide::Ai) => "took",
+                (ExchangeKind::Cancelled, _) => "cancelled",
+            };
+            if i < skip {
+                continue;
+            }
+            if y >= bottom {
+                break;
+            }
+
+            let secs = exchange.time.as_secs_f64();
+            let stamp = format!("{}:{:04.1}", (secs / 60.0) as u64, secs % 60.0);
+            buf.set_string(x, y, &stamp, theme::stat_label_style());
+            let text = format!("{} {} {}", side.name(), verb, exchange.lines);
+            buf.set_string(x + 8, y, &text, Style::default().fg(side.color()));
+            buf.set_string(
+                x + 30,
+                y,
+                format!("{:+}", balance),
+                theme::stat_value_style(),
+            );
+            y += 1;
+        }
+    }
+}
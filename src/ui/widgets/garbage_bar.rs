@@ -1,18 +1,30 @@
+use std::time::Duration;
+
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::widgets::Widget;
 
-use crate::game::board::VISIBLE_HEIGHT;
+use tetrs_core::game::board::VISIBLE_HEIGHT;
+use tetrs_core::game::garbage::GarbageQueue;
+
 use crate::ui::theme;
+use crate::ui::widgets::board::blend_color;
+
+/// Time before arriving when a batch starts flashing.
+const ARRIVING: Duration = Duration::from_millis(200);
 
-/// Widget that renders the incoming garbage indicator bar.
-pub struct GarbageBarWidget {
-    /// Number of pending garbage lines.
-    pub pending: u32,
+/// Length of each on and off phase of the flash.
+const FLASH_PHASE_MS: u128 = 50;
+
+/// Widget that renders the incoming garbage indicator bar: one segment per
+/// line, oldest batch at the bottom, brightening as each batch closes in
+/// and flashing just before it arrives.
+pub struct GarbageBarWidget<'a> {
+    pub garbage: &'a GarbageQueue,
 }
 
-impl Widget for GarbageBarWidget {
+impl<'a> Widget for GarbageBarWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if area.width < 1 || area.height < 2 {
             return;
@@ -20,22 +32,42 @@ impl Widget for GarbageBarWidget {
 
         let x = area.x;
         let bar_height = area.height.min(VISIBLE_HEIGHT as u16);
-
-        // Draw the bar from bottom to top
-        let filled = (self.pending as u16).min(bar_height);
+        let bottom = area.y + area.height.saturating_sub(1);
+        // More garbage than a safe amount charges towards the danger color
+        let charged = if self.garbage.pending() >= 8 {
+            theme::DANGER_COLOR
+        } else {
+            theme::GARBAGE_BAR_COLOR
+        };
 
         for row in 0..bar_height {
-            let y = area.y + area.height.saturating_sub(1) - row;
-            if row < filled {
-                // Color intensity increases with more garbage
-                let color = if self.pending >= 8 {
-                    theme::DANGER_COLOR
+            buf.set_string(
+                x,
+                bottom - row,
+                "▐",
+                Style::default().fg(theme::GARBAGE_BAR_BG),
+            );
+        }
+
+        let mut row = 0;
+        for (lines, remaining) in self.garbage.batches() {
+            let color = if remaining <= ARRIVING {
+                if (remaining.as_millis() / FLASH_PHASE_MS).is_multiple_of(2) {
+                    theme::TEXT_BRIGHT
                 } else {
-                    theme::GARBAGE_BAR_COLOR
-                };
-                buf.set_string(x, y, "▐", Style::default().fg(color));
+                    theme::DANGER_COLOR
+                }
             } else {
-                buf.set_string(x, y, "▐", Style::default().fg(theme::GARBAGE_BAR_BG));
+                let travel = self.garbage.travel_time.as_secs_f32();
+                let progress = 1.0 - remaining.as_secs_f32() / travel.max(f32::EPSILON);
+                blend_color(theme::GARBAGE_BAR_BG, charged, 0.4 + 0.6 * progress)
+            };
+            for _ in 0..lines {
+                if row >= bar_height {
+                    return;
+                }
+                buf.set_string(x, bottom - row, "▐", Style::default().fg(color));
+                row += 1;
             }
         }
     }
@@ -0,0 +1,66 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::Widget;
+
+use tetrs_core::game::board::VISIBLE_HEIGHT;
+use tetrs_core::game::DANGER_HEIGHT;
+
+use crate::ui::theme;
+
+const BAR_CHARS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+const SAFE_COLOR: Color = Color::Rgb(80, 200, 140);
+const HIGH_COLOR: Color = Color::Rgb(240, 200, 80);
+
+/// Stack height over a run as columns scaled to the visible board, so a
+/// full-height column means the stack reached the top.
+///
+/// Runs longer than the area are merged into wider columns that keep the
+/// highest sample, so brief spikes still show.
+pub struct HeightGraphWidget<'a> {
+    pub heights: &'a [u8],
+}
+
+impl<'a> Widget for HeightGraphWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 || self.heights.is_empty() {
+            return;
+        }
+
+        let group = self.heights.len().div_ceil(area.width as usize).max(1);
+        let steps = area.height as usize * BAR_CHARS.len();
+        for (i, chunk) in self.heights.chunks(group).enumerate() {
+            let height = chunk.iter().copied().max().unwrap_or(0) as usize;
+            let filled = (height.min(VISIBLE_HEIGHT) * steps).div_ceil(VISIBLE_HEIGHT);
+            let color = height_color(height);
+            let x = area.x + i as u16;
+
+            for row in 0..area.height as usize {
+                let y = area.y + area.height - 1 - row as u16;
+                let level = filled.saturating_sub(row * BAR_CHARS.len());
+                let (ch, style) = match level {
+                    0 => ("▁", Style::default().fg(theme::PANEL_COLOR)),
+                    n => (
+                        BAR_CHARS[n.min(BAR_CHARS.len()) - 1],
+                        Style::default().fg(color),
+                    ),
+                };
+                // Only the bottom row shows the empty baseline
+                if level > 0 || row == 0 {
+                    buf.set_string(x, y, ch, style);
+                }
+            }
+        }
+    }
+}
+
+/// Green while the stack is low, amber past halfway, red near the top.
+fn height_color(height: usize) -> Color {
+    if height >= DANGER_HEIGHT {
+        theme::DANGER_COLOR
+    } else if height >= VISIBLE_HEIGHT / 2 {
+        HIGH_COLOR
+    } else {
+        SAFE_COLOR
+    }
+}
@@ -0,0 +1,100 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use crate::input::keybinds::{Action, KeybindMap};
+use crate::ui::theme;
+
+/// Width of one column: action label, then its keys.
+const COLUMN_W: u16 = 34;
+const LABEL_W: u16 = 18;
+
+/// Every current binding, read from the keybind map, over the screen.
+/// Any key closes it.
+pub struct HelpOverlayWidget<'a> {
+    pub keybinds: &'a KeybindMap,
+}
+
+impl<'a> HelpOverlayWidget<'a> {
+    /// Draw a titled list of actions with their keys, returning the row
+    /// after it.
+    fn render_column(
+        &self,
+        title: &str,
+        actions: &[Action],
+        x: u16,
+        y: u16,
+        bottom: u16,
+        buf: &mut Buffer,
+    ) -> u16 {
+        if y >= bottom {
+            return y;
+        }
+        buf.set_string(x, y, title, theme::header_style());
+        let mut y = y + 1;
+        for &action in actions {
+            if y >= bottom {
+                break;
+            }
+            buf.set_string(x, y, action.label(), theme::stat_label_style());
+            let keys: String = self
+                .keybinds
+                .keys_display(action)
+                .chars()
+                .take((COLUMN_W - LABEL_W) as usize)
+                .collect();
+            buf.set_string(x + LABEL_W, y, &keys, theme::stat_value_style());
+            y += 1;
+        }
+        y
+    }
+}
+
+impl<'a> Widget for HelpOverlayWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < COLUMN_W + 2 || area.height < 4 {
+            return;
+        }
+
+        let bg = Style::default().bg(theme::BG_COLOR);
+        for y in area.y..area.y + area.height {
+            buf.set_string(area.x, y, " ".repeat(area.width as usize), bg);
+        }
+
+        // Menu-only actions; the shared ones are listed with the game's
+        let menu: Vec<Action> = Action::MENU
+            .into_iter()
+            .filter(|action| !Action::GAME.contains(action))
+            .collect();
+
+        let bottom = area.y + area.height - 2;
+        let title = "CONTROLS";
+        let center_x = area.x + area.width / 2;
+        buf.set_string(
+            center_x.saturating_sub(title.len() as u16 / 2),
+            area.y + 1,
+            title,
+            theme::title_style(),
+        );
+
+        let top = area.y + 3;
+        if area.width >= COLUMN_W * 2 + 4 {
+            let x = center_x - COLUMN_W;
+            self.render_column("IN GAME", &Action::GAME, x, top, bottom, buf);
+            self.render_column("MENUS", &menu, x + COLUMN_W + 2, top, bottom, buf);
+        } else {
+            let x = center_x - COLUMN_W / 2;
+            let y = self.render_column("IN GAME", &Action::GAME, x, top, bottom, buf);
+            self.render_column("MENUS", &menu, x, y + 1, bottom, buf);
+        }
+
+        let controls = "Any key: close";
+        buf.set_string(
+            center_x.saturating_sub(controls.len() as u16 / 2),
+            area.y + area.height - 1,
+            controls,
+            theme::menu_desc_style(),
+        );
+    }
+}
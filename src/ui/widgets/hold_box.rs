@@ -3,8 +3,9 @@ use ratatui::layout::Rect;
 use ratatui::style::Style;
 use ratatui::widgets::Widget;
 
-use crate::game::piece::{get_cells, PieceType, RotationState};
-use crate::ui::theme;
+use tetrs_core::game::piece::{get_cells, PieceType, RotationState};
+
+use crate::ui::theme::{self, PieceColors};
 
 /// Widget that renders the hold piece box.
 pub struct HoldBoxWidget {
@@ -51,7 +52,7 @@ impl Widget for HoldBoxWidget {
                 let sx = x + 1 + offset_x + (cx - min_x) as u16 * 2;
                 let sy = y + (max_y - cy) as u16;
                 if sx + 1 < buf.area.width && sy < buf.area.height {
-                    buf.set_string(sx, sy, theme::BLOCK_FULL, style);
+                    buf.set_string(sx, sy, theme::piece_glyph(piece_type), style);
                 }
             }
         }
@@ -0,0 +1,126 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::Widget;
+
+use tetrs_core::game::board::{Cell, BOARD_WIDTH, VISIBLE_HEIGHT};
+use tetrs_core::game::GameState;
+
+use crate::ui::theme::{self, PieceColors};
+
+/// Width and height of a miniature board, border included.
+pub const MINI_BOARD_W: u16 = BOARD_WIDTH as u16 + 2;
+pub const MINI_BOARD_H: u16 = VISIBLE_HEIGHT as u16 / 2 + 2;
+
+/// Widget that renders an opponent's board at one character per cell wide
+/// and two cells per character tall, with a label row underneath.
+pub struct MiniBoardWidget<'a> {
+    pub game: &'a GameState,
+    pub label: &'a str,
+    /// Finishing place once knocked out; the board is drawn dimmed.
+    pub place: Option<usize>,
+    pub badges: u32,
+    /// The player's attacks are going here.
+    pub targeted: bool,
+    /// This board's attacks are going to the player.
+    pub attacking: bool,
+}
+
+impl<'a> Widget for MiniBoardWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < MINI_BOARD_W || area.height < MINI_BOARD_H {
+            return;
+        }
+
+        let knocked_out = self.place.is_some();
+        let border = Style::default().fg(if knocked_out {
+            theme::PANEL_COLOR
+        } else if self.targeted {
+            theme::TARGET_COLOR
+        } else {
+            theme::BORDER_COLOR
+        });
+        let (x, y) = (area.x, area.y);
+        let right = x + MINI_BOARD_W - 1;
+        let bottom = y + MINI_BOARD_H - 1;
+
+        buf.set_string(x, y, theme::PANEL_TL, border);
+        buf.set_string(right, y, theme::PANEL_TR, border);
+        buf.set_string(x, bottom, theme::PANEL_BL, border);
+        buf.set_string(right, bottom, theme::PANEL_BR, border);
+        for col in x + 1..right {
+            buf.set_string(col, y, theme::PANEL_H, border);
+            buf.set_string(col, bottom, theme::PANEL_H, border);
+        }
+
+        for line in 0..VISIBLE_HEIGHT as u16 / 2 {
+            let sy = y + 1 + line;
+            buf.set_string(x, sy, theme::PANEL_V, border);
+            buf.set_string(right, sy, theme::PANEL_V, border);
+
+            // Each character shows two rows: upper half in fg, lower in bg
+            let top_row = VISIBLE_HEIGHT as i32 - 1 - 2 * line as i32;
+            for col in 0..BOARD_WIDTH as i32 {
+                let top = self.cell_color(col, top_row);
+                let low = self.cell_color(col, top_row - 1);
+                buf.set_string(
+                    x + 1 + col as u16,
+                    sy,
+                    "▀",
+                    Style::default().fg(top).bg(low),
+                );
+            }
+        }
+
+        if area.height > MINI_BOARD_H {
+            let label = match self.place {
+                Some(place) => format!("KO #{}", place),
+                None if self.badges > 0 => format!("{} \u{2605}{}", self.label, self.badges),
+                None => self.label.to_string(),
+            };
+            let label = if self.targeted && !knocked_out {
+                format!("\u{25B8}{}", label)
+            } else {
+                label
+            };
+            let style = if knocked_out {
+                Style::default().fg(theme::TEXT_DIM)
+            } else if self.targeted {
+                Style::default()
+                    .fg(theme::TARGET_COLOR)
+                    .add_modifier(Modifier::BOLD)
+            } else if self.attacking {
+                Style::default().fg(theme::ATTACKER_COLOR)
+            } else {
+                theme::stat_label_style()
+            };
+            let label: String = label.chars().take(MINI_BOARD_W as usize).collect();
+            let lx = x + (MINI_BOARD_W.saturating_sub(label.chars().count() as u16)) / 2;
+            buf.set_string(lx, bottom + 1, &label, style);
+        }
+    }
+}
+
+impl<'a> MiniBoardWidget<'a> {
+    fn cell_color(&self, col: i32, row: i32) -> Color {
+        let knocked_out = self.place.is_some();
+        let in_piece = self
+            .game
+            .current_piece
+            .as_ref()
+            .filter(|_| !knocked_out)
+            .filter(|piece| piece.cells().contains(&(col, row)));
+        if let Some(piece) = in_piece {
+            return piece.piece_type.bright_color();
+        }
+
+        match self.game.board.get(col, row) {
+            Cell::Empty => theme::BG_COLOR,
+            Cell::Solid => theme::SOLID_COLOR,
+            _ if knocked_out => theme::GARBAGE_DARK,
+            Cell::Filled(piece_type) => piece_type.color(),
+            Cell::Garbage => theme::GARBAGE_COLOR,
+            Cell::Bomb => theme::BOMB_COLOR,
+        }
+    }
+}
@@ -1,6 +1,20 @@
 pub mod action_text;
+pub mod ai_explain;
+pub mod attack_graph;
+pub mod bar_chart;
+pub mod battle_log;
 pub mod board;
+pub mod combo_bar;
+pub mod exchange_timeline;
 pub mod garbage_bar;
+pub mod height_graph;
+pub mod help_overlay;
 pub mod hold_box;
+pub mod mini_board;
 pub mod next_queue;
+pub mod pace_bar;
+pub mod perf_overlay;
 pub mod sidebar;
+pub mod text_input;
+pub mod toast;
+pub mod zone_meter;
@@ -0,0 +1,49 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use tetrs_core::game::board::VISIBLE_HEIGHT;
+
+use crate::ui::theme;
+
+/// Widget that renders progress toward the line goal against a ghost run:
+/// the ghost's progress dim, the player's bright, green when level or ahead.
+pub struct PaceBarWidget {
+    pub lines: u32,
+    pub ghost_lines: u32,
+    pub target: u32,
+}
+
+impl Widget for PaceBarWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 1 || area.height < 2 || self.target == 0 {
+            return;
+        }
+
+        let x = area.x;
+        let bar_height = area.height.min(VISIBLE_HEIGHT as u16);
+        let fill = |lines: u32| {
+            (lines.min(self.target) as u64 * bar_height as u64 / self.target as u64) as u16
+        };
+        let player = fill(self.lines);
+        let ghost = fill(self.ghost_lines);
+        let player_color = if self.lines >= self.ghost_lines {
+            theme::PACE_AHEAD_COLOR
+        } else {
+            theme::PACE_BEHIND_COLOR
+        };
+
+        for row in 0..bar_height {
+            let y = area.y + area.height.saturating_sub(1) - row;
+            let color = if row < player {
+                player_color
+            } else if row < ghost {
+                theme::GHOST_RUN_COLOR
+            } else {
+                theme::GARBAGE_BAR_BG
+            };
+            buf.set_string(x, y, "▌", Style::default().fg(color));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use crate::ui::theme;
+
+/// Weight of the newest sample in the moving averages.
+const SMOOTHING: f64 = 0.1;
+/// Overlay size in cells.
+const OVERLAY_W: u16 = 24;
+const OVERLAY_H: u16 = 8;
+
+/// Exponential moving average of a duration, in milliseconds.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Smoothed {
+    pub ms: f64,
+}
+
+impl Smoothed {
+    fn add(&mut self, sample: Duration) {
+        let ms = sample.as_secs_f64() * 1000.0;
+        self.ms = if self.ms == 0.0 {
+            ms
+        } else {
+            self.ms + (ms - self.ms) * SMOOTHING
+        };
+    }
+}
+
+/// Frame timing gathered by the main loop, shown by the debug overlay.
+#[derive(Debug, Clone, Default)]
+pub struct PerfStats {
+    pub visible: bool,
+    /// Time between the starts of consecutive frames.
+    pub frame: Smoothed,
+    pub update: Smoothed,
+    pub render: Smoothed,
+    /// AI time spent on the main thread during updates.
+    pub ai: Smoothed,
+    /// Frames whose update and render overran the frame budget.
+    pub dropped: u64,
+}
+
+impl PerfStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_frame(&mut self, frame: Duration, update: Duration, render: Duration) {
+        self.frame.add(frame);
+        self.update.add(update);
+        self.render.add(render);
+    }
+
+    pub fn record_ai(&mut self, ai: Duration) {
+        self.ai.add(ai);
+    }
+
+    pub fn record_dropped(&mut self) {
+        self.dropped += 1;
+    }
+}
+
+/// Small timing panel in the top-right corner.
+pub struct PerfOverlayWidget<'a> {
+    pub stats: &'a PerfStats,
+}
+
+impl<'a> Widget for PerfOverlayWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < OVERLAY_W || area.height < OVERLAY_H {
+            return;
+        }
+
+        let x = area.x + area.width - OVERLAY_W;
+        let mut y = area.y;
+
+        let bg = Style::default().bg(theme::BG_COLOR);
+        for row in y..y + OVERLAY_H {
+            buf.set_string(x, row, " ".repeat(OVERLAY_W as usize), bg);
+        }
+
+        buf.set_string(x + 1, y, "PERF", theme::header_style());
+        y += 1;
+        let sep: String = theme::PANEL_H.repeat(OVERLAY_W as usize);
+        buf.set_string(x, y, &sep, Style::default().fg(theme::PANEL_COLOR));
+        y += 1;
+
+        let s = self.stats;
+        let fps = if s.frame.ms > 0.0 {
+            1000.0 / s.frame.ms
+        } else {
+            0.0
+        };
+        let rows = [
+            ("frame", format!("{:.1}ms {:.0}fps", s.frame.ms, fps)),
+            ("update", format!("{:.2}ms", s.update.ms)),
+            ("render", format!("{:.2}ms", s.render.ms)),
+            ("ai", format!("{:.2}ms", s.ai.ms)),
+            ("dropped", s.dropped.to_string()),
+        ];
+        for (label, value) in rows {
+            buf.set_string(x + 1, y, label, theme::stat_label_style());
+            buf.set_string(x + 9, y, &value, theme::stat_value_style());
+            y += 1;
+        }
+    }
+}
@@ -1,11 +1,14 @@
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Style;
+use ratatui::style::{Color, Style};
 use ratatui::widgets::Widget;
 
-use crate::game::scoring::Scoring;
-use crate::game::stats::Stats;
+use tetrs_core::game::scoring::Scoring;
+use tetrs_core::game::stats::{Stats, ROLLING_WINDOW};
+use tetrs_core::modes::format_number;
+
 use crate::ui::theme;
+use crate::ui::widgets::combo_bar::ComboBarWidget;
 
 /// Widget that renders the score/stats sidebar panel.
 pub struct SidebarWidget<'a> {
@@ -69,13 +72,14 @@ impl<'a> Widget for SidebarWidget<'a> {
 
         // PPS
         if y + 1 < area.y + area.height {
-            self.draw_stat(
+            draw_rolling_stat(
                 buf,
                 x,
                 y,
-                area.width,
                 "PPS",
-                &format!("{:.2}", self.stats.pps()),
+                self.stats.pps(),
+                self.stats.rolling_pps(),
+                2,
             );
             y += 2;
         }
@@ -87,6 +91,15 @@ impl<'a> Widget for SidebarWidget<'a> {
             buf.set_string(x + 1, y, "COMBO", theme::stat_label_style());
             y += 1;
             buf.set_string(x + 1, y, &combo_str, combo_style);
+            if let Some(fraction) = self.scoring.combo_time_left() {
+                let bar_x = x + 2 + combo_str.len() as u16;
+                let bar = Rect::new(bar_x, y, (area.x + area.width).saturating_sub(bar_x + 1), 1);
+                ComboBarWidget {
+                    fraction,
+                    combo: self.scoring.combo as u32,
+                }
+                .render(bar, buf);
+            }
             y += 2;
         }
 
@@ -106,15 +119,39 @@ impl<'a> SidebarWidget<'a> {
     }
 }
 
-/// Format a number with thousands separators.
-pub fn format_number(n: u64) -> String {
-    let s = n.to_string();
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
-        }
-        result.push(c);
-    }
-    result.chars().rev().collect()
+/// Draw a run-average stat with its rolling-window value beside it.
+///
+/// The rolling value is tinted green when above the average and red when below.
+pub fn draw_rolling_stat(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    label: &str,
+    average: f64,
+    rolling: f64,
+    precision: usize,
+) {
+    let window = format!("{}s", ROLLING_WINDOW.as_secs());
+    buf.set_string(x + 1, y, label, theme::stat_label_style());
+    buf.set_string(x + 7, y, &window, Style::default().fg(theme::TEXT_DIM));
+
+    let rolling_color = if rolling > average * 1.05 {
+        Color::Rgb(120, 230, 140)
+    } else if rolling < average * 0.95 {
+        Color::Rgb(235, 120, 120)
+    } else {
+        theme::TEXT_COLOR
+    };
+    buf.set_string(
+        x + 1,
+        y + 1,
+        format!("{:.*}", precision, average),
+        theme::stat_value_style(),
+    );
+    buf.set_string(
+        x + 7,
+        y + 1,
+        format!("{:.*}", precision, rolling),
+        Style::default().fg(rolling_color),
+    );
 }
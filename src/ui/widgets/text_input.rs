@@ -0,0 +1,78 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Widget;
+
+use crate::ui::theme;
+
+/// Characters a text field accepts besides letters and digits.
+const ALLOWED_PUNCTUATION: &[char] = &[' ', '-', '_', '.'];
+
+/// Single-line text being typed, capped at a maximum length.
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    pub value: String,
+    pub max_len: usize,
+}
+
+impl TextInput {
+    pub fn new(value: &str, max_len: usize) -> Self {
+        Self {
+            value: value.chars().take(max_len).collect(),
+            max_len,
+        }
+    }
+
+    /// Append a character if it's allowed and there's room.
+    pub fn push(&mut self, c: char) {
+        let allowed = c.is_alphanumeric() || ALLOWED_PUNCTUATION.contains(&c);
+        if allowed && self.value.chars().count() < self.max_len {
+            self.value.push(c);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        self.value.pop();
+    }
+}
+
+/// Widget that renders a labelled text field with a cursor, centered in
+/// its area.
+pub struct TextInputWidget<'a> {
+    pub label: &'a str,
+    pub input: &'a TextInput,
+}
+
+impl<'a> Widget for TextInputWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 1 {
+            return;
+        }
+
+        // Cursor after the text, dropped once the field is full
+        let field: String = self
+            .input
+            .value
+            .chars()
+            .chain(std::iter::once('_'))
+            .chain(std::iter::repeat(' '))
+            .take(self.input.max_len)
+            .collect();
+        let width = self.label.chars().count() as u16 + 3 + self.input.max_len as u16;
+        let x = area.x + area.width.saturating_sub(width) / 2;
+
+        buf.set_string(x, area.y, self.label, theme::stat_label_style());
+        let fx = x + self.label.chars().count() as u16 + 1;
+        let border = Style::default().fg(theme::PANEL_COLOR);
+        buf.set_string(fx, area.y, "[", border);
+        buf.set_string(
+            fx + 1,
+            area.y,
+            &field,
+            Style::default()
+                .fg(theme::TEXT_COLOR)
+                .add_modifier(Modifier::BOLD),
+        );
+        buf.set_string(fx + 1 + self.input.max_len as u16, area.y, "]", border);
+    }
+}
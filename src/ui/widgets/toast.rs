@@ -0,0 +1,31 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::Widget;
+
+use crate::ui::theme;
+
+/// One-line banner across the top of the screen, drawn over whatever is
+/// there without taking input.
+pub struct ToastWidget<'a> {
+    pub message: &'a str,
+}
+
+impl<'a> Widget for ToastWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width < 4 {
+            return;
+        }
+
+        let style = Style::default()
+            .fg(theme::TEXT_BRIGHT)
+            .bg(theme::DANGER_COLOR)
+            .add_modifier(Modifier::BOLD);
+        buf.set_string(area.x, area.y, " ".repeat(area.width as usize), style);
+
+        let max = area.width as usize - 2;
+        let text: String = self.message.chars().take(max).collect();
+        let x = area.x + (area.width - text.chars().count() as u16) / 2;
+        buf.set_string(x, area.y, &text, style);
+    }
+}
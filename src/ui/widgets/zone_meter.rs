@@ -0,0 +1,50 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::widgets::Widget;
+
+use tetrs_core::game::board::VISIBLE_HEIGHT;
+use tetrs_core::game::zone::{Zone, QUARTERS};
+
+use crate::ui::theme;
+
+/// Widget that renders the zone meter: charge while waiting, time left while active.
+pub struct ZoneMeterWidget<'a> {
+    pub zone: &'a Zone,
+}
+
+impl<'a> Widget for ZoneMeterWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 1 || area.height < 2 {
+            return;
+        }
+
+        let x = area.x;
+        let bar_height = area.height.min(VISIBLE_HEIGHT as u16);
+        let quarter_height = (bar_height / QUARTERS as u16).max(1);
+
+        let active = self.zone.is_active();
+        let level = if active {
+            self.zone.time_left()
+        } else {
+            self.zone.fill()
+        };
+        let filled = (level * bar_height as f64).round() as u16;
+        // Only whole quarters can be spent; the partial one is drawn dimmer
+        let usable = self.zone.quarters() as u16 * quarter_height;
+
+        for row in 0..bar_height {
+            let y = area.y + area.height.saturating_sub(1) - row;
+            let color = if row >= filled {
+                theme::GARBAGE_BAR_BG
+            } else if active {
+                theme::ZONE_BRIGHT
+            } else if row < usable {
+                theme::ZONE_COLOR
+            } else {
+                theme::PANEL_COLOR
+            };
+            buf.set_string(x, y, "▌", Style::default().fg(color));
+        }
+    }
+}
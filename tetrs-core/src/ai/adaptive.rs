@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use crate::ai::difficulty::{AiDifficulty, AiParams};
+use crate::game::GameState;
+
+/// Skill an adaptive AI starts a match at: 0 plays like Easy, 1 like Expert.
+pub const START_SKILL: f64 = 0.4;
+
+/// Skill gained or lost per second for each line of lead.
+const SKILL_RATE: f64 = 0.01;
+
+/// Lead, in lines, that still counts as a close match.
+const DEAD_ZONE: f64 = 2.0;
+
+/// Largest lead the rate scales with, so a blowout doesn't swing the AI
+/// from one end to the other in a couple of seconds.
+const MAX_LEAD: f64 = 10.0;
+
+/// Score treated as worth one line of attack.
+const SCORE_PER_LINE: f64 = 400.0;
+
+/// How far ahead the AI is, in lines of attack: attack sent plus score,
+/// minus the same for its opponent. Negative when it's behind.
+pub fn lead(own: &GameState, opponent: &GameState) -> f64 {
+    let attack = own.stats.attack_sent as f64 - opponent.stats.attack_sent as f64;
+    let score = (own.stats.score as f64 - opponent.stats.score as f64) / SCORE_PER_LINE;
+    attack + score
+}
+
+/// Move `skill` against the lead: down while the AI is ahead, up while it's
+/// behind, unchanged inside the dead zone.
+pub fn step(skill: f64, lead: f64, dt: Duration) -> f64 {
+    let excess = lead.clamp(-MAX_LEAD, MAX_LEAD);
+    let excess = if excess.abs() <= DEAD_ZONE {
+        0.0
+    } else {
+        excess - DEAD_ZONE.copysign(excess)
+    };
+    (skill - excess * SKILL_RATE * dt.as_secs_f64()).clamp(0.0, 1.0)
+}
+
+/// Parameters for a skill level, blended between Easy and Expert.
+pub fn params(skill: f64) -> AiParams {
+    AiParams::lerp(
+        &AiDifficulty::Easy.params(),
+        &AiDifficulty::Expert.params(),
+        skill,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_pushes_against_lead() {
+        let second = Duration::from_secs(1);
+        assert!(step(0.5, 8.0, second) < 0.5);
+        assert!(step(0.5, -8.0, second) > 0.5);
+        assert_eq!(step(0.5, 1.5, second), 0.5);
+        assert_eq!(step(1.0, -50.0, Duration::from_secs(60)), 1.0);
+        assert_eq!(step(0.0, 50.0, Duration::from_secs(60)), 0.0);
+    }
+
+    #[test]
+    fn test_params_span_easy_to_expert() {
+        let easy = params(0.0);
+        let expert = params(1.0);
+        assert_eq!(easy.target_pps, AiDifficulty::Easy.target_pps());
+        assert_eq!(expert.target_pps, AiDifficulty::Expert.target_pps());
+        assert!(!easy.uses_hold && expert.uses_hold);
+        let start = AiDifficulty::Adaptive.params();
+        assert!(start.target_pps > easy.target_pps && start.target_pps < expert.target_pps);
+        assert!(start.error_rate < easy.error_rate && start.error_rate > expert.error_rate);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::game::board::{Board, BOARD_HEIGHT, BOARD_WIDTH};
+use crate::game::piece::{get_cells, PieceType, RotationState};
+
+/// All ten columns filled.
+const FULL_ROW: u16 = (1 << BOARD_WIDTH) - 1;
+
+/// Compact occupancy-only board for AI simulation: one `u16` per row, bit
+/// `c` set when column `c` is filled. Copying one is 80 bytes instead of the
+/// full cell grid, and the eval features reduce to bit operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitBoard {
+    /// Row 0 = bottom, matching `Board`.
+    pub rows: [u16; BOARD_HEIGHT],
+    /// Rows of solid garbage at the bottom, which are full but never clear.
+    pub floor: usize,
+}
+
+impl BitBoard {
+    pub fn from_board(board: &Board) -> Self {
+        let mut rows = [0u16; BOARD_HEIGHT];
+        for (row, bits) in rows.iter_mut().enumerate() {
+            for col in 0..BOARD_WIDTH {
+                if board.grid[row][col].is_occupied() {
+                    *bits |= 1 << col;
+                }
+            }
+        }
+        Self {
+            rows,
+            floor: board.solid_rows(),
+        }
+    }
+
+    pub fn is_filled(&self, col: i32, row: i32) -> bool {
+        if col < 0 || col >= BOARD_WIDTH as i32 || row < 0 || row >= BOARD_HEIGHT as i32 {
+            return true; // Out of bounds = solid
+        }
+        self.rows[row as usize] & (1 << col) != 0
+    }
+
+    /// Check if a piece fits at the given position and rotation.
+    pub fn fits(&self, piece_type: PieceType, x: i32, y: i32, rotation: RotationState) -> bool {
+        get_cells(piece_type, rotation)
+            .iter()
+            .all(|&(dx, dy)| !self.is_filled(x + dx, y + dy))
+    }
+
+    /// Lowest `y` the piece reaches when dropped straight down from `y`.
+    pub fn drop_y(&self, piece_type: PieceType, x: i32, y: i32, rotation: RotationState) -> i32 {
+        let mut y = y;
+        while self.fits(piece_type, x, y - 1, rotation) {
+            y -= 1;
+        }
+        y
+    }
+
+    /// Set the piece's cells. Cells outside the board are ignored.
+    pub fn lock(&mut self, piece_type: PieceType, x: i32, y: i32, rotation: RotationState) {
+        for (dx, dy) in get_cells(piece_type, rotation) {
+            let (col, row) = (x + dx, y + dy);
+            if (0..BOARD_WIDTH as i32).contains(&col) && (0..BOARD_HEIGHT as i32).contains(&row) {
+                self.rows[row as usize] |= 1 << col;
+            }
+        }
+    }
+
+    /// Remove full rows above the floor, collapsing the rest down. Returns
+    /// the number cleared.
+    pub fn clear_full_lines(&mut self) -> u32 {
+        let mut dest = self.floor;
+        for src in self.floor..BOARD_HEIGHT {
+            let bits = self.rows[src];
+            if bits != FULL_ROW {
+                self.rows[dest] = bits;
+                dest += 1;
+            }
+        }
+        let cleared = BOARD_HEIGHT - dest;
+        self.rows[dest..].fill(0);
+        cleared as u32
+    }
+
+    /// Whether nothing is left above the floor.
+    pub fn is_empty(&self) -> bool {
+        self.rows[self.floor..].iter().all(|&r| r == 0)
+    }
+
+    /// Height of every column (highest filled row + 1, or 0 if empty).
+    pub fn column_heights(&self) -> [usize; BOARD_WIDTH] {
+        let mut heights = [0; BOARD_WIDTH];
+        let mut remaining = FULL_ROW;
+        for row in (0..BOARD_HEIGHT).rev() {
+            let mut found = self.rows[row] & remaining;
+            remaining &= !found;
+            while found != 0 {
+                let col = found.trailing_zeros() as usize;
+                heights[col] = row + 1;
+                found &= found - 1;
+            }
+            if remaining == 0 {
+                break;
+            }
+        }
+        heights
+    }
+
+    /// Empty cells with at least one filled cell above them in the same column.
+    pub fn holes(&self) -> u32 {
+        let mut covered = 0u16;
+        let mut holes = 0;
+        for row in (0..BOARD_HEIGHT).rev() {
+            let bits = self.rows[row];
+            holes += (covered & !bits).count_ones();
+            covered |= bits;
+        }
+        holes
+    }
+
+    /// Filled/empty changes going up each column, counting the floor as filled.
+    pub fn column_transitions(&self) -> u32 {
+        let mut prev = FULL_ROW;
+        let mut transitions = 0;
+        for &bits in &self.rows {
+            transitions += ((bits ^ prev) & FULL_ROW).count_ones();
+            prev = bits;
+        }
+        transitions
+    }
+
+    /// Filled/empty changes across each row, counting both walls as filled.
+    pub fn row_transitions(&self) -> u32 {
+        // Bit 0 = left wall, bits 1..=10 = columns, bit 11 = right wall
+        const WALLED_PAIRS: u16 = (1 << (BOARD_WIDTH + 1)) - 1;
+        self.rows
+            .iter()
+            .map(|&bits| {
+                let walled = (bits << 1) | 1 | (1 << (BOARD_WIDTH + 1));
+                ((walled ^ (walled >> 1)) & WALLED_PAIRS).count_ones()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Cell;
+
+    fn sample_board() -> Board {
+        let mut board = Board::new();
+        // Uneven stack with a couple of covered holes
+        for (col, height) in [3, 1, 0, 4, 2, 2, 5, 0, 1, 3].iter().enumerate() {
+            for row in 0..*height {
+                board.set(col as i32, row, Cell::Garbage);
+            }
+        }
+        board.set(3, 1, Cell::Empty);
+        board.set(6, 0, Cell::Empty);
+        board
+    }
+
+    #[test]
+    fn test_metrics_match_board() {
+        let board = sample_board();
+        let bits = BitBoard::from_board(&board);
+
+        let heights = bits.column_heights();
+        for (col, &height) in heights.iter().enumerate() {
+            assert_eq!(height, board.column_height(col));
+        }
+        assert_eq!(bits.holes() as usize, board.count_holes());
+        assert_eq!(bits.is_empty(), board.is_empty());
+    }
+
+    #[test]
+    fn test_lock_and_clear() {
+        let mut bits = BitBoard::from_board(&Board::new());
+        bits.rows[0] = FULL_ROW & !(0b1111 << 3);
+
+        // Horizontal I dropped into the gap
+        let y = bits.drop_y(PieceType::I, 3, 20, RotationState::R0);
+        bits.lock(PieceType::I, 3, y, RotationState::R0);
+        assert_eq!(bits.clear_full_lines(), 1);
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn test_solid_rows_never_clear() {
+        let mut board = Board::new();
+        board.add_solid(2);
+        let mut bits = BitBoard::from_board(&board);
+        assert_eq!(bits.floor, 2);
+        assert!(bits.is_empty());
+
+        bits.rows[2] = FULL_ROW & !(0b1111 << 3);
+        let y = bits.drop_y(PieceType::I, 3, 20, RotationState::R0);
+        assert_eq!(y, 2);
+        bits.lock(PieceType::I, 3, y, RotationState::R0);
+        assert_eq!(bits.clear_full_lines(), 1);
+        assert_eq!(bits.rows[..2], [FULL_ROW; 2]);
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn test_empty_row_transitions() {
+        let bits = BitBoard::from_board(&Board::new());
+        // Each empty row changes once at each wall
+        assert_eq!(bits.row_transitions(), 2 * BOARD_HEIGHT as u32);
+        // Only the floor-to-first-row change in each column
+        assert_eq!(bits.column_transitions(), BOARD_WIDTH as u32);
+    }
+}
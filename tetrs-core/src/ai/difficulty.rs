@@ -0,0 +1,195 @@
+use super::adaptive;
+
+/// AI difficulty presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AiDifficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+    /// Rubber-banded between Easy and Expert to keep the match close.
+    Adaptive,
+}
+
+impl AiDifficulty {
+    pub const ALL: [AiDifficulty; 5] = [
+        AiDifficulty::Easy,
+        AiDifficulty::Medium,
+        AiDifficulty::Hard,
+        AiDifficulty::Expert,
+        AiDifficulty::Adaptive,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AiDifficulty::Easy => "Easy",
+            AiDifficulty::Medium => "Medium",
+            AiDifficulty::Hard => "Hard",
+            AiDifficulty::Expert => "Expert",
+            AiDifficulty::Adaptive => "Adaptive",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            AiDifficulty::Easy => "About 0.8 PPS, makes mistakes, sends little garbage",
+            AiDifficulty::Medium => "About 1.5 PPS, decent play",
+            AiDifficulty::Hard => "About 2.5 PPS, efficient, aggressive garbage",
+            AiDifficulty::Expert => "About 3.5 PPS, near-optimal play",
+            AiDifficulty::Adaptive => "Speeds up or eases off to keep it close",
+        }
+    }
+
+    /// Pieces per second the AI paces itself to.
+    pub fn target_pps(self) -> f64 {
+        match self {
+            AiDifficulty::Easy => 0.8,
+            AiDifficulty::Medium => 1.5,
+            AiDifficulty::Hard => 2.5,
+            AiDifficulty::Expert => 3.5,
+            AiDifficulty::Adaptive => self.params().target_pps,
+        }
+    }
+
+    /// How much each piece's time can stray from the target pace, as a
+    /// fraction either way; slower players are less even.
+    pub fn pace_variance(self) -> f64 {
+        match self {
+            AiDifficulty::Easy => 0.35,
+            AiDifficulty::Medium => 0.25,
+            AiDifficulty::Hard => 0.15,
+            AiDifficulty::Expert => 0.1,
+            AiDifficulty::Adaptive => self.params().pace_variance,
+        }
+    }
+
+    /// Probability of making a mistake (choosing suboptimal placement).
+    pub fn error_rate(self) -> f64 {
+        match self {
+            AiDifficulty::Easy => 0.15,
+            AiDifficulty::Medium => 0.05,
+            AiDifficulty::Hard => 0.01,
+            AiDifficulty::Expert => 0.0,
+            AiDifficulty::Adaptive => self.params().error_rate,
+        }
+    }
+
+    /// Whether the AI uses hold.
+    pub fn uses_hold(self) -> bool {
+        match self {
+            AiDifficulty::Easy => false,
+            AiDifficulty::Adaptive => self.params().uses_hold,
+            _ => true,
+        }
+    }
+
+    /// Evaluation weights for this difficulty.
+    pub fn weights(self) -> EvalWeights {
+        match self {
+            AiDifficulty::Easy => EvalWeights {
+                aggregate_height: -0.30,
+                holes: -0.25,
+                bumpiness: -0.10,
+                lines_cleared: 0.50,
+                wells: 0.05,
+                column_transitions: -0.05,
+                row_transitions: -0.03,
+                perfect_clear: 2.0,
+            },
+            AiDifficulty::Medium => EvalWeights {
+                aggregate_height: -0.45,
+                holes: -0.35,
+                bumpiness: -0.15,
+                lines_cleared: 0.65,
+                wells: 0.08,
+                column_transitions: -0.08,
+                row_transitions: -0.04,
+                perfect_clear: 4.0,
+            },
+            AiDifficulty::Hard => EvalWeights {
+                aggregate_height: -0.51,
+                holes: -0.36,
+                bumpiness: -0.18,
+                lines_cleared: 0.76,
+                wells: 0.10,
+                column_transitions: -0.10,
+                row_transitions: -0.05,
+                perfect_clear: 5.0,
+            },
+            AiDifficulty::Expert => EvalWeights {
+                aggregate_height: -0.51,
+                holes: -0.36,
+                bumpiness: -0.18,
+                lines_cleared: 0.76,
+                wells: 0.12,
+                column_transitions: -0.12,
+                row_transitions: -0.06,
+                perfect_clear: 6.0,
+            },
+            AiDifficulty::Adaptive => self.params().weights,
+        }
+    }
+
+    /// All tuning values for this difficulty. Adaptive starts partway
+    /// between Easy and Expert.
+    pub fn params(self) -> AiParams {
+        match self {
+            AiDifficulty::Adaptive => adaptive::params(adaptive::START_SKILL),
+            _ => AiParams {
+                target_pps: self.target_pps(),
+                pace_variance: self.pace_variance(),
+                error_rate: self.error_rate(),
+                uses_hold: self.uses_hold(),
+                weights: self.weights(),
+            },
+        }
+    }
+}
+
+/// Tuning an `AiPlayer` plays with, fixed for a preset difficulty and
+/// adjusted as the match goes for `Adaptive`.
+#[derive(Debug, Clone, Copy)]
+pub struct AiParams {
+    pub target_pps: f64,
+    pub pace_variance: f64,
+    pub error_rate: f64,
+    pub uses_hold: bool,
+    pub weights: EvalWeights,
+}
+
+impl AiParams {
+    /// Blend two sets of parameters. t=0 gives `a`, t=1 gives `b`.
+    pub fn lerp(a: &AiParams, b: &AiParams, t: f64) -> AiParams {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |x: f64, y: f64| x + (y - x) * t;
+        AiParams {
+            target_pps: mix(a.target_pps, b.target_pps),
+            pace_variance: mix(a.pace_variance, b.pace_variance),
+            error_rate: mix(a.error_rate, b.error_rate),
+            uses_hold: if t < 0.5 { a.uses_hold } else { b.uses_hold },
+            weights: EvalWeights {
+                aggregate_height: mix(a.weights.aggregate_height, b.weights.aggregate_height),
+                holes: mix(a.weights.holes, b.weights.holes),
+                bumpiness: mix(a.weights.bumpiness, b.weights.bumpiness),
+                lines_cleared: mix(a.weights.lines_cleared, b.weights.lines_cleared),
+                wells: mix(a.weights.wells, b.weights.wells),
+                column_transitions: mix(a.weights.column_transitions, b.weights.column_transitions),
+                row_transitions: mix(a.weights.row_transitions, b.weights.row_transitions),
+                perfect_clear: mix(a.weights.perfect_clear, b.weights.perfect_clear),
+            },
+        }
+    }
+}
+
+/// Heuristic evaluation weights.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct EvalWeights {
+    pub aggregate_height: f64,
+    pub holes: f64,
+    pub bumpiness: f64,
+    pub lines_cleared: f64,
+    pub wells: f64,
+    pub column_transitions: f64,
+    pub row_transitions: f64,
+    pub perfect_clear: f64,
+}
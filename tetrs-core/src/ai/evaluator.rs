@@ -0,0 +1,114 @@
+use crate::ai::bitboard::BitBoard;
+use crate::ai::difficulty::EvalWeights;
+use crate::game::board::{Board, BOARD_HEIGHT, BOARD_WIDTH};
+
+/// Evaluate a board state and return a score (higher = better).
+pub fn evaluate(board: &Board, lines_cleared: u32, weights: &EvalWeights) -> f64 {
+    evaluate_bits(&BitBoard::from_board(board), lines_cleared, weights)
+}
+
+/// Evaluate a bitboard state. This is the simulation path used for placements.
+pub fn evaluate_bits(board: &BitBoard, lines_cleared: u32, weights: &EvalWeights) -> f64 {
+    features(board, lines_cleared).score(weights)
+}
+
+/// The measurements a board is scored on, before weighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Features {
+    pub aggregate_height: usize,
+    pub holes: u32,
+    pub bumpiness: usize,
+    pub lines_cleared: u32,
+    pub wells: usize,
+    pub column_transitions: u32,
+    pub row_transitions: u32,
+    pub perfect_clear: bool,
+}
+
+impl Features {
+    pub fn score(&self, weights: &EvalWeights) -> f64 {
+        let mut score = 0.0;
+        score += weights.aggregate_height * self.aggregate_height as f64;
+        score += weights.holes * self.holes as f64;
+        score += weights.bumpiness * self.bumpiness as f64;
+        score += weights.lines_cleared * self.lines_cleared as f64;
+        score += weights.wells * self.wells as f64;
+        score += weights.column_transitions * self.column_transitions as f64;
+        score += weights.row_transitions * self.row_transitions as f64;
+
+        if self.perfect_clear {
+            score += weights.perfect_clear;
+        }
+
+        score
+    }
+}
+
+/// Measure a board left after clearing `lines_cleared` lines.
+pub fn features(board: &BitBoard, lines_cleared: u32) -> Features {
+    let heights = board.column_heights();
+    Features {
+        aggregate_height: heights.iter().sum(),
+        holes: board.holes(),
+        bumpiness: bumpiness(&heights),
+        lines_cleared,
+        wells: count_wells(&heights),
+        column_transitions: board.column_transitions(),
+        row_transitions: board.row_transitions(),
+        perfect_clear: board.is_empty() && lines_cleared > 0,
+    }
+}
+
+/// Sum of absolute differences between adjacent column heights.
+fn bumpiness(heights: &[usize; BOARD_WIDTH]) -> usize {
+    heights.windows(2).map(|w| w[0].abs_diff(w[1])).sum()
+}
+
+/// Count wells: sum of well depths. A well is a column lower than both neighbors.
+fn count_wells(heights: &[usize; BOARD_WIDTH]) -> usize {
+    let mut wells = 0;
+
+    for col in 0..BOARD_WIDTH {
+        let left_h = if col > 0 {
+            heights[col - 1]
+        } else {
+            BOARD_HEIGHT
+        };
+        let right_h = if col < BOARD_WIDTH - 1 {
+            heights[col + 1]
+        } else {
+            BOARD_HEIGHT
+        };
+        let h = heights[col];
+
+        if h < left_h && h < right_h {
+            wells += left_h.min(right_h) - h;
+        }
+    }
+
+    wells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::difficulty::AiDifficulty;
+
+    #[test]
+    fn test_empty_board_evaluation() {
+        let board = Board::new();
+        let weights = AiDifficulty::Hard.weights();
+        let score = evaluate(&board, 0, &weights);
+        // Empty board should have a relatively neutral score
+        assert!(score.is_finite());
+    }
+
+    #[test]
+    fn test_line_clear_improves_score() {
+        let board = Board::new();
+        let weights = AiDifficulty::Hard.weights();
+        let score_no_clear = evaluate(&board, 0, &weights);
+        let score_with_clear = evaluate(&board, 4, &weights);
+        assert!(score_with_clear > score_no_clear);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::ai::bitboard::BitBoard;
+use crate::ai::difficulty::EvalWeights;
+use crate::ai::evaluator::{self, Features};
+use crate::ai::placement::Placement;
+use crate::game::board::Board;
+use crate::game::piece::{PieceType, RotationState};
+
+/// A placement the AI weighed, broken down into what it was scored on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub piece_type: PieceType,
+    /// Leftmost column the piece covers.
+    pub column: i32,
+    pub rotation: RotationState,
+    pub use_hold: bool,
+    pub features: Features,
+    /// Weighted features alone.
+    pub eval: f64,
+    /// Full score, including the play style's bias.
+    pub score: f64,
+}
+
+impl Candidate {
+    /// Break down `placement` made on `board`.
+    pub fn new(board: &Board, placement: &Placement, weights: &EvalWeights) -> Self {
+        let mut after = BitBoard::from_board(board);
+        after.lock(
+            placement.piece_type,
+            placement.x,
+            placement.y,
+            placement.rotation,
+        );
+        let lines = after.clear_full_lines();
+        let features = evaluator::features(&after, lines);
+        Self {
+            piece_type: placement.piece_type,
+            column: placement.cells().iter().map(|&(c, _)| c).min().unwrap_or(0),
+            rotation: placement.rotation,
+            use_hold: placement.use_hold,
+            features,
+            eval: features.score(weights),
+            score: placement.score,
+        }
+    }
+
+    /// Score the play style added on top of the evaluation.
+    pub fn bias(&self) -> f64 {
+        self.score - self.eval
+    }
+}
+
+/// Why the AI picked its last placement: the chosen placement next to the
+/// best one it passed over.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub chosen: Candidate,
+    pub runner_up: Option<Candidate>,
+    /// Whether the choice was a deliberate mistake rather than the best.
+    pub mistake: bool,
+}
+
+impl Explanation {
+    /// Explain picking `chosen` on `board`, against `alternatives` sorted
+    /// best first.
+    pub fn new(
+        board: &Board,
+        chosen: &Placement,
+        alternatives: &[Placement],
+        weights: &EvalWeights,
+        mistake: bool,
+    ) -> Self {
+        let same = |p: &Placement| {
+            (p.piece_type, p.x, p.y, p.rotation, p.use_hold)
+                == (
+                    chosen.piece_type,
+                    chosen.x,
+                    chosen.y,
+                    chosen.rotation,
+                    chosen.use_hold,
+                )
+        };
+        Self {
+            chosen: Candidate::new(board, chosen, weights),
+            runner_up: alternatives
+                .iter()
+                .find(|p| !same(p))
+                .map(|p| Candidate::new(board, p, weights)),
+            mistake,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::difficulty::AiDifficulty;
+    use crate::ai::personality::AiPersonality;
+    use crate::ai::placement::generate_placements;
+    use crate::game::board::Cell;
+    use crate::game::srs::SrsPlus;
+
+    #[test]
+    fn test_explains_chosen_against_runner_up() {
+        // Bottom row one short at column 9, where an upright I clears it
+        let mut board = Board::new();
+        for col in 0..9 {
+            board.set(col, 0, Cell::Garbage);
+        }
+        let weights = AiDifficulty::Hard.weights();
+        let placements = generate_placements(
+            &board,
+            PieceType::I,
+            &weights,
+            AiPersonality::Balanced,
+            false,
+            &SrsPlus,
+        );
+        let explanation = Explanation::new(&board, &placements[0], &placements, &weights, false);
+
+        let chosen = &explanation.chosen;
+        assert_eq!(chosen.features.lines_cleared, 1);
+        assert_eq!(chosen.bias(), 0.0);
+        let runner_up = explanation.runner_up.as_ref().unwrap();
+        assert!(runner_up.score <= chosen.score);
+        assert_eq!((chosen.column, chosen.rotation), (9, RotationState::R1));
+        assert_ne!(
+            (runner_up.column, runner_up.rotation),
+            (chosen.column, chosen.rotation)
+        );
+    }
+}
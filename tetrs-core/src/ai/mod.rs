@@ -0,0 +1,331 @@
+pub mod adaptive;
+pub mod bitboard;
+pub mod difficulty;
+pub mod evaluator;
+pub mod explain;
+pub mod perfect_clear;
+pub mod personality;
+pub mod placement;
+pub mod rating;
+pub mod simulate;
+pub mod worker;
+
+use std::time::Duration;
+
+use self::difficulty::{AiDifficulty, AiParams};
+use self::explain::Explanation;
+use self::personality::AiPersonality;
+use self::placement::Placement;
+use self::worker::{SearchRequest, SearchResponse, SearchWorker};
+use crate::game::piece::PieceType;
+use crate::game::rng::GameRng;
+use crate::game::{GameAction, GameState};
+
+/// Share of each piece's time spent thinking before the first input; the
+/// rest is spread over the inputs.
+const THINK_SHARE: f64 = 0.5;
+
+/// What a search was run for; a result for anything else is stale.
+type SearchKey = (u32, PieceType, Option<PieceType>);
+
+/// AI player that controls a Tetris board.
+#[derive(Debug)]
+pub struct AiPlayer {
+    pub difficulty: AiDifficulty,
+    /// Play style, applied on top of the difficulty's tuning.
+    pub personality: AiPersonality,
+    /// Tuning currently played with; moves during the match when adaptive.
+    pub params: AiParams,
+    /// Adaptive skill between Easy (0) and Expert (1).
+    pub skill: f64,
+    /// Current target placement.
+    target: Option<Placement>,
+    /// Breakdown of the last placement picked.
+    explanation: Option<Explanation>,
+    /// Think timer (delay before AI starts moving).
+    think_timer: Duration,
+    /// Time between inputs for the current piece.
+    move_interval: Duration,
+    /// Time since the current turn's placement was picked.
+    piece_clock: Duration,
+    /// Seconds the current turn was given by the pace, and the part of
+    /// that left once catching up on `lag`.
+    budget: f64,
+    spend: Duration,
+    /// Seconds behind the target pace, at most one piece's worth.
+    lag: f64,
+    /// Move accumulator for movement speed.
+    move_accumulator: Duration,
+    /// Whether the piece being placed came out of hold, so its thinking
+    /// was already done.
+    held: bool,
+    /// Next input to play from the target's path.
+    path_step: usize,
+    /// Track previous attack sent to detect new attacks.
+    last_attack_sent: u32,
+    /// Source of deliberate mistakes.
+    rng: GameRng,
+    /// Placement search running off the render loop.
+    worker: SearchWorker,
+    /// Id and key of the search in flight.
+    pending: Option<(u64, SearchKey)>,
+    next_search_id: u64,
+}
+
+impl AiPlayer {
+    /// New AI with a random seed for its mistakes.
+    #[cfg(feature = "entropy")]
+    pub fn new(difficulty: AiDifficulty) -> Self {
+        Self::with_seed(difficulty, rand::random())
+    }
+
+    pub fn with_seed(difficulty: AiDifficulty, seed: u64) -> Self {
+        Self {
+            difficulty,
+            personality: AiPersonality::default(),
+            params: difficulty.params(),
+            skill: adaptive::START_SKILL,
+            target: None,
+            explanation: None,
+            think_timer: Duration::ZERO,
+            move_interval: Duration::ZERO,
+            piece_clock: Duration::ZERO,
+            budget: 0.0,
+            spend: Duration::ZERO,
+            lag: 0.0,
+            move_accumulator: Duration::ZERO,
+            held: false,
+            path_step: 0,
+            last_attack_sent: 0,
+            rng: GameRng::seed_from_u64(seed),
+            worker: SearchWorker::new(),
+            pending: None,
+            next_search_id: 0,
+        }
+    }
+
+    /// Search on the calling thread, so play depends only on the seed and
+    /// frame times; for headless runs.
+    pub fn with_inline_search(mut self) -> Self {
+        self.worker = SearchWorker::inline();
+        self
+    }
+
+    /// Think and return actions for this frame.
+    pub fn think(&mut self, game: &GameState, dt: Duration) -> Vec<GameAction> {
+        self.piece_clock += dt;
+        if game.game_over || game.current_piece.is_none() {
+            self.target = None;
+            return Vec::new();
+        }
+
+        // If we don't have a target, find one
+        if self.target.is_none() {
+            let Some(response) = self.poll_search(game) else {
+                // Still searching
+                return Vec::new();
+            };
+            let mut best = response.best;
+            let placements = response.alternatives;
+
+            // Error chance: sometimes pick a worse placement
+            let mut mistake = false;
+            if best.is_some()
+                && self.rng.next_f64() < self.params.error_rate
+                && placements.len() > 1
+            {
+                let idx = self.rng.range(1, placements.len());
+                best = Some(placements[idx].clone());
+                mistake = true;
+            }
+
+            if let Some(ref placement) = best {
+                tracing::debug!(
+                    difficulty = ?self.difficulty,
+                    personality = ?self.personality,
+                    piece = ?placement.piece_type,
+                    x = placement.x,
+                    rotation = ?placement.rotation,
+                    hold = placement.use_hold,
+                    score = placement.score,
+                    "ai placement"
+                );
+                let weights = self.personality.weights(self.params.weights);
+                self.explanation = Some(Explanation::new(
+                    &game.board,
+                    placement,
+                    &placements,
+                    &weights,
+                    mistake,
+                ));
+            }
+            self.pace(best.as_ref());
+            self.target = best;
+            self.path_step = 0;
+            self.move_accumulator = Duration::ZERO;
+        }
+
+        // Think delay
+        if self.think_timer > Duration::ZERO {
+            self.think_timer = self.think_timer.saturating_sub(dt);
+            return Vec::new();
+        }
+
+        let target = match &self.target {
+            Some(t) => t.clone(),
+            None => return vec![GameAction::HardDrop],
+        };
+
+        // Check if we need to hold
+        if target.use_hold && !game.hold.used_this_turn {
+            self.target = None; // Will re-evaluate after hold
+            self.held = true;
+            return vec![GameAction::Hold];
+        }
+
+        self.move_accumulator += dt;
+        if self.move_accumulator < self.move_interval {
+            return Vec::new();
+        }
+        self.move_accumulator -= self.move_interval;
+
+        // Follow the planned path one input per step; a run of soft drops
+        // counts as one step, released afterwards so gravity returns to normal
+        let Some(&action) = target.path.get(self.path_step) else {
+            self.target = None;
+            return vec![GameAction::HardDrop];
+        };
+
+        if action == GameAction::SoftDrop {
+            let drops = target.path[self.path_step..]
+                .iter()
+                .take_while(|&&a| a == GameAction::SoftDrop)
+                .count();
+            self.path_step += drops;
+            let mut actions = vec![GameAction::SoftDrop; drops];
+            actions.push(GameAction::SoftDropRelease);
+            return actions;
+        }
+
+        self.path_step += 1;
+        if action == GameAction::HardDrop {
+            self.target = None;
+        }
+        vec![action]
+    }
+
+    /// Split this piece's time, the target pace give or take the variance,
+    /// into thinking and then evenly spaced inputs. Time lost to line clears
+    /// and spawns is made up on the next pieces, so the AI holds its PPS.
+    fn pace(&mut self, target: Option<&Placement>) {
+        let steps = target.map_or(1, |t| input_steps(&t.path)).max(1) as u32;
+        if std::mem::take(&mut self.held) {
+            // Still the same turn: place the held piece in what's left of it
+            self.think_timer = Duration::ZERO;
+            self.move_interval = self.spend.saturating_sub(self.piece_clock) / steps;
+            return;
+        }
+
+        let piece_time = 1.0 / self.params.target_pps;
+        let over = self.piece_clock.as_secs_f64() - self.budget;
+        self.lag = (self.lag + over).clamp(0.0, piece_time);
+        self.piece_clock = Duration::ZERO;
+
+        let spread = self.params.pace_variance * (self.rng.next_f64() * 2.0 - 1.0);
+        self.budget = piece_time * (1.0 + spread);
+        let spend = (self.budget - self.lag).max(0.0);
+        self.spend = Duration::from_secs_f64(spend);
+        self.think_timer = Duration::from_secs_f64(spend * THINK_SHARE);
+        self.move_interval = Duration::from_secs_f64(spend * (1.0 - THINK_SHARE)) / steps;
+    }
+
+    /// Start a search for the current piece if none is running, and return
+    /// its result once finished. Results for an earlier piece are dropped.
+    fn poll_search(&mut self, game: &GameState) -> Option<SearchResponse> {
+        let piece_type = game.current_piece.as_ref()?.piece_type;
+        let key = (game.stats.pieces_placed, piece_type, game.hold.piece);
+
+        if self.pending.map(|(_, k)| k) != Some(key) {
+            let id = self.next_search_id;
+            self.next_search_id += 1;
+            self.worker.submit(SearchRequest {
+                id,
+                board: game.board.clone(),
+                current: piece_type,
+                hold: game.hold.piece,
+                weights: self.personality.weights(self.params.weights),
+                personality: self.personality,
+                use_hold: self.params.uses_hold && game.hold_enabled(),
+                rotation_system: game.ruleset.rotation_system,
+            });
+            self.pending = Some((id, key));
+        }
+
+        while let Some(response) = self.worker.poll() {
+            if self.pending.map(|(id, _)| id) == Some(response.id) {
+                self.pending = None;
+                return Some(response);
+            }
+        }
+        None
+    }
+
+    /// Piece and cells of the placement the AI is heading for, if it has
+    /// picked one.
+    pub fn plan(&self) -> Option<(PieceType, [(i32, i32); 4])> {
+        self.target.as_ref().map(|t| (t.piece_type, t.cells()))
+    }
+
+    /// Why the AI picked the last placement it went for.
+    pub fn explanation(&self) -> Option<&Explanation> {
+        self.explanation.as_ref()
+    }
+
+    /// Get the attack damage sent by the AI since last check.
+    pub fn check_attack(&mut self, game: &GameState) -> u32 {
+        let current = game.stats.attack_sent;
+        let delta = current.saturating_sub(self.last_attack_sent);
+        self.last_attack_sent = current;
+        delta
+    }
+
+    /// Rubber-band an adaptive AI against its opponent: ease off while it's
+    /// ahead, speed up while it's behind. No-op for fixed difficulties.
+    pub fn adapt(&mut self, own: &GameState, opponent: &GameState, dt: Duration) {
+        if self.difficulty != AiDifficulty::Adaptive || own.game_over || opponent.game_over {
+            return;
+        }
+        self.skill = adaptive::step(self.skill, adaptive::lead(own, opponent), dt);
+        self.params = adaptive::params(self.skill);
+    }
+
+    /// Reset AI state for new game.
+    pub fn reset(&mut self) {
+        self.params = self.difficulty.params();
+        self.skill = adaptive::START_SKILL;
+        self.target = None;
+        self.explanation = None;
+        self.think_timer = Duration::ZERO;
+        self.move_interval = Duration::ZERO;
+        self.piece_clock = Duration::ZERO;
+        self.budget = 0.0;
+        self.spend = Duration::ZERO;
+        self.lag = 0.0;
+        self.move_accumulator = Duration::ZERO;
+        self.held = false;
+        self.path_step = 0;
+        self.last_attack_sent = 0;
+        // Any result still in flight will carry an old id and be ignored
+        self.pending = None;
+    }
+}
+
+/// Inputs `think` plays a path in: a run of soft drops goes in one step.
+fn input_steps(path: &[GameAction]) -> usize {
+    path.iter()
+        .enumerate()
+        .filter(|&(i, &a)| {
+            a != GameAction::SoftDrop || i == 0 || path[i - 1] != GameAction::SoftDrop
+        })
+        .count()
+}
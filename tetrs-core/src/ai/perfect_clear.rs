@@ -0,0 +1,370 @@
+#[cfg(feature = "threads")]
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use std::collections::HashSet;
+
+use super::bitboard::BitBoard;
+use super::placement::lock_positions;
+use crate::game::board::{Board, BOARD_HEIGHT, BOARD_WIDTH};
+use crate::game::piece::{get_cells, PieceType};
+use crate::game::srs::{KickTable, RotationSystem};
+
+/// Tallest perfect clear searched for. Taller ones exist but need more
+/// pieces than the queue shows.
+pub const MAX_HEIGHT: usize = 4;
+/// Boards tried per search before giving up, keeping the worst case to a
+/// fraction of a second.
+const NODE_BUDGET: usize = 20_000;
+
+/// A piece in a perfect clear solution and the cells it locks into, in
+/// the rows of the board the search started from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcPiece {
+    pub piece_type: PieceType,
+    pub cells: [(i32, i32); 4],
+}
+
+/// What a perfect clear search found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PcOutcome {
+    /// A perfect clear with these pieces, placed in order.
+    Found(Vec<PcPiece>),
+    /// No perfect clear within `MAX_HEIGHT` rows using the pieces given.
+    Impossible,
+    /// The search ran out of budget before settling it either way.
+    GaveUp,
+}
+
+/// Inputs for one perfect clear search.
+#[derive(Debug, Clone)]
+pub struct PcRequest {
+    pub id: u64,
+    pub board: Board,
+    pub current: PieceType,
+    pub hold: Option<PieceType>,
+    /// Visible next pieces, soonest first.
+    pub queue: Vec<PieceType>,
+    pub hold_enabled: bool,
+    /// Whether hold was already used for the current piece.
+    pub hold_used: bool,
+    pub rotation_system: RotationSystem,
+}
+
+/// Result of a perfect clear search.
+#[derive(Debug, Clone)]
+pub struct PcResponse {
+    pub id: u64,
+    pub outcome: PcOutcome,
+}
+
+/// Depth-first search state shared across one request.
+struct Search<'a> {
+    pieces: Vec<PieceType>,
+    hold_enabled: bool,
+    hold_used: bool,
+    kick_table: &'a dyn KickTable,
+    /// Boards already tried, keyed with what was left to place them with.
+    seen: HashSet<(u64, usize, Option<PieceType>, usize)>,
+    nodes: usize,
+    path: Vec<PcPiece>,
+}
+
+/// Whether a perfect clear can still be reached with the pieces in hand,
+/// and one way to do it.
+pub fn solve(request: &PcRequest) -> PcOutcome {
+    // Searched on the rows above any solid garbage, which never clear, then
+    // mapped back up onto the board
+    let mut bits = BitBoard::from_board(&request.board);
+    let floor = bits.floor;
+    bits.rows.copy_within(floor.., 0);
+    bits.rows[BOARD_HEIGHT - floor..].fill(0);
+    bits.floor = 0;
+    let stack_height = bits.rows.iter().rposition(|&r| r != 0).map_or(0, |r| r + 1);
+    if stack_height > MAX_HEIGHT {
+        return PcOutcome::Impossible;
+    }
+    let filled: usize = bits.rows.iter().map(|r| r.count_ones() as usize).sum();
+
+    let mut pieces = vec![request.current];
+    pieces.extend(&request.queue);
+    let hold = request.hold.filter(|_| request.hold_enabled);
+    let mut search = Search {
+        pieces,
+        hold_enabled: request.hold_enabled,
+        hold_used: request.hold_used,
+        kick_table: request.rotation_system.kick_table(),
+        seen: HashSet::new(),
+        nodes: 0,
+        path: Vec::new(),
+    };
+
+    let mut outcome = PcOutcome::Impossible;
+    for height in stack_height.max(1)..=MAX_HEIGHT {
+        // Every piece adds four cells, so only some heights work out
+        if !(height * BOARD_WIDTH - filled).is_multiple_of(4) {
+            continue;
+        }
+        search.seen.clear();
+        let rows: Vec<i32> = (floor as i32..(floor + height) as i32).collect();
+        match search.place(bits, 0, hold, &rows) {
+            Some(true) => return PcOutcome::Found(search.path),
+            Some(false) => {}
+            None => outcome = PcOutcome::GaveUp,
+        }
+    }
+    outcome
+}
+
+impl Search<'_> {
+    /// Try to clear the bottom `rows.len()` rows of `bits` exactly, with
+    /// pieces from `next` on. `rows` maps each of those rows back to the
+    /// starting board. `None` when the budget runs out.
+    fn place(
+        &mut self,
+        bits: BitBoard,
+        next: usize,
+        hold: Option<PieceType>,
+        rows: &[i32],
+    ) -> Option<bool> {
+        if rows.is_empty() {
+            return Some(true);
+        }
+        let limit = rows.len();
+        let filled: usize = bits.rows[..limit]
+            .iter()
+            .map(|r| r.count_ones() as usize)
+            .sum();
+        // Each placement uses up one queue piece, whether or not it goes
+        // through hold
+        let needed = (limit * BOARD_WIDTH - filled) / 4;
+        if needed > self.pieces.len() - next {
+            return Some(false);
+        }
+        let packed = bits.rows[..limit]
+            .iter()
+            .fold(0u64, |acc, &r| acc << BOARD_WIDTH | r as u64);
+        if !self.seen.insert((packed, next, hold, limit)) {
+            return Some(false);
+        }
+        self.nodes += 1;
+        if self.nodes > NODE_BUDGET {
+            return None;
+        }
+
+        let current = self.pieces[next];
+        let mut choices = vec![(current, next + 1, hold)];
+        if self.hold_enabled && !(next == 0 && self.hold_used) {
+            match hold {
+                Some(held) if held != current => choices.push((held, next + 1, Some(current))),
+                Some(_) => {}
+                None if next + 1 < self.pieces.len() => {
+                    choices.push((self.pieces[next + 1], next + 2, Some(current)))
+                }
+                None => {}
+            }
+        }
+
+        let mut gave_up = false;
+        for (piece_type, after, hold) in choices {
+            let mut tried = HashSet::new();
+            for (x, y, rotation) in lock_positions(&bits, piece_type, self.kick_table) {
+                let mut cells = get_cells(piece_type, rotation).map(|(dx, dy)| (x + dx, y + dy));
+                if cells.iter().any(|&(_, row)| row >= limit as i32) {
+                    continue;
+                }
+                // Some rotations of I, S and Z fill the same cells
+                cells.sort_unstable();
+                if !tried.insert(cells) {
+                    continue;
+                }
+
+                let mut placed = bits;
+                placed.lock(piece_type, x, y, rotation);
+                let full_row = (1u16 << BOARD_WIDTH) - 1;
+                let left: Vec<i32> = (0..limit)
+                    .filter(|&r| placed.rows[r] != full_row)
+                    .map(|r| rows[r])
+                    .collect();
+                placed.clear_full_lines();
+
+                self.path.push(PcPiece {
+                    piece_type,
+                    cells: cells.map(|(col, row)| (col, rows[row as usize])),
+                });
+                match self.place(placed, after, hold, &left) {
+                    Some(true) => return Some(true),
+                    Some(false) => {}
+                    None => gave_up = true,
+                }
+                self.path.pop();
+                if gave_up {
+                    return None;
+                }
+            }
+        }
+        Some(false)
+    }
+}
+
+/// Runs perfect clear searches off the render loop, like
+/// `worker::SearchWorker` does for placements.
+#[derive(Debug)]
+pub struct PcWorker {
+    #[cfg(feature = "threads")]
+    thread: Option<(Sender<PcRequest>, Receiver<PcResponse>)>,
+    ready: Option<PcResponse>,
+}
+
+impl Default for PcWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PcWorker {
+    #[cfg(feature = "threads")]
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<PcRequest>();
+        let (response_tx, response_rx) = mpsc::channel();
+
+        std::thread::Builder::new()
+            .name("tetrs-pc-search".to_string())
+            .spawn(move || {
+                while let Ok(mut request) = request_rx.recv() {
+                    // Only the newest board is worth solving
+                    while let Ok(newer) = request_rx.try_recv() {
+                        request = newer;
+                    }
+                    let response = PcResponse {
+                        id: request.id,
+                        outcome: solve(&request),
+                    };
+                    if response_tx.send(response).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn perfect clear search thread");
+
+        Self {
+            thread: Some((request_tx, response_rx)),
+            ready: None,
+        }
+    }
+
+    #[cfg(not(feature = "threads"))]
+    pub fn new() -> Self {
+        Self { ready: None }
+    }
+
+    /// Queue a search.
+    pub fn submit(&mut self, request: PcRequest) {
+        #[cfg(feature = "threads")]
+        if let Some((ref requests, _)) = self.thread {
+            let _ = requests.send(request);
+            return;
+        }
+        self.ready = Some(PcResponse {
+            id: request.id,
+            outcome: solve(&request),
+        });
+    }
+
+    /// Take a finished search result, if any. Never blocks.
+    pub fn poll(&mut self) -> Option<PcResponse> {
+        #[cfg(feature = "threads")]
+        if let Some((_, ref responses)) = self.thread {
+            return responses.try_recv().ok();
+        }
+        self.ready.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Cell;
+
+    fn request(board: Board, current: PieceType, queue: &[PieceType]) -> PcRequest {
+        PcRequest {
+            id: 0,
+            board,
+            current,
+            hold: None,
+            queue: queue.to_vec(),
+            hold_enabled: true,
+            hold_used: false,
+            rotation_system: RotationSystem::default(),
+        }
+    }
+
+    /// Bottom `rows` rows filled except for the columns in `gaps`.
+    fn board_with_gaps(rows: usize, gaps: &[usize]) -> Board {
+        let mut board = Board::new();
+        for row in 0..rows {
+            for col in 0..BOARD_WIDTH {
+                if !gaps.contains(&col) {
+                    board.grid[row][col] = Cell::Garbage;
+                }
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn test_finds_a_single_piece_clear() {
+        // A four-deep well takes an upright I
+        let board = board_with_gaps(4, &[9]);
+        let outcome = solve(&request(board, PieceType::I, &[]));
+        let PcOutcome::Found(pieces) = outcome else {
+            panic!("expected a perfect clear, got {:?}", outcome);
+        };
+        assert_eq!(pieces.len(), 1);
+        let mut cells = pieces[0].cells;
+        cells.sort_unstable();
+        assert_eq!(cells, [(9, 0), (9, 1), (9, 2), (9, 3)]);
+    }
+
+    #[test]
+    fn test_uses_hold_to_reach_the_clear() {
+        let board = board_with_gaps(4, &[9]);
+        let outcome = solve(&request(board.clone(), PieceType::O, &[PieceType::I]));
+        assert!(matches!(outcome, PcOutcome::Found(ref p) if p[0].piece_type == PieceType::I));
+
+        let mut no_hold = request(board, PieceType::O, &[PieceType::I]);
+        no_hold.hold_enabled = false;
+        assert_eq!(solve(&no_hold), PcOutcome::Impossible);
+    }
+
+    #[test]
+    fn test_clears_down_to_solid_garbage() {
+        let mut board = board_with_gaps(1, &[3, 4, 5, 6]);
+        board.add_solid(2);
+        let outcome = solve(&request(board, PieceType::I, &[]));
+        let PcOutcome::Found(pieces) = outcome else {
+            panic!("expected a perfect clear, got {:?}", outcome);
+        };
+        assert_eq!(pieces[0].cells.map(|(_, row)| row), [2; 4]);
+    }
+
+    #[test]
+    fn test_pc_from_empty_board() {
+        // Two lines: the I pieces stacked on the left, an O beside them and
+        // two interlocking Js on the right
+        let queue = [PieceType::O, PieceType::I, PieceType::J, PieceType::J];
+        let outcome = solve(&request(Board::new(), PieceType::I, &queue));
+        let PcOutcome::Found(pieces) = outcome else {
+            panic!("expected a perfect clear, got {:?}", outcome);
+        };
+        assert_eq!(pieces.len(), 5);
+    }
+
+    #[test]
+    fn test_too_few_pieces_is_impossible() {
+        let board = board_with_gaps(2, &[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(
+            solve(&request(board, PieceType::O, &[])),
+            PcOutcome::Impossible
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::ai::bitboard::BitBoard;
+use crate::ai::difficulty::EvalWeights;
+use crate::game::board::BOARD_WIDTH;
+use crate::game::piece::{get_cells, PieceType, RotationState};
+use crate::modes::trainer::OPENERS;
+
+/// Columns a 4-wide player keeps open for combos.
+const FOUR_WIDE_WELL: std::ops::RangeInclusive<i32> = 3..=6;
+/// Rows scanned for T-spin double slots; slots higher up aren't worth fishing for.
+const SLOT_SCAN_ROWS: i32 = 16;
+
+/// How the AI likes to play, independent of how well: each reshapes the
+/// difficulty's evaluation weights and adds a bias toward the placements
+/// that play style is after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum AiPersonality {
+    /// The plain evaluator, going for a clean, low stack.
+    #[default]
+    Balanced,
+    /// Builds up both sides and combos down a 4-wide well in the middle.
+    FourWide,
+    /// Leaves T-spin double slots and fills them with a spun T.
+    TSpinFisher,
+    /// Keeps the stack low and clean above all, clearing whatever comes in.
+    Downstacker,
+    /// Opens every game with TKI when the pieces allow it.
+    OpenerBot,
+}
+
+impl AiPersonality {
+    pub const ALL: [AiPersonality; 5] = [
+        AiPersonality::Balanced,
+        AiPersonality::FourWide,
+        AiPersonality::TSpinFisher,
+        AiPersonality::Downstacker,
+        AiPersonality::OpenerBot,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AiPersonality::Balanced => "Balanced",
+            AiPersonality::FourWide => "4-Wide Spammer",
+            AiPersonality::TSpinFisher => "T-Spin Fisher",
+            AiPersonality::Downstacker => "Downstacker",
+            AiPersonality::OpenerBot => "Opener Bot",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            AiPersonality::Balanced => "Plays it straight: clean, low and steady",
+            AiPersonality::FourWide => "Stacks the sides and combos down a 4-wide well",
+            AiPersonality::TSpinFisher => "Sets up T-spin doubles and spins into them",
+            AiPersonality::Downstacker => "Digs out and keeps the stack low at all costs",
+            AiPersonality::OpenerBot => "Starts with a TKI opener, then plays normally",
+        }
+    }
+
+    /// `base` reshaped for this play style.
+    pub fn weights(self, base: EvalWeights) -> EvalWeights {
+        match self {
+            AiPersonality::Balanced | AiPersonality::OpenerBot => base,
+            // A tall stack is the point, and the well makes it bumpy
+            AiPersonality::FourWide => EvalWeights {
+                aggregate_height: base.aggregate_height * 0.3,
+                bumpiness: base.bumpiness * 0.4,
+                wells: 0.0,
+                ..base
+            },
+            // Slots are overhangs, so holes above them are tolerated more
+            AiPersonality::TSpinFisher => EvalWeights {
+                holes: base.holes * 0.7,
+                lines_cleared: base.lines_cleared * 0.5,
+                ..base
+            },
+            AiPersonality::Downstacker => EvalWeights {
+                aggregate_height: base.aggregate_height * 1.6,
+                holes: base.holes * 1.8,
+                lines_cleared: base.lines_cleared * 1.3,
+                ..base
+            },
+        }
+    }
+
+    /// Extra score for locking `piece` at (`x`, `y`, `rotation`) onto
+    /// `before`, leaving `after` once `lines` are cleared. `spun` is whether
+    /// the last input before the drop was a rotation.
+    pub fn bias(
+        self,
+        before: &BitBoard,
+        after: &BitBoard,
+        piece: PieceType,
+        (x, y, rotation): (i32, i32, RotationState),
+        lines: u32,
+        spun: bool,
+    ) -> f64 {
+        match self {
+            AiPersonality::Balanced | AiPersonality::Downstacker => 0.0,
+            AiPersonality::FourWide => -0.6 * well_cells(after) as f64,
+            AiPersonality::TSpinFisher => {
+                let spin = if piece == PieceType::T && spun && lines > 0 {
+                    3.0 * lines as f64
+                } else {
+                    0.0
+                };
+                spin + 1.5 * has_tsd_slot(after) as u8 as f64
+            }
+            AiPersonality::OpenerBot => {
+                let cells = get_cells(piece, rotation).map(|(dx, dy)| (x + dx, y + dy));
+                if within_opener(before) && cells.iter().all(|&c| opener_cell(c)) {
+                    4.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Filled cells in the 4-wide well columns.
+fn well_cells(board: &BitBoard) -> u32 {
+    let mask: u16 = FOUR_WIDE_WELL.map(|c| 1u16 << c).sum();
+    board.rows.iter().map(|row| (row & mask).count_ones()).sum()
+}
+
+/// Whether the board has a slot a T can spin into for a double: the stem
+/// cell open with both sides filled, the three cells above it open, and an
+/// overhang on at least one side to spin under.
+fn has_tsd_slot(board: &BitBoard) -> bool {
+    let open = |c: i32, r: i32| !board.is_filled(c, r);
+    (1..BOARD_WIDTH as i32 - 1).any(|c| {
+        (0..SLOT_SCAN_ROWS).any(|r| {
+            open(c, r)
+                && board.is_filled(c, r - 1)
+                && board.is_filled(c - 1, r)
+                && board.is_filled(c + 1, r)
+                && open(c - 1, r + 1)
+                && open(c, r + 1)
+                && open(c + 1, r + 1)
+                && (board.is_filled(c - 1, r + 2) || board.is_filled(c + 1, r + 2))
+        })
+    })
+}
+
+/// Whether `cell` is part of the TKI opener's finished shape.
+fn opener_cell(cell: (i32, i32)) -> bool {
+    OPENERS[0]
+        .steps
+        .iter()
+        .any(|step| step.cells().contains(&cell))
+}
+
+/// Whether everything on the board so far fits the opener's shape.
+fn within_opener(board: &BitBoard) -> bool {
+    board.rows.iter().enumerate().all(|(r, &row)| {
+        (0..BOARD_WIDTH as i32)
+            .filter(|&c| row & (1 << c) != 0)
+            .all(|c| opener_cell((c, r as i32)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::difficulty::AiDifficulty;
+    use crate::ai::placement::generate_placements;
+    use crate::game::board::{Board, Cell};
+    use crate::game::srs::SrsPlus;
+
+    #[test]
+    fn test_tsd_slot_found() {
+        // Stem hole at column 1, overhang over column 0:
+        //   X.........
+        //   ...XXXXXXX
+        //   X.XXXXXXXX
+        let mut board = Board::new();
+        for col in 0..10 {
+            if col != 1 {
+                board.set(col, 0, Cell::Garbage);
+            }
+            if col >= 3 {
+                board.set(col, 1, Cell::Garbage);
+            }
+        }
+        assert!(!has_tsd_slot(&BitBoard::from_board(&board)));
+        board.set(0, 2, Cell::Garbage);
+        assert!(has_tsd_slot(&BitBoard::from_board(&board)));
+    }
+
+    #[test]
+    fn test_four_wide_keeps_well_open() {
+        let board = Board::new();
+        let weights = AiDifficulty::Hard.weights();
+        let best = |personality: AiPersonality| {
+            generate_placements(
+                &board,
+                PieceType::O,
+                &personality.weights(weights),
+                personality,
+                false,
+                &SrsPlus,
+            )[0]
+            .cells()
+        };
+        let in_well =
+            |cells: [(i32, i32); 4]| cells.iter().any(|(c, _)| FOUR_WIDE_WELL.contains(c));
+        assert!(!in_well(best(AiPersonality::FourWide)));
+    }
+
+    #[test]
+    fn test_opener_bot_builds_tki() {
+        let board = Board::new();
+        let weights = AiDifficulty::Hard.weights();
+        let placements = generate_placements(
+            &board,
+            PieceType::I,
+            &weights,
+            AiPersonality::OpenerBot,
+            false,
+            &SrsPlus,
+        );
+        assert!(placements[0].cells().iter().all(|&c| opener_cell(c)));
+    }
+}
@@ -0,0 +1,366 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::ai::bitboard::BitBoard;
+use crate::ai::difficulty::EvalWeights;
+use crate::ai::evaluator;
+use crate::ai::personality::AiPersonality;
+use crate::game::board::Board;
+use crate::game::piece::{Piece, PieceType, RotationState};
+use crate::game::srs::KickTable;
+use crate::game::GameAction;
+
+/// A potential placement for a piece.
+#[derive(Debug, Clone)]
+pub struct Placement {
+    pub piece_type: PieceType,
+    pub rotation: RotationState,
+    pub x: i32,
+    pub y: i32,
+    pub score: f64,
+    pub use_hold: bool,
+    /// Inputs that take the piece from spawn to this position, ending in a
+    /// hard drop.
+    pub path: Vec<GameAction>,
+}
+
+impl Placement {
+    /// Board cells the piece locks into.
+    pub fn cells(&self) -> [(i32, i32); 4] {
+        Piece::new(self.piece_type).cells_at(self.x, self.y, self.rotation)
+    }
+}
+
+/// Piece position during the search: (x, y, rotation).
+type SearchState = (i32, i32, RotationState);
+
+/// Inputs explored by the search, in order of preference for equal-length paths.
+const SEARCH_MOVES: [GameAction; 6] = [
+    GameAction::MoveLeft,
+    GameAction::MoveRight,
+    GameAction::RotateCW,
+    GameAction::RotateCCW,
+    GameAction::Rotate180,
+    GameAction::SoftDrop,
+];
+
+/// Generate all placements reachable from spawn, best first.
+///
+/// Breadth-first search over (x, y, rotation) using the game's own movement
+/// and SRS kick rules, so tucks, slides, and spins under overhangs are found
+/// along with plain drops. Each placement carries the shortest input path
+/// to reach it, and is scored by `weights` plus the personality's bias.
+pub fn generate_placements(
+    board: &Board,
+    piece_type: PieceType,
+    weights: &EvalWeights,
+    personality: AiPersonality,
+    use_hold: bool,
+    kick_table: &dyn KickTable,
+) -> Vec<Placement> {
+    let bits = BitBoard::from_board(board);
+    let spawn = Piece::new(piece_type);
+    let start = (spawn.x, spawn.y, spawn.rotation);
+    if !bits.fits(piece_type, start.0, start.1, start.2) {
+        return Vec::new();
+    }
+
+    let mut came_from: HashMap<SearchState, Option<(SearchState, GameAction)>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    came_from.insert(start, None);
+    queue.push_back(start);
+
+    let mut placements = Vec::new();
+
+    while let Some(state) = queue.pop_front() {
+        for action in SEARCH_MOVES {
+            if let Some(next) = apply_move(&bits, kick_table, piece_type, state, action) {
+                if let Entry::Vacant(entry) = came_from.entry(next) {
+                    entry.insert(Some((state, action)));
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        // Only grounded positions can lock
+        let (x, y, rotation) = state;
+        if bits.fits(piece_type, x, y - 1, rotation) {
+            continue;
+        }
+
+        // Simulate locking and evaluate
+        let mut test_board = bits;
+        test_board.lock(piece_type, x, y, rotation);
+        let lines = test_board.clear_full_lines();
+
+        let path = build_path(&came_from, state);
+        let spun = path.len() >= 2
+            && matches!(
+                path[path.len() - 2],
+                GameAction::RotateCW | GameAction::RotateCCW | GameAction::Rotate180
+            );
+        let score = evaluator::evaluate_bits(&test_board, lines, weights)
+            + personality.bias(&bits, &test_board, piece_type, state, lines, spun);
+
+        placements.push(Placement {
+            piece_type,
+            rotation,
+            x,
+            y,
+            score,
+            use_hold,
+            path,
+        });
+    }
+
+    // Stable sort: ties keep the shorter path found first
+    placements.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    placements
+}
+
+/// Grounded positions a piece can reach from spawn and lock in, found the
+/// same way as `generate_placements` but without paths or scores.
+pub(crate) fn lock_positions(
+    bits: &BitBoard,
+    piece_type: PieceType,
+    kick_table: &dyn KickTable,
+) -> Vec<(i32, i32, RotationState)> {
+    let spawn = Piece::new(piece_type);
+    let start = (spawn.x, spawn.y, spawn.rotation);
+    if !bits.fits(piece_type, start.0, start.1, start.2) {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    let mut positions = Vec::new();
+    while let Some(state) = queue.pop_front() {
+        for action in SEARCH_MOVES {
+            if let Some(next) = apply_move(bits, kick_table, piece_type, state, action) {
+                if seen.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        let (x, y, rotation) = state;
+        if !bits.fits(piece_type, x, y - 1, rotation) {
+            positions.push(state);
+        }
+    }
+    positions
+}
+
+/// Position after one input, or `None` if the input does nothing.
+fn apply_move(
+    bits: &BitBoard,
+    kick_table: &dyn KickTable,
+    piece_type: PieceType,
+    (x, y, rotation): SearchState,
+    action: GameAction,
+) -> Option<SearchState> {
+    let target = match action {
+        GameAction::MoveLeft => {
+            return bits
+                .fits(piece_type, x - 1, y, rotation)
+                .then_some((x - 1, y, rotation))
+        }
+        GameAction::MoveRight => {
+            return bits
+                .fits(piece_type, x + 1, y, rotation)
+                .then_some((x + 1, y, rotation))
+        }
+        GameAction::SoftDrop => {
+            return bits
+                .fits(piece_type, x, y - 1, rotation)
+                .then_some((x, y - 1, rotation))
+        }
+        // The O piece looks the same in every rotation
+        _ if piece_type == PieceType::O => return None,
+        GameAction::RotateCW => rotation.cw(),
+        GameAction::RotateCCW => rotation.ccw(),
+        GameAction::Rotate180 => rotation.flip(),
+        _ => return None,
+    };
+
+    kick_table
+        .kicks(piece_type, rotation, target)
+        .iter()
+        .find(|&&(dx, dy)| bits.fits(piece_type, x + dx, y + dy, target))
+        .map(|&(dx, dy)| (x + dx, y + dy, target))
+}
+
+/// Walk back from `end` to spawn and return the inputs in order.
+fn build_path(
+    came_from: &HashMap<SearchState, Option<(SearchState, GameAction)>>,
+    end: SearchState,
+) -> Vec<GameAction> {
+    let mut path = Vec::new();
+    let mut state = end;
+    while let Some(&Some((prev, action))) = came_from.get(&state) {
+        path.push(action);
+        state = prev;
+    }
+    path.reverse();
+
+    // A hard drop covers any soft drops straight to the floor
+    while path.last() == Some(&GameAction::SoftDrop) {
+        path.pop();
+    }
+    path.push(GameAction::HardDrop);
+    path
+}
+
+/// Find the best placement for a piece, optionally considering hold.
+pub fn find_best_placement(
+    board: &Board,
+    current_type: PieceType,
+    hold_type: Option<PieceType>,
+    weights: &EvalWeights,
+    personality: AiPersonality,
+    use_hold: bool,
+    kick_table: &dyn KickTable,
+) -> Option<Placement> {
+    let mut best: Option<Placement> = None;
+
+    // Try current piece
+    let placements =
+        generate_placements(board, current_type, weights, personality, false, kick_table);
+    if let Some(p) = placements.first() {
+        best = Some(p.clone());
+    }
+
+    // Try hold piece if available and allowed
+    if use_hold {
+        if let Some(hold_type) = hold_type {
+            let hold_placements =
+                generate_placements(board, hold_type, weights, personality, true, kick_table);
+            if let Some(p) = hold_placements.first() {
+                if best.as_ref().is_none_or(|b| p.score > b.score) {
+                    best = Some(p.clone());
+                }
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::difficulty::AiDifficulty;
+    use crate::game::board::Cell;
+    use crate::game::movement;
+    use crate::game::srs::SrsPlus;
+
+    #[test]
+    fn test_generate_placements() {
+        let board = Board::new();
+        let weights = AiDifficulty::Hard.weights();
+        let placements = generate_placements(
+            &board,
+            PieceType::T,
+            &weights,
+            AiPersonality::Balanced,
+            false,
+            &SrsPlus,
+        );
+        assert!(!placements.is_empty());
+    }
+
+    /// Roof over columns 0-2 at row 2; the only way under is sliding along the floor.
+    fn overhang_board() -> Board {
+        let mut board = Board::new();
+        for col in 0..3 {
+            board.set(col, 2, Cell::Garbage);
+        }
+        board
+    }
+
+    #[test]
+    fn test_reaches_tuck_under_overhang() {
+        let board = overhang_board();
+        let weights = AiDifficulty::Hard.weights();
+        let placements = generate_placements(
+            &board,
+            PieceType::I,
+            &weights,
+            AiPersonality::Balanced,
+            false,
+            &SrsPlus,
+        );
+
+        let tuck = placements
+            .iter()
+            .find(|p| p.rotation == RotationState::R0 && p.x == 0 && p.y == 0)
+            .expect("tucked I placement should be reachable");
+        assert!(tuck.path.contains(&GameAction::SoftDrop));
+        assert_eq!(tuck.path.last(), Some(&GameAction::HardDrop));
+    }
+
+    #[test]
+    fn test_path_replays_to_placement() {
+        let board = overhang_board();
+        let weights = AiDifficulty::Hard.weights();
+        for placement in generate_placements(
+            &board,
+            PieceType::T,
+            &weights,
+            AiPersonality::Balanced,
+            false,
+            &SrsPlus,
+        ) {
+            let mut piece = Piece::new(PieceType::T);
+            for action in &placement.path {
+                let rotation = piece.rotation;
+                let moved = match action {
+                    GameAction::MoveLeft => movement::try_move_left(&board, &mut piece),
+                    GameAction::MoveRight => movement::try_move_right(&board, &mut piece),
+                    GameAction::SoftDrop => movement::try_move_down(&board, &mut piece),
+                    GameAction::HardDrop => {
+                        movement::hard_drop(&board, &mut piece);
+                        true
+                    }
+                    GameAction::RotateCW => {
+                        movement::try_rotate(&board, &mut piece, rotation.cw(), &SrsPlus).is_some()
+                    }
+                    GameAction::RotateCCW => {
+                        movement::try_rotate(&board, &mut piece, rotation.ccw(), &SrsPlus).is_some()
+                    }
+                    GameAction::Rotate180 => {
+                        movement::try_rotate(&board, &mut piece, rotation.flip(), &SrsPlus)
+                            .is_some()
+                    }
+                    _ => unreachable!(),
+                };
+                assert!(moved, "{action:?} failed on the way to {placement:?}");
+            }
+            assert_eq!(
+                (piece.x, piece.y, piece.rotation),
+                (placement.x, placement.y, placement.rotation)
+            );
+            assert_eq!(piece.cells(), placement.cells());
+        }
+    }
+
+    #[test]
+    fn test_find_best() {
+        let board = Board::new();
+        let weights = AiDifficulty::Hard.weights();
+        let best = find_best_placement(
+            &board,
+            PieceType::T,
+            None,
+            &weights,
+            AiPersonality::Balanced,
+            false,
+            &SrsPlus,
+        );
+        assert!(best.is_some());
+    }
+}
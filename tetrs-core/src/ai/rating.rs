@@ -0,0 +1,61 @@
+use crate::ai::difficulty::AiDifficulty;
+
+/// Rating a player starts from before their first rated match.
+pub const START_RATING: f64 = 1000.0;
+
+/// Most a single match can move a rating.
+const K_FACTOR: f64 = 32.0;
+
+/// Rating gap at which the stronger side is expected to win ten times as
+/// often.
+const SCALE: f64 = 400.0;
+
+/// Fixed rating of an AI opponent. Adaptive is rated by the skill it
+/// finished the match at, between Easy and Expert.
+pub fn ai_rating(difficulty: AiDifficulty, skill: f64) -> f64 {
+    match difficulty {
+        AiDifficulty::Easy => 800.0,
+        AiDifficulty::Medium => 1200.0,
+        AiDifficulty::Hard => 1600.0,
+        AiDifficulty::Expert => 2000.0,
+        AiDifficulty::Adaptive => {
+            let easy = ai_rating(AiDifficulty::Easy, 0.0);
+            let expert = ai_rating(AiDifficulty::Expert, 0.0);
+            easy + (expert - easy) * skill.clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// Chance of beating `opponent` at `rating`.
+pub fn expected(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / SCALE))
+}
+
+/// Rating after a match against `opponent`.
+pub fn rate(rating: f64, opponent: f64, won: bool) -> f64 {
+    let score = if won { 1.0 } else { 0.0 };
+    rating + K_FACTOR * (score - expected(rating, opponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_match_moves_half_k() {
+        assert_eq!(expected(1200.0, 1200.0), 0.5);
+        assert_eq!(rate(1200.0, 1200.0, true), 1216.0);
+        assert_eq!(rate(1200.0, 1200.0, false), 1184.0);
+    }
+
+    #[test]
+    fn test_upsets_count_more() {
+        let easy = ai_rating(AiDifficulty::Easy, 0.0);
+        let expert = ai_rating(AiDifficulty::Expert, 0.0);
+        let beat_expert = rate(START_RATING, expert, true) - START_RATING;
+        let beat_easy = rate(START_RATING, easy, true) - START_RATING;
+        assert!(beat_expert > beat_easy);
+        assert!(START_RATING - rate(START_RATING, easy, false) > beat_easy);
+        assert_eq!(ai_rating(AiDifficulty::Adaptive, 0.5), 1400.0);
+    }
+}
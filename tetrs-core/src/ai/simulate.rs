@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+use super::difficulty::{AiDifficulty, AiParams};
+use super::AiPlayer;
+use crate::game::GameState;
+
+/// Frame time of a simulated game, as if running at 60 FPS.
+const STEP: Duration = Duration::from_micros(16_667);
+
+/// Outcome of one headless AI game.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameSummary {
+    pub seed: u64,
+    pub lines: u32,
+    pub pieces: u32,
+    pub score: u64,
+    pub attack: u32,
+    pub pps: f64,
+    pub apm: f64,
+    /// Game time until topping out, or the time limit.
+    pub survival_secs: f64,
+    pub topped_out: bool,
+}
+
+/// Play one game with no opponent until the AI tops out or `time_limit`
+/// of game time passes. The same seed and parameters always give the same
+/// game.
+pub fn simulate_game(
+    difficulty: AiDifficulty,
+    params: AiParams,
+    seed: u64,
+    time_limit: Duration,
+) -> GameSummary {
+    let mut game = GameState::with_seed(seed);
+    game.start();
+    let mut ai = AiPlayer::with_seed(difficulty, seed.rotate_left(32)).with_inline_search();
+    ai.params = params;
+
+    while !game.game_over && game.stats.time < time_limit {
+        for action in ai.think(&game, STEP) {
+            game.handle_action(action);
+        }
+        game.update(STEP);
+        game.drain_events();
+    }
+
+    let stats = &game.stats;
+    GameSummary {
+        seed,
+        lines: stats.lines_cleared,
+        pieces: stats.pieces_placed,
+        score: stats.score,
+        attack: stats.attack_sent,
+        pps: stats.pps(),
+        apm: stats.apm(),
+        survival_secs: stats.time.as_secs_f64(),
+        topped_out: game.game_over,
+    }
+}
+
+/// Mean and range of one stat across games.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Spread {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Spread {
+    fn of(values: impl Iterator<Item = f64>) -> Self {
+        let (mut sum, mut count) = (0.0, 0);
+        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+        for value in values {
+            sum += value;
+            count += 1;
+            min = min.min(value);
+            max = max.max(value);
+        }
+        if count == 0 {
+            return Self {
+                mean: 0.0,
+                min: 0.0,
+                max: 0.0,
+            };
+        }
+        Self {
+            mean: sum / count as f64,
+            min,
+            max,
+        }
+    }
+}
+
+/// Aggregate stats over a batch of simulated games.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimReport {
+    pub games: usize,
+    pub topped_out: usize,
+    pub lines: Spread,
+    pub pps: Spread,
+    pub attack: Spread,
+    pub apm: Spread,
+    pub survival_secs: Spread,
+}
+
+impl SimReport {
+    pub fn new(games: &[GameSummary]) -> Self {
+        Self {
+            games: games.len(),
+            topped_out: games.iter().filter(|g| g.topped_out).count(),
+            lines: Spread::of(games.iter().map(|g| g.lines as f64)),
+            pps: Spread::of(games.iter().map(|g| g.pps)),
+            attack: Spread::of(games.iter().map(|g| g.attack as f64)),
+            apm: Spread::of(games.iter().map(|g| g.apm)),
+            survival_secs: Spread::of(games.iter().map(|g| g.survival_secs)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulation_is_deterministic() {
+        let difficulty = AiDifficulty::Expert;
+        let limit = Duration::from_secs(10);
+        let a = simulate_game(difficulty, difficulty.params(), 11, limit);
+        let b = simulate_game(difficulty, difficulty.params(), 11, limit);
+        assert_eq!(a, b);
+        assert!(a.pieces > 0);
+        assert!(!a.topped_out);
+        assert!(a.survival_secs >= 10.0);
+    }
+
+    #[test]
+    fn test_ai_keeps_to_target_pace() {
+        for difficulty in [AiDifficulty::Medium, AiDifficulty::Expert] {
+            let game = simulate_game(difficulty, difficulty.params(), 5, Duration::from_secs(30));
+            let target = difficulty.target_pps();
+            assert!(
+                game.pps > target * 0.8 && game.pps < target * 1.1,
+                "{difficulty:?}: {} PPS",
+                game.pps
+            );
+        }
+    }
+
+    #[test]
+    fn test_report_aggregates() {
+        let game = |lines, topped_out| GameSummary {
+            seed: 0,
+            lines,
+            pieces: 0,
+            score: 0,
+            attack: 0,
+            pps: 0.0,
+            apm: 0.0,
+            survival_secs: 0.0,
+            topped_out,
+        };
+        let report = SimReport::new(&[game(10, true), game(30, false)]);
+        assert_eq!(report.games, 2);
+        assert_eq!(report.topped_out, 1);
+        assert_eq!(
+            report.lines,
+            Spread {
+                mean: 20.0,
+                min: 10.0,
+                max: 30.0
+            }
+        );
+    }
+}
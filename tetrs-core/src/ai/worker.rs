@@ -0,0 +1,176 @@
+#[cfg(feature = "threads")]
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use super::difficulty::EvalWeights;
+use super::personality::AiPersonality;
+use super::placement::{find_best_placement, generate_placements, Placement};
+use crate::game::board::Board;
+use crate::game::piece::PieceType;
+use crate::game::srs::RotationSystem;
+
+/// Number of runner-up placements returned for deliberate mistakes.
+const ALTERNATIVES: usize = 5;
+
+/// Inputs for one placement search.
+#[derive(Debug, Clone)]
+pub struct SearchRequest {
+    pub id: u64,
+    pub board: Board,
+    pub current: PieceType,
+    pub hold: Option<PieceType>,
+    pub weights: EvalWeights,
+    pub personality: AiPersonality,
+    pub use_hold: bool,
+    pub rotation_system: RotationSystem,
+}
+
+/// Result of a placement search.
+#[derive(Debug, Clone)]
+pub struct SearchResponse {
+    pub id: u64,
+    pub best: Option<Placement>,
+    /// Best placements for the current piece without hold, best first.
+    pub alternatives: Vec<Placement>,
+}
+
+/// Run a search synchronously.
+pub fn run_search(request: &SearchRequest) -> SearchResponse {
+    let kick_table = request.rotation_system.kick_table();
+    let best = find_best_placement(
+        &request.board,
+        request.current,
+        request.hold,
+        &request.weights,
+        request.personality,
+        request.use_hold,
+        kick_table,
+    );
+    let mut alternatives = generate_placements(
+        &request.board,
+        request.current,
+        &request.weights,
+        request.personality,
+        false,
+        kick_table,
+    );
+    alternatives.truncate(ALTERNATIVES);
+
+    SearchResponse {
+        id: request.id,
+        best,
+        alternatives,
+    }
+}
+
+/// Runs placement searches off the render loop.
+///
+/// With the `threads` feature each worker owns a thread that lives as long
+/// as the worker. Without it (e.g. on wasm32), or when made with `inline`,
+/// searches run in `submit` and the result is handed back on the next
+/// `poll`.
+#[derive(Debug)]
+pub struct SearchWorker {
+    #[cfg(feature = "threads")]
+    thread: Option<(Sender<SearchRequest>, Receiver<SearchResponse>)>,
+    ready: Option<SearchResponse>,
+}
+
+impl Default for SearchWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchWorker {
+    #[cfg(feature = "threads")]
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<SearchRequest>();
+        let (response_tx, response_rx) = mpsc::channel();
+
+        std::thread::Builder::new()
+            .name("tetrs-ai-search".to_string())
+            .spawn(move || {
+                // Exits when the worker (and with it the request sender) is dropped
+                while let Ok(request) = request_rx.recv() {
+                    if response_tx.send(run_search(&request)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn AI search thread");
+
+        Self {
+            thread: Some((request_tx, response_rx)),
+            ready: None,
+        }
+    }
+
+    #[cfg(not(feature = "threads"))]
+    pub fn new() -> Self {
+        Self::inline()
+    }
+
+    /// Worker that searches on the calling thread, so results never depend
+    /// on how long a search takes.
+    pub fn inline() -> Self {
+        Self {
+            #[cfg(feature = "threads")]
+            thread: None,
+            ready: None,
+        }
+    }
+
+    /// Queue a search.
+    pub fn submit(&mut self, request: SearchRequest) {
+        #[cfg(feature = "threads")]
+        if let Some((ref requests, _)) = self.thread {
+            let _ = requests.send(request);
+            return;
+        }
+        self.ready = Some(run_search(&request));
+    }
+
+    /// Take a finished search result, if any. Never blocks.
+    pub fn poll(&mut self) -> Option<SearchResponse> {
+        #[cfg(feature = "threads")]
+        if let Some((_, ref responses)) = self.thread {
+            return responses.try_recv().ok();
+        }
+        self.ready.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::difficulty::AiDifficulty;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_worker_answers_request() {
+        let mut worker = SearchWorker::new();
+        worker.submit(SearchRequest {
+            id: 7,
+            board: Board::new(),
+            current: PieceType::T,
+            hold: None,
+            weights: AiDifficulty::Hard.weights(),
+            personality: AiPersonality::Balanced,
+            use_hold: false,
+            rotation_system: RotationSystem::default(),
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let response = loop {
+            if let Some(response) = worker.poll() {
+                break response;
+            }
+            assert!(Instant::now() < deadline, "search timed out");
+            std::thread::sleep(Duration::from_millis(1));
+        };
+
+        assert_eq!(response.id, 7);
+        assert!(response.best.is_some());
+        assert!(!response.alternatives.is_empty());
+    }
+}
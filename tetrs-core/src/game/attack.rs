@@ -0,0 +1,229 @@
+use super::clear::ClearType;
+
+/// Shortest back-to-back chain that surges when broken.
+pub const SURGE_MIN_CHAIN: i32 = 4;
+
+/// Jstris combo bonus, added to the base attack of any clear.
+const JSTRIS_COMBO: [u32; 13] = [0, 0, 1, 1, 1, 2, 2, 3, 3, 4, 4, 4, 5];
+
+/// Extra garbage for a clear that continues a back-to-back chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum BackToBackBonus {
+    None,
+    /// The same bonus at any chain length.
+    Flat(u32),
+    /// TETR.IO's logarithmic bonus, growing slowly with the chain.
+    Logarithmic,
+}
+
+impl BackToBackBonus {
+    fn bonus(self, btb: i32) -> u32 {
+        if btb <= 0 {
+            return 0;
+        }
+        match self {
+            BackToBackBonus::None => 0,
+            BackToBackBonus::Flat(bonus) => bonus,
+            BackToBackBonus::Logarithmic => {
+                let x = (1.0 + btb as f64 * 0.8).ln();
+                let bonus = (x + 1.0).floor() + (1.0 + (x % 1.0)) / 3.0;
+                bonus.floor() as u32
+            }
+        }
+    }
+}
+
+/// Garbage sent per clear, indexed by combo count. Each row's last entry
+/// repeats for longer combos. Missing fields in a config take TETR.IO's values.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AttackTable {
+    pub single: Vec<u32>,
+    pub double: Vec<u32>,
+    pub triple: Vec<u32>,
+    pub quad: Vec<u32>,
+    pub tspin_single: Vec<u32>,
+    pub tspin_double: Vec<u32>,
+    pub tspin_triple: Vec<u32>,
+    pub mini_tspin_single: Vec<u32>,
+    pub back_to_back: BackToBackBonus,
+    pub perfect_clear: u32,
+    /// Attack for a clear past the third combo that would otherwise send nothing.
+    pub combo_minimum: u32,
+    /// Whether breaking a long back-to-back chain releases its length as garbage.
+    pub surge: bool,
+}
+
+impl Default for AttackTable {
+    fn default() -> Self {
+        Self::tetrio()
+    }
+}
+
+/// Built-in attack tables, selectable by name in configs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AttackTablePreset {
+    TetrIo,
+    Jstris,
+    Classic,
+}
+
+impl AttackTablePreset {
+    pub fn table(self) -> AttackTable {
+        match self {
+            AttackTablePreset::TetrIo => AttackTable::tetrio(),
+            AttackTablePreset::Jstris => AttackTable::jstris(),
+            AttackTablePreset::Classic => AttackTable::classic(),
+        }
+    }
+}
+
+/// Jstris: a flat base plus the shared combo bonus.
+fn jstris_row(base: u32) -> Vec<u32> {
+    JSTRIS_COMBO.iter().map(|bonus| base + bonus).collect()
+}
+
+impl AttackTable {
+    /// TETR.IO multiplayer numbers: combos scale with the clear size.
+    pub fn tetrio() -> Self {
+        let small = vec![
+            0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3,
+        ];
+        let triple = vec![
+            2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12,
+        ];
+        let quad = vec![
+            4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        ];
+        Self {
+            single: small.clone(),
+            double: vec![
+                1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 6,
+            ],
+            triple: triple.clone(),
+            quad: quad.clone(),
+            tspin_single: triple,
+            tspin_double: quad,
+            tspin_triple: vec![
+                6, 7, 9, 10, 12, 13, 15, 16, 18, 19, 21, 22, 24, 25, 27, 28, 30, 31, 33, 34, 36,
+            ],
+            mini_tspin_single: small,
+            back_to_back: BackToBackBonus::Logarithmic,
+            perfect_clear: 10,
+            combo_minimum: 1,
+            surge: false,
+        }
+    }
+
+    /// Jstris multiplayer numbers: fixed base attack plus a flat combo table.
+    pub fn jstris() -> Self {
+        Self {
+            single: jstris_row(0),
+            double: jstris_row(1),
+            triple: jstris_row(2),
+            quad: jstris_row(4),
+            tspin_single: jstris_row(2),
+            tspin_double: jstris_row(4),
+            tspin_triple: jstris_row(6),
+            mini_tspin_single: jstris_row(0),
+            back_to_back: BackToBackBonus::Flat(1),
+            perfect_clear: 10,
+            combo_minimum: 0,
+            surge: false,
+        }
+    }
+
+    /// Old versus rules: lines minus one, four for a quad, no bonuses.
+    pub fn classic() -> Self {
+        Self {
+            single: vec![0],
+            double: vec![1],
+            triple: vec![2],
+            quad: vec![4],
+            tspin_single: vec![0],
+            tspin_double: vec![1],
+            tspin_triple: vec![2],
+            mini_tspin_single: vec![0],
+            back_to_back: BackToBackBonus::None,
+            perfect_clear: 0,
+            combo_minimum: 0,
+            surge: false,
+        }
+    }
+
+    fn row(&self, clear_type: &ClearType) -> Option<&[u32]> {
+        let row = match clear_type {
+            ClearType::Single => &self.single,
+            ClearType::Double => &self.double,
+            ClearType::Triple => &self.triple,
+            ClearType::Quad => &self.quad,
+            ClearType::TSpinSingle => &self.tspin_single,
+            ClearType::TSpinDouble => &self.tspin_double,
+            ClearType::TSpinTriple => &self.tspin_triple,
+            ClearType::MiniTSpinSingle => &self.mini_tspin_single,
+            _ => return None,
+        };
+        Some(row)
+    }
+
+    /// Garbage for a clear at the given combo and back-to-back chain.
+    pub fn attack(
+        &self,
+        clear_type: &ClearType,
+        combo: i32,
+        btb: i32,
+        is_perfect_clear: bool,
+    ) -> u32 {
+        let combo = combo.max(0) as usize;
+        let base = self
+            .row(clear_type)
+            .and_then(|row| row.get(combo).or(row.last()))
+            .copied()
+            .unwrap_or(0);
+        let pc_bonus = if is_perfect_clear {
+            self.perfect_clear
+        } else {
+            0
+        };
+        base + self.back_to_back.bonus(btb) + pc_bonus
+    }
+
+    /// Surge garbage for breaking a back-to-back chain of length `btb`.
+    pub fn surge(&self, btb: i32) -> u32 {
+        if self.surge && btb >= SURGE_MIN_CHAIN {
+            btb as u32
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_combo_repeats_last_entry() {
+        let table = AttackTable::tetrio();
+        assert_eq!(table.attack(&ClearType::Quad, 20, -1, false), 24);
+        assert_eq!(table.attack(&ClearType::Quad, 40, -1, false), 24);
+    }
+
+    #[test]
+    fn test_surge_needs_flag_and_chain() {
+        let mut table = AttackTable::tetrio();
+        assert_eq!(table.surge(8), 0);
+        table.surge = true;
+        assert_eq!(table.surge(SURGE_MIN_CHAIN - 1), 0);
+        assert_eq!(table.surge(8), 8);
+    }
+
+    #[test]
+    fn test_jstris_numbers() {
+        let table = AttackTable::jstris();
+        assert_eq!(table.attack(&ClearType::Quad, 0, -1, false), 4);
+        // Back-to-back quad on a 4 combo: 4 base + 1 combo + 1 B2B
+        assert_eq!(table.attack(&ClearType::Quad, 4, 1, false), 6);
+        assert_eq!(table.attack(&ClearType::TSpinDouble, 0, 0, true), 14);
+    }
+}
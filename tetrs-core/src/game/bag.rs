@@ -0,0 +1,412 @@
+use std::collections::VecDeque;
+
+use super::piece::PieceType;
+use super::rng::GameRng;
+
+/// Pieces generated ahead of time so the preview always matches the draws.
+const LOOKAHEAD: usize = 14;
+
+/// Source of the piece sequence.
+pub trait Randomizer: std::fmt::Debug + Send {
+    /// Produce the next piece of the sequence.
+    fn generate(&mut self, rng: &mut GameRng) -> PieceType;
+
+    fn clone_box(&self) -> Box<dyn Randomizer>;
+}
+
+impl Clone for Box<dyn Randomizer> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Selectable randomizer, as stored in configs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RandomizerKind {
+    /// Each bag holds one of every piece.
+    #[default]
+    SevenBag,
+    /// Each bag holds two of every piece.
+    FourteenBag,
+    /// Every piece independently random.
+    Memoryless,
+    /// Reroll pieces found in the last four, up to four times.
+    TgmHistory,
+    /// One of every piece plus `x` random extras per bag.
+    SevenPlus(u8),
+}
+
+impl RandomizerKind {
+    pub const ALL: [RandomizerKind; 5] = [
+        RandomizerKind::SevenBag,
+        RandomizerKind::FourteenBag,
+        RandomizerKind::Memoryless,
+        RandomizerKind::TgmHistory,
+        RandomizerKind::SevenPlus(1),
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            RandomizerKind::SevenBag => "7-Bag".to_string(),
+            RandomizerKind::FourteenBag => "14-Bag".to_string(),
+            RandomizerKind::Memoryless => "Memoryless".to_string(),
+            RandomizerKind::TgmHistory => "TGM History".to_string(),
+            RandomizerKind::SevenPlus(extra) => format!("7+{} Bag", extra),
+        }
+    }
+
+    pub fn build(self) -> Box<dyn Randomizer> {
+        match self {
+            RandomizerKind::SevenBag => Box::new(Bag::new(1, 0)),
+            RandomizerKind::FourteenBag => Box::new(Bag::new(2, 0)),
+            RandomizerKind::Memoryless => Box::new(Memoryless),
+            RandomizerKind::TgmHistory => Box::new(TgmHistory::new()),
+            RandomizerKind::SevenPlus(extra) => Box::new(Bag::new(1, extra as usize)),
+        }
+    }
+}
+
+/// Bag randomizer: `copies` of every piece plus `extra` random ones, shuffled.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Bag {
+    copies: usize,
+    extra: usize,
+    /// Remaining pieces, drawn from the end.
+    current: Vec<PieceType>,
+}
+
+impl Bag {
+    pub fn new(copies: usize, extra: usize) -> Self {
+        Self {
+            copies,
+            extra,
+            current: Vec::new(),
+        }
+    }
+
+    fn refill(&mut self, rng: &mut GameRng) {
+        for _ in 0..self.copies {
+            self.current.extend_from_slice(&PieceType::ALL);
+        }
+        for _ in 0..self.extra {
+            self.current
+                .push(PieceType::ALL[rng.below(PieceType::ALL.len())]);
+        }
+        rng.shuffle(&mut self.current);
+    }
+}
+
+impl Randomizer for Bag {
+    fn generate(&mut self, rng: &mut GameRng) -> PieceType {
+        if self.current.is_empty() {
+            self.refill(rng);
+        }
+        self.current.pop().unwrap()
+    }
+
+    fn clone_box(&self) -> Box<dyn Randomizer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Classic randomizer: every piece uniformly random, no memory.
+#[derive(Debug, Clone)]
+pub struct Memoryless;
+
+impl Randomizer for Memoryless {
+    fn generate(&mut self, rng: &mut GameRng) -> PieceType {
+        PieceType::ALL[rng.below(PieceType::ALL.len())]
+    }
+
+    fn clone_box(&self) -> Box<dyn Randomizer> {
+        Box::new(self.clone())
+    }
+}
+
+/// TGM-style history randomizer: rolls up to four times for a piece not in
+/// the last four, and never opens with S, Z, or O.
+#[derive(Debug, Clone)]
+pub struct TgmHistory {
+    history: VecDeque<PieceType>,
+    first: bool,
+}
+
+impl TgmHistory {
+    const ROLLS: usize = 4;
+    const FIRST_PIECES: [PieceType; 4] = [PieceType::I, PieceType::T, PieceType::J, PieceType::L];
+
+    pub fn new() -> Self {
+        Self {
+            history: VecDeque::from([PieceType::Z; 4]),
+            first: true,
+        }
+    }
+}
+
+impl Default for TgmHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Randomizer for TgmHistory {
+    fn generate(&mut self, rng: &mut GameRng) -> PieceType {
+        let piece = if self.first {
+            self.first = false;
+            Self::FIRST_PIECES[rng.below(Self::FIRST_PIECES.len())]
+        } else {
+            let mut piece = PieceType::ALL[rng.below(PieceType::ALL.len())];
+            for _ in 1..Self::ROLLS {
+                if !self.history.contains(&piece) {
+                    break;
+                }
+                piece = PieceType::ALL[rng.below(PieceType::ALL.len())];
+            }
+            piece
+        };
+
+        self.history.pop_front();
+        self.history.push_back(piece);
+        piece
+    }
+
+    fn clone_box(&self) -> Box<dyn Randomizer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Error from parsing a piece script: a character that isn't a piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownPiece(pub char);
+
+impl std::fmt::Display for UnknownPiece {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a piece", self.0)
+    }
+}
+
+/// Fixed list of pieces, then 7-bags once it runs out, or the list again
+/// if it repeats. Used to deal the pieces of a scripted setup such as an
+/// opener or a drill.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Sequence {
+    pieces: Vec<PieceType>,
+    /// Index of the next piece in `pieces`.
+    dealt: usize,
+    repeat: bool,
+    then: Bag,
+}
+
+impl Sequence {
+    pub fn new(pieces: &[PieceType]) -> Self {
+        Self {
+            pieces: pieces.to_vec(),
+            dealt: 0,
+            repeat: false,
+            then: Bag::new(1, 0),
+        }
+    }
+
+    /// Deal `pieces` over and over.
+    pub fn repeating(pieces: &[PieceType]) -> Self {
+        Self {
+            repeat: !pieces.is_empty(),
+            ..Self::new(pieces)
+        }
+    }
+
+    /// Parse a script of piece letters such as `TIOSZLJ`. Case, whitespace
+    /// and commas are ignored, `#` comments out the rest of a line, and a
+    /// `*` at the end repeats the script instead of moving on to 7-bags.
+    pub fn parse(script: &str) -> Result<Self, UnknownPiece> {
+        let mut pieces = Vec::new();
+        let mut repeat = false;
+        for line in script.lines() {
+            let line = line.split('#').next().unwrap_or("");
+            for c in line.chars().filter(|c| !c.is_whitespace() && *c != ',') {
+                if repeat {
+                    // Only the very end may say to repeat
+                    return Err(UnknownPiece('*'));
+                }
+                if c == '*' {
+                    repeat = true;
+                    continue;
+                }
+                pieces.push(PieceType::from_letter(c).ok_or(UnknownPiece(c))?);
+            }
+        }
+        Ok(if repeat {
+            Self::repeating(&pieces)
+        } else {
+            Self::new(&pieces)
+        })
+    }
+}
+
+impl Randomizer for Sequence {
+    fn generate(&mut self, rng: &mut GameRng) -> PieceType {
+        if self.repeat && self.dealt == self.pieces.len() {
+            self.dealt = 0;
+        }
+        match self.pieces.get(self.dealt) {
+            Some(&piece) => {
+                self.dealt += 1;
+                piece
+            }
+            None => self.then.generate(rng),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Randomizer> {
+        Box::new(self.clone())
+    }
+}
+
+/// Upcoming pieces, generated ahead by a randomizer for the preview.
+#[derive(Debug, Clone)]
+pub struct PieceQueue {
+    randomizer: Box<dyn Randomizer>,
+    upcoming: VecDeque<PieceType>,
+    drawn: usize,
+}
+
+impl PieceQueue {
+    pub fn new(mut randomizer: Box<dyn Randomizer>, rng: &mut GameRng) -> Self {
+        let upcoming = (0..LOOKAHEAD).map(|_| randomizer.generate(rng)).collect();
+        Self {
+            randomizer,
+            upcoming,
+            drawn: 0,
+        }
+    }
+
+    /// Draw the next piece.
+    pub fn next(&mut self, rng: &mut GameRng) -> PieceType {
+        self.drawn += 1;
+        self.upcoming.push_back(self.randomizer.generate(rng));
+        self.upcoming.pop_front().unwrap()
+    }
+
+    /// Peek at upcoming pieces (0 = next piece, 1 = one after, etc.)
+    pub fn peek(&self, count: usize) -> Vec<PieceType> {
+        self.upcoming.iter().take(count).copied().collect()
+    }
+
+    /// How many pieces have been drawn total.
+    pub fn pieces_drawn(&self) -> usize {
+        self.drawn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(kind: RandomizerKind) -> (PieceQueue, GameRng) {
+        let mut rng = GameRng::seed_from_u64(1);
+        let queue = PieceQueue::new(kind.build(), &mut rng);
+        (queue, rng)
+    }
+
+    fn counts(pieces: &[PieceType]) -> [usize; 7] {
+        let mut counts = [0; 7];
+        for piece in pieces {
+            counts[piece.index()] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_bag_produces_all_pieces() {
+        let (mut queue, mut rng) = queue(RandomizerKind::SevenBag);
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..7 {
+            seen.insert(queue.next(&mut rng));
+        }
+        assert_eq!(seen.len(), 7);
+    }
+
+    #[test]
+    fn test_bag_peek() {
+        let (queue, _) = queue(RandomizerKind::SevenBag);
+        let preview = queue.peek(3);
+        assert_eq!(preview.len(), 3);
+    }
+
+    #[test]
+    fn test_bag_peek_matches_next() {
+        for kind in RandomizerKind::ALL {
+            let (mut queue, mut rng) = queue(kind);
+            let preview = queue.peek(5);
+            let drawn: Vec<_> = (0..5).map(|_| queue.next(&mut rng)).collect();
+            assert_eq!(preview, drawn, "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_bag_crosses_boundary() {
+        let (mut queue, mut rng) = queue(RandomizerKind::SevenBag);
+        let pieces: Vec<_> = (0..21).map(|_| queue.next(&mut rng)).collect();
+        for bag in pieces.chunks(7) {
+            assert_eq!(counts(bag), [1; 7]);
+        }
+    }
+
+    #[test]
+    fn test_fourteen_bag_holds_two_of_each() {
+        let (mut queue, mut rng) = queue(RandomizerKind::FourteenBag);
+        let pieces: Vec<_> = (0..28).map(|_| queue.next(&mut rng)).collect();
+        for bag in pieces.chunks(14) {
+            assert_eq!(counts(bag), [2; 7]);
+        }
+    }
+
+    #[test]
+    fn test_seven_plus_bag_size() {
+        let (mut queue, mut rng) = queue(RandomizerKind::SevenPlus(2));
+        let pieces: Vec<_> = (0..18).map(|_| queue.next(&mut rng)).collect();
+        for bag in pieces.chunks(9) {
+            assert!(counts(bag).iter().all(|&c| c >= 1));
+        }
+    }
+
+    #[test]
+    fn test_sequence_deals_its_pieces_first() {
+        let mut rng = GameRng::seed_from_u64(1);
+        let opening = [PieceType::T, PieceType::T, PieceType::I];
+        let mut queue = PieceQueue::new(Box::new(Sequence::new(&opening)), &mut rng);
+        let pieces: Vec<_> = (0..10).map(|_| queue.next(&mut rng)).collect();
+        assert_eq!(pieces[..3], opening);
+        assert_eq!(counts(&pieces[3..]), [1; 7]);
+    }
+
+    #[test]
+    fn test_sequence_parse() {
+        let script = "# TKI\ntio sz,lj\n";
+        let mut rng = GameRng::seed_from_u64(1);
+        let mut sequence = Sequence::parse(script).unwrap();
+        let pieces: String = (0..7).map(|_| sequence.generate(&mut rng).name()).collect();
+        assert_eq!(pieces, "TIOSZLJ");
+
+        assert_eq!(Sequence::parse("TIX").unwrap_err(), UnknownPiece('X'));
+        assert_eq!(Sequence::parse("T*I").unwrap_err(), UnknownPiece('*'));
+    }
+
+    #[test]
+    fn test_sequence_repeats_with_star() {
+        let mut rng = GameRng::seed_from_u64(1);
+        let mut sequence = Sequence::parse("IIT *").unwrap();
+        let pieces: String = (0..7).map(|_| sequence.generate(&mut rng).name()).collect();
+        assert_eq!(pieces, "IITIITI");
+    }
+
+    #[test]
+    fn test_tgm_never_opens_with_s_z_o() {
+        for seed in 0..50 {
+            let mut rng = GameRng::seed_from_u64(seed);
+            let mut queue = PieceQueue::new(RandomizerKind::TgmHistory.build(), &mut rng);
+            let first = queue.next(&mut rng);
+            assert!(!matches!(first, PieceType::S | PieceType::Z | PieceType::O));
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use super::piece::{Piece, PieceType, RotationState};
-use super::srs;
+use super::srs::KickTable;
 
 pub const BOARD_WIDTH: usize = 10;
 pub const BOARD_HEIGHT: usize = 40; // 20 visible + 20 buffer
@@ -11,6 +11,10 @@ pub enum Cell {
     Empty,
     Filled(PieceType),
     Garbage,
+    /// Garbage that can't be cleared. Whole rows of it rise from the floor.
+    Solid,
+    /// Garbage that blows up the cells around it when its row is cleared.
+    Bomb,
 }
 
 impl Cell {
@@ -24,12 +28,18 @@ impl Cell {
 }
 
 /// The game board / playfield.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Board {
     /// Grid stored row-major, row 0 = bottom.
     pub grid: [[Cell; BOARD_WIDTH]; BOARD_HEIGHT],
 }
 
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Board {
     pub fn new() -> Self {
         Self {
@@ -76,9 +86,14 @@ impl Board {
         true
     }
 
-    /// Try rotating a piece with SRS kicks. Returns the successful kick offset if any.
-    pub fn try_rotate(&self, piece: &Piece, target_rotation: RotationState) -> Option<(i32, i32)> {
-        let kicks = srs::get_kicks(piece.piece_type, piece.rotation, target_rotation);
+    /// Try rotating a piece with the given kicks. Returns the successful kick offset if any.
+    pub fn try_rotate(
+        &self,
+        piece: &Piece,
+        target_rotation: RotationState,
+        kick_table: &dyn KickTable,
+    ) -> Option<(i32, i32)> {
+        let kicks = kick_table.kicks(piece.piece_type, piece.rotation, target_rotation);
         for &(dx, dy) in kicks {
             if self.fits_at(piece, piece.x + dx, piece.y + dy, target_rotation) {
                 return Some((dx, dy));
@@ -95,21 +110,25 @@ impl Board {
     }
 
     /// Check for and clear full lines. Returns the row indices that were cleared (sorted ascending).
+    /// Rows with solid garbage in them are never full.
     pub fn find_full_lines(&self) -> Vec<usize> {
         let mut full = Vec::new();
         for row in 0..BOARD_HEIGHT {
-            if self.grid[row].iter().all(|c| c.is_occupied()) {
+            let cells = &self.grid[row];
+            if cells.iter().all(|c| c.is_occupied()) && !cells.contains(&Cell::Solid) {
                 full.push(row);
             }
         }
         full
     }
 
-    /// Remove the given rows and collapse everything above down.
+    /// Remove the given rows and collapse everything above down. Bombs in
+    /// them go off first.
     pub fn clear_lines(&mut self, rows: &[usize]) {
         if rows.is_empty() {
             return;
         }
+        self.detonate(rows);
         // Build new grid excluding cleared rows
         let mut new_grid = [[Cell::Empty; BOARD_WIDTH]; BOARD_HEIGHT];
         let mut dest = 0;
@@ -122,9 +141,30 @@ impl Board {
         self.grid = new_grid;
     }
 
+    /// Move the given rows down to sit on the `floor` rows beneath them,
+    /// keeping everything else in order. Rows below `floor` stay put.
+    pub fn sink_lines(&mut self, rows: &[usize], floor: usize) {
+        if rows.is_empty() {
+            return;
+        }
+        let mut new_grid = self.grid;
+        let sunk = rows
+            .iter()
+            .filter(|&&row| row >= floor)
+            .map(|&row| self.grid[row]);
+        let rest = (floor..BOARD_HEIGHT)
+            .filter(|row| !rows.contains(row))
+            .map(|row| self.grid[row]);
+        for (dest, row) in (floor..BOARD_HEIGHT).zip(sunk.chain(rest)) {
+            new_grid[dest] = row;
+        }
+        self.grid = new_grid;
+    }
+
     /// Check if the board is completely empty (for Perfect Clear detection).
+    /// Solid garbage can't be cleared, so clearing down to it counts.
     pub fn is_empty(&self) -> bool {
-        for row in 0..BOARD_HEIGHT {
+        for row in self.solid_rows()..BOARD_HEIGHT {
             for col in 0..BOARD_WIDTH {
                 if self.grid[row][col].is_occupied() {
                     return false;
@@ -184,15 +224,54 @@ impl Board {
 
     /// Add garbage lines at the bottom with a gap at the given column.
     pub fn add_garbage(&mut self, count: usize, gap_col: usize) {
-        // Shift everything up
-        for row in (0..BOARD_HEIGHT).rev() {
-            if row >= count {
-                self.grid[row] = self.grid[row - count];
-            } else {
-                // Fill garbage row
-                let mut garbage_row = [Cell::Garbage; BOARD_WIDTH];
-                garbage_row[gap_col] = Cell::Empty;
-                self.grid[row] = garbage_row;
+        let mut garbage_row = [Cell::Garbage; BOARD_WIDTH];
+        garbage_row[gap_col] = Cell::Empty;
+        for _ in 0..count {
+            self.add_garbage_row(garbage_row);
+        }
+    }
+
+    /// Push one garbage row in from below, on top of any solid rows.
+    pub fn add_garbage_row(&mut self, cells: [Cell; BOARD_WIDTH]) {
+        let floor = self.solid_rows();
+        self.grid.copy_within(floor..BOARD_HEIGHT - 1, floor + 1);
+        self.grid[floor] = cells;
+    }
+
+    /// Raise the floor by `count` rows of solid garbage.
+    pub fn add_solid(&mut self, count: usize) {
+        for _ in 0..count {
+            self.grid.copy_within(..BOARD_HEIGHT - 1, 1);
+            self.grid[0] = [Cell::Solid; BOARD_WIDTH];
+        }
+    }
+
+    /// Rows of solid garbage at the bottom of the board.
+    pub fn solid_rows(&self) -> usize {
+        self.grid
+            .iter()
+            .take_while(|row| row.contains(&Cell::Solid))
+            .count()
+    }
+
+    /// Set off the bombs in rows about to be cleared: each empties the
+    /// cells around it, solid garbage aside, in the rows that stay.
+    fn detonate(&mut self, rows: &[usize]) {
+        for &row in rows {
+            for col in 0..BOARD_WIDTH {
+                if self.grid[row][col] != Cell::Bomb {
+                    continue;
+                }
+                for r in row.saturating_sub(1)..=(row + 1).min(BOARD_HEIGHT - 1) {
+                    if rows.contains(&r) {
+                        continue;
+                    }
+                    for c in col.saturating_sub(1)..=(col + 1).min(BOARD_WIDTH - 1) {
+                        if self.grid[r][c] != Cell::Solid {
+                            self.grid[r][c] = Cell::Empty;
+                        }
+                    }
+                }
             }
         }
     }
@@ -200,7 +279,11 @@ impl Board {
     /// Count garbage lines cleared from a set of cleared rows.
     pub fn count_garbage_in_rows(&self, rows: &[usize]) -> usize {
         rows.iter()
-            .filter(|&&row| self.grid[row].iter().any(|c| matches!(c, Cell::Garbage)))
+            .filter(|&&row| {
+                self.grid[row]
+                    .iter()
+                    .any(|c| matches!(c, Cell::Garbage | Cell::Bomb))
+            })
             .count()
     }
 
@@ -259,6 +342,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sink_lines() {
+        let mut board = Board::new();
+        // One banked row, a partial row, then a full row on top
+        for col in 0..BOARD_WIDTH {
+            board.set(col as i32, 0, Cell::Garbage);
+            board.set(col as i32, 2, Cell::Filled(PieceType::O));
+        }
+        board.set(4, 1, Cell::Filled(PieceType::T));
+
+        board.sink_lines(&[2], 1);
+        assert!(matches!(board.get(0, 0), Cell::Garbage));
+        assert_eq!(board.find_full_lines(), vec![0, 1]);
+        assert!(board.get(4, 2).is_occupied());
+        assert!(board.get(0, 2).is_empty());
+    }
+
     #[test]
     fn test_garbage_insertion() {
         let mut board = Board::new();
@@ -274,6 +374,44 @@ mod tests {
         assert!(board.get(3, 1).is_empty()); // Gap
     }
 
+    #[test]
+    fn test_solid_rows_never_clear() {
+        let mut board = Board::new();
+        board.add_solid(2);
+        assert_eq!(board.solid_rows(), 2);
+        assert!(board.find_full_lines().is_empty());
+
+        // New garbage goes in above the solid floor
+        board.add_garbage(1, 4);
+        assert_eq!(board.get(0, 1), Cell::Solid);
+        assert_eq!(board.get(0, 2), Cell::Garbage);
+        assert!(board.get(4, 2).is_empty());
+    }
+
+    #[test]
+    fn test_bomb_clears_cells_around_it() {
+        let mut board = Board::new();
+        let mut row = [Cell::Garbage; BOARD_WIDTH];
+        row[4] = Cell::Empty;
+        row[5] = Cell::Bomb;
+        board.add_garbage_row(row);
+        board.add_garbage_row(row);
+        // Stack above the bomb row
+        for col in 0..BOARD_WIDTH {
+            board.set(col as i32, 2, Cell::Filled(PieceType::O));
+        }
+        board.set(4, 1, Cell::Filled(PieceType::I));
+
+        // Clearing the top garbage row blasts the cells around its bomb
+        board.clear_lines(&[1]);
+        for col in 4..=6 {
+            assert!(board.get(col, 0).is_empty(), "column {} below", col);
+            assert!(board.get(col, 1).is_empty(), "column {} above", col);
+        }
+        assert_eq!(board.get(3, 0), Cell::Garbage);
+        assert_eq!(board.get(7, 1), Cell::Filled(PieceType::O));
+    }
+
     #[test]
     fn test_column_height() {
         let mut board = Board::new();
@@ -294,10 +432,12 @@ mod tests {
 
     #[test]
     fn test_srs_rotation() {
+        use crate::game::srs::SrsPlus;
+
         let board = Board::new();
         let piece = Piece::new(PieceType::T);
         // Should be able to rotate on empty board
-        let result = board.try_rotate(&piece, RotationState::R1);
+        let result = board.try_rotate(&piece, RotationState::R1, &SrsPlus);
         assert!(result.is_some());
         assert_eq!(result.unwrap(), (0, 0)); // No kick needed
     }
@@ -1,7 +1,8 @@
 use super::piece::PieceType;
+use super::ruleset::SpinRule;
 
 /// Type of line clear or spin.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub enum ClearType {
     None,
     Single,
@@ -19,9 +20,10 @@ pub enum ClearType {
 }
 
 impl ClearType {
-    /// Determine clear type from the number of lines, spin state, and piece type.
-    pub fn classify(lines: u32, spin: SpinType, _piece_type: PieceType) -> Self {
-        match spin {
+    /// Determine clear type from the number of lines, spin state, and piece
+    /// type. Spins the rule doesn't recognize count as plain clears.
+    pub fn classify(lines: u32, spin: SpinType, _piece_type: PieceType, rule: SpinRule) -> Self {
+        match rule.filter(spin) {
             SpinType::TSpin => match lines {
                 0 => ClearType::TSpin,
                 1 => ClearType::TSpinSingle,
@@ -107,7 +109,7 @@ impl ClearType {
 }
 
 /// Spin detection result.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 pub enum SpinType {
     None,
     TSpin,
@@ -127,28 +129,28 @@ mod tests {
 
     #[test]
     fn test_classify_single() {
-        let ct = ClearType::classify(1, SpinType::None, PieceType::T);
+        let ct = ClearType::classify(1, SpinType::None, PieceType::T, SpinRule::AllSpin);
         assert_eq!(ct, ClearType::Single);
         assert!(!ct.is_difficult());
     }
 
     #[test]
     fn test_classify_tetris() {
-        let ct = ClearType::classify(4, SpinType::None, PieceType::I);
+        let ct = ClearType::classify(4, SpinType::None, PieceType::I, SpinRule::AllSpin);
         assert_eq!(ct, ClearType::Quad);
         assert!(ct.is_difficult());
     }
 
     #[test]
     fn test_classify_tspin_double() {
-        let ct = ClearType::classify(2, SpinType::TSpin, PieceType::T);
+        let ct = ClearType::classify(2, SpinType::TSpin, PieceType::T, SpinRule::AllSpin);
         assert_eq!(ct, ClearType::TSpinDouble);
         assert!(ct.is_difficult());
     }
 
     #[test]
     fn test_classify_mini_tspin() {
-        let ct = ClearType::classify(1, SpinType::MiniTSpin, PieceType::T);
+        let ct = ClearType::classify(1, SpinType::MiniTSpin, PieceType::T, SpinRule::AllSpin);
         assert_eq!(ct, ClearType::MiniTSpinSingle);
         assert!(ct.is_difficult());
     }
@@ -0,0 +1,441 @@
+use super::board::{Cell, BOARD_WIDTH};
+use super::rng::GameRng;
+use std::time::Duration;
+
+/// A pending garbage batch with travel time.
+#[derive(Debug, Clone)]
+struct GarbageBatch {
+    lines: u32,
+    time_remaining: Duration,
+}
+
+/// Where the holes in consecutive garbage lines go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GarbagePattern {
+    /// Mostly one column, moving at random with the queue's messiness.
+    #[default]
+    Messy,
+    /// The same column for the whole game.
+    Clean,
+    /// One column over each line, bouncing off the walls.
+    Staircase,
+    /// Two mirrored columns in turn.
+    Alternating,
+    /// A new random column every line.
+    Random,
+}
+
+impl GarbagePattern {
+    pub const ALL: [GarbagePattern; 5] = [
+        GarbagePattern::Messy,
+        GarbagePattern::Clean,
+        GarbagePattern::Staircase,
+        GarbagePattern::Alternating,
+        GarbagePattern::Random,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GarbagePattern::Messy => "Messy",
+            GarbagePattern::Clean => "Clean",
+            GarbagePattern::Staircase => "Staircase",
+            GarbagePattern::Alternating => "Alternating",
+            GarbagePattern::Random => "Random",
+        }
+    }
+}
+
+/// What incoming garbage lines are made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum GarbageKind {
+    /// Grey rows with one hole.
+    #[default]
+    Standard,
+    /// Grey rows with two holes, the second anywhere.
+    Cheese,
+    /// One hole with a bomb beside it, which clears the cells around it
+    /// when its row goes.
+    Bomb,
+}
+
+impl GarbageKind {
+    pub const ALL: [GarbageKind; 3] = [
+        GarbageKind::Standard,
+        GarbageKind::Cheese,
+        GarbageKind::Bomb,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GarbageKind::Standard => "Standard",
+            GarbageKind::Cheese => "Cheese",
+            GarbageKind::Bomb => "Bomb",
+        }
+    }
+}
+
+/// What became of some garbage lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeKind {
+    /// Attack that got past the pending garbage and went to the opponent.
+    Sent,
+    /// Attack spent cancelling pending garbage.
+    Cancelled,
+    /// Garbage that landed on the board.
+    Received,
+}
+
+/// One step in the garbage exchange, kept for the whole game so a match
+/// can be looked back over once it ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Exchange {
+    /// Game time it happened at.
+    pub time: Duration,
+    pub kind: ExchangeKind,
+    pub lines: u32,
+}
+
+/// Garbage queue: manages incoming garbage with travel time and cancellation.
+#[derive(Debug, Clone)]
+pub struct GarbageQueue {
+    queue: Vec<GarbageBatch>,
+    /// Travel time for new garbage (default 500ms).
+    pub travel_time: Duration,
+    /// Garbage messiness for `GarbagePattern::Messy`: 0.0 = same column
+    /// every line, 1.0 = random column.
+    pub messiness: f64,
+    pub pattern: GarbagePattern,
+    pub kind: GarbageKind,
+    /// How often a row of solid garbage rises from the floor; None for
+    /// never. Solid rows come on this timer alone, not from attacks.
+    pub solid_interval: Option<Duration>,
+    solid_timer: Duration,
+    /// Mercy rule: how long garbage that's due may wait while the stack is
+    /// in danger. None lets it in straight away.
+    pub mercy: Option<Duration>,
+    /// Time spent in danger so far, counting against `mercy`.
+    mercy_used: Duration,
+    /// Time out of danger since it was last in danger.
+    mercy_calm: Duration,
+    /// Last gap column used, None before the first garbage line.
+    last_gap: Option<usize>,
+    /// Staircase direction: +1 right, -1 left.
+    step: i32,
+}
+
+impl Default for GarbageQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GarbageQueue {
+    pub fn new() -> Self {
+        Self {
+            queue: Vec::new(),
+            travel_time: Duration::from_millis(500),
+            messiness: 0.3,
+            pattern: GarbagePattern::default(),
+            kind: GarbageKind::default(),
+            solid_interval: None,
+            solid_timer: Duration::ZERO,
+            mercy: None,
+            mercy_used: Duration::ZERO,
+            mercy_calm: Duration::ZERO,
+            last_gap: None,
+            step: 1,
+        }
+    }
+
+    /// Add garbage to the queue.
+    pub fn add(&mut self, lines: u32) {
+        if lines > 0 {
+            self.queue.push(GarbageBatch {
+                lines,
+                time_remaining: self.travel_time,
+            });
+        }
+    }
+
+    /// Cancel incoming garbage with attack damage. Returns remaining attack after cancellation.
+    pub fn cancel(&mut self, mut attack: u32) -> u32 {
+        while attack > 0 && !self.queue.is_empty() {
+            let batch = &mut self.queue[0];
+            if attack >= batch.lines {
+                attack -= batch.lines;
+                self.queue.remove(0);
+            } else {
+                batch.lines -= attack;
+                attack = 0;
+            }
+        }
+        attack
+    }
+
+    /// Tick all garbage timers. Returns total lines of garbage ready to deploy.
+    /// Under the mercy rule, garbage that's due while `in_danger` stays
+    /// queued, where attacks can still cancel it, until the grace runs out.
+    /// The grace only comes back once the stack has stayed out of danger as
+    /// long again, so dipping in and out can't hold garbage off for good.
+    pub fn tick(&mut self, dt: Duration, in_danger: bool) -> u32 {
+        let holding = match self.mercy {
+            Some(grace) if in_danger => {
+                self.mercy_calm = Duration::ZERO;
+                self.mercy_used += dt;
+                self.mercy_used <= grace
+            }
+            Some(grace) => {
+                self.mercy_calm += dt;
+                if self.mercy_calm >= grace {
+                    self.mercy_used = Duration::ZERO;
+                }
+                false
+            }
+            None => false,
+        };
+        let mut ready = 0;
+        self.queue.retain_mut(|batch| {
+            if dt >= batch.time_remaining {
+                if holding {
+                    batch.time_remaining = Duration::ZERO;
+                    return true;
+                }
+                ready += batch.lines;
+                false
+            } else {
+                batch.time_remaining -= dt;
+                true
+            }
+        });
+        ready
+    }
+
+    /// Count down to the next solid row. Returns true when one is due.
+    pub fn tick_solid(&mut self, dt: Duration) -> bool {
+        let Some(interval) = self.solid_interval else {
+            return false;
+        };
+        self.solid_timer += dt;
+        if self.solid_timer < interval {
+            return false;
+        }
+        self.solid_timer -= interval;
+        true
+    }
+
+    /// Build the next garbage line, its holes following the pattern.
+    pub fn garbage_row(&mut self, rng: &mut GameRng) -> [Cell; BOARD_WIDTH] {
+        let gap = self.gap_column(rng);
+        let mut row = [Cell::Garbage; BOARD_WIDTH];
+        row[gap] = Cell::Empty;
+        match self.kind {
+            GarbageKind::Standard => {}
+            GarbageKind::Cheese => {
+                // Any other column, so there are always two holes
+                let second = (gap + 1 + rng.below(BOARD_WIDTH - 1)) % BOARD_WIDTH;
+                row[second] = Cell::Empty;
+            }
+            GarbageKind::Bomb => {
+                let bomb = if gap + 1 < BOARD_WIDTH {
+                    gap + 1
+                } else {
+                    gap - 1
+                };
+                row[bomb] = Cell::Bomb;
+            }
+        }
+        row
+    }
+
+    /// Get a gap column for a garbage line.
+    pub fn gap_column(&mut self, rng: &mut GameRng) -> usize {
+        let Some(last) = self.last_gap else {
+            let gap = rng.below(BOARD_WIDTH);
+            self.last_gap = Some(gap);
+            return gap;
+        };
+
+        let gap = match self.pattern {
+            GarbagePattern::Messy if rng.next_f64() < self.messiness => rng.below(BOARD_WIDTH),
+            GarbagePattern::Messy | GarbagePattern::Clean => last,
+            GarbagePattern::Staircase => {
+                let next = last as i32 + self.step;
+                if !(0..BOARD_WIDTH as i32).contains(&next) {
+                    self.step = -self.step;
+                }
+                (last as i32 + self.step) as usize
+            }
+            GarbagePattern::Alternating => BOARD_WIDTH - 1 - last,
+            GarbagePattern::Random => rng.below(BOARD_WIDTH),
+        };
+        self.last_gap = Some(gap);
+        gap
+    }
+
+    /// Pending batches, first to arrive first, as lines and the time left
+    /// before they arrive.
+    pub fn batches(&self) -> impl Iterator<Item = (u32, Duration)> + '_ {
+        self.queue.iter().map(|b| (b.lines, b.time_remaining))
+    }
+
+    /// Total pending garbage lines.
+    pub fn pending(&self) -> u32 {
+        self.queue.iter().map(|b| b.lines).sum()
+    }
+
+    /// Clear all pending garbage and restart the hole pattern.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.solid_timer = Duration::ZERO;
+        self.mercy_used = Duration::ZERO;
+        self.mercy_calm = Duration::ZERO;
+        self.last_gap = None;
+        self.step = 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_garbage() {
+        let mut gq = GarbageQueue::new();
+        gq.add(4);
+        assert_eq!(gq.pending(), 4);
+    }
+
+    #[test]
+    fn test_cancel_garbage() {
+        let mut gq = GarbageQueue::new();
+        gq.add(4);
+        let remaining = gq.cancel(2);
+        assert_eq!(remaining, 0);
+        assert_eq!(gq.pending(), 2);
+    }
+
+    #[test]
+    fn test_cancel_overflow() {
+        let mut gq = GarbageQueue::new();
+        gq.add(2);
+        let remaining = gq.cancel(5);
+        assert_eq!(remaining, 3);
+        assert_eq!(gq.pending(), 0);
+    }
+
+    fn gaps(pattern: GarbagePattern, count: usize) -> Vec<usize> {
+        let mut gq = GarbageQueue::new();
+        gq.pattern = pattern;
+        let mut rng = GameRng::seed_from_u64(3);
+        (0..count).map(|_| gq.gap_column(&mut rng)).collect()
+    }
+
+    #[test]
+    fn test_clean_pattern_keeps_column() {
+        let gaps = gaps(GarbagePattern::Clean, 20);
+        assert!(gaps.iter().all(|&g| g == gaps[0]));
+    }
+
+    #[test]
+    fn test_staircase_pattern_bounces() {
+        let gaps = gaps(GarbagePattern::Staircase, 30);
+        for pair in gaps.windows(2) {
+            assert_eq!((pair[0] as i32 - pair[1] as i32).abs(), 1);
+        }
+        assert!(gaps.contains(&0) && gaps.contains(&(BOARD_WIDTH - 1)));
+    }
+
+    #[test]
+    fn test_alternating_pattern() {
+        let gaps = gaps(GarbagePattern::Alternating, 6);
+        for pair in gaps.windows(2) {
+            assert_eq!(pair[0] + pair[1], BOARD_WIDTH - 1);
+        }
+    }
+
+    #[test]
+    fn test_garbage_kinds() {
+        let mut rng = GameRng::seed_from_u64(5);
+        let mut gq = GarbageQueue::new();
+        let holes = |row: &[Cell; BOARD_WIDTH]| row.iter().filter(|c| c.is_empty()).count();
+
+        gq.kind = GarbageKind::Cheese;
+        for _ in 0..20 {
+            assert_eq!(holes(&gq.garbage_row(&mut rng)), 2);
+        }
+
+        gq.kind = GarbageKind::Bomb;
+        for _ in 0..20 {
+            let row = gq.garbage_row(&mut rng);
+            let gap = row.iter().position(|c| c.is_empty()).unwrap();
+            let bomb = row.iter().position(|&c| c == Cell::Bomb).unwrap();
+            assert_eq!(holes(&row), 1);
+            assert_eq!(gap.abs_diff(bomb), 1);
+        }
+    }
+
+    #[test]
+    fn test_solid_rows_rise_on_a_timer() {
+        let mut gq = GarbageQueue::new();
+        assert!(!gq.tick_solid(Duration::from_secs(60)));
+
+        gq.solid_interval = Some(Duration::from_secs(10));
+        assert!(!gq.tick_solid(Duration::from_secs(6)));
+        assert!(gq.tick_solid(Duration::from_secs(6)));
+        // The leftover carries over to the next row
+        assert!(gq.tick_solid(Duration::from_secs(8)));
+    }
+
+    #[test]
+    fn test_tick_garbage() {
+        let mut gq = GarbageQueue::new();
+        gq.add(3);
+        // Not ready yet
+        let ready = gq.tick(Duration::from_millis(200), false);
+        assert_eq!(ready, 0);
+        // Now ready
+        let ready = gq.tick(Duration::from_millis(400), false);
+        assert_eq!(ready, 3);
+    }
+
+    #[test]
+    fn test_mercy_holds_garbage_while_in_danger() {
+        let mut gq = GarbageQueue::new();
+        gq.mercy = Some(Duration::from_secs(2));
+        gq.add(3);
+        assert_eq!(gq.tick(Duration::from_secs(1), true), 0);
+        // Still waiting, and attacks can still cancel it
+        assert_eq!(gq.tick(Duration::from_millis(500), true), 0);
+        assert_eq!(gq.cancel(1), 0);
+        // The grace runs out
+        assert_eq!(gq.tick(Duration::from_secs(1), true), 2);
+
+        // A brief dip out of danger doesn't bring the grace back
+        assert_eq!(gq.tick(Duration::from_secs(1), false), 0);
+        gq.add(1);
+        assert_eq!(gq.tick(Duration::from_secs(1), true), 1);
+
+        // Staying out of danger as long as the grace lasts does, and
+        // leaving danger lets held garbage in
+        assert_eq!(gq.tick(Duration::from_secs(2), false), 0);
+        gq.add(1);
+        assert_eq!(gq.tick(Duration::from_secs(1), true), 0);
+        assert_eq!(gq.tick(Duration::ZERO, false), 1);
+    }
+
+    #[test]
+    fn test_batches_count_down() {
+        let mut gq = GarbageQueue::new();
+        gq.add(2);
+        gq.tick(Duration::from_millis(300), false);
+        gq.add(1);
+        let batches: Vec<_> = gq.batches().collect();
+        assert_eq!(
+            batches,
+            [
+                (2, Duration::from_millis(200)),
+                (1, Duration::from_millis(500))
+            ]
+        );
+    }
+}
@@ -9,17 +9,26 @@ pub struct Gravity {
     pub level: u32,
     /// Whether soft drop is active.
     pub soft_dropping: bool,
+    /// Pieces fall this many times as fast as the level's gravity.
+    pub multiplier: u32,
 }
 
 /// Soft drop speed multiplier.
 const SOFT_DROP_FACTOR: f64 = 20.0;
 
+impl Default for Gravity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Gravity {
     pub fn new() -> Self {
         Self {
             accumulator: Duration::ZERO,
             level: 0,
             soft_dropping: false,
+            multiplier: 1,
         }
     }
 
@@ -27,7 +36,8 @@ impl Gravity {
     /// Uses the guideline formula: (0.8 - (level * 0.007))^level seconds.
     pub fn interval(&self) -> Duration {
         let level = self.level as f64;
-        let seconds = (0.8 - (level * 0.007)).max(0.001).powf(level);
+        let seconds =
+            (0.8 - (level * 0.007)).max(0.001).powf(level) / self.multiplier.max(1) as f64;
         let seconds = if self.soft_dropping {
             seconds / SOFT_DROP_FACTOR
         } else {
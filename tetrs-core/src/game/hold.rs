@@ -1,5 +1,9 @@
 use super::piece::PieceType;
 
+/// Error returned when hold was already used this turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HoldUsed;
+
 /// Hold piece state.
 #[derive(Debug, Clone)]
 pub struct Hold {
@@ -7,6 +11,12 @@ pub struct Hold {
     pub used_this_turn: bool,
 }
 
+impl Default for Hold {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hold {
     pub fn new() -> Self {
         Self {
@@ -16,10 +26,10 @@ impl Hold {
     }
 
     /// Attempt to hold the current piece. Returns the previously held piece (if any).
-    /// Returns None if hold was already used this turn.
-    pub fn hold(&mut self, current: PieceType) -> Result<Option<PieceType>, ()> {
+    /// Returns `HoldUsed` if hold was already used this turn.
+    pub fn hold(&mut self, current: PieceType) -> Result<Option<PieceType>, HoldUsed> {
         if self.used_this_turn {
-            return Err(());
+            return Err(HoldUsed);
         }
         self.used_this_turn = true;
         let prev = self.piece;
@@ -0,0 +1,238 @@
+use std::time::Duration;
+
+/// Default lock delay configuration.
+pub const LOCK_DELAY: Duration = Duration::from_millis(500);
+pub const MAX_LOCK_RESETS: u32 = 15;
+
+/// What restarts the lock timer of a grounded piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum LockResetMode {
+    /// Moves and rotations, up to the reset limit.
+    #[default]
+    Move,
+    /// Moves and rotations, without limit.
+    Infinity,
+    /// Only reaching a lower row than before.
+    Step,
+}
+
+impl LockResetMode {
+    pub const ALL: [LockResetMode; 3] = [
+        LockResetMode::Move,
+        LockResetMode::Infinity,
+        LockResetMode::Step,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LockResetMode::Move => "Move",
+            LockResetMode::Infinity => "Infinity",
+            LockResetMode::Step => "Step",
+        }
+    }
+}
+
+/// Lock delay system: handles the delay before a grounded piece locks.
+#[derive(Debug, Clone)]
+pub struct LockDelay {
+    /// Time remaining before lock.
+    pub timer: Duration,
+    /// Number of resets performed.
+    pub resets: u32,
+    /// Whether the piece is currently on the ground.
+    pub grounded: bool,
+    /// Whether the lock delay is active.
+    pub active: bool,
+    /// Time a grounded piece waits before locking.
+    pub delay: Duration,
+    /// Moves or rotations on the ground that restart the timer.
+    pub max_resets: u32,
+    pub mode: LockResetMode,
+    /// Lowest row the piece has reached, for step resets.
+    lowest_row: Option<i32>,
+}
+
+impl Default for LockDelay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LockDelay {
+    pub fn new() -> Self {
+        Self::with_limits(LOCK_DELAY, MAX_LOCK_RESETS, LockResetMode::Move)
+    }
+
+    pub fn with_limits(delay: Duration, max_resets: u32, mode: LockResetMode) -> Self {
+        Self {
+            timer: delay,
+            resets: 0,
+            grounded: false,
+            active: false,
+            delay,
+            max_resets,
+            mode,
+            lowest_row: None,
+        }
+    }
+
+    /// Called when the piece touches the ground.
+    pub fn start_if_grounded(&mut self, is_grounded: bool) {
+        if is_grounded && !self.active {
+            self.active = true;
+            self.grounded = true;
+        } else if !is_grounded {
+            // Piece lifted off ground (e.g., rotation kick), reset timer
+            // unless only new rows may reset it
+            self.active = false;
+            self.grounded = false;
+            if self.mode != LockResetMode::Step {
+                self.timer = self.delay;
+            }
+        }
+        self.grounded = is_grounded;
+    }
+
+    /// Called when the piece is moved or rotated while on the ground.
+    /// Returns true if the reset was accepted.
+    pub fn try_reset(&mut self) -> bool {
+        let allowed = match self.mode {
+            LockResetMode::Move => self.resets < self.max_resets,
+            LockResetMode::Infinity => true,
+            LockResetMode::Step => false,
+        };
+        if self.active && allowed {
+            self.timer = self.delay;
+            self.resets += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Called with the piece's row each frame. In step mode, reaching a
+    /// lower row than before restarts the timer.
+    pub fn note_row(&mut self, row: i32) {
+        if self.lowest_row.is_some_and(|lowest| row >= lowest) {
+            return;
+        }
+        self.lowest_row = Some(row);
+        if self.mode == LockResetMode::Step {
+            self.timer = self.delay;
+        }
+    }
+
+    /// Tick the lock timer. Returns true if the piece should lock.
+    pub fn tick(&mut self, dt: Duration) -> bool {
+        if !self.active {
+            return false;
+        }
+        if dt >= self.timer {
+            self.timer = Duration::ZERO;
+            true
+        } else {
+            self.timer -= dt;
+            false
+        }
+    }
+
+    /// Get lock progress as 0.0 (just started) to 1.0 (about to lock).
+    pub fn progress(&self) -> f64 {
+        if !self.active {
+            return 0.0;
+        }
+        if self.delay.is_zero() {
+            return 1.0;
+        }
+        1.0 - (self.timer.as_secs_f64() / self.delay.as_secs_f64())
+    }
+
+    /// Full reset for new piece.
+    pub fn reset(&mut self) {
+        self.timer = self.delay;
+        self.resets = 0;
+        self.lowest_row = None;
+        self.grounded = false;
+        self.active = false;
+    }
+
+    pub fn resets_remaining(&self) -> u32 {
+        match self.mode {
+            LockResetMode::Move => self.max_resets.saturating_sub(self.resets),
+            LockResetMode::Infinity => u32::MAX,
+            LockResetMode::Step => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_delay_activation() {
+        let mut ld = LockDelay::new();
+        assert!(!ld.active);
+        ld.start_if_grounded(true);
+        assert!(ld.active);
+    }
+
+    #[test]
+    fn test_lock_delay_tick() {
+        let mut ld = LockDelay::new();
+        ld.start_if_grounded(true);
+        // Tick less than lock delay
+        assert!(!ld.tick(Duration::from_millis(200)));
+        // Tick past remaining
+        assert!(ld.tick(Duration::from_millis(400)));
+    }
+
+    #[test]
+    fn test_lock_reset() {
+        let mut ld = LockDelay::new();
+        ld.start_if_grounded(true);
+        ld.tick(Duration::from_millis(400));
+        assert!(ld.try_reset());
+        // Timer should be reset, so this shouldn't lock
+        assert!(!ld.tick(Duration::from_millis(400)));
+    }
+
+    #[test]
+    fn test_max_resets() {
+        let mut ld = LockDelay::new();
+        ld.start_if_grounded(true);
+        for _ in 0..MAX_LOCK_RESETS {
+            assert!(ld.try_reset());
+        }
+        // Should no longer accept resets
+        assert!(!ld.try_reset());
+    }
+
+    #[test]
+    fn test_infinity_never_runs_out() {
+        let mut ld = LockDelay::with_limits(LOCK_DELAY, MAX_LOCK_RESETS, LockResetMode::Infinity);
+        ld.start_if_grounded(true);
+        for _ in 0..MAX_LOCK_RESETS * 4 {
+            assert!(ld.try_reset());
+        }
+    }
+
+    #[test]
+    fn test_step_resets_only_on_new_rows() {
+        let mut ld = LockDelay::with_limits(LOCK_DELAY, MAX_LOCK_RESETS, LockResetMode::Step);
+        ld.note_row(10);
+        ld.start_if_grounded(true);
+        ld.tick(Duration::from_millis(400));
+        assert!(!ld.try_reset());
+
+        // Lifting off and landing on the same row keeps the timer
+        ld.start_if_grounded(false);
+        ld.note_row(10);
+        ld.start_if_grounded(true);
+        assert!(ld.tick(Duration::from_millis(200)));
+
+        // A lower row starts it over
+        ld.note_row(9);
+        assert!(!ld.tick(Duration::from_millis(400)));
+    }
+}
@@ -0,0 +1,1372 @@
+pub mod attack;
+pub mod bag;
+pub mod board;
+pub mod clear;
+pub mod garbage;
+pub mod ghost;
+pub mod gravity;
+pub mod hold;
+pub mod locking;
+pub mod modifiers;
+pub mod movement;
+pub mod piece;
+pub mod rng;
+pub mod ruleset;
+pub mod scoring;
+pub mod snapshot;
+pub mod srs;
+pub mod stats;
+pub mod zone;
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use self::bag::{PieceQueue, Randomizer, Sequence};
+use self::board::{Board, VISIBLE_HEIGHT};
+use self::clear::{ClearType, SpinType};
+use self::garbage::{Exchange, ExchangeKind, GarbageQueue};
+use self::ghost::ghost_y;
+use self::gravity::Gravity;
+use self::hold::Hold;
+use self::locking::LockDelay;
+use self::modifiers::{Modifier, Modifiers};
+use self::movement::{detect_spin, is_grounded};
+use self::piece::{Piece, PieceType};
+use self::rng::GameRng;
+use self::ruleset::Ruleset;
+use self::scoring::Scoring;
+use self::stats::Stats;
+use self::zone::Zone;
+
+/// Actions the player can take.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameAction {
+    MoveLeft,
+    MoveRight,
+    SoftDrop,
+    HardDrop,
+    RotateCW,
+    RotateCCW,
+    Rotate180,
+    Hold,
+    SoftDropRelease,
+    /// Spend the zone meter, if the ruleset has one.
+    Zone,
+}
+
+/// Result of a game update tick.
+#[derive(Debug, Clone)]
+pub struct TickResult {
+    pub lines_cleared: u32,
+    pub clear_type: ClearType,
+    pub is_perfect_clear: bool,
+    pub score_gained: u64,
+    pub attack: u32,
+    pub piece_locked: bool,
+    pub game_over: bool,
+    pub hard_dropped: bool,
+    pub spin_type: SpinType,
+}
+
+impl TickResult {
+    fn none() -> Self {
+        Self {
+            lines_cleared: 0,
+            clear_type: ClearType::None,
+            is_perfect_clear: false,
+            score_gained: 0,
+            attack: 0,
+            piece_locked: false,
+            game_over: false,
+            hard_dropped: false,
+            spin_type: SpinType::None,
+        }
+    }
+}
+
+/// Visual effect event for the renderer.
+#[derive(Debug, Clone, serde::Serialize)]
+pub enum GameEvent {
+    PieceLocked,
+    LinesClear(Vec<usize>),
+    /// Points a line clear scored, and the middle of the rows it cleared.
+    Scored {
+        points: u64,
+        row: usize,
+    },
+    HardDrop {
+        cells: u32,
+    },
+    TSpin(SpinType),
+    PerfectClear,
+    Clear(ClearType),
+    AttackSent(u32),
+    Combo(u32),
+    BackToBack(u32),
+    GarbageReceived(u32),
+    GameOver,
+    LevelUp(u32),
+    /// A long back-to-back chain broke, releasing this much extra garbage.
+    Surge(u32),
+    ZoneStarted,
+    /// Zone ran out, releasing this many banked lines.
+    ZoneEnded {
+        lines: u32,
+    },
+}
+
+/// Stack height at which the board starts warning that it's getting high.
+pub const DANGER_WARNING_HEIGHT: usize = 14;
+/// Stack height at which a top out is a piece or two away.
+pub const DANGER_CRITICAL_HEIGHT: usize = 18;
+/// Stack height counted as in danger by `GameState::is_danger`, for the
+/// mercy rule and the announcer: within 4 rows of the top. The board's
+/// warnings escalate by `DangerTier` instead.
+pub const DANGER_HEIGHT: usize = VISIBLE_HEIGHT - 4;
+
+/// How close the stack is to the top, for warnings that escalate as it
+/// climbs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DangerTier {
+    #[default]
+    Safe,
+    Warning,
+    Critical,
+}
+
+impl DangerTier {
+    /// The tier for a stack `height` rows tall.
+    pub fn from_height(height: usize) -> Self {
+        if height >= DANGER_CRITICAL_HEIGHT {
+            DangerTier::Critical
+        } else if height >= DANGER_WARNING_HEIGHT {
+            DangerTier::Warning
+        } else {
+            DangerTier::Safe
+        }
+    }
+}
+
+/// Core game state for a single Tetris board.
+#[derive(Debug, Clone)]
+pub struct GameState {
+    pub board: Board,
+    pub current_piece: Option<Piece>,
+    pub queue: PieceQueue,
+    /// Rules `start` sets the game up with.
+    pub ruleset: Ruleset,
+    /// Scripted pieces `start` deals instead of the ruleset's randomizer.
+    pub script: Option<Sequence>,
+    /// Challenges the game is played with.
+    pub modifiers: Modifiers,
+    pub hold: Hold,
+    pub gravity: Gravity,
+    pub lock_delay: LockDelay,
+    pub scoring: Scoring,
+    pub garbage: GarbageQueue,
+    /// Every garbage line sent, cancelled and received this game, oldest first.
+    pub exchanges: Vec<Exchange>,
+    pub zone: Zone,
+    pub stats: Stats,
+    /// Seed for `rng`; `start` rewinds to it so a seed always plays the same game.
+    pub seed: u64,
+    /// Draws the pieces, and nothing else, so two games on one seed get the
+    /// same queue however differently they play.
+    pub rng: GameRng,
+    /// Picks garbage holes, seeded apart from `rng`.
+    pub garbage_rng: GameRng,
+    pub game_over: bool,
+    pub started: bool,
+
+    // State tracking for spin detection
+    pub last_was_rotation: bool,
+    pub last_kick: Option<(i32, i32)>,
+
+    // Visual state
+    pub clearing_lines: Option<(Vec<usize>, Duration)>,
+    /// Entry delay left before the next piece spawns.
+    pub entry_delay: Option<Duration>,
+    pub events: Vec<GameEvent>,
+    pub last_clear_type: Option<ClearType>,
+    pub last_clear_time: Duration,
+    pub action_text_timer: Duration,
+
+    // Line clear animation duration
+    clear_delay: Duration,
+
+    // Inputs pressed while waiting for the next piece (IRS/IHS)
+    buffered_rotation: Option<GameAction>,
+    buffered_hold: bool,
+}
+
+#[cfg(feature = "entropy")]
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Salt mixed into a game's seed for its garbage holes.
+const GARBAGE_SALT: u64 = 0x6761_7262_6167_6521;
+
+fn garbage_rng(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed ^ GARBAGE_SALT)
+}
+
+impl GameState {
+    /// New game with a random seed.
+    #[cfg(feature = "entropy")]
+    pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    /// New game whose pieces and garbage holes are fully determined by `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self::with_ruleset(seed, Ruleset::default())
+    }
+
+    /// New game played under `ruleset`.
+    pub fn with_ruleset(seed: u64, ruleset: Ruleset) -> Self {
+        let mut rng = GameRng::seed_from_u64(seed);
+        let queue = PieceQueue::new(ruleset.randomizer.build(), &mut rng);
+        let mut scoring = Scoring::new();
+        scoring.system = ruleset.scoring;
+        scoring.attack_table = ruleset.attack.clone();
+        let lock_delay = ruleset.new_lock_delay();
+        Self {
+            board: Board::new(),
+            current_piece: None,
+            queue,
+            ruleset,
+            script: None,
+            modifiers: Modifiers::default(),
+            hold: Hold::new(),
+            gravity: Gravity::new(),
+            lock_delay,
+            scoring,
+            garbage: GarbageQueue::new(),
+            exchanges: Vec::new(),
+            zone: Zone::new(),
+            stats: Stats::new(),
+            seed,
+            rng,
+            garbage_rng: garbage_rng(seed),
+            game_over: false,
+            started: false,
+            last_was_rotation: false,
+            last_kick: None,
+            clearing_lines: None,
+            entry_delay: None,
+            events: Vec::new(),
+            last_clear_type: None,
+            last_clear_time: Duration::ZERO,
+            action_text_timer: Duration::ZERO,
+            clear_delay: Duration::from_millis(200),
+            buffered_rotation: None,
+            buffered_hold: false,
+        }
+    }
+
+    /// Start / reset the game.
+    pub fn start(&mut self) {
+        let mut rng = GameRng::seed_from_u64(self.seed);
+        self.board = Board::new();
+        let randomizer: Box<dyn Randomizer> = match &self.script {
+            Some(script) => Box::new(script.clone()),
+            None => self.ruleset.randomizer.build(),
+        };
+        self.queue = PieceQueue::new(randomizer, &mut rng);
+        self.hold.reset();
+        self.gravity = Gravity::new();
+        if self.modifiers.contains(Modifier::DoubleGravity) {
+            self.gravity.multiplier = 2;
+        }
+        self.lock_delay = self.ruleset.new_lock_delay();
+        self.scoring.reset();
+        self.scoring.system = self.ruleset.scoring;
+        self.scoring.attack_table = self.ruleset.attack.clone();
+        self.garbage.clear();
+        self.exchanges.clear();
+        self.zone.reset();
+        self.stats.reset();
+        self.rng = rng;
+        self.garbage_rng = garbage_rng(self.seed);
+        self.game_over = false;
+        self.started = true;
+        self.current_piece = None;
+        self.clearing_lines = None;
+        self.entry_delay = None;
+        self.buffered_rotation = None;
+        self.buffered_hold = false;
+        self.events.clear();
+        self.last_clear_type = None;
+        self.last_was_rotation = false;
+        self.last_kick = None;
+        self.action_text_timer = Duration::ZERO;
+
+        // Spawn first piece
+        self.spawn_piece();
+    }
+
+    /// Spawn the next piece from the queue.
+    fn spawn_piece(&mut self) {
+        let piece_type = self.queue.next(&mut self.rng);
+        let piece = Piece::new(piece_type);
+
+        if self.board.is_blocked(&piece) {
+            self.game_over = true;
+            self.events.push(GameEvent::GameOver);
+            return;
+        }
+
+        self.current_piece = Some(piece);
+        self.lock_delay.reset();
+        self.gravity.reset();
+        self.last_was_rotation = false;
+        self.last_kick = None;
+    }
+
+    /// Process a player action. Returns any events generated.
+    pub fn handle_action(&mut self, action: GameAction) -> TickResult {
+        if self.game_over || !self.started {
+            return TickResult::none();
+        }
+        if self.current_piece.is_none() {
+            self.buffer_action(action);
+            return TickResult::none();
+        }
+
+        // Letting go of soft drop isn't a key press
+        if action != GameAction::SoftDropRelease {
+            let is_move = matches!(action, GameAction::MoveLeft | GameAction::MoveRight);
+            self.stats.record_input(is_move);
+        }
+        self.apply_action(action)
+    }
+
+    /// Process a move repeated by DAS and ARR rather than pressed: it counts
+    /// towards auto shift use instead of keys pressed.
+    pub fn handle_auto_shift(&mut self, action: GameAction) -> TickResult {
+        if self.game_over || !self.started || self.current_piece.is_none() {
+            return TickResult::none();
+        }
+        self.stats.record_auto_shift();
+        self.apply_action(action)
+    }
+
+    fn apply_action(&mut self, action: GameAction) -> TickResult {
+        match action {
+            GameAction::MoveLeft => self.do_move_left(),
+            GameAction::MoveRight => self.do_move_right(),
+            GameAction::SoftDrop => self.do_soft_drop(),
+            GameAction::HardDrop => self.do_hard_drop(),
+            GameAction::RotateCW => self.do_rotate_cw(),
+            GameAction::RotateCCW => self.do_rotate_ccw(),
+            GameAction::Rotate180 => self.do_rotate_180(),
+            GameAction::Hold => self.do_hold(),
+            GameAction::SoftDropRelease => {
+                self.gravity.soft_dropping = false;
+                TickResult::none()
+            }
+            GameAction::Zone => {
+                self.activate_zone();
+                TickResult::none()
+            }
+        }
+    }
+
+    /// Start the zone if the ruleset has one and the meter allows it.
+    fn activate_zone(&mut self) {
+        if self.ruleset.zone && self.zone.activate() {
+            self.events.push(GameEvent::ZoneStarted);
+        }
+    }
+
+    /// Release the lines banked during the zone all at once.
+    fn end_zone(&mut self) -> TickResult {
+        let lines = std::mem::take(&mut self.zone.lines);
+        self.events.push(GameEvent::ZoneEnded { lines });
+        if lines == 0 {
+            return TickResult::none();
+        }
+
+        let rows: Vec<usize> = (0..lines as usize).collect();
+        let garbage_cleared = self.board.count_garbage_in_rows(&rows);
+        self.stats.garbage_cleared += garbage_cleared as u32;
+        self.board.clear_lines(&rows);
+        let is_pc = self.board.is_empty();
+        if is_pc {
+            self.stats.perfect_clears += 1;
+            self.events.push(GameEvent::PerfectClear);
+        }
+
+        let (score_gained, attack) = self.scoring.process_zone(lines, is_pc);
+        self.stats.record_score(self.scoring.score);
+        self.stats.level = self.scoring.level;
+        self.stats.lines_cleared = self.scoring.lines_cleared;
+        self.gravity.level = self.scoring.level;
+
+        let remaining_attack = self.exchange_attack(attack);
+        if remaining_attack > 0 {
+            self.events.push(GameEvent::AttackSent(remaining_attack));
+        }
+        self.events.push(GameEvent::LinesClear(rows));
+
+        TickResult {
+            lines_cleared: lines,
+            is_perfect_clear: is_pc,
+            score_gained,
+            attack: remaining_attack,
+            ..TickResult::none()
+        }
+    }
+
+    /// Hold on to rotations and holds pressed between pieces, for rulesets
+    /// with entry delay.
+    fn buffer_action(&mut self, action: GameAction) {
+        match action {
+            GameAction::SoftDrop => self.gravity.soft_dropping = true,
+            GameAction::SoftDropRelease => self.gravity.soft_dropping = false,
+            GameAction::Zone => self.activate_zone(),
+            _ if !self.ruleset.has_entry_delay() => {}
+            GameAction::RotateCW | GameAction::RotateCCW | GameAction::Rotate180 => {
+                self.buffered_rotation = Some(action);
+            }
+            GameAction::Hold => self.buffered_hold = true,
+            _ => {}
+        }
+    }
+
+    /// Spawn the next piece and apply any buffered hold, then rotation.
+    fn spawn_after_delay(&mut self) {
+        self.spawn_piece();
+        if std::mem::take(&mut self.buffered_hold) {
+            self.do_hold();
+        }
+        match self.buffered_rotation.take() {
+            Some(GameAction::RotateCW) => self.do_rotate_cw(),
+            Some(GameAction::RotateCCW) => self.do_rotate_ccw(),
+            Some(GameAction::Rotate180) => self.do_rotate_180(),
+            _ => TickResult::none(),
+        };
+    }
+
+    /// Wait out the entry delay, or spawn right away without one.
+    fn start_entry_delay(&mut self, delay: Duration) {
+        if delay.is_zero() {
+            self.spawn_after_delay();
+        } else {
+            self.entry_delay = Some(delay);
+        }
+    }
+
+    /// Update game state for a frame. Call after processing all actions.
+    pub fn update(&mut self, dt: Duration) -> TickResult {
+        if self.game_over || !self.started {
+            return TickResult::none();
+        }
+
+        self.stats.time += dt;
+        self.stats.sample_height(self.board.max_height());
+        self.scoring.tick_combo(dt);
+
+        // Action text timer
+        if self.action_text_timer > Duration::ZERO {
+            self.action_text_timer = self.action_text_timer.saturating_sub(dt);
+            if self.action_text_timer == Duration::ZERO {
+                self.last_clear_type = None;
+            }
+        }
+
+        // Zone countdown
+        if self.zone.tick(dt) {
+            return self.end_zone();
+        }
+
+        // Line clear animation
+        if let Some((ref lines, ref mut timer)) = self.clearing_lines {
+            if dt >= *timer {
+                let lines = lines.clone();
+                self.clearing_lines = None;
+                self.board.clear_lines(&lines);
+
+                // Deploy any ready garbage
+                self.deploy_garbage();
+
+                // Spawn next piece
+                self.start_entry_delay(self.ruleset.line_are());
+                return TickResult::none();
+            } else {
+                *timer -= dt;
+                return TickResult::none();
+            }
+        }
+
+        // Entry delay
+        if let Some(ref mut timer) = self.entry_delay {
+            if dt >= *timer {
+                self.entry_delay = None;
+                self.spawn_after_delay();
+            } else {
+                *timer -= dt;
+            }
+            return TickResult::none();
+        }
+
+        // Time stands still during the zone
+        if self.zone.is_active() {
+            return TickResult::none();
+        }
+
+        // Apply gravity
+        if let Some(ref mut piece) = self.current_piece {
+            let drops = self.gravity.tick(dt);
+            for _ in 0..drops {
+                if !movement::try_move_down(&self.board, piece) {
+                    break;
+                }
+                self.last_was_rotation = false;
+                if self.gravity.soft_dropping {
+                    self.scoring.add_soft_drop(1);
+                }
+            }
+
+            // Check grounding and lock delay
+            self.lock_delay.note_row(piece.y);
+            let grounded = is_grounded(&self.board, piece);
+            self.lock_delay.start_if_grounded(grounded);
+
+            if grounded && self.lock_delay.tick(dt) {
+                return self.lock_current_piece();
+            }
+        }
+
+        // Tick garbage timers
+        let ready_garbage = self.garbage.tick(dt, self.is_danger());
+        if ready_garbage > 0 && self.clearing_lines.is_none() {
+            for _ in 0..ready_garbage {
+                let row = self.garbage.garbage_row(&mut self.garbage_rng);
+                self.board.add_garbage_row(row);
+            }
+            self.stats.record_received(ready_garbage);
+            self.log_exchange(ExchangeKind::Received, ready_garbage);
+            self.events.push(GameEvent::GarbageReceived(ready_garbage));
+            tracing::debug!(lines = ready_garbage, "garbage received");
+            self.check_buried();
+        }
+
+        // Rising solid floor
+        if self.clearing_lines.is_none() && self.garbage.tick_solid(dt) {
+            self.board.add_solid(1);
+            self.check_buried();
+        }
+
+        TickResult::none()
+    }
+
+    /// End the game if rows pushed in from below ran into the current piece.
+    fn check_buried(&mut self) {
+        if let Some(ref piece) = self.current_piece {
+            if !self.board.piece_fits(piece) {
+                self.game_over = true;
+                self.events.push(GameEvent::GameOver);
+            }
+        }
+    }
+
+    fn do_move_left(&mut self) -> TickResult {
+        if let Some(ref mut piece) = self.current_piece {
+            if movement::try_move_left(&self.board, piece) {
+                self.last_was_rotation = false;
+                if is_grounded(&self.board, piece) {
+                    self.lock_delay.try_reset();
+                }
+            }
+        }
+        TickResult::none()
+    }
+
+    fn do_move_right(&mut self) -> TickResult {
+        if let Some(ref mut piece) = self.current_piece {
+            if movement::try_move_right(&self.board, piece) {
+                self.last_was_rotation = false;
+                if is_grounded(&self.board, piece) {
+                    self.lock_delay.try_reset();
+                }
+            }
+        }
+        TickResult::none()
+    }
+
+    fn do_soft_drop(&mut self) -> TickResult {
+        self.gravity.soft_dropping = true;
+        if let Some(ref mut piece) = self.current_piece {
+            if movement::try_move_down(&self.board, piece) {
+                self.last_was_rotation = false;
+                self.scoring.add_soft_drop(1);
+                self.gravity.reset();
+            }
+        }
+        TickResult::none()
+    }
+
+    fn do_hard_drop(&mut self) -> TickResult {
+        if let Some(ref mut piece) = self.current_piece {
+            let cells = movement::hard_drop(&self.board, piece);
+            self.scoring.add_hard_drop(cells);
+            self.events.push(GameEvent::HardDrop { cells });
+            let mut result = self.lock_current_piece();
+            result.hard_dropped = true;
+            return result;
+        }
+        TickResult::none()
+    }
+
+    fn do_rotate_cw(&mut self) -> TickResult {
+        let kicks = self.ruleset.rotation_system.kick_table();
+        if let Some(ref mut piece) = self.current_piece {
+            let target = piece.rotation.cw();
+            if let Some(kick) = movement::try_rotate(&self.board, piece, target, kicks) {
+                self.last_was_rotation = true;
+                self.last_kick = Some(kick);
+                if is_grounded(&self.board, piece) {
+                    self.lock_delay.try_reset();
+                }
+            }
+        }
+        TickResult::none()
+    }
+
+    fn do_rotate_ccw(&mut self) -> TickResult {
+        let kicks = self.ruleset.rotation_system.kick_table();
+        if let Some(ref mut piece) = self.current_piece {
+            let target = piece.rotation.ccw();
+            if let Some(kick) = movement::try_rotate(&self.board, piece, target, kicks) {
+                self.last_was_rotation = true;
+                self.last_kick = Some(kick);
+                if is_grounded(&self.board, piece) {
+                    self.lock_delay.try_reset();
+                }
+            }
+        }
+        TickResult::none()
+    }
+
+    fn do_rotate_180(&mut self) -> TickResult {
+        let kicks = self.ruleset.rotation_system.kick_table();
+        if let Some(ref mut piece) = self.current_piece {
+            let target = piece.rotation.flip();
+            if let Some(kick) = movement::try_rotate(&self.board, piece, target, kicks) {
+                self.last_was_rotation = true;
+                self.last_kick = Some(kick);
+                if is_grounded(&self.board, piece) {
+                    self.lock_delay.try_reset();
+                }
+            }
+        }
+        TickResult::none()
+    }
+
+    fn do_hold(&mut self) -> TickResult {
+        if !self.hold_enabled() {
+            return TickResult::none();
+        }
+        if let Some(ref piece) = self.current_piece {
+            let piece_type = piece.piece_type;
+            // Err means hold was already used this turn
+            if let Ok(prev) = self.hold.hold(piece_type) {
+                match prev {
+                    Some(held_type) => {
+                        // Swap with held piece
+                        let new_piece = Piece::new(held_type);
+                        if !self.board.piece_fits(&new_piece) {
+                            // Can't swap, undo hold
+                            self.hold.piece = Some(piece_type);
+                            self.hold.used_this_turn = false;
+                            return TickResult::none();
+                        }
+                        self.current_piece = Some(new_piece);
+                    }
+                    None => {
+                        // First hold, spawn next
+                        self.current_piece = None;
+                        self.spawn_piece();
+                    }
+                }
+                self.lock_delay.reset();
+                self.gravity.reset();
+                self.last_was_rotation = false;
+                self.last_kick = None;
+            }
+        }
+        TickResult::none()
+    }
+
+    /// Lock the current piece and process line clears.
+    fn lock_current_piece(&mut self) -> TickResult {
+        let piece = match self.current_piece.take() {
+            Some(p) => p,
+            None => return TickResult::none(),
+        };
+
+        // Detect spin before locking
+        let spin = detect_spin(
+            &self.board,
+            &piece,
+            self.last_was_rotation,
+            self.last_kick,
+            self.ruleset.spins,
+        );
+
+        // Lock piece onto board
+        self.board.lock_piece(&piece);
+        self.stats.record_piece(piece.piece_type);
+        self.hold.reset_turn();
+        self.events.push(GameEvent::PieceLocked);
+
+        // Check for lockout (all cells above visible)
+        let all_above = piece
+            .cells()
+            .iter()
+            .all(|&(_, row)| row >= VISIBLE_HEIGHT as i32);
+        if all_above {
+            self.game_over = true;
+            self.events.push(GameEvent::GameOver);
+            return TickResult {
+                game_over: true,
+                piece_locked: true,
+                ..TickResult::none()
+            };
+        }
+
+        // Find full lines, leaving out those already banked by the zone
+        let zone_floor = self.zone.lines as usize;
+        let full_lines: Vec<usize> = self
+            .board
+            .find_full_lines()
+            .into_iter()
+            .filter(|&row| row >= zone_floor)
+            .collect();
+        let lines = full_lines.len() as u32;
+        if self.zone.is_active() && lines > 0 {
+            return self.bank_zone_lines(full_lines, spin);
+        }
+        if self.ruleset.zone {
+            self.zone.charge(lines);
+        }
+
+        // Count garbage cleared
+        let garbage_cleared = self.board.count_garbage_in_rows(&full_lines);
+        self.stats.garbage_cleared += garbage_cleared as u32;
+
+        // Classify the clear
+        let clear_type = ClearType::classify(lines, spin, piece.piece_type, self.ruleset.spins);
+
+        // Track clear type in stats
+        match &clear_type {
+            ClearType::Single => self.stats.singles += 1,
+            ClearType::Double => self.stats.doubles += 1,
+            ClearType::Triple => self.stats.triples += 1,
+            ClearType::Quad => self.stats.quads += 1,
+            ClearType::TSpin | ClearType::MiniTSpin => self.stats.tspins += 1,
+            ClearType::TSpinSingle => {
+                self.stats.tspin_singles += 1;
+                self.stats.tspins += 1;
+            }
+            ClearType::TSpinDouble => {
+                self.stats.tspin_doubles += 1;
+                self.stats.tspins += 1;
+            }
+            ClearType::TSpinTriple => {
+                self.stats.tspin_triples += 1;
+                self.stats.tspins += 1;
+            }
+            ClearType::MiniTSpinSingle | ClearType::MiniTSpinDouble => {
+                self.stats.mini_tspins += 1;
+            }
+            ClearType::AllSpin(_) => self.stats.all_spins += 1,
+            ClearType::None => {}
+        }
+
+        // Check for perfect clear (board will be empty after clearing)
+        let is_pc = if lines > 0 {
+            // Simulate clearing to check
+            let mut test_board = self.board.clone();
+            test_board.clear_lines(&full_lines);
+            test_board.is_empty()
+        } else {
+            false
+        };
+
+        if is_pc {
+            self.stats.perfect_clears += 1;
+            self.events.push(GameEvent::PerfectClear);
+        }
+
+        // Process scoring
+        let (score_gained, attack) = self.scoring.process_clear(&clear_type, lines, is_pc);
+        self.stats.record_score(self.scoring.score);
+        self.stats.level = self.scoring.level;
+        self.stats.lines_cleared = self.scoring.lines_cleared;
+
+        // Track max combo/btb
+        if self.scoring.combo >= 0 {
+            self.stats.max_combo = self.stats.max_combo.max(self.scoring.combo as u32);
+        }
+        if self.scoring.btb >= 0 {
+            self.stats.max_btb = self.stats.max_btb.max(self.scoring.btb as u32);
+        }
+
+        // Cancel garbage with attack, send remaining
+        let remaining_attack = self.exchange_attack(attack);
+
+        // Update gravity level
+        self.gravity.level = self.scoring.level;
+
+        // Emit events
+        if spin != SpinType::None {
+            self.events.push(GameEvent::TSpin(spin));
+        }
+        if !matches!(clear_type, ClearType::None) {
+            self.events.push(GameEvent::Clear(clear_type.clone()));
+        }
+        if remaining_attack > 0 {
+            self.events.push(GameEvent::AttackSent(remaining_attack));
+        }
+        if self.scoring.combo > 0 {
+            self.events
+                .push(GameEvent::Combo(self.scoring.combo as u32));
+        }
+        if self.scoring.btb > 0 {
+            self.events
+                .push(GameEvent::BackToBack(self.scoring.btb as u32));
+        }
+        if lines > 0 && self.scoring.last_surge > 0 {
+            self.events.push(GameEvent::Surge(self.scoring.last_surge));
+        }
+        if lines > 0 && score_gained > 0 {
+            let row = full_lines.iter().sum::<usize>() / full_lines.len();
+            self.events.push(GameEvent::Scored {
+                points: score_gained,
+                row,
+            });
+        }
+        if lines > 0 {
+            self.events.push(GameEvent::LinesClear(full_lines.clone()));
+        }
+
+        // Set action text
+        if !matches!(clear_type, ClearType::None) {
+            self.last_clear_type = Some(clear_type.clone());
+            self.action_text_timer = Duration::from_millis(2000);
+        }
+
+        // Start line clear animation or spawn next piece
+        if !full_lines.is_empty() {
+            self.clearing_lines = Some((full_lines, self.clear_delay));
+        } else {
+            // Deploy pending garbage
+            self.deploy_garbage();
+            // Spawn next piece after the entry delay
+            self.start_entry_delay(self.ruleset.are());
+        }
+
+        TickResult {
+            lines_cleared: lines,
+            clear_type: ClearType::classify(lines, spin, piece.piece_type, self.ruleset.spins),
+            is_perfect_clear: is_pc,
+            score_gained,
+            attack: remaining_attack,
+            piece_locked: true,
+            game_over: self.game_over,
+            hard_dropped: false,
+            spin_type: spin,
+        }
+    }
+
+    /// Sink lines cleared during the zone to the bottom of the board, to be
+    /// released when it ends.
+    fn bank_zone_lines(&mut self, full_lines: Vec<usize>, spin: SpinType) -> TickResult {
+        let lines = full_lines.len() as u32;
+        self.board.sink_lines(&full_lines, self.zone.lines as usize);
+        self.zone.lines += lines;
+        self.start_entry_delay(self.ruleset.are());
+        TickResult {
+            lines_cleared: lines,
+            piece_locked: true,
+            game_over: self.game_over,
+            spin_type: spin,
+            ..TickResult::none()
+        }
+    }
+
+    /// Spend `attack` cancelling pending garbage first, and send what is
+    /// left over. Returns the lines sent.
+    fn exchange_attack(&mut self, attack: u32) -> u32 {
+        if attack == 0 {
+            return 0;
+        }
+        let remaining = self.garbage.cancel(attack);
+        self.log_exchange(ExchangeKind::Cancelled, attack - remaining);
+        if remaining > 0 {
+            self.stats.record_attack(remaining);
+            self.log_exchange(ExchangeKind::Sent, remaining);
+        }
+        remaining
+    }
+
+    fn log_exchange(&mut self, kind: ExchangeKind, lines: u32) {
+        if lines > 0 {
+            self.exchanges.push(Exchange {
+                time: self.stats.time,
+                kind,
+                lines,
+            });
+        }
+    }
+
+    /// Deploy any pending garbage that's ready.
+    fn deploy_garbage(&mut self) {
+        // Garbage is deployed via the tick method in update()
+        // This is called after line clears to allow garbage insertion
+    }
+
+    /// Get preview pieces.
+    pub fn preview(&self) -> Vec<PieceType> {
+        self.queue.peek(3)
+    }
+
+    /// Get ghost piece Y position.
+    pub fn ghost_y(&self) -> Option<i32> {
+        self.current_piece.as_ref().map(|p| ghost_y(&self.board, p))
+    }
+
+    /// Whether the next piece would top out on spawning, once the current
+    /// one is dropped where its ghost shows and any lines it fills clear.
+    pub fn next_spawn_blocked(&self) -> bool {
+        let Some(&next) = self.queue.peek(1).first() else {
+            return false;
+        };
+        let mut board = self.board.clone();
+        if let Some((ref rows, _)) = self.clearing_lines {
+            board.clear_lines(rows);
+        }
+        if let Some(ref piece) = self.current_piece {
+            let mut landed = piece.clone();
+            landed.y = ghost_y(&board, piece);
+            board.lock_piece(&landed);
+            let full = board.find_full_lines();
+            board.clear_lines(&full);
+        }
+        board.is_blocked(&Piece::new(next))
+    }
+
+    /// Where the held piece would land if swapped in now: it spawns afresh,
+    /// so this drops it from the spawn. None when hold can't be used.
+    pub fn hold_ghost(&self) -> Option<Piece> {
+        if !self.hold_enabled() || self.hold.used_this_turn || self.current_piece.is_none() {
+            return None;
+        }
+        let mut piece = Piece::new(self.hold.piece?);
+        if !self.board.piece_fits(&piece) {
+            return None;
+        }
+        piece.y = ghost_y(&self.board, &piece);
+        Some(piece)
+    }
+
+    /// Check if the board is in a danger state (stack within 4 rows of the
+    /// top).
+    pub fn is_danger(&self) -> bool {
+        self.board.max_height() >= DANGER_HEIGHT
+    }
+
+    /// How close the stack is to the top, by tier.
+    pub fn danger_tier(&self) -> DangerTier {
+        DangerTier::from_height(self.board.max_height())
+    }
+
+    /// Whether the hold piece can be used, by the ruleset and modifiers.
+    pub fn hold_enabled(&self) -> bool {
+        self.ruleset.hold_enabled && !self.modifiers.contains(Modifier::NoHold)
+    }
+
+    /// Jump to a level, with its gravity. Later levels follow from it as
+    /// lines are cleared.
+    pub fn set_level(&mut self, level: u32) {
+        self.scoring.level = level;
+        self.stats.level = level;
+        self.gravity.level = level;
+    }
+
+    /// Drain all pending events.
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::ruleset::RulesetPreset;
+
+    #[test]
+    fn test_script_survives_restart() {
+        let mut game = GameState::with_seed(1);
+        game.script = Some(Sequence::parse("ZZS").unwrap());
+        for _ in 0..2 {
+            game.start();
+            assert_eq!(
+                game.current_piece.as_ref().unwrap().piece_type,
+                PieceType::Z
+            );
+            assert_eq!(game.queue.peek(2), [PieceType::Z, PieceType::S]);
+        }
+    }
+
+    #[test]
+    fn test_modifiers_apply_on_start() {
+        let mut game = GameState::with_seed(3);
+        game.modifiers = Modifiers::from(vec![
+            Modifier::NoHold,
+            Modifier::DoubleGravity,
+            Modifier::Mirror,
+        ]);
+        game.start();
+        assert_eq!(game.gravity.multiplier, 2);
+
+        let piece = game.current_piece.clone().unwrap();
+        game.handle_action(GameAction::Hold);
+        assert_eq!(game.hold.piece, None);
+        assert_eq!(
+            game.current_piece.as_ref().unwrap().piece_type,
+            piece.piece_type
+        );
+
+        // Mirror is swapped at the keys, so the game moves where it's told
+        game.handle_action(GameAction::MoveLeft);
+        assert_eq!(game.current_piece.as_ref().unwrap().x, piece.x - 1);
+    }
+
+    #[test]
+    fn test_game_start() {
+        let mut game = GameState::new();
+        game.start();
+        assert!(game.started);
+        assert!(!game.game_over);
+        assert!(game.current_piece.is_some());
+    }
+
+    #[test]
+    fn test_same_seed_same_game() {
+        let mut a = GameState::with_seed(42);
+        let mut b = GameState::with_seed(42);
+        a.start();
+        b.start();
+        for _ in 0..10 {
+            assert_eq!(
+                a.current_piece.as_ref().map(|p| p.piece_type),
+                b.current_piece.as_ref().map(|p| p.piece_type)
+            );
+            assert_eq!(a.preview(), b.preview());
+            a.handle_action(GameAction::HardDrop);
+            b.handle_action(GameAction::HardDrop);
+        }
+
+        // Restarting rewinds to the seed
+        let first = {
+            let mut c = GameState::with_seed(42);
+            c.start();
+            c.preview()
+        };
+        a.start();
+        assert_eq!(a.preview(), first);
+    }
+
+    #[test]
+    fn test_hard_drop_locks() {
+        let mut game = GameState::new();
+        game.start();
+        let result = game.handle_action(GameAction::HardDrop);
+        assert!(result.piece_locked);
+        // Should have spawned a new piece
+        assert!(game.current_piece.is_some() || game.game_over);
+    }
+
+    #[test]
+    fn test_next_spawn_blocked_after_drop() {
+        let mut game = GameState::new();
+        game.start();
+        // A stack up to the top visible row, open on the right
+        for row in 0..VISIBLE_HEIGHT as i32 {
+            for col in 0..9 {
+                game.board.set(col, row, board::Cell::Garbage);
+            }
+        }
+        game.current_piece = None;
+        assert!(!game.next_spawn_blocked());
+
+        // An I dropped on it fills the spawn row
+        game.current_piece = Some(Piece::new(PieceType::I));
+        assert!(game.next_spawn_blocked());
+
+        // A stack into the spawn row blocks it outright, unless the drop
+        // clears the lines under it
+        for col in 0..9 {
+            game.board
+                .set(col, VISIBLE_HEIGHT as i32, board::Cell::Garbage);
+        }
+        game.current_piece = None;
+        assert!(game.next_spawn_blocked());
+        for row in 0..VISIBLE_HEIGHT as i32 - 3 {
+            game.board.set(9, row, board::Cell::Garbage);
+        }
+        game.current_piece = Some(Piece {
+            rotation: piece::RotationState::R1,
+            x: 7,
+            ..Piece::new(PieceType::I)
+        });
+        assert!(!game.next_spawn_blocked());
+    }
+
+    #[test]
+    fn test_hold_ghost() {
+        let mut game = GameState::new();
+        game.start();
+        assert_eq!(game.hold_ghost(), None);
+
+        game.hold.piece = Some(PieceType::O);
+        let ghost = game.hold_ghost().expect("a held piece to preview");
+        assert_eq!(ghost.piece_type, PieceType::O);
+        assert_eq!(ghost.x, Piece::new(PieceType::O).x);
+        assert!(ghost.cells().iter().any(|&(_, row)| row == 0));
+
+        // Nothing to show once hold is spent or turned off
+        game.hold.used_this_turn = true;
+        assert_eq!(game.hold_ghost(), None);
+        game.hold.used_this_turn = false;
+        game.modifiers.set(Modifier::NoHold, true);
+        assert_eq!(game.hold_ghost(), None);
+    }
+
+    #[test]
+    fn test_danger_tier_follows_stack_height() {
+        let mut game = GameState::new();
+        assert_eq!(game.danger_tier(), DangerTier::Safe);
+
+        let mut stack_to = |height: usize| {
+            for row in 0..height as i32 {
+                game.board.set(0, row, board::Cell::Garbage);
+            }
+            game.danger_tier()
+        };
+        assert_eq!(stack_to(DANGER_WARNING_HEIGHT - 1), DangerTier::Safe);
+        assert_eq!(stack_to(DANGER_WARNING_HEIGHT), DangerTier::Warning);
+        assert_eq!(stack_to(DANGER_CRITICAL_HEIGHT - 1), DangerTier::Warning);
+        assert_eq!(stack_to(DANGER_CRITICAL_HEIGHT), DangerTier::Critical);
+        assert!(DangerTier::Critical > DangerTier::Warning);
+    }
+
+    #[test]
+    fn test_danger_within_four_rows_of_the_top() {
+        let mut game = GameState::new();
+        for row in 0..DANGER_HEIGHT as i32 - 1 {
+            game.board.set(0, row, board::Cell::Garbage);
+        }
+        assert!(!game.is_danger());
+        assert_eq!(game.danger_tier(), DangerTier::Warning);
+        game.board
+            .set(0, DANGER_HEIGHT as i32 - 1, board::Cell::Garbage);
+        assert!(game.is_danger());
+    }
+
+    #[test]
+    fn test_clear_event_emitted() {
+        let mut game = GameState::new();
+        game.start();
+        // Leave a gap exactly where a spawned I piece lands
+        for col in 0..10 {
+            if !(3..=6).contains(&col) {
+                game.board.set(col, 0, board::Cell::Garbage);
+            }
+        }
+        game.current_piece = Some(Piece::new(PieceType::I));
+        game.drain_events();
+
+        game.handle_action(GameAction::HardDrop);
+        let events = game.drain_events();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GameEvent::Clear(ClearType::Single))));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            GameEvent::Scored { points, row: 0 } if *points > 0
+        )));
+    }
+
+    #[test]
+    fn test_exchanges_logged() {
+        let mut game = GameState::with_seed(5);
+        game.start();
+        game.garbage.travel_time = Duration::ZERO;
+        // A quad's worth of well, then two pending lines for it to cancel
+        for row in 0..4 {
+            for col in 0..9 {
+                game.board.set(col, row, board::Cell::Garbage);
+            }
+        }
+        // Not a perfect clear, which would send more
+        game.board.set(0, 4, board::Cell::Garbage);
+        game.garbage.add(2);
+        game.current_piece = Some(Piece::new(PieceType::I));
+        game.handle_action(GameAction::RotateCW);
+        for _ in 0..5 {
+            game.handle_action(GameAction::MoveRight);
+        }
+        game.handle_action(GameAction::HardDrop);
+
+        let kinds: Vec<_> = game.exchanges.iter().map(|e| (e.kind, e.lines)).collect();
+        assert_eq!(
+            kinds,
+            [(ExchangeKind::Cancelled, 2), (ExchangeKind::Sent, 2)]
+        );
+
+        // Lands once the clear animation is over
+        game.garbage.add(3);
+        for _ in 0..20 {
+            game.update(Duration::from_millis(16));
+        }
+        assert_eq!(
+            game.exchanges.last().map(|e| (e.kind, e.lines)),
+            Some((ExchangeKind::Received, 3))
+        );
+
+        game.start();
+        assert!(game.exchanges.is_empty());
+    }
+
+    #[test]
+    fn test_same_seed_same_pieces_despite_garbage() {
+        let mut a = GameState::with_seed(21);
+        let mut b = GameState::with_seed(21);
+        a.start();
+        b.start();
+        a.garbage.travel_time = Duration::ZERO;
+        a.garbage.add(4);
+        let mut pieces = (Vec::new(), Vec::new());
+        for (game, seen) in [(&mut a, &mut pieces.0), (&mut b, &mut pieces.1)] {
+            for _ in 0..8 {
+                game.update(Duration::from_millis(16));
+                seen.extend(game.current_piece.as_ref().map(|p| p.piece_type));
+                game.handle_action(GameAction::HardDrop);
+            }
+            // Reach past the next bag refill
+            seen.extend(game.queue.peek(usize::MAX));
+        }
+        assert!(a.stats.garbage_received > 0);
+        let drawn = pieces.0.len().min(pieces.1.len());
+        assert!(drawn >= 20);
+        assert_eq!(pieces.0[..drawn], pieces.1[..drawn]);
+    }
+
+    #[test]
+    fn test_hold() {
+        let mut game = GameState::new();
+        game.start();
+        let original_type = game.current_piece.as_ref().unwrap().piece_type;
+        game.handle_action(GameAction::Hold);
+        assert_eq!(game.hold.piece, Some(original_type));
+        assert!(game.current_piece.is_some());
+    }
+
+    #[test]
+    fn test_ruleset_applied_on_start() {
+        let mut game = GameState::with_ruleset(1, RulesetPreset::Classic.ruleset());
+        game.start();
+        assert_eq!(game.lock_delay.delay, Duration::from_millis(250));
+        assert_eq!(game.lock_delay.resets_remaining(), 0);
+
+        // Classic has no hold
+        let original_type = game.current_piece.as_ref().unwrap().piece_type;
+        game.handle_action(GameAction::Hold);
+        assert_eq!(game.hold.piece, None);
+        assert_eq!(
+            game.current_piece.as_ref().unwrap().piece_type,
+            original_type
+        );
+    }
+
+    #[test]
+    fn test_entry_delay_buffers_rotation() {
+        let ruleset = Ruleset {
+            are_ms: 100,
+            ..Ruleset::default()
+        };
+        let mut game = GameState::with_ruleset(3, ruleset);
+        game.start();
+        game.handle_action(GameAction::HardDrop);
+        assert!(game.current_piece.is_none());
+
+        // Rotation pressed during ARE applies to the next piece on spawn
+        game.handle_action(GameAction::RotateCW);
+        game.update(Duration::from_millis(50));
+        assert!(game.current_piece.is_none());
+        game.update(Duration::from_millis(60));
+        let piece = game.current_piece.as_ref().unwrap();
+        assert_eq!(piece.rotation, piece::RotationState::R1);
+    }
+
+    #[test]
+    fn test_zone_banks_and_releases_lines() {
+        let ruleset = Ruleset {
+            zone: true,
+            ..Ruleset::default()
+        };
+        let mut game = GameState::with_ruleset(5, ruleset);
+        game.start();
+        game.zone.charge(zone::LINES_PER_QUARTER);
+        game.handle_action(GameAction::Zone);
+        assert!(game.zone.is_active());
+
+        // A single during the zone sinks to the bottom instead of clearing
+        for col in 0..10 {
+            if !(3..=6).contains(&col) {
+                game.board.set(col, 0, board::Cell::Garbage);
+            }
+        }
+        game.current_piece = Some(Piece::new(PieceType::I));
+        let result = game.handle_action(GameAction::HardDrop);
+        assert_eq!(result.lines_cleared, 1);
+        assert_eq!(game.zone.lines, 1);
+        assert_eq!(game.board.find_full_lines(), vec![0]);
+        assert_eq!(game.stats.lines_cleared, 0);
+
+        // Gravity is frozen while it lasts
+        let y = game.current_piece.as_ref().unwrap().y;
+        game.update(Duration::from_secs(2));
+        assert_eq!(game.current_piece.as_ref().unwrap().y, y);
+
+        game.drain_events();
+        let result = game.update(zone::TIME_PER_QUARTER);
+        assert_eq!(result.lines_cleared, 1);
+        assert!(!game.zone.is_active());
+        assert!(game.board.find_full_lines().is_empty());
+        assert_eq!(game.stats.lines_cleared, 1);
+        assert!(game
+            .drain_events()
+            .iter()
+            .any(|e| matches!(e, GameEvent::ZoneEnded { lines: 1 })));
+    }
+
+    #[test]
+    fn test_gravity_drops_piece() {
+        let mut game = GameState::new();
+        game.start();
+        let start_y = game.current_piece.as_ref().unwrap().y;
+        // Tick for 2 seconds (level 0 gravity = 1s per drop)
+        game.update(Duration::from_millis(1100));
+        let end_y = game.current_piece.as_ref().map(|p| p.y).unwrap_or(0);
+        assert!(end_y < start_y || game.current_piece.is_none());
+    }
+}
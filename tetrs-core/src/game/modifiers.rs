@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+
+/// Challenge that makes a game harder. Any number can be combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Modifier {
+    /// Hold is turned off.
+    NoHold,
+    /// The ghost piece isn't drawn.
+    NoGhost,
+    /// Pieces vanish once they lock.
+    Invisible,
+    /// Pieces fall twice as fast at every level.
+    DoubleGravity,
+    /// Left and right moves are swapped.
+    Mirror,
+    /// The board is drawn flipped left to right. Only the view changes;
+    /// the game itself plays on the usual columns.
+    MirrorView,
+}
+
+impl Modifier {
+    pub const ALL: [Modifier; 6] = [
+        Modifier::NoHold,
+        Modifier::NoGhost,
+        Modifier::Invisible,
+        Modifier::DoubleGravity,
+        Modifier::Mirror,
+        Modifier::MirrorView,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Modifier::NoHold => "No Hold",
+            Modifier::NoGhost => "No Ghost",
+            Modifier::Invisible => "Invisible",
+            Modifier::DoubleGravity => "Double Gravity",
+            Modifier::Mirror => "Mirror",
+            Modifier::MirrorView => "Mirror View",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Modifier::NoHold => "Hold is turned off",
+            Modifier::NoGhost => "No ghost piece showing where you'll land",
+            Modifier::Invisible => "Pieces vanish as soon as they lock",
+            Modifier::DoubleGravity => "Pieces fall twice as fast at every level",
+            Modifier::Mirror => "Left moves right and right moves left",
+            Modifier::MirrorView => "The board is drawn flipped left to right",
+        }
+    }
+
+    /// Short tag for tables, e.g. "NH".
+    pub fn badge(self) -> &'static str {
+        match self {
+            Modifier::NoHold => "NH",
+            Modifier::NoGhost => "NG",
+            Modifier::Invisible => "INV",
+            Modifier::DoubleGravity => "2G",
+            Modifier::Mirror => "MIR",
+            Modifier::MirrorView => "MV",
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The modifiers a game is played with. Stored as a list of names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "Vec<Modifier>", into = "Vec<Modifier>")]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub fn contains(self, modifier: Modifier) -> bool {
+        self.0 & modifier.bit() != 0
+    }
+
+    /// Turn a modifier on or off.
+    pub fn set(&mut self, modifier: Modifier, on: bool) {
+        if on {
+            self.0 |= modifier.bit();
+        } else {
+            self.0 &= !modifier.bit();
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Only hold and the ghost turned off. These take help away without
+    /// changing the game, so such runs are ranked in their own category.
+    pub fn is_purist(self) -> bool {
+        let aids = Modifier::NoHold.bit() | Modifier::NoGhost.bit();
+        !self.is_empty() && self.0 & !aids == 0
+    }
+
+    /// Modifiers turned on, in `Modifier::ALL` order.
+    pub fn iter(self) -> impl Iterator<Item = Modifier> {
+        Modifier::ALL.into_iter().filter(move |&m| self.contains(m))
+    }
+
+    /// Names joined for display, e.g. "No Hold, Mirror".
+    pub fn label(self) -> String {
+        self.iter()
+            .map(Modifier::name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl From<Vec<Modifier>> for Modifiers {
+    fn from(list: Vec<Modifier>) -> Self {
+        let mut modifiers = Self::default();
+        for modifier in list {
+            modifiers.set(modifier, true);
+        }
+        modifiers
+    }
+}
+
+impl From<Modifiers> for Vec<Modifier> {
+    fn from(modifiers: Modifiers) -> Self {
+        modifiers.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_iterate() {
+        let mut modifiers = Modifiers::default();
+        assert!(modifiers.is_empty());
+        modifiers.set(Modifier::Mirror, true);
+        modifiers.set(Modifier::NoHold, true);
+        modifiers.set(Modifier::NoHold, true);
+        assert!(modifiers.contains(Modifier::Mirror));
+        assert!(!modifiers.contains(Modifier::Invisible));
+        assert_eq!(
+            modifiers.iter().collect::<Vec<_>>(),
+            [Modifier::NoHold, Modifier::Mirror]
+        );
+        assert_eq!(modifiers.label(), "No Hold, Mirror");
+
+        modifiers.set(Modifier::NoHold, false);
+        assert_eq!(modifiers.iter().collect::<Vec<_>>(), [Modifier::Mirror]);
+    }
+
+    #[test]
+    fn test_purist_runs_only_drop_hold_and_ghost() {
+        assert!(!Modifiers::default().is_purist());
+        assert!(Modifiers::from(vec![Modifier::NoHold]).is_purist());
+        assert!(Modifiers::from(vec![Modifier::NoHold, Modifier::NoGhost]).is_purist());
+        assert!(!Modifiers::from(vec![Modifier::NoGhost, Modifier::Mirror]).is_purist());
+        assert!(!Modifiers::from(vec![Modifier::Invisible]).is_purist());
+    }
+
+    #[test]
+    fn test_round_trips_as_a_list() {
+        let modifiers = Modifiers::from(vec![Modifier::Invisible, Modifier::DoubleGravity]);
+        let list: Vec<Modifier> = modifiers.into();
+        assert_eq!(list, [Modifier::Invisible, Modifier::DoubleGravity]);
+        assert_eq!(Modifiers::from(list), modifiers);
+    }
+}
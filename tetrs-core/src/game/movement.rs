@@ -1,6 +1,8 @@
 use super::board::{Board, BOARD_WIDTH};
 use super::clear::SpinType;
 use super::piece::{Piece, PieceType, RotationState};
+use super::ruleset::SpinRule;
+use super::srs::KickTable;
 
 /// Result of a movement attempt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -10,22 +12,29 @@ pub enum MoveResult {
     Locked, // Hard drop or natural lock
 }
 
-/// T-Spin detection using the 3-corner rule.
-/// Returns the spin type based on the T piece's corners after rotation.
+/// Spin detection for a piece about to lock: the 3-corner rule for T pieces,
+/// immobility for the rest. Which pieces can spin, and how, is up to `rule`.
 pub fn detect_spin(
     board: &Board,
     piece: &Piece,
     last_was_rotation: bool,
     last_kick: Option<(i32, i32)>,
+    rule: SpinRule,
 ) -> SpinType {
     if !last_was_rotation {
         return SpinType::None;
     }
 
-    match piece.piece_type {
-        PieceType::T => detect_tspin(board, piece, last_kick),
-        PieceType::O => SpinType::None,
-        _ => detect_allspin(board, piece),
+    match (rule, piece.piece_type) {
+        (SpinRule::None, _) | (_, PieceType::O) => SpinType::None,
+        (SpinRule::Immobile, PieceType::T) => match detect_allspin(board, piece) {
+            SpinType::AllSpin => SpinType::TSpin,
+            _ => SpinType::None,
+        },
+        (SpinRule::Immobile, _) => detect_allspin(board, piece),
+        (_, PieceType::T) => detect_tspin(board, piece, last_kick),
+        (SpinRule::TSpinOnly, _) => SpinType::None,
+        (SpinRule::AllSpin, _) => detect_allspin(board, piece),
     }
 }
 
@@ -158,8 +167,13 @@ pub fn hard_drop(board: &Board, piece: &mut Piece) -> u32 {
 }
 
 /// Try to rotate a piece. Returns (success, kick_offset) if rotation worked.
-pub fn try_rotate(board: &Board, piece: &mut Piece, target: RotationState) -> Option<(i32, i32)> {
-    if let Some((dx, dy)) = board.try_rotate(piece, target) {
+pub fn try_rotate(
+    board: &Board,
+    piece: &mut Piece,
+    target: RotationState,
+    kick_table: &dyn KickTable,
+) -> Option<(i32, i32)> {
+    if let Some((dx, dy)) = board.try_rotate(piece, target, kick_table) {
         piece.x += dx;
         piece.y += dy;
         piece.rotation = target;
@@ -221,7 +235,7 @@ mod tests {
             }
         }
         for col in 0..10 {
-            if col < 0 || col > 2 {
+            if !(0..=2).contains(&col) {
                 board.set(col, 1, super::super::board::Cell::Filled(PieceType::I));
             }
         }
@@ -237,9 +251,50 @@ mod tests {
         piece.y = 1;
         piece.rotation = RotationState::R2; // T pointing down
 
-        let spin = detect_spin(&board, &piece, true, Some((0, 0)));
+        let spin = detect_spin(&board, &piece, true, Some((0, 0)), SpinRule::AllSpin);
         // Should detect some kind of spin (exact type depends on corner occupancy)
         // The key thing is the detection logic runs without panicking
         assert!(spin == SpinType::TSpin || spin == SpinType::MiniTSpin || spin == SpinType::None);
     }
+
+    /// A piece at the floor with every other cell of the bottom rows filled.
+    fn boxed_in(piece_type: PieceType) -> (Board, Piece) {
+        let mut board = Board::new();
+        let mut piece = Piece::new(piece_type);
+        piece.x = 3;
+        piece.y = 0;
+        let cells = piece.cells();
+        for row in 0..4 {
+            for col in 0..BOARD_WIDTH as i32 {
+                if !cells.contains(&(col, row)) {
+                    board.set(col, row, super::super::board::Cell::Garbage);
+                }
+            }
+        }
+        (board, piece)
+    }
+
+    #[test]
+    fn test_spin_rules() {
+        let (board, piece) = boxed_in(PieceType::L);
+        let spin = |rule| detect_spin(&board, &piece, true, None, rule);
+        assert_eq!(spin(SpinRule::AllSpin), SpinType::AllSpin);
+        assert_eq!(spin(SpinRule::Immobile), SpinType::AllSpin);
+        assert_eq!(spin(SpinRule::TSpinOnly), SpinType::None);
+        assert_eq!(spin(SpinRule::None), SpinType::None);
+
+        // Under the immobile rule a T needs to be stuck, not just cornered
+        let (board, piece) = boxed_in(PieceType::T);
+        assert_eq!(
+            detect_spin(&board, &piece, true, None, SpinRule::Immobile),
+            SpinType::TSpin
+        );
+        let open = Board::new();
+        let mut piece = Piece::new(PieceType::T);
+        piece.y = 0;
+        assert_eq!(
+            detect_spin(&open, &piece, true, None, SpinRule::Immobile),
+            SpinType::None
+        );
+    }
 }
@@ -1,5 +1,3 @@
-use ratatui::style::Color;
-
 /// The 7 standard tetromino types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum PieceType {
@@ -23,45 +21,41 @@ impl PieceType {
         PieceType::L,
     ];
 
-    pub fn color(self) -> Color {
+    /// Position of this type in `ALL`.
+    pub fn index(self) -> usize {
         match self {
-            PieceType::I => Color::Rgb(101, 219, 200), // Cyan
-            PieceType::O => Color::Rgb(242, 215, 76),  // Yellow
-            PieceType::T => Color::Rgb(193, 50, 208),  // Purple
-            PieceType::S => Color::Rgb(122, 205, 68),  // Green
-            PieceType::Z => Color::Rgb(216, 58, 40),   // Red
-            PieceType::J => Color::Rgb(51, 88, 221),   // Blue
-            PieceType::L => Color::Rgb(237, 169, 63),  // Orange
+            PieceType::I => 0,
+            PieceType::O => 1,
+            PieceType::T => 2,
+            PieceType::S => 3,
+            PieceType::Z => 4,
+            PieceType::J => 5,
+            PieceType::L => 6,
         }
     }
 
-    pub fn bright_color(self) -> Color {
+    pub fn name(self) -> &'static str {
         match self {
-            PieceType::I => Color::Rgb(140, 240, 225),
-            PieceType::O => Color::Rgb(255, 235, 120),
-            PieceType::T => Color::Rgb(225, 100, 240),
-            PieceType::S => Color::Rgb(160, 235, 110),
-            PieceType::Z => Color::Rgb(245, 100, 85),
-            PieceType::J => Color::Rgb(100, 135, 245),
-            PieceType::L => Color::Rgb(255, 200, 110),
+            PieceType::I => "I",
+            PieceType::O => "O",
+            PieceType::T => "T",
+            PieceType::S => "S",
+            PieceType::Z => "Z",
+            PieceType::J => "J",
+            PieceType::L => "L",
         }
     }
 
-    pub fn dim_color(self) -> Color {
-        match self {
-            PieceType::I => Color::Rgb(50, 110, 100),
-            PieceType::O => Color::Rgb(121, 107, 38),
-            PieceType::T => Color::Rgb(96, 25, 104),
-            PieceType::S => Color::Rgb(61, 102, 34),
-            PieceType::Z => Color::Rgb(108, 29, 20),
-            PieceType::J => Color::Rgb(25, 44, 110),
-            PieceType::L => Color::Rgb(118, 84, 31),
-        }
+    /// Piece named by a letter, in either case.
+    pub fn from_letter(letter: char) -> Option<Self> {
+        PieceType::ALL
+            .into_iter()
+            .find(|p| p.name().starts_with(letter.to_ascii_uppercase()))
     }
 }
 
 /// Rotation state of a piece.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize)]
 pub enum RotationState {
     /// Spawn state (0)
     R0,
@@ -112,7 +106,7 @@ impl RotationState {
 }
 
 /// Active piece on the board.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct Piece {
     pub piece_type: PieceType,
     pub rotation: RotationState,
@@ -263,8 +257,8 @@ mod tests {
     #[test]
     fn test_o_piece_rotation_invariant() {
         // O piece should have same cells in all rotations
-        for i in 0..4 {
-            assert_eq!(O_CELLS[i], O_CELLS[0]);
+        for cells in &O_CELLS {
+            assert_eq!(*cells, O_CELLS[0]);
         }
     }
 }
@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use super::attack::AttackTable;
+use super::bag::RandomizerKind;
+use super::clear::SpinType;
+use super::locking::{LockDelay, LockResetMode, LOCK_DELAY, MAX_LOCK_RESETS};
+use super::scoring::ScoringSystem;
+use super::srs::RotationSystem;
+
+/// Which spins are recognized when a piece locks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum SpinRule {
+    /// T-spins by the 3-corner rule, other pieces when immobile.
+    #[default]
+    AllSpin,
+    /// Only T-spins count.
+    TSpinOnly,
+    /// Any piece, T included, spins only when it can't move in any direction.
+    Immobile,
+    /// Spins are plain clears.
+    None,
+}
+
+impl SpinRule {
+    pub const ALL: [SpinRule; 4] = [
+        SpinRule::AllSpin,
+        SpinRule::TSpinOnly,
+        SpinRule::Immobile,
+        SpinRule::None,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SpinRule::AllSpin => "All-Spin",
+            SpinRule::TSpinOnly => "T-Spin Only",
+            SpinRule::Immobile => "Immobile",
+            SpinRule::None => "None",
+        }
+    }
+
+    /// Drop spins this rule does not recognize.
+    pub fn filter(self, spin: SpinType) -> SpinType {
+        match (self, spin) {
+            (SpinRule::AllSpin, _) => spin,
+            (SpinRule::TSpinOnly, SpinType::AllSpin) => SpinType::None,
+            (SpinRule::TSpinOnly, _) => spin,
+            (SpinRule::Immobile, SpinType::MiniTSpin) => SpinType::None,
+            (SpinRule::Immobile, _) => spin,
+            (SpinRule::None, _) => SpinType::None,
+        }
+    }
+}
+
+/// Everything that decides how a game plays, apart from its mode.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Ruleset {
+    pub randomizer: RandomizerKind,
+    pub rotation_system: RotationSystem,
+    /// Time a grounded piece waits before locking.
+    pub lock_delay_ms: u64,
+    /// Moves or rotations on the ground that restart the lock timer.
+    pub max_lock_resets: u32,
+    pub lock_reset: LockResetMode,
+    /// Entry delay between a piece locking and the next spawning.
+    pub are_ms: u64,
+    /// Entry delay after a line clear, once the clear animation ends.
+    pub line_are_ms: u64,
+    pub hold_enabled: bool,
+    pub spins: SpinRule,
+    pub scoring: ScoringSystem,
+    pub attack: AttackTable,
+    /// Whether line clears charge a zone meter that can stop time.
+    pub zone: bool,
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        RulesetPreset::default().ruleset()
+    }
+}
+
+impl Ruleset {
+    pub fn lock_delay(&self) -> Duration {
+        Duration::from_millis(self.lock_delay_ms)
+    }
+
+    /// Fresh lock delay state for a piece under these rules.
+    pub fn new_lock_delay(&self) -> LockDelay {
+        LockDelay::with_limits(self.lock_delay(), self.max_lock_resets, self.lock_reset)
+    }
+
+    pub fn are(&self) -> Duration {
+        Duration::from_millis(self.are_ms)
+    }
+
+    pub fn line_are(&self) -> Duration {
+        Duration::from_millis(self.line_are_ms)
+    }
+
+    /// Whether pieces wait between locking and spawning, so rotations and
+    /// holds pressed in between carry over to the next piece.
+    pub fn has_entry_delay(&self) -> bool {
+        self.are_ms > 0 || self.line_are_ms > 0
+    }
+}
+
+/// Named rulesets offered before starting a mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RulesetPreset {
+    /// Modern guideline: 7-bag, SRS, hold, T-spins.
+    Guideline,
+    /// NES-like: memoryless, no kicks, no hold, no spins, short ARE.
+    Classic,
+    /// Arcade: TGM history, ARS kicks, no hold, step lock resets, long ARE.
+    Tgm,
+    /// Guideline with SRS+, all-spins, and the combo attack table.
+    #[default]
+    TetrIo,
+}
+
+impl RulesetPreset {
+    pub const ALL: [RulesetPreset; 4] = [
+        RulesetPreset::Guideline,
+        RulesetPreset::Classic,
+        RulesetPreset::Tgm,
+        RulesetPreset::TetrIo,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RulesetPreset::Guideline => "Guideline",
+            RulesetPreset::Classic => "Classic",
+            RulesetPreset::Tgm => "TGM",
+            RulesetPreset::TetrIo => "TETR.IO",
+        }
+    }
+
+    pub fn ruleset(self) -> Ruleset {
+        let modern = Ruleset {
+            randomizer: RandomizerKind::SevenBag,
+            rotation_system: RotationSystem::SrsPlus,
+            lock_delay_ms: LOCK_DELAY.as_millis() as u64,
+            max_lock_resets: MAX_LOCK_RESETS,
+            lock_reset: LockResetMode::Move,
+            are_ms: 0,
+            line_are_ms: 0,
+            hold_enabled: true,
+            spins: SpinRule::AllSpin,
+            scoring: ScoringSystem::Guideline,
+            attack: AttackTable::tetrio(),
+            zone: false,
+        };
+        match self {
+            RulesetPreset::Guideline => Ruleset {
+                rotation_system: RotationSystem::Srs,
+                spins: SpinRule::TSpinOnly,
+                ..modern
+            },
+            RulesetPreset::Classic => Ruleset {
+                randomizer: RandomizerKind::Memoryless,
+                rotation_system: RotationSystem::None,
+                lock_delay_ms: 250,
+                max_lock_resets: 0,
+                lock_reset: LockResetMode::Move,
+                are_ms: 167,
+                line_are_ms: 167,
+                hold_enabled: false,
+                spins: SpinRule::None,
+                scoring: ScoringSystem::Classic,
+                attack: AttackTable::classic(),
+                zone: false,
+            },
+            RulesetPreset::Tgm => Ruleset {
+                randomizer: RandomizerKind::TgmHistory,
+                rotation_system: RotationSystem::Ars,
+                lock_reset: LockResetMode::Step,
+                are_ms: 450,
+                line_are_ms: 450,
+                hold_enabled: false,
+                spins: SpinRule::None,
+                scoring: ScoringSystem::Classic,
+                attack: AttackTable::classic(),
+                ..modern
+            },
+            RulesetPreset::TetrIo => modern,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_tetrio_preset() {
+        assert_eq!(Ruleset::default(), RulesetPreset::TetrIo.ruleset());
+        assert_eq!(
+            Ruleset::default().rotation_system,
+            RotationSystem::default()
+        );
+    }
+
+    #[test]
+    fn test_spin_rule_filter() {
+        assert_eq!(
+            SpinRule::AllSpin.filter(SpinType::AllSpin),
+            SpinType::AllSpin
+        );
+        assert_eq!(
+            SpinRule::TSpinOnly.filter(SpinType::AllSpin),
+            SpinType::None
+        );
+        assert_eq!(SpinRule::TSpinOnly.filter(SpinType::TSpin), SpinType::TSpin);
+        assert_eq!(
+            SpinRule::Immobile.filter(SpinType::MiniTSpin),
+            SpinType::None
+        );
+        assert_eq!(SpinRule::None.filter(SpinType::MiniTSpin), SpinType::None);
+    }
+}
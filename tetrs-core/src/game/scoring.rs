@@ -0,0 +1,400 @@
+use std::time::Duration;
+
+use super::attack::AttackTable;
+use super::clear::ClearType;
+
+/// Base score values per clear type.
+fn base_score(clear_type: &ClearType) -> u32 {
+    match clear_type {
+        ClearType::Single => 100,
+        ClearType::Double => 300,
+        ClearType::Triple => 500,
+        ClearType::Quad => 800,
+        ClearType::TSpin => 400,
+        ClearType::TSpinSingle => 800,
+        ClearType::TSpinDouble => 1200,
+        ClearType::TSpinTriple => 1600,
+        ClearType::MiniTSpin => 100,
+        ClearType::MiniTSpinSingle => 200,
+        ClearType::MiniTSpinDouble => 400,
+        ClearType::AllSpin(_) => 400,
+        ClearType::None => 0,
+    }
+}
+
+/// How clears are scored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ScoringSystem {
+    /// Guideline points with spin, combo, back-to-back, and drop bonuses.
+    #[default]
+    Guideline,
+    /// NES-style 40/100/300/1200 per level; spins and drops score nothing extra.
+    Classic,
+}
+
+/// Classic points per line count, before the level multiplier.
+fn classic_score(lines: u32) -> u32 {
+    match lines {
+        0 => 0,
+        1 => 40,
+        2 => 100,
+        3 => 300,
+        _ => 1200,
+    }
+}
+
+/// Time limit that keeps a combo going instead of the next piece: the
+/// combo lasts while each clear comes within `window` of the last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComboTimer {
+    pub window: Duration,
+    /// Time left before the combo ends.
+    pub left: Duration,
+}
+
+impl ComboTimer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            left: Duration::ZERO,
+        }
+    }
+}
+
+/// Scoring and combo/BTB state.
+#[derive(Debug, Clone)]
+pub struct Scoring {
+    pub score: u64,
+    pub combo: i32, // -1 = no combo
+    pub btb: i32,   // -1 = no BTB, 0+ = BTB chain length
+    pub level: u32,
+    pub lines_cleared: u32,
+    pub lines_per_level: u32,
+    pub system: ScoringSystem,
+    /// Garbage sent per clear in versus.
+    pub attack_table: AttackTable,
+    /// Surge garbage included in the last clear's attack.
+    pub last_surge: u32,
+    /// Set by modes where combos run on time rather than on pieces.
+    pub combo_timer: Option<ComboTimer>,
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scoring {
+    pub fn new() -> Self {
+        Self {
+            score: 0,
+            combo: -1,
+            btb: -1,
+            level: 0,
+            lines_cleared: 0,
+            lines_per_level: 10,
+            system: ScoringSystem::default(),
+            attack_table: AttackTable::default(),
+            last_surge: 0,
+            combo_timer: None,
+        }
+    }
+
+    /// Process a line clear. Returns (score_gained, attack_damage).
+    pub fn process_clear(
+        &mut self,
+        clear_type: &ClearType,
+        lines: u32,
+        is_perfect_clear: bool,
+    ) -> (u64, u32) {
+        if matches!(clear_type, ClearType::None) && lines == 0 {
+            // No clear: reset combo, unless a timer decides when it ends
+            if self.combo >= 0 && self.combo_timer.is_none() {
+                self.combo = -1;
+            }
+            return (0, 0);
+        }
+
+        // Increment combo
+        self.combo += 1;
+        if let Some(ref mut timer) = self.combo_timer {
+            timer.left = timer.window;
+        }
+
+        // Calculate BTB
+        let is_difficult = clear_type.is_difficult();
+        let mut broken_btb = -1;
+        if lines > 0 {
+            if is_difficult {
+                self.btb += 1;
+            } else {
+                broken_btb = self.btb;
+                self.btb = -1;
+            }
+        }
+
+        if self.system == ScoringSystem::Classic {
+            let score = classic_score(lines) as u64 * (self.level + 1) as u64;
+            self.score += score;
+            let attack = self.calculate_attack(clear_type, is_perfect_clear, broken_btb);
+            self.lines_cleared += lines;
+            self.check_level_up();
+            return (score, attack);
+        }
+
+        // Base score
+        let mut score = base_score(clear_type) as u64;
+
+        // Perfect clear bonus
+        if is_perfect_clear {
+            score += 3500;
+        }
+
+        // Combo bonus
+        if self.combo > 0 {
+            score += 50 * self.combo as u64;
+        }
+
+        // BTB multiplier
+        if self.btb > 0 {
+            score = score * 3 / 2;
+        }
+
+        // Level multiplier
+        score *= (self.level + 1) as u64;
+
+        self.score += score;
+
+        // Calculate attack
+        let mut attack = self.calculate_attack(clear_type, is_perfect_clear, broken_btb);
+
+        // Track lines and level
+        self.lines_cleared += lines;
+        self.check_level_up();
+
+        // Ensure we return at least something for non-zero clears in attack
+        if lines > 0 && attack == 0 && self.combo > 2 {
+            attack = self.attack_table.combo_minimum;
+        }
+
+        (score, attack)
+    }
+
+    /// Score a zone release of `lines` banked lines. Points grow with the
+    /// square of the lines and garbage is one per line; combo and
+    /// back-to-back are left alone. Returns (score_gained, attack_damage).
+    pub fn process_zone(&mut self, lines: u32, is_perfect_clear: bool) -> (u64, u32) {
+        if lines == 0 {
+            return (0, 0);
+        }
+        let mut score = (lines * lines) as u64 * 50;
+        if is_perfect_clear && self.system == ScoringSystem::Guideline {
+            score += 3500;
+        }
+        score *= (self.level + 1) as u64;
+        self.score += score;
+
+        let mut attack = lines;
+        if is_perfect_clear {
+            attack += self.attack_table.perfect_clear;
+        }
+        self.lines_cleared += lines;
+        self.check_level_up();
+        (score, attack)
+    }
+
+    /// Calculate attack damage for versus mode. `broken_btb` is the chain
+    /// this clear ended, or -1; it surges if the attack table allows.
+    fn calculate_attack(
+        &mut self,
+        clear_type: &ClearType,
+        is_perfect_clear: bool,
+        broken_btb: i32,
+    ) -> u32 {
+        self.last_surge = self.attack_table.surge(broken_btb);
+        self.attack_table
+            .attack(clear_type, self.combo, self.btb, is_perfect_clear)
+            + self.last_surge
+    }
+
+    /// Add points for hard drop.
+    pub fn add_hard_drop(&mut self, cells: u32) {
+        if self.system == ScoringSystem::Classic {
+            return;
+        }
+        self.score += cells as u64 * 2;
+    }
+
+    /// Add points for soft drop.
+    pub fn add_soft_drop(&mut self, cells: u32) {
+        self.score += cells as u64;
+    }
+
+    /// Check if we should level up.
+    fn check_level_up(&mut self) {
+        while self.lines_cleared >= (self.level + 1) * self.lines_per_level {
+            self.level += 1;
+        }
+    }
+
+    /// Reset progress, keeping the scoring and attack rules.
+    /// Run down the combo timer, if there is one; the combo ends when it
+    /// runs out.
+    pub fn tick_combo(&mut self, dt: Duration) {
+        let Some(ref mut timer) = self.combo_timer else {
+            return;
+        };
+        if self.combo < 0 {
+            return;
+        }
+        timer.left = timer.left.saturating_sub(dt);
+        if timer.left.is_zero() {
+            self.combo = -1;
+        }
+    }
+
+    /// Share of the combo timer left, from 1 down to 0, while a combo is
+    /// running on one.
+    pub fn combo_time_left(&self) -> Option<f32> {
+        let timer = self.combo_timer.filter(|_| self.combo >= 0)?;
+        if timer.window.is_zero() {
+            return None;
+        }
+        Some(timer.left.as_secs_f32() / timer.window.as_secs_f32())
+    }
+
+    pub fn reset(&mut self) {
+        let attack_table = std::mem::take(&mut self.attack_table);
+        *self = Self {
+            system: self.system,
+            attack_table,
+            ..Self::new()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_score() {
+        let mut scoring = Scoring::new();
+        let (score, _) = scoring.process_clear(&ClearType::Single, 1, false);
+        assert_eq!(score, 100); // 100 * (0+1)
+    }
+
+    #[test]
+    fn test_tetris_score() {
+        let mut scoring = Scoring::new();
+        let (score, _) = scoring.process_clear(&ClearType::Quad, 4, false);
+        assert_eq!(score, 800);
+    }
+
+    #[test]
+    fn test_combo_bonus() {
+        let mut scoring = Scoring::new();
+        scoring.process_clear(&ClearType::Single, 1, false);
+        let (score, _) = scoring.process_clear(&ClearType::Single, 1, false);
+        // Second clear: base 100 + combo 50*1 = 150
+        assert_eq!(score, 150);
+    }
+
+    #[test]
+    fn test_btb() {
+        let mut scoring = Scoring::new();
+        scoring.process_clear(&ClearType::Quad, 4, false);
+        let (score, _) = scoring.process_clear(&ClearType::Quad, 4, false);
+        // BTB: 800 * 1.5 = 1200 (plus combo)
+        assert!(score > 800);
+    }
+
+    #[test]
+    fn test_perfect_clear() {
+        let mut scoring = Scoring::new();
+        let (score, attack) = scoring.process_clear(&ClearType::Quad, 4, true);
+        assert!(score >= 3500 + 800); // At least PC bonus + quad
+        assert!(attack >= 10); // PC attack bonus
+    }
+
+    #[test]
+    fn test_combo_reset() {
+        let mut scoring = Scoring::new();
+        scoring.process_clear(&ClearType::Single, 1, false);
+        assert_eq!(scoring.combo, 0);
+        scoring.process_clear(&ClearType::None, 0, false);
+        assert_eq!(scoring.combo, -1);
+    }
+
+    #[test]
+    fn test_combo_timer() {
+        let mut scoring = Scoring::new();
+        scoring.combo_timer = Some(ComboTimer::new(Duration::from_secs(2)));
+        assert_eq!(scoring.combo_time_left(), None);
+
+        scoring.process_clear(&ClearType::Single, 1, false);
+        scoring.process_clear(&ClearType::None, 0, false);
+        assert_eq!(scoring.combo, 0);
+        scoring.tick_combo(Duration::from_secs(1));
+        assert_eq!(scoring.combo_time_left(), Some(0.5));
+
+        // A clear refills the timer
+        scoring.process_clear(&ClearType::Single, 1, false);
+        assert_eq!(scoring.combo, 1);
+        assert_eq!(scoring.combo_time_left(), Some(1.0));
+
+        scoring.tick_combo(Duration::from_secs(2));
+        assert_eq!(scoring.combo, -1);
+        assert_eq!(scoring.combo_time_left(), None);
+    }
+
+    #[test]
+    fn test_classic_rules() {
+        let mut scoring = Scoring::new();
+        scoring.system = ScoringSystem::Classic;
+        scoring.attack_table = AttackTable::classic();
+        scoring.process_clear(&ClearType::Quad, 4, false);
+        let (score, attack) = scoring.process_clear(&ClearType::TSpinDouble, 2, true);
+        // No back-to-back, combo, spin, or perfect clear bonuses
+        assert_eq!((score, attack), (100, 1));
+    }
+
+    #[test]
+    fn test_level_up() {
+        let mut scoring = Scoring::new();
+        for _ in 0..10 {
+            scoring.process_clear(&ClearType::Single, 1, false);
+        }
+        assert_eq!(scoring.level, 1);
+    }
+
+    #[test]
+    fn test_zone_release() {
+        let mut scoring = Scoring::new();
+        let (score, attack) = scoring.process_zone(12, false);
+        assert_eq!((score, attack), (12 * 12 * 50, 12));
+        // Several levels can pass in one release
+        scoring.process_zone(20, false);
+        assert_eq!(scoring.level, 3);
+        assert_eq!(scoring.combo, -1);
+    }
+
+    #[test]
+    fn test_surge_on_broken_chain() {
+        let mut scoring = Scoring::new();
+        scoring.attack_table.surge = true;
+        for _ in 0..5 {
+            scoring.process_clear(&ClearType::Quad, 4, false);
+        }
+        assert_eq!(scoring.btb, 4);
+        let (_, attack) = scoring.process_clear(&ClearType::Single, 1, false);
+        assert_eq!(scoring.last_surge, 4);
+        assert_eq!(
+            attack,
+            4 + scoring
+                .attack_table
+                .attack(&ClearType::Single, 5, -1, false)
+        );
+    }
+}
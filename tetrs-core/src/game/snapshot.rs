@@ -0,0 +1,253 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::bag::{PieceQueue, Randomizer, Sequence};
+use super::board::{Board, Cell, BOARD_HEIGHT, BOARD_WIDTH};
+use super::modifiers::Modifiers;
+use super::piece::{Piece, PieceType};
+use super::rng::GameRng;
+use super::ruleset::Ruleset;
+use super::stats::Stats;
+use super::GameState;
+
+/// Serializable picture of a game in progress, enough to pick it up again
+/// after the app dies. Meant to be taken as a piece spawns: timers that are
+/// running (gravity, lock delay, garbage delay) start over on restore. The
+/// randomizer is rebuilt by dealing again from the seed, so the game keeps
+/// following its own bags, history or script.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub seed: u64,
+    pub ruleset: Ruleset,
+    #[serde(default)]
+    pub modifiers: Modifiers,
+    /// Board rows from the bottom, up to the highest filled one: `.` is
+    /// empty, `#` garbage, and a piece letter a locked piece.
+    pub rows: Vec<String>,
+    pub current: Option<PieceType>,
+    pub hold: Option<PieceType>,
+    pub upcoming: Vec<PieceType>,
+    /// Pieces dealt so far, to deal again on restore. Missing from older
+    /// snapshots, which carry on from `upcoming` instead.
+    #[serde(default)]
+    pub pieces_drawn: Option<usize>,
+    /// Script the pieces were dealt from, as it was before dealing.
+    #[serde(default)]
+    pub script: Option<Sequence>,
+    /// Incoming garbage lines not yet on the board.
+    pub garbage: u32,
+    pub zone_charge: u32,
+    pub combo: i32,
+    pub btb: i32,
+    pub stats: Stats,
+    pub rng: GameRng,
+    /// Missing from snapshots taken before garbage holes had their own
+    /// generator; those start it over from the seed.
+    #[serde(default)]
+    pub garbage_rng: Option<GameRng>,
+}
+
+fn cell_char(cell: Cell) -> char {
+    match cell {
+        Cell::Empty => '.',
+        Cell::Garbage => '#',
+        Cell::Solid => '=',
+        Cell::Bomb => '*',
+        Cell::Filled(piece_type) => piece_type.name().chars().next().unwrap_or('?'),
+    }
+}
+
+fn char_cell(c: char) -> Cell {
+    match c {
+        '#' => Cell::Garbage,
+        '=' => Cell::Solid,
+        '*' => Cell::Bomb,
+        _ => PieceType::from_letter(c).map_or(Cell::Empty, Cell::Filled),
+    }
+}
+
+impl GameState {
+    /// Capture the game for a later `from_snapshot`.
+    pub fn snapshot(&self) -> Snapshot {
+        let height = self.board.max_height();
+        let rows = self.board.grid[..height]
+            .iter()
+            .map(|row| row.iter().map(|&cell| cell_char(cell)).collect())
+            .collect();
+        Snapshot {
+            seed: self.seed,
+            ruleset: self.ruleset.clone(),
+            modifiers: self.modifiers,
+            rows,
+            current: self.current_piece.as_ref().map(|p| p.piece_type),
+            hold: self.hold.piece,
+            upcoming: self.queue.peek(usize::MAX),
+            pieces_drawn: Some(self.queue.pieces_drawn()),
+            script: self.script.clone(),
+            garbage: self.garbage.pending(),
+            zone_charge: self.zone.charge,
+            combo: self.scoring.combo,
+            btb: self.scoring.btb,
+            stats: self.stats.clone(),
+            rng: self.rng.clone(),
+            garbage_rng: Some(self.garbage_rng.clone()),
+        }
+    }
+
+    /// Rebuild a started game from a snapshot, with its piece at the spawn.
+    pub fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let mut game = GameState::with_ruleset(snapshot.seed, snapshot.ruleset.clone());
+        game.modifiers = snapshot.modifiers;
+        game.script = snapshot.script.clone();
+        game.start();
+
+        game.board = Board::new();
+        for (y, row) in snapshot.rows.iter().take(BOARD_HEIGHT).enumerate() {
+            for (x, c) in row.chars().take(BOARD_WIDTH).enumerate() {
+                game.board.set(x as i32, y as i32, char_cell(c));
+            }
+        }
+
+        if let Some(ref rng) = snapshot.garbage_rng {
+            game.garbage_rng = rng.clone();
+        }
+        // The game's generator only feeds the queue, so dealing the same
+        // number of pieces again leaves both where they were
+        if let Some(drawn) = snapshot.pieces_drawn {
+            while game.queue.pieces_drawn() < drawn {
+                game.queue.next(&mut game.rng);
+            }
+        }
+        let replayed = game.rng == snapshot.rng
+            && game.queue.peek(snapshot.upcoming.len()) == snapshot.upcoming;
+        if !replayed {
+            // A queue set up by hand, or an older snapshot: deal the saved
+            // preview, then whatever the game deals from
+            game.rng = snapshot.rng.clone();
+            let then: Box<dyn Randomizer> = match snapshot.script {
+                Some(ref script) => Box::new(script.clone()),
+                None => snapshot.ruleset.randomizer.build(),
+            };
+            let resumed = Resumed {
+                upcoming: snapshot.upcoming.iter().copied().collect(),
+                then,
+            };
+            game.queue = PieceQueue::new(Box::new(resumed), &mut game.rng);
+        }
+        let current = match snapshot.current {
+            Some(piece_type) => piece_type,
+            None => game.queue.next(&mut game.rng),
+        };
+        game.current_piece = Some(Piece::new(current));
+        game.hold.piece = snapshot.hold;
+
+        if snapshot.garbage > 0 {
+            game.garbage.add(snapshot.garbage);
+        }
+        game.zone.charge = snapshot.zone_charge;
+        game.scoring.score = snapshot.stats.score;
+        game.scoring.level = snapshot.stats.level;
+        game.scoring.lines_cleared = snapshot.stats.lines_cleared;
+        game.scoring.combo = snapshot.combo;
+        game.scoring.btb = snapshot.btb;
+        game.gravity.level = snapshot.stats.level;
+        game.stats = snapshot.stats.clone();
+        game
+    }
+}
+
+/// Deals a saved preview, then carries on with the game's own randomizer.
+#[derive(Debug, Clone)]
+struct Resumed {
+    upcoming: VecDeque<PieceType>,
+    then: Box<dyn Randomizer>,
+}
+
+impl Randomizer for Resumed {
+    fn generate(&mut self, rng: &mut GameRng) -> PieceType {
+        match self.upcoming.pop_front() {
+            Some(piece) => piece,
+            None => self.then.generate(rng),
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn Randomizer> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::game::bag::RandomizerKind;
+    use crate::game::GameAction;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut game = GameState::with_seed(9);
+        game.start();
+        for action in [
+            GameAction::HardDrop,
+            GameAction::Hold,
+            GameAction::MoveLeft,
+            GameAction::HardDrop,
+        ] {
+            game.handle_action(action);
+            game.update(Duration::from_millis(300));
+        }
+        game.garbage.add(2);
+
+        let snapshot = game.snapshot();
+        let restored = GameState::from_snapshot(&snapshot);
+        assert_eq!(restored.board, game.board);
+        assert_eq!(restored.hold.piece, game.hold.piece);
+        assert_eq!(restored.queue.peek(5), game.queue.peek(5));
+        assert_eq!(
+            restored.current_piece.as_ref().map(|p| p.piece_type),
+            game.current_piece.as_ref().map(|p| p.piece_type)
+        );
+        assert_eq!(restored.garbage.pending(), 2);
+        assert_eq!(restored.stats.pieces_placed, 2);
+        assert_eq!(restored.snapshot().rows, snapshot.rows);
+        assert_eq!(restored.snapshot().rng, snapshot.rng);
+    }
+
+    /// Pieces dealt after the current one, well past the preview.
+    fn deal(game: &mut GameState, count: usize) -> Vec<PieceType> {
+        (0..count).map(|_| game.queue.next(&mut game.rng)).collect()
+    }
+
+    #[test]
+    fn test_restore_keeps_dealing_from_the_same_randomizer() {
+        let ruleset = Ruleset {
+            randomizer: RandomizerKind::TgmHistory,
+            ..Ruleset::default()
+        };
+        let mut game = GameState::with_ruleset(4, ruleset);
+        game.start();
+        for _ in 0..5 {
+            game.handle_action(GameAction::HardDrop);
+            game.update(Duration::from_millis(300));
+        }
+
+        let mut restored = GameState::from_snapshot(&game.snapshot());
+        assert_eq!(restored.queue.pieces_drawn(), game.queue.pieces_drawn());
+        assert_eq!(restored.rng, game.rng);
+        assert_eq!(deal(&mut restored, 40), deal(&mut game, 40));
+    }
+
+    #[test]
+    fn test_restore_keeps_the_script() {
+        let mut game = GameState::with_seed(2);
+        game.script = Some(Sequence::parse("TTTTTTTTTTTTTTTTTTTT").unwrap());
+        game.start();
+        game.handle_action(GameAction::HardDrop);
+
+        let mut restored = GameState::from_snapshot(&game.snapshot());
+        assert_eq!(deal(&mut restored, 40), deal(&mut game, 40));
+        assert!(restored.script.is_some());
+    }
+}
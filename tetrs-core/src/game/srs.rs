@@ -0,0 +1,305 @@
+use super::piece::{PieceType, RotationState};
+
+/// Wall kicks tried, in order, when a piece rotates. The first offset where
+/// the rotated piece fits wins.
+pub trait KickTable: std::fmt::Debug + Send + Sync {
+    /// (dx, dy) offsets to try for rotating `piece_type` from `from` to `to`.
+    fn kicks(
+        &self,
+        piece_type: PieceType,
+        from: RotationState,
+        to: RotationState,
+    ) -> &'static [(i32, i32)];
+}
+
+/// Selectable rotation system, as stored in rulesets and configs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum RotationSystem {
+    /// Guideline SRS; 180 rotations get no kicks.
+    Srs,
+    /// SRS with TETR.IO's SRS+ 180 kicks.
+    #[default]
+    SrsPlus,
+    /// SRS with the wider SRS-X 180 kicks.
+    SrsX,
+    /// Arika-style: try right, then left; the I piece never kicks.
+    Ars,
+    /// Rotate in place or not at all.
+    None,
+}
+
+impl RotationSystem {
+    pub const ALL: [RotationSystem; 5] = [
+        RotationSystem::Srs,
+        RotationSystem::SrsPlus,
+        RotationSystem::SrsX,
+        RotationSystem::Ars,
+        RotationSystem::None,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RotationSystem::Srs => "SRS",
+            RotationSystem::SrsPlus => "SRS+",
+            RotationSystem::SrsX => "SRS-X",
+            RotationSystem::Ars => "ARS",
+            RotationSystem::None => "None",
+        }
+    }
+
+    pub fn kick_table(self) -> &'static dyn KickTable {
+        match self {
+            RotationSystem::Srs => &Srs,
+            RotationSystem::SrsPlus => &SrsPlus,
+            RotationSystem::SrsX => &SrsX,
+            RotationSystem::Ars => &Ars,
+            RotationSystem::None => &NoKicks,
+        }
+    }
+}
+
+const NO_KICK: &[(i32, i32)] = &[(0, 0)];
+
+fn is_180(from: RotationState, to: RotationState) -> bool {
+    (from.index() as i32 - to.index() as i32).abs() == 2
+}
+
+/// SRS 90 degree kicks, shared by the SRS variants.
+fn srs_kicks(
+    piece_type: PieceType,
+    from: RotationState,
+    to: RotationState,
+) -> &'static [(i32, i32)] {
+    match piece_type {
+        PieceType::O => NO_KICK,
+        PieceType::I => get_i_kicks(from, to),
+        _ => get_normal_kicks(from, to),
+    }
+}
+
+/// Guideline SRS.
+#[derive(Debug, Clone, Copy)]
+pub struct Srs;
+
+impl KickTable for Srs {
+    fn kicks(
+        &self,
+        piece_type: PieceType,
+        from: RotationState,
+        to: RotationState,
+    ) -> &'static [(i32, i32)] {
+        if is_180(from, to) {
+            return NO_KICK;
+        }
+        srs_kicks(piece_type, from, to)
+    }
+}
+
+/// SRS + SRS+ 180 kicks (the default).
+#[derive(Debug, Clone, Copy)]
+pub struct SrsPlus;
+
+impl KickTable for SrsPlus {
+    fn kicks(
+        &self,
+        piece_type: PieceType,
+        from: RotationState,
+        to: RotationState,
+    ) -> &'static [(i32, i32)] {
+        if piece_type == PieceType::O {
+            return NO_KICK;
+        }
+        if is_180(from, to) {
+            return if piece_type == PieceType::I {
+                get_i_180_kicks(from, to)
+            } else {
+                get_normal_180_kicks(from, to)
+            };
+        }
+        srs_kicks(piece_type, from, to)
+    }
+}
+
+/// SRS + SRS-X 180 kicks (Heboris), shared by every piece but O.
+#[derive(Debug, Clone, Copy)]
+pub struct SrsX;
+
+impl KickTable for SrsX {
+    fn kicks(
+        &self,
+        piece_type: PieceType,
+        from: RotationState,
+        to: RotationState,
+    ) -> &'static [(i32, i32)] {
+        if piece_type == PieceType::O {
+            return NO_KICK;
+        }
+        if is_180(from, to) {
+            return get_srs_x_180_kicks(from, to);
+        }
+        srs_kicks(piece_type, from, to)
+    }
+}
+
+/// Arika-style kicks on SRS piece states: one cell right, then left.
+#[derive(Debug, Clone, Copy)]
+pub struct Ars;
+
+impl KickTable for Ars {
+    fn kicks(
+        &self,
+        piece_type: PieceType,
+        _from: RotationState,
+        _to: RotationState,
+    ) -> &'static [(i32, i32)] {
+        match piece_type {
+            PieceType::I | PieceType::O => NO_KICK,
+            _ => &[(0, 0), (1, 0), (-1, 0)],
+        }
+    }
+}
+
+/// No kicks at all.
+#[derive(Debug, Clone, Copy)]
+pub struct NoKicks;
+
+impl KickTable for NoKicks {
+    fn kicks(
+        &self,
+        _piece_type: PieceType,
+        _from: RotationState,
+        _to: RotationState,
+    ) -> &'static [(i32, i32)] {
+        NO_KICK
+    }
+}
+
+/// Standard SRS kicks for J/L/S/T/Z pieces.
+fn get_normal_kicks(from: RotationState, to: RotationState) -> &'static [(i32, i32)] {
+    use RotationState::*;
+    match (from, to) {
+        (R0, R1) => &[(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+        (R1, R0) => &[(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+        (R1, R2) => &[(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+        (R2, R1) => &[(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+        (R2, R3) => &[(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+        (R3, R2) => &[(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+        (R3, R0) => &[(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+        (R0, R3) => &[(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+        _ => NO_KICK,
+    }
+}
+
+/// SRS kicks for I piece.
+fn get_i_kicks(from: RotationState, to: RotationState) -> &'static [(i32, i32)] {
+    use RotationState::*;
+    match (from, to) {
+        (R0, R1) => &[(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+        (R1, R0) => &[(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+        (R1, R2) => &[(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+        (R2, R1) => &[(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+        (R2, R3) => &[(0, 0), (2, 0), (-1, 0), (2, 1), (-1, -2)],
+        (R3, R2) => &[(0, 0), (-2, 0), (1, 0), (-2, -1), (1, 2)],
+        (R3, R0) => &[(0, 0), (1, 0), (-2, 0), (1, -2), (-2, 1)],
+        (R0, R3) => &[(0, 0), (-1, 0), (2, 0), (-1, 2), (2, -1)],
+        _ => NO_KICK,
+    }
+}
+
+/// SRS+ 180° kicks for normal pieces (from TETR.IO).
+fn get_normal_180_kicks(from: RotationState, to: RotationState) -> &'static [(i32, i32)] {
+    use RotationState::*;
+    match (from, to) {
+        (R0, R2) => &[(0, 0), (0, 1), (1, 1), (-1, 1), (1, 0), (-1, 0)],
+        (R2, R0) => &[(0, 0), (0, -1), (-1, -1), (1, -1), (-1, 0), (1, 0)],
+        (R1, R3) => &[(0, 0), (1, 0), (1, 2), (1, 1), (0, 2), (0, 1)],
+        (R3, R1) => &[(0, 0), (-1, 0), (-1, 2), (-1, 1), (0, 2), (0, 1)],
+        _ => NO_KICK,
+    }
+}
+
+/// SRS+ 180° kicks for I piece.
+fn get_i_180_kicks(from: RotationState, to: RotationState) -> &'static [(i32, i32)] {
+    use RotationState::*;
+    match (from, to) {
+        (R0, R2) => &[(0, 0), (-1, 0), (-2, 0), (1, 0), (2, 0), (0, 1)],
+        (R2, R0) => &[(0, 0), (1, 0), (2, 0), (-1, 0), (-2, 0), (0, -1)],
+        (R1, R3) => &[(0, 0), (0, 1), (0, 2), (0, -1), (0, -2), (-1, 0)],
+        (R3, R1) => &[(0, 0), (0, 1), (0, 2), (0, -1), (0, -2), (1, 0)],
+        _ => NO_KICK,
+    }
+}
+
+/// SRS-X 180 kicks.
+#[rustfmt::skip]
+fn get_srs_x_180_kicks(from: RotationState, to: RotationState) -> &'static [(i32, i32)] {
+    use RotationState::*;
+    match (from, to) {
+        (R0, R2) => &[
+            (0, 0), (1, 0), (2, 0), (1, 1), (2, 1), (-1, 0),
+            (-2, 0), (-1, 1), (-2, 1), (0, -1), (3, 0), (-3, 0),
+        ],
+        (R1, R3) => &[
+            (0, 0), (0, 1), (0, 2), (-1, 1), (-1, 2), (0, -1),
+            (0, -2), (-1, -1), (-1, -2), (1, 0), (0, 3), (0, -3),
+        ],
+        (R2, R0) => &[
+            (0, 0), (-1, 0), (-2, 0), (-1, -1), (-2, -1), (1, 0),
+            (2, 0), (1, -1), (2, -1), (0, 1), (-3, 0), (3, 0),
+        ],
+        (R3, R1) => &[
+            (0, 0), (0, 1), (0, 2), (1, 1), (1, 2), (0, -1),
+            (0, -2), (1, -1), (1, -2), (-1, 0), (0, 3), (0, -3),
+        ],
+        _ => NO_KICK,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_o_piece_no_kicks() {
+        for system in RotationSystem::ALL {
+            let kicks =
+                system
+                    .kick_table()
+                    .kicks(PieceType::O, RotationState::R0, RotationState::R1);
+            assert_eq!(kicks, NO_KICK);
+        }
+    }
+
+    #[test]
+    fn test_normal_kicks_have_5_entries() {
+        let kicks = SrsPlus.kicks(PieceType::T, RotationState::R0, RotationState::R1);
+        assert_eq!(kicks.len(), 5);
+        assert_eq!(kicks[0], (0, 0)); // First kick is always (0,0)
+    }
+
+    #[test]
+    fn test_i_kicks_have_5_entries() {
+        let kicks = SrsPlus.kicks(PieceType::I, RotationState::R0, RotationState::R1);
+        assert_eq!(kicks.len(), 5);
+    }
+
+    #[test]
+    fn test_180_kicks_have_6_entries() {
+        let kicks = SrsPlus.kicks(PieceType::T, RotationState::R0, RotationState::R2);
+        assert_eq!(kicks.len(), 6);
+    }
+
+    #[test]
+    fn test_180_kicks_per_system() {
+        let (from, to) = (RotationState::R0, RotationState::R2);
+        assert_eq!(Srs.kicks(PieceType::T, from, to), NO_KICK);
+        assert_eq!(SrsX.kicks(PieceType::T, from, to).len(), 12);
+        assert_eq!(NoKicks.kicks(PieceType::T, from, to), NO_KICK);
+        // SRS variants agree on 90 degree turns
+        let (from, to) = (RotationState::R0, RotationState::R1);
+        assert_eq!(
+            Srs.kicks(PieceType::I, from, to),
+            SrsX.kicks(PieceType::I, from, to)
+        );
+    }
+}
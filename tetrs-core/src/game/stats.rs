@@ -0,0 +1,483 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::game::piece::PieceType;
+
+/// Width of one attack timeline bucket.
+pub const TIMELINE_BUCKET: Duration = Duration::from_secs(10);
+
+/// Time between stack height samples.
+pub const HEIGHT_SAMPLE: Duration = Duration::from_secs(1);
+
+/// Window for the rolling PPS/APM metrics.
+pub const ROLLING_WINDOW: Duration = Duration::from_secs(12);
+
+/// Length of an Ultra run, the window `Stats::best_ultra` is taken over.
+pub const ULTRA_WINDOW: Duration = Duration::from_secs(120);
+
+/// Game statistics tracking.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Stats {
+    pub pieces_placed: u32,
+    /// Pieces placed per type, indexed by `PieceType::index`.
+    #[serde(default)]
+    pub pieces_by_type: [u32; 7],
+    pub lines_cleared: u32,
+    pub score: u64,
+    /// Most score gained within any `ULTRA_WINDOW` of the run, so a long
+    /// marathon also says what its best two minutes were worth.
+    #[serde(default)]
+    pub best_ultra: u64,
+    pub level: u32,
+    pub time: Duration,
+    pub attack_sent: u32,
+    pub garbage_received: u32,
+    pub garbage_cleared: u32,
+
+    // Clear type counts
+    pub singles: u32,
+    pub doubles: u32,
+    pub triples: u32,
+    pub quads: u32,
+    pub tspins: u32,
+    pub tspin_singles: u32,
+    pub tspin_doubles: u32,
+    pub tspin_triples: u32,
+    pub mini_tspins: u32,
+    pub all_spins: u32,
+    pub perfect_clears: u32,
+
+    // Combo/BTB tracking
+    pub max_combo: u32,
+    pub max_btb: u32,
+
+    // Input tracking
+    pub inputs: u32,
+    /// Moves made by DAS and ARR rather than key presses.
+    #[serde(default)]
+    pub auto_shifts: u32,
+
+    // Attack sent / garbage received per timeline bucket
+    #[serde(default)]
+    pub attack_timeline: Vec<u32>,
+    #[serde(default)]
+    pub received_timeline: Vec<u32>,
+    /// Highest column at each `HEIGHT_SAMPLE` of the run.
+    #[serde(default)]
+    pub height_timeline: Vec<u8>,
+
+    // Recent lock times and attacks, for the rolling metrics
+    #[serde(skip)]
+    recent_pieces: VecDeque<Duration>,
+    #[serde(skip)]
+    recent_attack: VecDeque<(Duration, u32)>,
+    // Score gains over the last `ULTRA_WINDOW`, saved with the rest so a
+    // resumed run picks its window up where it left off
+    #[serde(default)]
+    recent_score: VecDeque<(Duration, u64)>,
+    // Recent key presses, and sideways moves with whether auto shift made them
+    #[serde(skip)]
+    recent_inputs: VecDeque<Duration>,
+    #[serde(skip)]
+    recent_moves: VecDeque<(Duration, bool)>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self {
+            pieces_placed: 0,
+            pieces_by_type: [0; 7],
+            lines_cleared: 0,
+            score: 0,
+            best_ultra: 0,
+            level: 0,
+            time: Duration::ZERO,
+            attack_sent: 0,
+            garbage_received: 0,
+            garbage_cleared: 0,
+            singles: 0,
+            doubles: 0,
+            triples: 0,
+            quads: 0,
+            tspins: 0,
+            tspin_singles: 0,
+            tspin_doubles: 0,
+            tspin_triples: 0,
+            mini_tspins: 0,
+            all_spins: 0,
+            perfect_clears: 0,
+            max_combo: 0,
+            max_btb: 0,
+            inputs: 0,
+            auto_shifts: 0,
+            attack_timeline: Vec::new(),
+            received_timeline: Vec::new(),
+            height_timeline: Vec::new(),
+            recent_pieces: VecDeque::new(),
+            recent_attack: VecDeque::new(),
+            recent_score: VecDeque::new(),
+            recent_inputs: VecDeque::new(),
+            recent_moves: VecDeque::new(),
+        }
+    }
+
+    /// Record a locked piece.
+    pub fn record_piece(&mut self, piece_type: PieceType) {
+        self.pieces_placed += 1;
+        self.pieces_by_type[piece_type.index()] += 1;
+        self.recent_pieces.push_back(self.time);
+        let cutoff = self.rolling_cutoff();
+        while self.recent_pieces.front().is_some_and(|&t| t < cutoff) {
+            self.recent_pieces.pop_front();
+        }
+    }
+
+    /// Record attack sent at the current game time.
+    pub fn record_attack(&mut self, lines: u32) {
+        self.attack_sent += lines;
+        let bucket = self.timeline_bucket();
+        add_to_bucket(&mut self.attack_timeline, bucket, lines);
+        self.recent_attack.push_back((self.time, lines));
+        let cutoff = self.rolling_cutoff();
+        while self.recent_attack.front().is_some_and(|&(t, _)| t < cutoff) {
+            self.recent_attack.pop_front();
+        }
+    }
+
+    /// Take `score` as the running total, counting what it gained towards
+    /// the best `ULTRA_WINDOW`.
+    pub fn record_score(&mut self, score: u64) {
+        let gained = score.saturating_sub(self.score);
+        self.score = score;
+        if gained == 0 {
+            return;
+        }
+        self.recent_score.push_back((self.time, gained));
+        let cutoff = self.time.saturating_sub(ULTRA_WINDOW);
+        while self.recent_score.front().is_some_and(|&(t, _)| t <= cutoff) {
+            self.recent_score.pop_front();
+        }
+        // Only a gain can raise the window's total, so checking here is enough
+        let window: u64 = self.recent_score.iter().map(|&(_, s)| s).sum();
+        self.best_ultra = self.best_ultra.max(window);
+    }
+
+    /// Record a key press, noting whether it moved the piece sideways.
+    pub fn record_input(&mut self, is_move: bool) {
+        self.inputs += 1;
+        self.recent_inputs.push_back(self.time);
+        if is_move {
+            self.recent_moves.push_back((self.time, false));
+        }
+        self.prune_inputs();
+    }
+
+    /// Record a sideways move made by auto shift.
+    pub fn record_auto_shift(&mut self) {
+        self.auto_shifts += 1;
+        self.recent_moves.push_back((self.time, true));
+        self.prune_inputs();
+    }
+
+    fn prune_inputs(&mut self) {
+        let cutoff = self.rolling_cutoff();
+        while self.recent_inputs.front().is_some_and(|&t| t < cutoff) {
+            self.recent_inputs.pop_front();
+        }
+        while self.recent_moves.front().is_some_and(|&(t, _)| t < cutoff) {
+            self.recent_moves.pop_front();
+        }
+    }
+
+    /// Record garbage received at the current game time.
+    pub fn record_received(&mut self, lines: u32) {
+        self.garbage_received += lines;
+        let bucket = self.timeline_bucket();
+        add_to_bucket(&mut self.received_timeline, bucket, lines);
+    }
+
+    /// Take `height` as the stack height for every sample due by now.
+    pub fn sample_height(&mut self, height: usize) {
+        let due = (self.time.as_millis() / HEIGHT_SAMPLE.as_millis()) as usize + 1;
+        let height = height.min(u8::MAX as usize) as u8;
+        if self.height_timeline.len() < due {
+            self.height_timeline.resize(due, height);
+        }
+    }
+
+    fn timeline_bucket(&self) -> usize {
+        (self.time.as_millis() / TIMELINE_BUCKET.as_millis()) as usize
+    }
+
+    fn rolling_cutoff(&self) -> Duration {
+        self.time.saturating_sub(ROLLING_WINDOW)
+    }
+
+    /// Length of the rolling window so far (shorter at the start of a run).
+    fn rolling_span(&self) -> Duration {
+        self.time.min(ROLLING_WINDOW)
+    }
+
+    /// Pieces per second over the last `ROLLING_WINDOW`.
+    pub fn rolling_pps(&self) -> f64 {
+        let secs = self.rolling_span().as_secs_f64();
+        if secs > 0.0 {
+            let cutoff = self.rolling_cutoff();
+            let pieces = self.recent_pieces.iter().filter(|&&t| t >= cutoff).count();
+            pieces as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Attack per minute over the last `ROLLING_WINDOW`.
+    pub fn rolling_apm(&self) -> f64 {
+        let mins = self.rolling_span().as_secs_f64() / 60.0;
+        if mins > 0.0 {
+            let cutoff = self.rolling_cutoff();
+            let attack: u32 = self
+                .recent_attack
+                .iter()
+                .filter(|&&(t, _)| t >= cutoff)
+                .map(|&(_, lines)| lines)
+                .sum();
+            attack as f64 / mins
+        } else {
+            0.0
+        }
+    }
+
+    /// Keys pressed per second over the last `ROLLING_WINDOW`.
+    pub fn rolling_kps(&self) -> f64 {
+        let secs = self.rolling_span().as_secs_f64();
+        if secs > 0.0 {
+            let cutoff = self.rolling_cutoff();
+            let keys = self.recent_inputs.iter().filter(|&&t| t >= cutoff).count();
+            keys as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Share of sideways moves over the last `ROLLING_WINDOW` that auto
+    /// shift made, or None if there were none.
+    pub fn rolling_auto_shift(&self) -> Option<f64> {
+        let cutoff = self.rolling_cutoff();
+        let (moves, auto) = self
+            .recent_moves
+            .iter()
+            .filter(|&&(t, _)| t >= cutoff)
+            .fold((0, 0), |(moves, auto), &(_, is_auto)| {
+                (moves + 1, auto + is_auto as u32)
+            });
+        (moves > 0).then(|| auto as f64 / moves as f64)
+    }
+
+    /// Pieces per second.
+    pub fn pps(&self) -> f64 {
+        let secs = self.time.as_secs_f64();
+        if secs > 0.0 {
+            self.pieces_placed as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Attack per minute.
+    pub fn apm(&self) -> f64 {
+        let mins = self.time.as_secs_f64() / 60.0;
+        if mins > 0.0 {
+            self.attack_sent as f64 / mins
+        } else {
+            0.0
+        }
+    }
+
+    /// VS score as TETR.IO counts it: attack sent plus garbage cleared, per
+    /// 100 seconds. Digging out counts alongside sending, so it rewards
+    /// surviving pressure as well as applying it.
+    pub fn vs(&self) -> f64 {
+        let secs = self.time.as_secs_f64();
+        if secs > 0.0 {
+            (self.attack_sent + self.garbage_cleared) as f64 * 100.0 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Lines per minute.
+    pub fn lpm(&self) -> f64 {
+        let mins = self.time.as_secs_f64() / 60.0;
+        if mins > 0.0 {
+            self.lines_cleared as f64 / mins
+        } else {
+            0.0
+        }
+    }
+
+    /// Keys pressed per second.
+    pub fn kps(&self) -> f64 {
+        let secs = self.time.as_secs_f64();
+        if secs > 0.0 {
+            self.inputs as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Keys per piece.
+    pub fn kpp(&self) -> f64 {
+        if self.pieces_placed > 0 {
+            self.inputs as f64 / self.pieces_placed as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Format time as MM:SS.mmm
+    pub fn format_time(&self) -> String {
+        let total_ms = self.time.as_millis();
+        let minutes = total_ms / 60_000;
+        let seconds = (total_ms % 60_000) / 1000;
+        let millis = total_ms % 1000;
+        format!("{:02}:{:02}.{:03}", minutes, seconds, millis)
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Add to a timeline bucket, padding earlier quiet buckets with zeros.
+fn add_to_bucket(timeline: &mut Vec<u32>, bucket: usize, lines: u32) {
+    if timeline.len() <= bucket {
+        timeline.resize(bucket + 1, 0);
+    }
+    timeline[bucket] += lines;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeline_buckets() {
+        let mut stats = Stats::new();
+        stats.record_attack(2);
+        stats.time = Duration::from_secs(9);
+        stats.record_attack(1);
+        stats.time = Duration::from_secs(25);
+        stats.record_attack(4);
+        stats.record_received(3);
+
+        assert_eq!(stats.attack_sent, 7);
+        assert_eq!(stats.attack_timeline, vec![3, 0, 4]);
+        assert_eq!(stats.garbage_received, 3);
+        assert_eq!(stats.received_timeline, vec![0, 0, 3]);
+    }
+
+    #[test]
+    fn test_height_sampled_each_second() {
+        let mut stats = Stats::new();
+        stats.time = Duration::from_millis(16);
+        stats.sample_height(2);
+        stats.time = Duration::from_millis(500);
+        stats.sample_height(5);
+        // A long frame fills the seconds it skipped
+        stats.time = Duration::from_millis(3100);
+        stats.sample_height(7);
+        assert_eq!(stats.height_timeline, vec![2, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_rolling_metrics_drop_old_activity() {
+        let mut stats = Stats::new();
+        for _ in 0..10 {
+            stats.record_piece(PieceType::T);
+        }
+        stats.record_attack(4);
+
+        stats.time = Duration::from_secs(6);
+        assert!((stats.rolling_pps() - 10.0 / 6.0).abs() < 1e-9);
+        assert!((stats.rolling_apm() - 40.0).abs() < 1e-9);
+
+        // Everything above is now outside the window
+        stats.time = Duration::from_secs(30);
+        stats.record_piece(PieceType::I);
+        stats.record_piece(PieceType::I);
+        stats.record_piece(PieceType::I);
+        assert!((stats.rolling_pps() - 3.0 / 12.0).abs() < 1e-9);
+        assert_eq!(stats.rolling_apm(), 0.0);
+        assert!(stats.pps() > stats.rolling_pps());
+    }
+
+    #[test]
+    fn test_rolling_kps_and_auto_shift() {
+        let mut stats = Stats::new();
+        assert_eq!(stats.rolling_auto_shift(), None);
+        stats.record_input(true);
+        stats.record_auto_shift();
+        stats.record_auto_shift();
+        stats.record_auto_shift();
+        stats.time = Duration::from_secs(2);
+        stats.record_input(false);
+        stats.record_input(false);
+        stats.record_input(false);
+        assert_eq!(stats.rolling_kps(), 2.0);
+        assert_eq!(stats.rolling_auto_shift(), Some(0.75));
+        assert_eq!(stats.kps(), 2.0);
+
+        // Only the last press is left in the window
+        stats.time = Duration::from_secs(20);
+        stats.record_input(true);
+        assert!((stats.rolling_kps() - 1.0 / 12.0).abs() < 1e-9);
+        assert_eq!(stats.rolling_auto_shift(), Some(0.0));
+        assert_eq!(stats.auto_shifts, 3);
+    }
+
+    #[test]
+    fn test_best_ultra_window() {
+        let mut stats = Stats::new();
+        stats.time = Duration::from_secs(10);
+        stats.record_score(1000);
+        stats.time = Duration::from_secs(100);
+        stats.record_score(1500);
+        // The first gain has left the window by now
+        stats.time = Duration::from_secs(130);
+        stats.record_score(1800);
+        assert_eq!(stats.best_ultra, 1500);
+        stats.time = Duration::from_secs(200);
+        stats.record_score(2800);
+        assert_eq!(stats.best_ultra, 1800);
+        assert_eq!(stats.score, 2800);
+    }
+
+    #[test]
+    fn test_vs_score() {
+        let mut stats = Stats::new();
+        assert_eq!(stats.vs(), 0.0);
+        stats.time = Duration::from_secs(50);
+        stats.attack_sent = 30;
+        stats.garbage_cleared = 10;
+        assert_eq!(stats.vs(), 80.0);
+    }
+
+    #[test]
+    fn test_pieces_by_type() {
+        let mut stats = Stats::new();
+        stats.record_piece(PieceType::T);
+        stats.record_piece(PieceType::T);
+        stats.record_piece(PieceType::I);
+
+        assert_eq!(stats.pieces_placed, 3);
+        assert_eq!(stats.pieces_by_type[PieceType::T.index()], 2);
+        assert_eq!(stats.pieces_by_type[PieceType::I.index()], 1);
+        assert_eq!(stats.pieces_by_type[PieceType::O.index()], 0);
+    }
+}
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+/// Lines that fill one quarter of the meter.
+pub const LINES_PER_QUARTER: u32 = 8;
+/// Quarters in a full meter.
+pub const QUARTERS: u32 = 4;
+/// Zone time granted per filled quarter.
+pub const TIME_PER_QUARTER: Duration = Duration::from_secs(5);
+
+/// Zone meter: charged by line clears, spent to stop time. While active,
+/// gravity and garbage are frozen and cleared lines pile up at the bottom of
+/// the board, to be released together when the time runs out.
+#[derive(Debug, Clone, Default)]
+pub struct Zone {
+    /// Lines charged toward the meter, up to a full meter.
+    pub charge: u32,
+    /// Time left while the zone is active.
+    pub remaining: Option<Duration>,
+    /// Full length of the active zone.
+    pub duration: Duration,
+    /// Lines banked at the bottom of the board during the zone.
+    pub lines: u32,
+}
+
+impl Zone {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// Filled quarters of the meter.
+    pub fn quarters(&self) -> u32 {
+        self.charge / LINES_PER_QUARTER
+    }
+
+    /// Meter fill as 0.0 (empty) to 1.0 (full).
+    pub fn fill(&self) -> f64 {
+        self.charge as f64 / (LINES_PER_QUARTER * QUARTERS) as f64
+    }
+
+    /// Time left as 1.0 (just started) to 0.0 (ending), or 0.0 when inactive.
+    pub fn time_left(&self) -> f64 {
+        match self.remaining {
+            Some(remaining) if !self.duration.is_zero() => {
+                remaining.as_secs_f64() / self.duration.as_secs_f64()
+            }
+            _ => 0.0,
+        }
+    }
+
+    pub fn can_activate(&self) -> bool {
+        !self.is_active() && self.quarters() > 0
+    }
+
+    /// Add cleared lines to the meter. Lines cleared during the zone don't charge it.
+    pub fn charge(&mut self, lines: u32) {
+        if !self.is_active() {
+            self.charge = (self.charge + lines).min(LINES_PER_QUARTER * QUARTERS);
+        }
+    }
+
+    /// Spend the filled quarters to start the zone. Returns false if there
+    /// isn't a full quarter or the zone is already running.
+    pub fn activate(&mut self) -> bool {
+        if !self.can_activate() {
+            return false;
+        }
+        self.duration = TIME_PER_QUARTER * self.quarters();
+        self.remaining = Some(self.duration);
+        self.charge %= LINES_PER_QUARTER;
+        self.lines = 0;
+        true
+    }
+
+    /// Count down the zone. Returns true on the tick it runs out.
+    pub fn tick(&mut self, dt: Duration) -> bool {
+        match self.remaining {
+            Some(remaining) if dt >= remaining => {
+                self.remaining = None;
+                true
+            }
+            Some(ref mut remaining) => {
+                *remaining -= dt;
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charge_caps_at_full_meter() {
+        let mut zone = Zone::new();
+        zone.charge(LINES_PER_QUARTER * QUARTERS + 5);
+        assert_eq!(zone.quarters(), QUARTERS);
+        assert_eq!(zone.fill(), 1.0);
+    }
+
+    #[test]
+    fn test_activation_needs_a_quarter() {
+        let mut zone = Zone::new();
+        zone.charge(LINES_PER_QUARTER - 1);
+        assert!(!zone.activate());
+
+        zone.charge(LINES_PER_QUARTER + 2);
+        assert!(zone.activate());
+        assert_eq!(zone.duration, TIME_PER_QUARTER * 2);
+        // Leftover lines stay on the meter
+        assert_eq!(zone.charge, 1);
+        // Clears during the zone don't charge it
+        zone.charge(4);
+        assert_eq!(zone.charge, 1);
+    }
+
+    #[test]
+    fn test_tick_ends_zone() {
+        let mut zone = Zone::new();
+        zone.charge(LINES_PER_QUARTER);
+        zone.activate();
+        assert!(!zone.tick(Duration::from_secs(3)));
+        assert!(zone.is_active());
+        assert!(zone.tick(Duration::from_secs(3)));
+        assert!(!zone.is_active());
+    }
+}
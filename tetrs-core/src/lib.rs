@@ -0,0 +1,10 @@
+//! Terminal-agnostic Tetris engine used by tetrs.
+//!
+//! `game` holds the rules and state, `ai` the versus opponent, and `modes`
+//! the win/loss conditions layered on top. Nothing here depends on a
+//! renderer; frontends drive `GameState` with actions and time deltas and
+//! draw it however they like.
+
+pub mod ai;
+pub mod game;
+pub mod modes;
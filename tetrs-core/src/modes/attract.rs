@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use crate::ai::difficulty::AiDifficulty;
+use crate::ai::AiPlayer;
+use crate::game::GameState;
+
+/// Self-playing AI demo shown behind the main menu when idle.
+pub struct AttractMode {
+    pub ai: AiPlayer,
+    pub game: GameState,
+}
+
+#[cfg(feature = "entropy")]
+impl Default for AttractMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AttractMode {
+    #[cfg(feature = "entropy")]
+    pub fn new() -> Self {
+        Self::with_seed(rand::random())
+    }
+
+    pub fn with_seed(seed: u64) -> Self {
+        let mut game = GameState::with_seed(seed);
+        game.start();
+        Self {
+            ai: AiPlayer::with_seed(AiDifficulty::Hard, seed.rotate_left(32)),
+            game,
+        }
+    }
+
+    /// Advance the demo by one frame. Restarts when the AI tops out.
+    pub fn update(&mut self, dt: Duration) {
+        if self.game.game_over {
+            // Move on to the next seed so the demo doesn't repeat itself
+            self.game = GameState::with_seed(self.game.seed.wrapping_add(1));
+            self.game.start();
+            self.ai.reset();
+        }
+
+        let actions = self.ai.think(&self.game, dt);
+        for action in actions {
+            self.game.handle_action(action);
+        }
+        self.game.update(dt);
+
+        // Nothing consumes demo events
+        self.game.drain_events();
+    }
+}
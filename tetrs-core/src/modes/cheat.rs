@@ -0,0 +1,142 @@
+use crate::game::board::Board;
+use crate::game::piece::{Piece, PieceType};
+use crate::game::GameState;
+
+/// Highest level the `level` command accepts; gravity is 20G from here.
+pub const MAX_LEVEL: u32 = 20;
+
+/// Most garbage rows one `garbage` command adds.
+pub const MAX_GARBAGE: u32 = 20;
+
+/// Sandbox command, typed at the practice prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cheat {
+    /// Swap the piece in play for another.
+    Give(PieceType),
+    /// Empty the board and any incoming garbage.
+    ClearBoard,
+    /// Push this many garbage rows in from the bottom.
+    Garbage(u32),
+    /// Jump to a level, which sets the gravity.
+    Level(u32),
+}
+
+impl Cheat {
+    /// Parse a command such as `give t`, `clear`, `garbage 4` or `level 10`.
+    /// A piece letter alone is short for `give`.
+    pub fn parse(command: &str) -> Result<Self, String> {
+        let mut words = command.split_whitespace();
+        let name = words.next().unwrap_or("").to_ascii_lowercase();
+        let arg = words.next();
+        if words.next().is_some() {
+            return Err("too many arguments".to_string());
+        }
+        let number = |default: Option<u32>| match arg {
+            Some(arg) => arg
+                .parse::<u32>()
+                .map_err(|_| format!("'{}' is not a number", arg)),
+            None => default.ok_or_else(|| format!("{} needs a number", name)),
+        };
+
+        match name.as_str() {
+            "give" | "piece" => {
+                let arg = arg.ok_or("give needs a piece")?;
+                let mut letters = arg.chars();
+                match (
+                    letters.next().and_then(PieceType::from_letter),
+                    letters.next(),
+                ) {
+                    (Some(piece), None) => Ok(Cheat::Give(piece)),
+                    _ => Err(format!("'{}' is not a piece", arg)),
+                }
+            }
+            "clear" => Ok(Cheat::ClearBoard),
+            "garbage" => Ok(Cheat::Garbage(number(Some(1))?.clamp(1, MAX_GARBAGE))),
+            "level" => Ok(Cheat::Level(number(None)?.min(MAX_LEVEL))),
+            "" => Err("type a command".to_string()),
+            _ if arg.is_none() && name.chars().count() == 1 => name
+                .chars()
+                .next()
+                .and_then(PieceType::from_letter)
+                .map(Cheat::Give)
+                .ok_or_else(|| format!("unknown command '{}'", name)),
+            _ => Err(format!("unknown command '{}'", name)),
+        }
+    }
+
+    /// Apply the command to a game.
+    pub fn apply(self, game: &mut GameState) {
+        match self {
+            Cheat::Give(piece_type) => {
+                if game.current_piece.is_some() {
+                    game.current_piece = Some(Piece::new(piece_type));
+                    game.lock_delay.reset();
+                    game.gravity.reset();
+                    game.last_was_rotation = false;
+                    game.last_kick = None;
+                }
+            }
+            Cheat::ClearBoard => {
+                game.board = Board::new();
+                game.garbage.clear();
+            }
+            Cheat::Garbage(rows) => {
+                for _ in 0..rows {
+                    let row = game.garbage.garbage_row(&mut game.garbage_rng);
+                    game.board.add_garbage_row(row);
+                }
+                // Start the piece over if the rows pushed into it
+                if let Some(ref mut piece) = game.current_piece {
+                    if !game.board.piece_fits(piece) {
+                        *piece = Piece::new(piece.piece_type);
+                    }
+                }
+            }
+            Cheat::Level(level) => game.set_level(level),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(Cheat::parse("give T"), Ok(Cheat::Give(PieceType::T)));
+        assert_eq!(Cheat::parse("i"), Ok(Cheat::Give(PieceType::I)));
+        assert_eq!(Cheat::parse(" clear "), Ok(Cheat::ClearBoard));
+        assert_eq!(Cheat::parse("garbage"), Ok(Cheat::Garbage(1)));
+        assert_eq!(Cheat::parse("GARBAGE 4"), Ok(Cheat::Garbage(4)));
+        assert_eq!(Cheat::parse("level 99"), Ok(Cheat::Level(MAX_LEVEL)));
+        assert!(Cheat::parse("level").is_err());
+        assert!(Cheat::parse("give X").is_err());
+        assert!(Cheat::parse("garbage two").is_err());
+        assert!(Cheat::parse("fly").is_err());
+    }
+
+    #[test]
+    fn test_garbage_and_clear() {
+        let mut game = GameState::with_seed(3);
+        game.start();
+        Cheat::Garbage(3).apply(&mut game);
+        assert_eq!(game.board.max_height(), 3);
+        assert_eq!(game.board.find_full_lines(), Vec::<usize>::new());
+
+        Cheat::ClearBoard.apply(&mut game);
+        assert!(game.board.is_empty());
+    }
+
+    #[test]
+    fn test_give_swaps_piece_in_play() {
+        let mut game = GameState::with_seed(3);
+        game.start();
+        let next = game.queue.peek(1);
+        Cheat::Give(PieceType::O).apply(&mut game);
+        assert_eq!(
+            game.current_piece.as_ref().unwrap().piece_type,
+            PieceType::O
+        );
+        assert_eq!(game.queue.peek(1), next);
+    }
+}
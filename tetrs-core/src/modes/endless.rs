@@ -0,0 +1,86 @@
+use crate::game::GameState;
+use crate::modes::GameMode;
+use crate::modes::{format_number, GameResult};
+
+/// Endless marathon mode.
+pub struct EndlessMode {
+    /// Level the game starts at.
+    pub start_level: u32,
+}
+
+impl Default for EndlessMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EndlessMode {
+    pub fn new() -> Self {
+        Self { start_level: 0 }
+    }
+
+    /// Marathon starting at a higher level and its gravity.
+    pub fn with_start_level(start_level: u32) -> Self {
+        Self { start_level }
+    }
+}
+
+impl GameMode for EndlessMode {
+    fn name(&self) -> &str {
+        "ENDLESS"
+    }
+
+    fn on_start(&mut self, game: &mut GameState) {
+        game.set_level(self.start_level);
+    }
+
+    fn on_update(&mut self, _game: &mut GameState) {
+        // Nothing per-tick
+    }
+
+    fn check_complete(&self, game: &GameState) -> Option<GameResult> {
+        if game.game_over {
+            Some(GameResult {
+                mode_name: "Endless Marathon".to_string(),
+                primary_label: "SCORE".to_string(),
+                primary_value: format_number(game.scoring.score),
+                is_new_high_score: false,
+                stats: game.stats.clone(),
+                won: None,
+                best_ultra: Some(game.stats.best_ultra),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn info_text(&self, _game: &GameState) -> Option<String> {
+        None
+    }
+
+    fn resumable(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_level_sets_gravity() {
+        let mut game = GameState::with_seed(1);
+        game.start();
+        let mut endless = EndlessMode::with_start_level(7);
+        endless.on_start(&mut game);
+        assert_eq!(game.scoring.level, 7);
+        assert_eq!(game.stats.level, 7);
+        assert_eq!(game.gravity.level, 7);
+
+        // Restarts go back to the chosen level
+        game.set_level(12);
+        game.start();
+        endless.on_start(&mut game);
+        assert_eq!(game.gravity.level, 7);
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use crate::ai::difficulty::AiDifficulty;
+
+/// Opponents in order, one per stage.
+pub const STAGES: [AiDifficulty; 4] = [
+    AiDifficulty::Easy,
+    AiDifficulty::Medium,
+    AiDifficulty::Hard,
+    AiDifficulty::Expert,
+];
+
+/// Lost rounds the run can survive; losing with none left ends it.
+pub const LIVES: u32 = 3;
+
+/// Break between rounds before the next one starts on its own.
+pub const BREAK_TIME: Duration = Duration::from_secs(5);
+
+/// What happens after a gauntlet round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome {
+    /// Won; the next stage is up.
+    NextStage,
+    /// Lost with lives to spare; the same stage again.
+    Retry,
+    /// Won the last stage.
+    Cleared,
+    /// Lost the last life.
+    Eliminated,
+}
+
+impl RoundOutcome {
+    pub fn is_final(self) -> bool {
+        matches!(self, RoundOutcome::Cleared | RoundOutcome::Eliminated)
+    }
+}
+
+/// A single run through every AI difficulty back to back. Lives and the
+/// clock carry over from round to round.
+#[derive(Debug, Clone)]
+pub struct Gauntlet {
+    /// Index into `STAGES` of the current opponent.
+    pub stage: usize,
+    pub lives: u32,
+    /// Play time summed over every round so far.
+    pub total_time: Duration,
+    /// Outcome of the last finished round.
+    pub last_outcome: Option<RoundOutcome>,
+}
+
+impl Default for Gauntlet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Gauntlet {
+    pub fn new() -> Self {
+        Self {
+            stage: 0,
+            lives: LIVES,
+            total_time: Duration::ZERO,
+            last_outcome: None,
+        }
+    }
+
+    /// Opponent for the current stage.
+    pub fn difficulty(&self) -> AiDifficulty {
+        STAGES[self.stage.min(STAGES.len() - 1)]
+    }
+
+    /// Stage number shown to the player, starting at 1.
+    pub fn stage_number(&self) -> usize {
+        self.stage.min(STAGES.len() - 1) + 1
+    }
+
+    /// Stages beaten so far.
+    pub fn stages_cleared(&self) -> u32 {
+        match self.last_outcome {
+            Some(RoundOutcome::Cleared) => STAGES.len() as u32,
+            _ => self.stage as u32,
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.last_outcome.is_some_and(RoundOutcome::is_final)
+    }
+
+    /// Record a finished round and move the run along.
+    pub fn record_round(&mut self, won: bool, time: Duration) -> RoundOutcome {
+        self.total_time += time;
+        let outcome = if won {
+            if self.stage + 1 >= STAGES.len() {
+                RoundOutcome::Cleared
+            } else {
+                self.stage += 1;
+                RoundOutcome::NextStage
+            }
+        } else if self.lives > 1 {
+            self.lives -= 1;
+            RoundOutcome::Retry
+        } else {
+            self.lives = 0;
+            RoundOutcome::Eliminated
+        };
+        self.last_outcome = Some(outcome);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_win_every_stage() {
+        let mut gauntlet = Gauntlet::new();
+        for (stage, &difficulty) in STAGES.iter().enumerate() {
+            assert_eq!(gauntlet.difficulty(), difficulty);
+            let outcome = gauntlet.record_round(true, Duration::from_secs(60));
+            assert_eq!(outcome.is_final(), stage == STAGES.len() - 1);
+        }
+        assert!(gauntlet.is_over());
+        assert_eq!(gauntlet.stages_cleared(), 4);
+        assert_eq!(gauntlet.total_time, Duration::from_secs(240));
+    }
+
+    #[test]
+    fn test_losses_cost_lives() {
+        let mut gauntlet = Gauntlet::new();
+        gauntlet.record_round(true, Duration::from_secs(30));
+        for _ in 1..LIVES {
+            assert_eq!(
+                gauntlet.record_round(false, Duration::from_secs(30)),
+                RoundOutcome::Retry
+            );
+            // A lost round replays the same opponent
+            assert_eq!(gauntlet.difficulty(), AiDifficulty::Medium);
+        }
+        assert_eq!(
+            gauntlet.record_round(false, Duration::from_secs(30)),
+            RoundOutcome::Eliminated
+        );
+        assert!(gauntlet.is_over());
+        assert_eq!(gauntlet.stages_cleared(), 1);
+    }
+}
@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::board::BOARD_WIDTH;
+use crate::game::{GameAction, GameState};
+
+/// Taps of one direction further apart than this don't count towards tap
+/// speed: the player wasn't tapping in a row.
+const TAP_CHAIN_MS: u64 = 500;
+
+/// Snapshot of a run whenever its stack or line count changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GhostFrame {
+    pub time_ms: u64,
+    pub lines: u32,
+    /// Height of every column, for drawing the stack's silhouette.
+    pub heights: [u8; BOARD_WIDTH],
+}
+
+/// A game key going down or up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputStamp {
+    pub time_ms: u64,
+    pub action: GameAction,
+    /// False when the key was let go.
+    pub down: bool,
+}
+
+/// How a run was handled, worked out from its input timings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Handling {
+    /// Direction presses let go before auto shift started.
+    pub taps: u32,
+    /// Mean time between taps of the same direction in a row.
+    pub tap_interval: Option<Duration>,
+    /// Direction presses held into auto shift.
+    pub das_holds: u32,
+    /// Mean time those were held, charging and then shifting.
+    pub das_charge: Option<Duration>,
+    pub hard_drops: u32,
+    /// Mean and shortest time between hard drops.
+    pub drop_interval: Option<Duration>,
+    pub fastest_drop: Option<Duration>,
+}
+
+/// Timeline of a run, recorded while playing and raced against later.
+///
+/// This is a progress trace rather than an input replay: it can draw the
+/// run's stack and pace, not re-simulate it. The key timings alongside are
+/// for looking at how the run was handled.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GhostRun {
+    pub frames: Vec<GhostFrame>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<InputStamp>,
+}
+
+impl GhostRun {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a frame for the game's current state, unless nothing changed
+    /// since the last one.
+    pub fn record(&mut self, game: &GameState) {
+        let mut heights = [0; BOARD_WIDTH];
+        for (col, height) in heights.iter_mut().enumerate() {
+            *height = game.board.column_height(col) as u8;
+        }
+        let lines = game.scoring.lines_cleared;
+        if self
+            .frames
+            .last()
+            .is_some_and(|f| f.lines == lines && f.heights == heights)
+        {
+            return;
+        }
+        self.frames.push(GhostFrame {
+            time_ms: game.stats.time.as_millis() as u64,
+            lines,
+            heights,
+        });
+    }
+
+    /// Note a game key going down or up `time` into the run.
+    pub fn record_input(&mut self, time: Duration, action: GameAction, down: bool) {
+        self.inputs.push(InputStamp {
+            time_ms: time.as_millis() as u64,
+            action,
+            down,
+        });
+    }
+
+    /// Tap speed, DAS use and hard drop pace, for a run played with `das`.
+    /// A direction pressed again without being let go, as on terminals
+    /// without key release events, counts as a tap.
+    pub fn handling(&self, das: Duration) -> Handling {
+        let das_ms = das.as_millis() as u64;
+        let mut handling = Handling::default();
+        // Per direction: when it went down, and when the last tap did
+        let mut down_at = [None::<u64>; 2];
+        let mut last_tap = [None::<u64>; 2];
+        let mut tap_gaps = Vec::new();
+        let mut charges = Vec::new();
+        let mut last_drop = None;
+        let mut drop_gaps = Vec::new();
+
+        for stamp in &self.inputs {
+            let dir = match stamp.action {
+                GameAction::MoveLeft => 0,
+                GameAction::MoveRight => 1,
+                GameAction::HardDrop if stamp.down => {
+                    handling.hard_drops += 1;
+                    if let Some(last) = last_drop.replace(stamp.time_ms) {
+                        drop_gaps.push(stamp.time_ms - last);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            let Some(pressed) = down_at[dir].take() else {
+                if stamp.down {
+                    down_at[dir] = Some(stamp.time_ms);
+                }
+                continue;
+            };
+            let held = stamp.time_ms - pressed;
+            if stamp.down || held < das_ms {
+                handling.taps += 1;
+                if let Some(last) = last_tap[dir].filter(|&t| pressed - t <= TAP_CHAIN_MS) {
+                    tap_gaps.push(pressed - last);
+                }
+                last_tap[dir] = Some(pressed);
+            } else {
+                handling.das_holds += 1;
+                charges.push(held);
+                last_tap[dir] = None;
+            }
+            if stamp.down {
+                down_at[dir] = Some(stamp.time_ms);
+            }
+        }
+
+        let mean = |gaps: &[u64]| {
+            (!gaps.is_empty())
+                .then(|| Duration::from_millis(gaps.iter().sum::<u64>() / gaps.len() as u64))
+        };
+        handling.tap_interval = mean(&tap_gaps);
+        handling.das_charge = mean(&charges);
+        handling.drop_interval = mean(&drop_gaps);
+        handling.fastest_drop = drop_gaps.iter().min().map(|&ms| Duration::from_millis(ms));
+        handling
+    }
+
+    /// The run as it stood at `time`.
+    pub fn at(&self, time: Duration) -> Option<&GhostFrame> {
+        let ms = time.as_millis() as u64;
+        let idx = self.frames.partition_point(|f| f.time_ms <= ms);
+        idx.checked_sub(1).map(|i| &self.frames[i])
+    }
+
+    /// When the run first reached `lines` cleared lines.
+    pub fn split(&self, lines: u32) -> Option<Duration> {
+        self.frames
+            .iter()
+            .find(|f| f.lines >= lines)
+            .map(|f| Duration::from_millis(f.time_ms))
+    }
+
+    /// Signed time in milliseconds between reaching `lines` now and in this
+    /// run: negative when ahead.
+    pub fn split_delta_ms(&self, lines: u32, time: Duration) -> Option<i64> {
+        if lines == 0 {
+            return None;
+        }
+        let split = self.split(lines)?;
+        Some(time.as_millis() as i64 - split.as_millis() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::board::Cell;
+
+    #[test]
+    fn test_record_and_look_up() {
+        let mut game = GameState::with_seed(3);
+        game.start();
+        let mut run = GhostRun::new();
+        run.record(&game);
+        run.record(&game);
+        assert_eq!(run.frames.len(), 1);
+
+        game.stats.time = Duration::from_secs(2);
+        game.board.set(0, 0, Cell::Garbage);
+        run.record(&game);
+        game.stats.time = Duration::from_secs(5);
+        game.scoring.lines_cleared = 4;
+        run.record(&game);
+
+        assert_eq!(run.frames.len(), 3);
+        assert_eq!(run.at(Duration::from_secs(3)).unwrap().heights[0], 1);
+        assert_eq!(run.split(4), Some(Duration::from_secs(5)));
+        assert_eq!(run.split(5), None);
+        assert_eq!(
+            run.split_delta_ms(4, Duration::from_millis(4500)),
+            Some(-500)
+        );
+    }
+
+    #[test]
+    fn test_handling_from_inputs() {
+        let mut run = GhostRun::new();
+        let mut at =
+            |ms: u64, action, down| run.record_input(Duration::from_millis(ms), action, down);
+        // Two quick taps left, 120 ms apart
+        at(0, GameAction::MoveLeft, true);
+        at(40, GameAction::MoveLeft, false);
+        at(120, GameAction::MoveLeft, true);
+        at(160, GameAction::MoveLeft, false);
+        at(200, GameAction::HardDrop, true);
+        // Held right into auto shift for 300 ms
+        at(300, GameAction::MoveRight, true);
+        at(600, GameAction::MoveRight, false);
+        at(650, GameAction::HardDrop, true);
+        at(950, GameAction::HardDrop, true);
+
+        let handling = run.handling(Duration::from_millis(133));
+        assert_eq!(handling.taps, 2);
+        assert_eq!(handling.tap_interval, Some(Duration::from_millis(120)));
+        assert_eq!(handling.das_holds, 1);
+        assert_eq!(handling.das_charge, Some(Duration::from_millis(300)));
+        assert_eq!(handling.hard_drops, 3);
+        assert_eq!(handling.drop_interval, Some(Duration::from_millis(375)));
+        assert_eq!(handling.fastest_drop, Some(Duration::from_millis(300)));
+    }
+}
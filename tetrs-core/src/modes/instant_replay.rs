@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::game::board::Board;
+use crate::game::piece::{Piece, PieceType};
+use crate::game::GameState;
+
+/// How much of the end of a game is kept for the replay.
+pub const REPLAY_WINDOW: Duration = Duration::from_secs(10);
+
+/// What the player could see at one moment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayFrame {
+    pub time: Duration,
+    pub board: Board,
+    pub piece: Option<Piece>,
+    pub hold: Option<PieceType>,
+    pub next: Vec<PieceType>,
+    /// Incoming garbage lines.
+    pub garbage: u32,
+}
+
+/// The last `REPLAY_WINDOW` of a game, recorded as it's played so the
+/// moments before a top out can be watched again.
+///
+/// Frames are only kept when something on screen changed, so a slow
+/// stretch costs little.
+#[derive(Debug, Clone, Default)]
+pub struct InstantReplay {
+    frames: VecDeque<ReplayFrame>,
+}
+
+impl InstantReplay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a frame for the game's current state, unless it looks the same
+    /// as the last one, and drop frames that fell out of the window. Call
+    /// `clear` whenever a game starts, restarts or is resumed.
+    pub fn record(&mut self, game: &GameState) {
+        let time = game.stats.time;
+        let unchanged = self.frames.back().is_some_and(|last| {
+            last.piece == game.current_piece
+                && last.hold == game.hold.piece
+                && last.garbage == game.garbage.pending()
+                && last.board == game.board
+        });
+        if !unchanged {
+            self.frames.push_back(ReplayFrame {
+                time,
+                board: game.board.clone(),
+                piece: game.current_piece.clone(),
+                hold: game.hold.piece,
+                next: game.preview(),
+                garbage: game.garbage.pending(),
+            });
+        }
+
+        // Keep the frame on screen as the window opens
+        let cutoff = time.saturating_sub(REPLAY_WINDOW);
+        while self.frames.get(1).is_some_and(|f| f.time <= cutoff) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Forget the last game's frames.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Game time the replay starts and ends at.
+    pub fn span(&self) -> Option<(Duration, Duration)> {
+        let first = self.frames.front()?.time;
+        let last = self.frames.back()?.time;
+        Some((first.max(last.saturating_sub(REPLAY_WINDOW)), last))
+    }
+
+    /// What was on screen at `time`.
+    pub fn at(&self, time: Duration) -> Option<&ReplayFrame> {
+        let after = self.frames.partition_point(|f| f.time <= time);
+        self.frames.get(after.saturating_sub(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameAction;
+
+    #[test]
+    fn test_keeps_only_changes_in_window() {
+        let mut game = GameState::with_seed(2);
+        game.start();
+        let mut replay = InstantReplay::new();
+        replay.record(&game);
+        game.stats.time = Duration::from_secs(1);
+        replay.record(&game);
+        // Nothing moved, so no new frame
+        assert_eq!(replay.frames.len(), 1);
+
+        game.handle_action(GameAction::MoveLeft);
+        game.stats.time = Duration::from_secs(2);
+        replay.record(&game);
+        assert_eq!(replay.frames.len(), 2);
+        assert_eq!(
+            replay.at(Duration::from_millis(1500)).unwrap().time,
+            Duration::ZERO
+        );
+        assert_eq!(
+            replay.at(Duration::from_secs(5)).unwrap().time,
+            Duration::from_secs(2)
+        );
+
+        game.handle_action(GameAction::MoveRight);
+        game.stats.time = Duration::from_secs(15);
+        replay.record(&game);
+        // The 2s frame is still what showed when the window opens at 5s
+        assert_eq!(replay.frames.len(), 2);
+        assert_eq!(
+            replay.span(),
+            Some((Duration::from_secs(5), Duration::from_secs(15)))
+        );
+        assert_eq!(
+            replay.at(Duration::from_secs(5)).unwrap().time,
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_resumed_game_starts_a_fresh_replay() {
+        let mut game = GameState::with_seed(2);
+        game.start();
+        let mut replay = InstantReplay::new();
+        replay.record(&game);
+
+        // A resumed game can be further along than the last one recorded
+        replay.clear();
+        game.handle_action(GameAction::MoveLeft);
+        game.stats.time = Duration::from_secs(30);
+        replay.record(&game);
+        assert_eq!(
+            replay.span(),
+            Some((Duration::from_secs(30), Duration::from_secs(30)))
+        );
+    }
+}
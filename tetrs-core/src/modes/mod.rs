@@ -0,0 +1,120 @@
+pub mod attract;
+pub mod cheat;
+pub mod endless;
+pub mod gauntlet;
+pub mod ghost_run;
+pub mod instant_replay;
+pub mod royale;
+pub mod sprint;
+pub mod trainer;
+pub mod versus;
+pub mod zen;
+
+use crate::game::piece::PieceType;
+use crate::game::stats::Stats;
+use crate::game::{GameAction, GameState};
+use crate::modes::cheat::Cheat;
+use crate::modes::ghost_run::GhostRun;
+
+/// Result to display on game over.
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    pub mode_name: String,
+    pub primary_label: String,
+    pub primary_value: String,
+    pub is_new_high_score: bool,
+    pub stats: Stats,
+    pub won: Option<bool>, // For versus: Some(true/false), else None
+    /// Best two minutes' score (`Stats::best_ultra`), for modes that line
+    /// it up against an Ultra run.
+    pub best_ultra: Option<u64>,
+}
+
+/// Trait for game modes.
+pub trait GameMode {
+    /// Mode display name.
+    fn name(&self) -> &str;
+
+    /// Called when the game starts.
+    fn on_start(&mut self, game: &mut GameState);
+
+    /// Called each update tick. Can modify game state or check completion.
+    fn on_update(&mut self, game: &mut GameState);
+
+    /// Check if the mode is complete. Returns result if so.
+    fn check_complete(&self, game: &GameState) -> Option<GameResult>;
+
+    /// Get mode-specific info text to display (e.g., "Lines: 12/40").
+    fn info_text(&self, game: &GameState) -> Option<String>;
+
+    /// Rewind the last placement. Returns false if the mode doesn't support
+    /// undo or there is nothing to rewind.
+    fn undo(&mut self, _game: &mut GameState) -> bool {
+        false
+    }
+
+    /// Earlier run raced against this game, if the mode has one.
+    fn ghost(&self) -> Option<&GhostRun> {
+        None
+    }
+
+    /// This game's own recording, kept as the next ghost on a personal best.
+    fn recording(&self) -> Option<&GhostRun> {
+        None
+    }
+
+    /// A game key went down or up, for modes that record their inputs.
+    fn record_input(&mut self, _game: &GameState, _action: GameAction, _down: bool) {}
+
+    /// Lines to clear to finish, for modes with a line goal.
+    fn line_goal(&self) -> Option<u32> {
+        None
+    }
+
+    /// Milliseconds into the game at which each line was cleared, kept with
+    /// the high score for pacing later games.
+    fn splits(&self) -> Option<&[u64]> {
+        None
+    }
+
+    /// Cells the current piece should lock into, for modes that coach
+    /// placements.
+    fn guide(&self) -> Option<(PieceType, [(i32, i32); 4])> {
+        None
+    }
+
+    /// Whether an interrupted game can be picked up again from an autosave.
+    fn resumable(&self) -> bool {
+        false
+    }
+
+    /// Whether results go to the high score tables and the leaderboard.
+    /// Modes defined outside the game don't, whatever they're called.
+    fn ranked(&self) -> bool {
+        true
+    }
+
+    /// Whether the sandbox prompt is open in this mode. Only unranked
+    /// practice modes take cheats.
+    fn allows_cheats(&self) -> bool {
+        false
+    }
+
+    /// Apply a sandbox command. Returns false if the mode refuses it.
+    fn cheat(&mut self, _game: &mut GameState, _cheat: Cheat) -> bool {
+        false
+    }
+}
+
+/// Format a number with thousands separators.
+pub fn format_number(n: u64) -> String {
+    let s = n.to_string();
+    let mut result = String::new();
+    for (i, c) in s.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            result.push(',');
+        }
+        result.push(c);
+    }
+    result.chars().rev().collect()
+}
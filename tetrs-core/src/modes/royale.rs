@@ -0,0 +1,486 @@
+use std::time::Duration;
+
+use crate::ai::difficulty::AiDifficulty;
+use crate::ai::AiPlayer;
+use crate::game::rng::GameRng;
+use crate::game::GameState;
+use crate::modes::GameMode;
+use crate::modes::GameResult;
+
+/// Opponents in a standard lobby.
+pub const ROYALE_OPPONENTS: usize = 8;
+
+/// Difficulty mix of a lobby, repeated for larger ones.
+const LOBBY: [AiDifficulty; 8] = [
+    AiDifficulty::Easy,
+    AiDifficulty::Easy,
+    AiDifficulty::Medium,
+    AiDifficulty::Medium,
+    AiDifficulty::Medium,
+    AiDifficulty::Hard,
+    AiDifficulty::Hard,
+    AiDifficulty::Expert,
+];
+
+/// Someone in the lobby an attack can be sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Player,
+    Opponent(usize),
+}
+
+/// How the player's attacks pick an opponent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetingStrategy {
+    /// One random opponent, kept until it's knocked out.
+    #[default]
+    Random,
+    /// Whoever is attacking the player; the current target if nobody is.
+    Attackers,
+    /// The opponent holding the most badges.
+    Badges,
+    /// The opponent closest to topping out.
+    KOs,
+}
+
+impl TargetingStrategy {
+    pub const ALL: [TargetingStrategy; 4] = [
+        TargetingStrategy::Random,
+        TargetingStrategy::Attackers,
+        TargetingStrategy::Badges,
+        TargetingStrategy::KOs,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TargetingStrategy::Random => "Random",
+            TargetingStrategy::Attackers => "Attackers",
+            TargetingStrategy::Badges => "Badges",
+            TargetingStrategy::KOs => "KOs",
+        }
+    }
+
+    /// The strategy after this one, wrapping around.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&s| s == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+/// One AI board in the lobby.
+pub struct Opponent {
+    pub ai: AiPlayer,
+    pub game: GameState,
+    /// Who this opponent's attacks go to.
+    pub target: Target,
+    /// Finishing place, once knocked out.
+    pub place: Option<usize>,
+    /// Badges won by knocking others out; each KO takes the victim's too.
+    pub badges: u32,
+    /// Who last sent garbage here, credited with the KO.
+    last_attacker: Option<Target>,
+}
+
+impl Opponent {
+    pub fn is_alive(&self) -> bool {
+        self.place.is_none()
+    }
+
+    /// How close the board is to topping out: stack height plus garbage on
+    /// the way.
+    pub fn danger(&self) -> u32 {
+        self.game.board.max_height() as u32 + self.game.garbage.pending()
+    }
+}
+
+/// Battle royale: the player against a lobby of AI boards, last one standing
+/// wins.
+///
+/// The player's attacks go to one opponent at a time, picked by the current
+/// `TargetingStrategy`. Each AI picks a new random target (possibly the
+/// player) after every attack it sends. Knocking a board out wins its
+/// badges.
+pub struct RoyaleMode {
+    pub opponents: Vec<Opponent>,
+    /// Opponent the player's attacks go to.
+    pub target: Option<usize>,
+    pub strategy: TargetingStrategy,
+    /// Badges the player has won.
+    pub player_badges: u32,
+    /// Opponents the player has knocked out.
+    pub player_kos: u32,
+    /// Everyone knocked out so far, first out first.
+    pub eliminated: Vec<Target>,
+    /// Player's finishing place, once knocked out or the last one standing.
+    pub player_place: Option<usize>,
+    last_player_attack: u32,
+    /// Who last sent garbage to the player.
+    player_last_attacker: Option<usize>,
+    rng: GameRng,
+}
+
+impl RoyaleMode {
+    #[cfg(feature = "entropy")]
+    pub fn new(count: usize) -> Self {
+        Self::with_seed(count, rand::random())
+    }
+
+    /// Lobby of `count` opponents with boards and decisions derived from
+    /// `seed`.
+    pub fn with_seed(count: usize, seed: u64) -> Self {
+        let mut rng = GameRng::seed_from_u64(seed);
+        let opponents = (0..count)
+            .map(|i| {
+                let difficulty = LOBBY[i % LOBBY.len()];
+                Opponent {
+                    ai: AiPlayer::with_seed(difficulty, rng.next_u64()),
+                    game: GameState::with_seed(rng.next_u64()),
+                    target: Target::Player,
+                    place: None,
+                    badges: 0,
+                    last_attacker: None,
+                }
+            })
+            .collect();
+        Self {
+            opponents,
+            target: None,
+            strategy: TargetingStrategy::default(),
+            player_badges: 0,
+            player_kos: 0,
+            eliminated: Vec::new(),
+            player_place: None,
+            last_player_attack: 0,
+            player_last_attacker: None,
+            rng,
+        }
+    }
+
+    /// Opponents still in the game.
+    pub fn alive(&self) -> usize {
+        self.opponents.iter().filter(|o| o.is_alive()).count()
+    }
+
+    /// Start every opponent on the player's ruleset and garbage settings.
+    pub fn start(&mut self, player: &GameState) {
+        for opponent in &mut self.opponents {
+            opponent.game.ruleset = player.ruleset.clone();
+            opponent.game.garbage.pattern = player.garbage.pattern;
+            opponent.game.garbage.kind = player.garbage.kind;
+            opponent.game.start();
+            opponent.ai.reset();
+            opponent.place = None;
+            opponent.badges = 0;
+            opponent.last_attacker = None;
+        }
+        self.eliminated.clear();
+        self.player_place = None;
+        self.player_badges = 0;
+        self.player_kos = 0;
+        self.last_player_attack = 0;
+        self.player_last_attacker = None;
+        for i in 0..self.opponents.len() {
+            self.opponents[i].target = self.random_target(Target::Opponent(i));
+        }
+        self.target = None;
+        self.target = self.choose_target();
+    }
+
+    /// Switch to the next targeting strategy, re-picking the target at once.
+    pub fn cycle_strategy(&mut self) {
+        self.strategy = self.strategy.next();
+        self.target = self.choose_target();
+    }
+
+    /// Run the lobby for one frame: play every AI, route attacks, and track
+    /// knock-outs. Returns the opponents knocked out this frame.
+    pub fn update(&mut self, player: &mut GameState, dt: Duration) -> Vec<usize> {
+        self.target = self.choose_target();
+
+        // Player attack to the current target
+        let player_delta = player
+            .stats
+            .attack_sent
+            .saturating_sub(self.last_player_attack);
+        self.last_player_attack = player.stats.attack_sent;
+        if player_delta > 0 {
+            if let Some(target) = self.target {
+                tracing::debug!(
+                    lines = player_delta,
+                    to = target,
+                    "royale attack from player"
+                );
+                let opponent = &mut self.opponents[target];
+                opponent.game.garbage.add(player_delta);
+                opponent.last_attacker = Some(Target::Player);
+            }
+        }
+
+        for i in 0..self.opponents.len() {
+            let opponent = &mut self.opponents[i];
+            if !opponent.is_alive() {
+                continue;
+            }
+            let actions = opponent.ai.think(&opponent.game, dt);
+            for action in actions {
+                opponent.game.handle_action(action);
+            }
+            opponent.game.update(dt);
+            // Nothing in the lobby reads opponent events
+            opponent.game.drain_events();
+
+            let attack = opponent.ai.check_attack(&opponent.game);
+            if attack > 0 {
+                tracing::debug!(lines = attack, from = i, to = ?opponent.target, "royale attack");
+                match opponent.target {
+                    Target::Player => {
+                        player.garbage.add(attack);
+                        self.player_last_attacker = Some(i);
+                    }
+                    Target::Opponent(j) => {
+                        self.opponents[j].game.garbage.add(attack);
+                        self.opponents[j].last_attacker = Some(Target::Opponent(i));
+                    }
+                }
+                self.opponents[i].target = self.random_target(Target::Opponent(i));
+            }
+        }
+
+        let knocked_out = self.check_eliminations(player);
+        if !knocked_out.is_empty() {
+            self.retarget();
+        }
+        knocked_out
+    }
+
+    /// Record newly topped-out boards, giving each its finishing place.
+    fn check_eliminations(&mut self, player: &GameState) -> Vec<usize> {
+        // Everyone who tops out on the same frame shares the place
+        let place = self.alive() + usize::from(self.player_place.is_none());
+        let mut knocked_out = Vec::new();
+        for i in 0..self.opponents.len() {
+            let opponent = &mut self.opponents[i];
+            if opponent.is_alive() && opponent.game.game_over {
+                opponent.place = Some(place);
+                let badges = opponent.badges + 1;
+                match opponent.last_attacker {
+                    Some(Target::Player) => {
+                        self.player_badges += badges;
+                        self.player_kos += 1;
+                    }
+                    Some(Target::Opponent(j)) => self.opponents[j].badges += badges,
+                    None => {}
+                }
+                self.eliminated.push(Target::Opponent(i));
+                knocked_out.push(i);
+            }
+        }
+        if self.player_place.is_none() {
+            if player.game_over {
+                self.player_place = Some(place);
+                self.eliminated.push(Target::Player);
+                if let Some(j) = self.player_last_attacker {
+                    self.opponents[j].badges += self.player_badges + 1;
+                }
+            } else if self.alive() == 0 {
+                self.player_place = Some(1);
+            }
+        }
+        knocked_out
+    }
+
+    /// Point anyone aiming at a knocked-out board somewhere else.
+    fn retarget(&mut self) {
+        self.target = self.choose_target();
+        for i in 0..self.opponents.len() {
+            if let Target::Opponent(j) = self.opponents[i].target {
+                if !self.opponents[j].is_alive() {
+                    self.opponents[i].target = self.random_target(Target::Opponent(i));
+                }
+            }
+        }
+    }
+
+    /// The player's target under the current strategy. Ties keep the current
+    /// target, then go to the first opponent in the lobby.
+    fn choose_target(&mut self) -> Option<usize> {
+        let current = self.target.filter(|&t| self.opponents[t].is_alive());
+        let alive = (0..self.opponents.len()).filter(|&i| self.opponents[i].is_alive());
+        let best_by = |key: &dyn Fn(&Opponent) -> u32| {
+            alive
+                .clone()
+                .max_by_key(|&i| (key(&self.opponents[i]), Some(i) == current, usize::MAX - i))
+        };
+        let choice = match self.strategy {
+            TargetingStrategy::Random => current,
+            TargetingStrategy::Attackers => best_by(&|o| u32::from(o.target == Target::Player))
+                .filter(|&i| self.opponents[i].target == Target::Player)
+                .or(current),
+            TargetingStrategy::Badges => best_by(&|o| o.badges),
+            TargetingStrategy::KOs => best_by(&Opponent::danger),
+        };
+        choice.or_else(|| self.random_opponent())
+    }
+
+    /// Random opponent still in the game.
+    fn random_opponent(&mut self) -> Option<usize> {
+        let alive: Vec<usize> = (0..self.opponents.len())
+            .filter(|&i| self.opponents[i].is_alive())
+            .collect();
+        (!alive.is_empty()).then(|| alive[self.rng.below(alive.len())])
+    }
+
+    /// Random target for `from`, never itself. Falls back to the player.
+    fn random_target(&mut self, from: Target) -> Target {
+        let mut targets: Vec<Target> = (0..self.opponents.len())
+            .filter(|&i| self.opponents[i].is_alive())
+            .map(Target::Opponent)
+            .filter(|&t| t != from)
+            .collect();
+        if self.player_place.is_none() {
+            targets.push(Target::Player);
+        }
+        if targets.is_empty() {
+            return Target::Player;
+        }
+        targets[self.rng.below(targets.len())]
+    }
+}
+
+impl GameMode for RoyaleMode {
+    fn name(&self) -> &str {
+        "ROYALE"
+    }
+
+    fn on_start(&mut self, game: &mut GameState) {
+        self.start(game);
+    }
+
+    fn on_update(&mut self, _game: &mut GameState) {
+        // The lobby needs frame time, so it's updated separately in the app loop
+    }
+
+    fn check_complete(&self, game: &GameState) -> Option<GameResult> {
+        let place = self.player_place?;
+        let won = place == 1;
+        Some(GameResult {
+            mode_name: "Battle Royale".to_string(),
+            primary_label: if won { "VICTORY ROYALE" } else { "KNOCKED OUT" }.to_string(),
+            primary_value: format!(
+                "Place: #{} of {} | KOs: {}",
+                place,
+                self.opponents.len() + 1,
+                self.player_kos
+            ),
+            is_new_high_score: false,
+            stats: game.stats.clone(),
+            won: Some(won),
+            best_ultra: None,
+        })
+    }
+
+    fn info_text(&self, _game: &GameState) -> Option<String> {
+        let badges = match self.player_badges {
+            0 => String::new(),
+            n => format!(" \u{2605}{}", n),
+        };
+        Some(format!(
+            "ALIVE:{}/{}{} \u{25B8}{}",
+            self.alive() + 1,
+            self.opponents.len() + 1,
+            badges,
+            self.strategy.name()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lobby(count: usize) -> (RoyaleMode, GameState) {
+        let mut player = GameState::with_seed(1);
+        player.start();
+        let mut royale = RoyaleMode::with_seed(count, 7);
+        royale.start(&player);
+        (royale, player)
+    }
+
+    #[test]
+    fn test_targets_are_never_self() {
+        let (royale, _) = lobby(ROYALE_OPPONENTS);
+        assert!(royale.target.is_some());
+        for (i, opponent) in royale.opponents.iter().enumerate() {
+            assert_ne!(opponent.target, Target::Opponent(i));
+        }
+    }
+
+    #[test]
+    fn test_elimination_order() {
+        let (mut royale, mut player) = lobby(3);
+        royale.opponents[1].game.game_over = true;
+        royale.update(&mut player, Duration::ZERO);
+        assert_eq!(royale.opponents[1].place, Some(4));
+        assert_ne!(royale.target, Some(1));
+
+        royale.opponents[0].game.game_over = true;
+        royale.opponents[2].game.game_over = true;
+        let knocked_out = royale.update(&mut player, Duration::ZERO);
+        assert_eq!(knocked_out, vec![0, 2]);
+        assert_eq!(royale.opponents[2].place, Some(3));
+        assert_eq!(royale.player_place, Some(1));
+        assert_eq!(
+            royale.eliminated,
+            vec![
+                Target::Opponent(1),
+                Target::Opponent(0),
+                Target::Opponent(2)
+            ]
+        );
+
+        let result = royale.check_complete(&player).unwrap();
+        assert_eq!(result.won, Some(true));
+    }
+
+    #[test]
+    fn test_targeting_strategies() {
+        let (mut royale, mut player) = lobby(4);
+        royale.strategy = TargetingStrategy::Badges;
+        royale.opponents[2].badges = 3;
+        royale.update(&mut player, Duration::ZERO);
+        assert_eq!(royale.target, Some(2));
+
+        royale.cycle_strategy();
+        assert_eq!(royale.strategy, TargetingStrategy::KOs);
+        royale.opponents[3].game.garbage.add(12);
+        royale.update(&mut player, Duration::ZERO);
+        assert_eq!(royale.target, Some(3));
+
+        royale.strategy = TargetingStrategy::Attackers;
+        for opponent in &mut royale.opponents {
+            opponent.target = Target::Opponent(0);
+        }
+        royale.opponents[1].target = Target::Player;
+        assert_eq!(royale.choose_target(), Some(1));
+    }
+
+    #[test]
+    fn test_ko_wins_badges() {
+        let (mut royale, mut player) = lobby(3);
+        royale.opponents[0].badges = 2;
+        royale.opponents[0].last_attacker = Some(Target::Player);
+        royale.opponents[0].game.game_over = true;
+        royale.update(&mut player, Duration::ZERO);
+        assert_eq!(royale.player_badges, 3);
+        assert_eq!(royale.player_kos, 1);
+    }
+
+    #[test]
+    fn test_player_knocked_out() {
+        let (mut royale, mut player) = lobby(ROYALE_OPPONENTS);
+        player.game_over = true;
+        royale.update(&mut player, Duration::ZERO);
+        assert_eq!(royale.player_place, Some(ROYALE_OPPONENTS + 1));
+        assert_eq!(royale.check_complete(&player).unwrap().won, Some(false));
+    }
+}
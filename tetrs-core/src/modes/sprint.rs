@@ -0,0 +1,179 @@
+use std::time::Duration;
+
+use crate::game::{GameAction, GameState};
+use crate::modes::ghost_run::GhostRun;
+use crate::modes::GameMode;
+use crate::modes::GameResult;
+
+/// 40-line sprint mode.
+pub struct SprintMode {
+    pub target_lines: u32,
+    /// Personal-best run to race.
+    pub ghost: Option<GhostRun>,
+    /// This run, saved as the new ghost if it sets a personal best.
+    pub recording: GhostRun,
+    /// Time in milliseconds at which each line was cleared.
+    pub splits: Vec<u64>,
+    /// Splits of the personal best, empty if there's none to pace against.
+    pub pb_splits: Vec<u64>,
+}
+
+impl Default for SprintMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SprintMode {
+    pub fn new() -> Self {
+        Self {
+            target_lines: 40,
+            ghost: None,
+            recording: GhostRun::new(),
+            splits: Vec::new(),
+            pb_splits: Vec::new(),
+        }
+    }
+
+    /// Sprint raced against an earlier run.
+    pub fn with_ghost(ghost: Option<GhostRun>) -> Self {
+        Self {
+            ghost,
+            ..Self::new()
+        }
+    }
+
+    /// Pace the run against a personal best's splits.
+    pub fn with_pb_splits(mut self, pb_splits: Vec<u64>) -> Self {
+        self.pb_splits = pb_splits;
+        self
+    }
+
+    /// Signed time in milliseconds between reaching `lines` now and in the
+    /// personal best: negative when ahead. Falls back to the ghost for a
+    /// personal best saved without splits.
+    pub fn pace_ms(&self, lines: u32, time: Duration) -> Option<i64> {
+        if lines == 0 {
+            return None;
+        }
+        match self.pb_splits.get(lines as usize - 1) {
+            Some(&split) => Some(time.as_millis() as i64 - split as i64),
+            None if self.pb_splits.is_empty() => self
+                .ghost
+                .as_ref()
+                .and_then(|ghost| ghost.split_delta_ms(lines, time)),
+            None => None,
+        }
+    }
+}
+
+impl GameMode for SprintMode {
+    fn name(&self) -> &str {
+        "SPRINT"
+    }
+
+    fn on_start(&mut self, game: &mut GameState) {
+        self.recording = GhostRun::new();
+        self.recording.record(game);
+        self.splits.clear();
+    }
+
+    fn on_update(&mut self, game: &mut GameState) {
+        self.recording.record(game);
+        let time_ms = game.stats.time.as_millis() as u64;
+        let lines = game.scoring.lines_cleared.min(self.target_lines) as usize;
+        while self.splits.len() < lines {
+            self.splits.push(time_ms);
+        }
+    }
+
+    fn check_complete(&self, game: &GameState) -> Option<GameResult> {
+        if game.game_over {
+            return Some(GameResult {
+                mode_name: format!("{}-Line Sprint", self.target_lines),
+                primary_label: "TIME".to_string(),
+                primary_value: game.stats.format_time(),
+                is_new_high_score: false, // Set by app
+                stats: game.stats.clone(),
+                won: None,
+                best_ultra: None,
+            });
+        }
+
+        if game.scoring.lines_cleared >= self.target_lines {
+            return Some(GameResult {
+                mode_name: format!("{}-Line Sprint", self.target_lines),
+                primary_label: "TIME".to_string(),
+                primary_value: game.stats.format_time(),
+                is_new_high_score: false,
+                stats: game.stats.clone(),
+                won: None,
+                best_ultra: None,
+            });
+        }
+
+        None
+    }
+
+    fn info_text(&self, game: &GameState) -> Option<String> {
+        let lines = game.scoring.lines_cleared;
+        let remaining = self.target_lines.saturating_sub(lines);
+        match self.pace_ms(lines, game.stats.time) {
+            Some(delta) => Some(format!(
+                "{} left  PB {}{}.{:02}",
+                remaining,
+                if delta < 0 { '-' } else { '+' },
+                delta.unsigned_abs() / 1000,
+                delta.unsigned_abs() % 1000 / 10
+            )),
+            None => Some(format!("{} lines left", remaining)),
+        }
+    }
+
+    fn ghost(&self) -> Option<&GhostRun> {
+        self.ghost.as_ref()
+    }
+
+    fn recording(&self) -> Option<&GhostRun> {
+        Some(&self.recording)
+    }
+
+    fn record_input(&mut self, game: &GameState, action: GameAction, down: bool) {
+        self.recording.record_input(game.stats.time, action, down);
+    }
+
+    fn line_goal(&self) -> Option<u32> {
+        Some(self.target_lines)
+    }
+
+    fn splits(&self) -> Option<&[u64]> {
+        Some(&self.splits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pace_against_pb_splits() {
+        let mut game = GameState::with_seed(5);
+        game.start();
+        let mut mode = SprintMode::new().with_pb_splits(vec![1000, 1000, 2500, 4000]);
+        mode.on_start(&mut game);
+
+        game.stats.time = Duration::from_millis(1200);
+        game.scoring.lines_cleared = 2;
+        mode.on_update(&mut game);
+        game.stats.time = Duration::from_millis(2000);
+        game.scoring.lines_cleared = 3;
+        mode.on_update(&mut game);
+
+        assert_eq!(mode.splits, vec![1200, 1200, 2000]);
+        assert_eq!(mode.pace_ms(2, Duration::from_millis(1200)), Some(200));
+        assert_eq!(mode.pace_ms(3, Duration::from_millis(2000)), Some(-500));
+        assert_eq!(mode.pace_ms(5, Duration::from_millis(5000)), None);
+        assert_eq!(mode.pace_ms(0, Duration::ZERO), None);
+        assert!(mode.info_text(&game).unwrap().contains("PB -0.50"));
+    }
+}
@@ -0,0 +1,384 @@
+use crate::game::bag::{PieceQueue, Sequence};
+use crate::game::board::{Board, Cell};
+use crate::game::piece::{get_cells, Piece, PieceType, RotationState};
+use crate::game::GameState;
+use crate::modes::GameMode;
+use crate::modes::GameResult;
+
+/// One placement of an opener: a piece and where it locks, in board
+/// coordinates at the time it is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub piece: PieceType,
+    pub rotation: RotationState,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Step {
+    /// Cells the piece fills once locked.
+    pub fn cells(&self) -> [(i32, i32); 4] {
+        get_cells(self.piece, self.rotation).map(|(dx, dy)| (self.x + dx, self.y + dy))
+    }
+}
+
+const fn step(piece: PieceType, rotation: RotationState, x: i32, y: i32) -> Step {
+    Step {
+        piece,
+        rotation,
+        x,
+        y,
+    }
+}
+
+/// A scripted opening, dealt piece by piece in placement order.
+#[derive(Debug)]
+pub struct Opener {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub steps: &'static [Step],
+}
+
+impl Opener {
+    /// Pieces in the order they are dealt.
+    pub fn pieces(&self) -> Vec<PieceType> {
+        self.steps.iter().map(|s| s.piece).collect()
+    }
+}
+
+/// Openers the trainer knows.
+pub const OPENERS: [Opener; 3] = [
+    Opener {
+        name: "TKI",
+        description: "T-spin double with the first bag's T",
+        steps: &TKI,
+    },
+    Opener {
+        name: "DT Cannon",
+        description: "T-spin double, then a T-spin triple under it",
+        steps: &DT_CANNON,
+    },
+    Opener {
+        name: "PCO",
+        description: "Four-line perfect clear over the first ten pieces",
+        steps: &PCO,
+    },
+];
+
+// Final stack before the T, bottom row last:
+//   .....S....
+//   L..ZZSS.OO
+//   L...ZZSJOO
+//   LL.IIIIJJJ
+const TKI: [Step; 7] = [
+    step(PieceType::L, RotationState::R1, -1, 1),
+    step(PieceType::I, RotationState::R0, 3, 0),
+    step(PieceType::J, RotationState::R0, 7, 0),
+    step(PieceType::Z, RotationState::R0, 3, 1),
+    step(PieceType::S, RotationState::R1, 4, 2),
+    step(PieceType::O, RotationState::R0, 8, 1),
+    step(PieceType::T, RotationState::R2, 1, 1),
+];
+
+// Stack before the double, which clears rows 3-4 and opens the triple's
+// slot below; an I laid over it after the double makes the roof:
+//   .......L..
+//   ...IIIIL..
+//   S...JJJLLZ
+//   SS.IOOJZZZ
+//   LS.IOOZZZJ
+//   L..IOOZSSJ
+//   LL.IOOSSJJ
+const DT_CANNON: [Step; 15] = [
+    step(PieceType::L, RotationState::R1, -1, 1),
+    step(PieceType::I, RotationState::R1, 1, 2),
+    step(PieceType::O, RotationState::R0, 4, 0),
+    step(PieceType::J, RotationState::R3, 8, 1),
+    step(PieceType::S, RotationState::R0, 6, 0),
+    step(PieceType::Z, RotationState::R1, 5, 2),
+    step(PieceType::S, RotationState::R1, -1, 3),
+    step(PieceType::O, RotationState::R0, 4, 2),
+    step(PieceType::Z, RotationState::R1, 7, 3),
+    step(PieceType::J, RotationState::R2, 4, 4),
+    step(PieceType::L, RotationState::R1, 6, 5),
+    step(PieceType::I, RotationState::R0, 3, 5),
+    step(PieceType::T, RotationState::R2, 1, 4),
+    step(PieceType::I, RotationState::R0, 2, 4),
+    step(PieceType::T, RotationState::R3, 1, 1),
+];
+
+// The finished clear, the I going in last:
+//   ILLLTTTTZJ
+//   ILLLTTTZZJ
+//   IOOLTSSZJJ
+//   IOOLSSIIII
+const PCO: [Step; 10] = [
+    step(PieceType::O, RotationState::R0, 1, 0),
+    step(PieceType::L, RotationState::R3, 2, 1),
+    step(PieceType::I, RotationState::R0, 6, 0),
+    step(PieceType::S, RotationState::R0, 4, 0),
+    step(PieceType::J, RotationState::R3, 8, 2),
+    step(PieceType::Z, RotationState::R1, 6, 2),
+    step(PieceType::T, RotationState::R1, 3, 2),
+    step(PieceType::L, RotationState::R2, 1, 3),
+    step(PieceType::T, RotationState::R2, 5, 3),
+    step(PieceType::I, RotationState::R1, -2, 2),
+];
+
+/// Opener trainer: deals an opener's pieces in order, shows where each one
+/// goes, and checks every placement. A wrong placement is taken back so the
+/// step can be tried again.
+pub struct TrainerMode {
+    pub opener: &'static Opener,
+    /// Index of the step being played.
+    step: usize,
+    /// Board each step should leave, after its line clears.
+    expected: Vec<Board>,
+    /// Game as the current step's piece spawned, restored after a mistake.
+    checkpoint: Option<GameState>,
+    pub mistakes: u32,
+}
+
+impl TrainerMode {
+    pub fn new(opener: &'static Opener) -> Self {
+        let mut board = Board::new();
+        let expected = opener
+            .steps
+            .iter()
+            .map(|step| {
+                for (x, y) in step.cells() {
+                    board.set(x, y, Cell::Filled(step.piece));
+                }
+                let full = board.find_full_lines();
+                board.clear_lines(&full);
+                board.clone()
+            })
+            .collect();
+        Self {
+            opener,
+            step: 0,
+            expected,
+            checkpoint: None,
+            mistakes: 0,
+        }
+    }
+
+    /// The step the current piece is for, if any are left.
+    pub fn current_step(&self) -> Option<&Step> {
+        self.opener.steps.get(self.step)
+    }
+}
+
+/// Whether two boards have the same cells filled, whatever fills them.
+fn same_shape(a: &Board, b: &Board) -> bool {
+    a.grid.iter().zip(&b.grid).all(|(ra, rb)| {
+        ra.iter()
+            .zip(rb)
+            .all(|(ca, cb)| ca.is_occupied() == cb.is_occupied())
+    })
+}
+
+impl GameMode for TrainerMode {
+    fn name(&self) -> &str {
+        "TRAINER"
+    }
+
+    fn on_start(&mut self, game: &mut GameState) {
+        let sequence = Sequence::new(&self.opener.pieces());
+        game.queue = PieceQueue::new(Box::new(sequence), &mut game.rng);
+        game.current_piece = Some(Piece::new(game.queue.next(&mut game.rng)));
+        self.step = 0;
+        self.mistakes = 0;
+        self.checkpoint = None;
+    }
+
+    fn on_update(&mut self, game: &mut GameState) {
+        // No gravity pressure while learning the shape
+        game.gravity.level = 0;
+
+        let Some(checkpoint) = &self.checkpoint else {
+            // Keep the new step's piece at its spawn to retry from
+            if let Some(ref piece) = game.current_piece {
+                let mut checkpoint = game.clone();
+                checkpoint.current_piece = Some(Piece::new(piece.piece_type));
+                self.checkpoint = Some(checkpoint);
+            }
+            return;
+        };
+        // Judge a placement once its lines have cleared
+        if game.stats.pieces_placed == checkpoint.stats.pieces_placed
+            || game.clearing_lines.is_some()
+        {
+            return;
+        }
+
+        if same_shape(&game.board, &self.expected[self.step]) {
+            self.step += 1;
+            self.checkpoint = None;
+        } else {
+            self.mistakes += 1;
+            let time = game.stats.time;
+            *game = checkpoint.clone();
+            game.stats.time = time;
+        }
+    }
+
+    fn check_complete(&self, game: &GameState) -> Option<GameResult> {
+        let done = self.step == self.opener.steps.len();
+        if !done && !game.game_over {
+            return None;
+        }
+        Some(GameResult {
+            mode_name: format!("Opener Trainer ({})", self.opener.name),
+            primary_label: if done {
+                "OPENER COMPLETE"
+            } else {
+                "TOPPED OUT"
+            }
+            .to_string(),
+            primary_value: format!(
+                "Steps: {}/{} | Mistakes: {}",
+                self.step,
+                self.opener.steps.len(),
+                self.mistakes
+            ),
+            is_new_high_score: false,
+            stats: game.stats.clone(),
+            won: None,
+            best_ultra: None,
+        })
+    }
+
+    fn info_text(&self, _game: &GameState) -> Option<String> {
+        Some(format!(
+            "{} {}/{}  MISSES: {}",
+            self.opener.name,
+            self.step,
+            self.opener.steps.len(),
+            self.mistakes
+        ))
+    }
+
+    fn guide(&self) -> Option<(PieceType, [(i32, i32); 4])> {
+        self.current_step().map(|step| (step.piece, step.cells()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::ai::difficulty::AiDifficulty;
+    use crate::ai::personality::AiPersonality;
+    use crate::ai::placement::generate_placements;
+    use crate::game::clear::SpinType;
+    use crate::game::srs::Srs;
+    use crate::game::{GameAction, TickResult};
+
+    fn start(opener: &'static Opener) -> (TrainerMode, GameState) {
+        let mut game = GameState::with_seed(1);
+        game.start();
+        let mut trainer = TrainerMode::new(opener);
+        trainer.on_start(&mut game);
+        trainer.on_update(&mut game);
+        (trainer, game)
+    }
+
+    /// Play every step with inputs found by the AI's move search, returning
+    /// what each final hard drop did.
+    fn play(opener: &'static Opener) -> Vec<TickResult> {
+        let (mut trainer, mut game) = start(opener);
+        let weights = AiDifficulty::Medium.weights();
+        let mut drops = Vec::new();
+        for (i, step) in opener.steps.iter().enumerate() {
+            let mut want = step.cells();
+            want.sort();
+            let piece = game.current_piece.as_ref().unwrap().piece_type;
+            assert_eq!(piece, step.piece, "{} step {}", opener.name, i);
+            let placement = generate_placements(
+                &game.board,
+                piece,
+                &weights,
+                AiPersonality::Balanced,
+                false,
+                &Srs,
+            )
+            .into_iter()
+            .find(|p| {
+                let mut cells = Step {
+                    piece,
+                    rotation: p.rotation,
+                    x: p.x,
+                    y: p.y,
+                }
+                .cells();
+                cells.sort();
+                cells == want
+            })
+            .unwrap_or_else(|| panic!("{} step {} unreachable", opener.name, i));
+
+            let mut result = None;
+            for action in placement.path {
+                result = Some(game.handle_action(action));
+            }
+            drops.push(result.unwrap());
+            for _ in 0..20 {
+                game.update(Duration::from_millis(50));
+                trainer.on_update(&mut game);
+            }
+            assert_eq!(trainer.step, i + 1, "{} step {}", opener.name, i);
+        }
+        assert_eq!(trainer.mistakes, 0);
+        assert!(trainer.check_complete(&game).is_some());
+        drops
+    }
+
+    #[test]
+    fn test_tki_ends_in_tspin_double() {
+        let last = play(&OPENERS[0]).pop().unwrap();
+        assert_eq!(last.lines_cleared, 2);
+        assert_eq!(last.spin_type, SpinType::TSpin);
+    }
+
+    #[test]
+    fn test_dt_cannon_ends_in_double_then_triple() {
+        // The last I goes in between, roofing the triple's slot
+        let drops = play(&OPENERS[1]);
+        let [double, _, triple] = &drops[drops.len() - 3..] else {
+            unreachable!()
+        };
+        assert_eq!(
+            (double.lines_cleared, double.spin_type),
+            (2, SpinType::TSpin)
+        );
+        assert_eq!(
+            (triple.lines_cleared, triple.spin_type),
+            (3, SpinType::TSpin)
+        );
+    }
+
+    #[test]
+    fn test_pco_ends_in_perfect_clear() {
+        let drops = play(&OPENERS[2]);
+        assert!(drops.last().unwrap().is_perfect_clear);
+        assert_eq!(drops.iter().map(|d| d.lines_cleared).sum::<u32>(), 4);
+    }
+
+    #[test]
+    fn test_wrong_placement_is_taken_back() {
+        let (mut trainer, mut game) = start(&OPENERS[0]);
+        game.handle_action(GameAction::HardDrop);
+        for _ in 0..5 {
+            game.update(Duration::from_millis(50));
+            trainer.on_update(&mut game);
+        }
+        assert_eq!(trainer.mistakes, 1);
+        assert_eq!(trainer.step, 0);
+        assert!(game.board.is_empty());
+        assert_eq!(
+            game.current_piece.as_ref().unwrap().piece_type,
+            PieceType::L
+        );
+    }
+}
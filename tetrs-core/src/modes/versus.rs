@@ -0,0 +1,263 @@
+use std::time::Duration;
+
+use crate::ai::difficulty::AiDifficulty;
+use crate::ai::AiPlayer;
+use crate::game::GameState;
+use crate::modes::GameMode;
+use crate::modes::GameResult;
+
+/// Attack a side must send to win an attack race.
+pub const ATTACK_RACE_TARGET: u32 = 100;
+/// Length of a timed attack match.
+pub const TIMED_ATTACK_LIMIT: Duration = Duration::from_secs(120);
+/// Lines a side must clear to win a line race.
+pub const LINE_RACE_TARGET: u32 = 40;
+/// How long the mercy rule holds back garbage while the player's stack is
+/// near the top.
+pub const MERCY_GRACE: Duration = Duration::from_secs(3);
+
+/// What wins a versus match. Topping out loses under every goal, and the
+/// AI takes any tie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum VersusGoal {
+    /// Last board standing.
+    #[default]
+    Survival,
+    /// First to send `ATTACK_RACE_TARGET` attack.
+    AttackRace,
+    /// Most attack sent in `TIMED_ATTACK_LIMIT`.
+    TimedAttack,
+    /// First to clear `LINE_RACE_TARGET` lines.
+    LineRace,
+}
+
+impl VersusGoal {
+    pub const ALL: [VersusGoal; 4] = [
+        VersusGoal::Survival,
+        VersusGoal::AttackRace,
+        VersusGoal::TimedAttack,
+        VersusGoal::LineRace,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            VersusGoal::Survival => "Survival",
+            VersusGoal::AttackRace => "100 Attack",
+            VersusGoal::TimedAttack => "2 Min Attack",
+            VersusGoal::LineRace => "40 Lines",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            VersusGoal::Survival => "Last board standing wins",
+            VersusGoal::AttackRace => "First to send 100 attack wins",
+            VersusGoal::TimedAttack => "Most attack sent in two minutes wins",
+            VersusGoal::LineRace => "First to clear 40 lines wins",
+        }
+    }
+
+    /// Whether the player won, once the match is decided.
+    fn outcome(self, player: &GameState, ai: &GameState) -> Option<bool> {
+        if player.game_over || ai.game_over {
+            return Some(ai.game_over && !player.game_over);
+        }
+        let (mine, theirs) = match self {
+            VersusGoal::Survival => return None,
+            VersusGoal::AttackRace => (player.stats.attack_sent, ai.stats.attack_sent),
+            VersusGoal::TimedAttack => {
+                let done = player.stats.time >= TIMED_ATTACK_LIMIT;
+                return done.then_some(player.stats.attack_sent > ai.stats.attack_sent);
+            }
+            VersusGoal::LineRace => (player.stats.lines_cleared, ai.stats.lines_cleared),
+        };
+        let target = match self {
+            VersusGoal::LineRace => LINE_RACE_TARGET,
+            _ => ATTACK_RACE_TARGET,
+        };
+        // Both reaching it on the same frame goes to whoever is further past
+        (mine >= target || theirs >= target).then_some(mine > theirs)
+    }
+}
+
+/// Versus AI mode.
+pub struct VersusMode {
+    pub ai: AiPlayer,
+    pub ai_game: GameState,
+    pub difficulty: AiDifficulty,
+    /// Deal the AI the player's pieces rather than its own.
+    pub fair_bag: bool,
+    pub goal: VersusGoal,
+}
+
+impl VersusMode {
+    #[cfg(feature = "entropy")]
+    pub fn new(difficulty: AiDifficulty) -> Self {
+        Self::with_seed(difficulty, rand::random())
+    }
+
+    /// Versus with the AI's board and decisions derived from `seed`.
+    pub fn with_seed(difficulty: AiDifficulty, seed: u64) -> Self {
+        Self {
+            ai: AiPlayer::with_seed(difficulty, seed.rotate_left(32)),
+            ai_game: GameState::with_seed(seed),
+            difficulty,
+            fair_bag: false,
+            goal: VersusGoal::Survival,
+        }
+    }
+
+    /// Give the AI's board the player's seed and script, so once both
+    /// start they draw the same pieces in the same order.
+    pub fn share_bag(&mut self, player: &GameState) {
+        self.ai_game.seed = player.seed;
+        self.ai_game.script = player.script.clone();
+    }
+
+    /// Update the AI game state. Should be called each frame.
+    pub fn update_ai(&mut self, player: &GameState, dt: Duration) {
+        if self.ai_game.game_over {
+            return;
+        }
+
+        self.ai.adapt(&self.ai_game, player, dt);
+
+        // AI thinks and acts
+        let actions = self.ai.think(&self.ai_game, dt);
+        for action in actions {
+            self.ai_game.handle_action(action);
+        }
+
+        let result = self.ai_game.update(dt);
+        if result.attack > 0 {
+            // AI sends attack to player (handled in app)
+        }
+    }
+
+    /// Get the AI's attack damage from last update (drain events).
+    pub fn drain_ai_attack(&mut self) -> u32 {
+        let _events = self.ai_game.drain_events();
+        self.ai_game.stats.attack_sent
+    }
+}
+
+impl GameMode for VersusMode {
+    fn name(&self) -> &str {
+        "VERSUS"
+    }
+
+    fn on_start(&mut self, _game: &mut GameState) {
+        self.ai_game.start();
+        self.ai.reset();
+    }
+
+    fn on_update(&mut self, _game: &mut GameState) {
+        // AI update happens separately in the app loop
+    }
+
+    fn check_complete(&self, game: &GameState) -> Option<GameResult> {
+        let won = self.goal.outcome(game, &self.ai_game)?;
+        Some(GameResult {
+            mode_name: format!("Versus AI ({})", self.difficulty.name()),
+            primary_label: if won { "VICTORY" } else { "DEFEAT" }.to_string(),
+            primary_value: format!(
+                "ATK: {} | RCV: {}",
+                game.stats.attack_sent, game.stats.garbage_received
+            ),
+            is_new_high_score: false,
+            stats: game.stats.clone(),
+            won: Some(won),
+            best_ultra: None,
+        })
+    }
+
+    fn info_text(&self, game: &GameState) -> Option<String> {
+        let stats = &game.stats;
+        Some(match self.goal {
+            VersusGoal::Survival => {
+                format!("ATK:{} RCV:{}", stats.attack_sent, stats.garbage_received)
+            }
+            VersusGoal::AttackRace => format!(
+                "ATK:{}/{} AI:{}",
+                stats.attack_sent, ATTACK_RACE_TARGET, self.ai_game.stats.attack_sent
+            ),
+            VersusGoal::TimedAttack => {
+                let left = TIMED_ATTACK_LIMIT.saturating_sub(stats.time).as_secs();
+                format!(
+                    "ATK:{} AI:{} {}:{:02}",
+                    stats.attack_sent,
+                    self.ai_game.stats.attack_sent,
+                    left / 60,
+                    left % 60
+                )
+            }
+            VersusGoal::LineRace => format!(
+                "LN:{}/{} AI:{}",
+                stats.lines_cleared, LINE_RACE_TARGET, self.ai_game.stats.lines_cleared
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versus(goal: VersusGoal) -> (VersusMode, GameState) {
+        let mut vs = VersusMode::with_seed(AiDifficulty::Medium, 1);
+        vs.goal = goal;
+        (vs, GameState::with_seed(2))
+    }
+
+    #[test]
+    fn test_races_end_at_target() {
+        let (mut vs, mut player) = versus(VersusGoal::AttackRace);
+        player.stats.attack_sent = ATTACK_RACE_TARGET - 1;
+        assert!(vs.check_complete(&player).is_none());
+        vs.ai_game.stats.attack_sent = ATTACK_RACE_TARGET;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(false));
+
+        let (vs, mut player) = versus(VersusGoal::LineRace);
+        player.stats.lines_cleared = LINE_RACE_TARGET;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(true));
+    }
+
+    #[test]
+    fn test_timed_attack_needs_more_attack() {
+        let (mut vs, mut player) = versus(VersusGoal::TimedAttack);
+        player.stats.attack_sent = 30;
+        vs.ai_game.stats.attack_sent = 30;
+        player.stats.time = TIMED_ATTACK_LIMIT - Duration::from_millis(1);
+        assert!(vs.check_complete(&player).is_none());
+        player.stats.time = TIMED_ATTACK_LIMIT;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(false));
+        player.stats.attack_sent = 31;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(true));
+    }
+
+    #[test]
+    fn test_ties_go_to_the_ai() {
+        let (mut vs, mut player) = versus(VersusGoal::AttackRace);
+        player.stats.attack_sent = ATTACK_RACE_TARGET;
+        vs.ai_game.stats.attack_sent = ATTACK_RACE_TARGET;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(false));
+
+        let (mut vs, mut player) = versus(VersusGoal::LineRace);
+        player.stats.lines_cleared = LINE_RACE_TARGET;
+        vs.ai_game.stats.lines_cleared = LINE_RACE_TARGET;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(false));
+
+        let (mut vs, mut player) = versus(VersusGoal::Survival);
+        player.game_over = true;
+        vs.ai_game.game_over = true;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(false));
+    }
+
+    #[test]
+    fn test_topping_out_loses_any_race() {
+        let (vs, mut player) = versus(VersusGoal::LineRace);
+        player.stats.lines_cleared = LINE_RACE_TARGET;
+        player.game_over = true;
+        assert_eq!(vs.check_complete(&player).unwrap().won, Some(false));
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::VecDeque;
+
+use crate::game::piece::Piece;
+use crate::game::GameState;
+use crate::modes::cheat::Cheat;
+use crate::modes::GameMode;
+use crate::modes::GameResult;
+
+/// Number of placements that can be undone.
+const UNDO_DEPTH: usize = 50;
+
+/// Zen practice mode: gravity never speeds up, the run never ends, and
+/// placements can be undone to experiment with setups.
+pub struct ZenMode {
+    /// Snapshots taken as each piece spawns, oldest first.
+    history: VecDeque<GameState>,
+    /// `pieces_placed` at the most recent snapshot.
+    snapshot_pieces: Option<u32>,
+    /// Gravity level, changed only by the `level` cheat.
+    level: u32,
+    /// Gravity level each game starts at.
+    start_level: u32,
+}
+
+impl Default for ZenMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZenMode {
+    pub fn new() -> Self {
+        Self {
+            history: VecDeque::new(),
+            snapshot_pieces: None,
+            level: 0,
+            start_level: 0,
+        }
+    }
+
+    /// Zen held at a gravity level of your choosing.
+    pub fn with_start_level(start_level: u32) -> Self {
+        Self {
+            start_level,
+            ..Self::new()
+        }
+    }
+
+    /// Number of placements that can currently be undone.
+    pub fn undo_available(&self, game: &GameState) -> usize {
+        match self.history.back() {
+            Some(top) if top.stats.pieces_placed == game.stats.pieces_placed => {
+                self.history.len() - 1
+            }
+            _ => self.history.len(),
+        }
+    }
+
+    fn take_snapshot(&mut self, game: &GameState) {
+        // Store the piece at its spawn position so undo hands it back untouched
+        let mut snapshot = game.clone();
+        if let Some(ref mut piece) = snapshot.current_piece {
+            *piece = Piece::new(piece.piece_type);
+        }
+        snapshot.lock_delay.reset();
+        snapshot.gravity.reset();
+        snapshot.last_was_rotation = false;
+        snapshot.last_kick = None;
+        snapshot.events.clear();
+
+        self.history.push_back(snapshot);
+        while self.history.len() > UNDO_DEPTH {
+            self.history.pop_front();
+        }
+        self.snapshot_pieces = Some(game.stats.pieces_placed);
+    }
+}
+
+impl GameMode for ZenMode {
+    fn name(&self) -> &str {
+        "ZEN"
+    }
+
+    fn on_start(&mut self, game: &mut GameState) {
+        self.history.clear();
+        self.snapshot_pieces = None;
+        self.level = self.start_level;
+        game.set_level(self.start_level);
+    }
+
+    fn on_update(&mut self, game: &mut GameState) {
+        // Gravity stays put however many lines are cleared
+        game.gravity.level = self.level;
+
+        if game.current_piece.is_some() && self.snapshot_pieces != Some(game.stats.pieces_placed) {
+            self.take_snapshot(game);
+        }
+    }
+
+    fn check_complete(&self, _game: &GameState) -> Option<GameResult> {
+        // Zen never ends; topping out waits for an undo or restart
+        None
+    }
+
+    fn info_text(&self, game: &GameState) -> Option<String> {
+        if game.game_over {
+            Some("TOPPED OUT  u: undo".to_string())
+        } else {
+            Some(format!("UNDO: {}", self.undo_available(game)))
+        }
+    }
+
+    fn undo(&mut self, game: &mut GameState) -> bool {
+        if self.undo_available(game) == 0 {
+            return false;
+        }
+
+        // Drop the snapshot of the piece in play, if any, to get the previous one
+        if self.history.back().map(|top| top.stats.pieces_placed) == Some(game.stats.pieces_placed)
+        {
+            self.history.pop_back();
+        }
+        let Some(snapshot) = self.history.back() else {
+            return false;
+        };
+
+        let time = game.stats.time;
+        *game = snapshot.clone();
+        game.stats.time = time;
+        self.snapshot_pieces = Some(game.stats.pieces_placed);
+        true
+    }
+
+    fn resumable(&self) -> bool {
+        true
+    }
+
+    fn allows_cheats(&self) -> bool {
+        true
+    }
+
+    fn cheat(&mut self, game: &mut GameState, cheat: Cheat) -> bool {
+        cheat.apply(game);
+        if let Cheat::Level(level) = cheat {
+            self.level = level;
+        }
+        // Retake the piece's snapshot so undo comes back to the changed board
+        if self.history.back().map(|top| top.stats.pieces_placed) == Some(game.stats.pieces_placed)
+        {
+            self.history.pop_back();
+        }
+        if game.current_piece.is_some() {
+            self.take_snapshot(game);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameAction;
+
+    fn zen_game() -> (ZenMode, GameState) {
+        let mut game = GameState::with_seed(5);
+        game.start();
+        let mut zen = ZenMode::new();
+        zen.on_start(&mut game);
+        zen.on_update(&mut game);
+        (zen, game)
+    }
+
+    #[test]
+    fn test_undo_rewinds_one_placement() {
+        let (mut zen, mut game) = zen_game();
+        let first = game.current_piece.as_ref().unwrap().piece_type;
+        let board_before = game.board.clone();
+
+        game.handle_action(GameAction::HardDrop);
+        zen.on_update(&mut game);
+        assert_eq!(game.stats.pieces_placed, 1);
+        assert_eq!(zen.undo_available(&game), 1);
+
+        assert!(zen.undo(&mut game));
+        assert_eq!(game.stats.pieces_placed, 0);
+        assert_eq!(game.current_piece.as_ref().unwrap().piece_type, first);
+        assert_eq!(game.board, board_before);
+        assert!(!zen.undo(&mut game));
+    }
+
+    #[test]
+    fn test_undo_restores_spawn_position() {
+        let (mut zen, mut game) = zen_game();
+        game.handle_action(GameAction::HardDrop);
+        zen.on_update(&mut game);
+        game.handle_action(GameAction::MoveLeft);
+        game.handle_action(GameAction::HardDrop);
+        zen.on_update(&mut game);
+
+        assert!(zen.undo(&mut game));
+        let piece = game.current_piece.as_ref().unwrap();
+        assert_eq!(*piece, Piece::new(piece.piece_type));
+        assert_eq!(game.stats.pieces_placed, 1);
+    }
+
+    #[test]
+    fn test_undo_keeps_cheats() {
+        let (mut zen, mut game) = zen_game();
+        assert!(zen.cheat(&mut game, Cheat::Garbage(2)));
+        assert!(zen.cheat(&mut game, Cheat::Level(5)));
+        zen.on_update(&mut game);
+        assert_eq!(game.gravity.level, 5);
+
+        game.handle_action(GameAction::HardDrop);
+        zen.on_update(&mut game);
+        assert!(zen.undo(&mut game));
+        assert_eq!(game.board.max_height(), 2);
+        assert!(!zen.undo(&mut game));
+    }
+
+    #[test]
+    fn test_start_level_holds_gravity() {
+        let mut game = GameState::with_seed(5);
+        game.start();
+        let mut zen = ZenMode::with_start_level(9);
+        zen.on_start(&mut game);
+        game.scoring.level = 10;
+        zen.on_update(&mut game);
+        assert_eq!(game.gravity.level, 9);
+    }
+
+    #[test]
+    fn test_restart_clears_history() {
+        let (mut zen, mut game) = zen_game();
+        game.handle_action(GameAction::HardDrop);
+        zen.on_update(&mut game);
+
+        let mut fresh = GameState::with_seed(6);
+        fresh.start();
+        zen.on_start(&mut fresh);
+        zen.on_update(&mut fresh);
+        assert_eq!(zen.undo_available(&fresh), 0);
+    }
+}